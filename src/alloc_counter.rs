@@ -0,0 +1,165 @@
+//! Allocation counting for debugging, enabled by the `alloc-counter` feature.
+//!
+//! Install [CountingAllocator] as the global allocator of your debug build
+//! and wrap the nodes you want to inspect with [CountAllocations] to see how
+//! many heap allocations they perform per tick.
+//! It helps keeping the hot path of a tree allocation-free.
+//!
+//! ```
+//! use tiny_behavior_tree::alloc_counter::{CountAllocations, CountingAllocator};
+//! # use tiny_behavior_tree::*;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! struct Greet;
+//!
+//! impl BehaviorNodeBase<(), String, ()> for Greet {
+//!     fn tick(&mut self, _payload: ()) -> BehaviorResult<String, ()> {
+//!         BehaviorResult::Success("Hello".to_string())
+//!     }
+//! }
+//!
+//! let mut node = CountAllocations::new(Greet);
+//! let stats = node.stats();
+//! node.tick(());
+//! assert_eq!(stats.last(), 1);
+//! ```
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::rc::Rc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A global allocator that counts allocations per thread and delegates to [System].
+///
+/// Reallocations are counted as allocations, deallocations are not counted.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count() {
+        // The thread local may be already destroyed while the thread exits.
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::count();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::count();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Returns the number of allocations performed by the current thread so far.
+///
+/// It is always 0 unless [CountingAllocator] is the global allocator.
+pub fn allocations() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+/// Allocation counts of a node wrapped by [CountAllocations].
+///
+/// The counts include allocations made by the descendants of the node.
+#[derive(Default, Debug)]
+pub struct AllocationStats {
+    last: Cell<usize>,
+    peak: Cell<usize>,
+    total: Cell<usize>,
+    ticks: Cell<usize>,
+}
+
+impl AllocationStats {
+    /// The number of allocations in the last tick.
+    pub fn last(&self) -> usize {
+        self.last.get()
+    }
+
+    /// The maximum number of allocations in a tick.
+    pub fn peak(&self) -> usize {
+        self.peak.get()
+    }
+
+    /// The number of allocations in all ticks.
+    pub fn total(&self) -> usize {
+        self.total.get()
+    }
+
+    /// The number of ticks counted.
+    pub fn ticks(&self) -> usize {
+        self.ticks.get()
+    }
+
+    fn record(&self, allocations: usize) {
+        self.last.set(allocations);
+        self.peak.set(self.peak.get().max(allocations));
+        self.total.set(self.total.get() + allocations);
+        self.ticks.set(self.ticks.get() + 1);
+    }
+}
+
+/// A decorator that counts allocations performed while ticking the child node.
+///
+/// Obtain the handle to the counts with [CountAllocations::stats] before
+/// putting the node into a tree.
+/// Recording the counts does not allocate, so wrapped nodes can be nested.
+pub struct CountAllocations<T> {
+    node: T,
+    stats: Rc<AllocationStats>,
+}
+
+impl<T> CountAllocations<T> {
+    pub fn new(node: T) -> Self {
+        Self {
+            node,
+            stats: Rc::default(),
+        }
+    }
+
+    pub fn stats(&self) -> Rc<AllocationStats> {
+        self.stats.clone()
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for CountAllocations<T>
+where
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        let before = allocations();
+        let res = self.node.tick(payload);
+        self.stats.record(allocations() - before);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        let before = allocations();
+        let res = self.node.try_tick(payload);
+        self.stats.record(allocations() - before);
+        res
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
@@ -0,0 +1,122 @@
+//! Checks a definition file against a registry manifest without the program
+//! that runs the tree, enabled by the `bt-tool` feature.
+//!
+//! ```text
+//! bt-tool <TREE> --manifest <MANIFEST> [--dot <FILE>] [--mermaid <FILE>]
+//! ```
+//!
+//! The tree is read as JSON, as YAML with the `yaml` feature if the extension
+//! is `.yaml` or `.yml`, and as a BehaviorTree.CPP file with the `xml`
+//! feature if the extension is `.xml`.
+//! It is built with the built-in node types and the node types in the
+//! manifest, see [Manifest], and printed as ASCII if it is valid.
+//! `--dot` and `--mermaid` export it to the files, or to stdout with `-`.
+use std::path::Path;
+use std::process::ExitCode;
+use tiny_behavior_tree::loader::TreeDef;
+use tiny_behavior_tree::manifest::Manifest;
+use tiny_behavior_tree::{NodeFactory, TreeOutline};
+
+const USAGE: &str = "Usage: bt-tool <TREE> --manifest <MANIFEST> [--dot <FILE>] [--mermaid <FILE>]";
+
+struct Args {
+    tree: String,
+    manifest: String,
+    dot: Option<String>,
+    mermaid: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut tree = None;
+    let mut manifest = None;
+    let mut dot = None;
+    let mut mermaid = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "--manifest" => &mut manifest,
+            "--dot" => &mut dot,
+            "--mermaid" => &mut mermaid,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if tree.is_none() => {
+                tree = Some(arg);
+                continue;
+            }
+            _ => return Err(format!("Unexpected argument {}", arg)),
+        };
+        *value = Some(
+            args.next()
+                .ok_or_else(|| format!("Missing value of {}", arg))?,
+        );
+    }
+    Ok(Args {
+        tree: tree.ok_or("Missing tree file")?,
+        manifest: manifest.ok_or("Missing --manifest")?,
+        dot,
+        mermaid,
+    })
+}
+
+fn read(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn parse_tree(path: &str, text: &str) -> Result<TreeDef, String> {
+    let extension = Path::new(path).extension().and_then(|ext| ext.to_str());
+    match extension {
+        #[cfg(feature = "yaml")]
+        Some("yaml" | "yml") => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+        #[cfg(feature = "xml")]
+        Some("xml") => tiny_behavior_tree::btcpp::parse(text).map_err(|e| e.to_string()),
+        _ => serde_json::from_str(text).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| format!("{}: {}", path, e))
+}
+
+fn write(path: &str, text: &str) -> Result<(), String> {
+    if path == "-" {
+        print!("{}", text);
+        Ok(())
+    } else {
+        std::fs::write(path, text).map_err(|e| format!("{}: {}", path, e))
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let manifest = Manifest::from_json(&read(&args.manifest)?)
+        .map_err(|e| format!("{}: {}", args.manifest, e))?;
+    let def = parse_tree(&args.tree, &read(&args.tree)?)?;
+
+    let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+    manifest.register(&mut factory);
+    let mut root = factory
+        .build(&def)
+        .map_err(|e| format!("{}: {}", args.tree, e))?;
+
+    let outline = TreeOutline::new(root.as_mut(), None);
+    print!("{}", outline);
+    if let Some(path) = &args.dot {
+        write(path, &outline.to_dot())?;
+    }
+    if let Some(path) = &args.mermaid {
+        write(path, &outline.to_mermaid())?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,808 @@
+use crate::intern::{Interner, Symbol};
+use std::any::{type_name, Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::time::Instant;
+
+/// A key to look up an entry in the [Blackboard].
+///
+/// It is implemented for `&str` and [Symbol].
+/// Looking up with a `Symbol` obtained from [Blackboard::key] beforehand
+/// avoids hashing the string in every tick.
+pub trait BlackboardKey {
+    fn find(&self, interner: &Interner) -> Option<Symbol>;
+    fn intern(&self, interner: &mut Interner) -> Symbol;
+}
+
+impl BlackboardKey for &str {
+    fn find(&self, interner: &Interner) -> Option<Symbol> {
+        interner.get(self)
+    }
+
+    fn intern(&self, interner: &mut Interner) -> Symbol {
+        interner.intern(self)
+    }
+}
+
+impl BlackboardKey for Symbol {
+    fn find(&self, _interner: &Interner) -> Option<Symbol> {
+        Some(*self)
+    }
+
+    fn intern(&self, _interner: &mut Interner) -> Symbol {
+        *self
+    }
+}
+
+/// A key that also tells the type of the value, so that reading an entry
+/// does not need a type annotation and cannot ask for a wrong type.
+///
+/// Define the keys as constants shared by the nodes that exchange the value.
+///
+/// ```
+/// # use tiny_behavior_tree::{Blackboard, TypedKey};
+/// const TARGET: TypedKey<(i32, i32)> = TypedKey::new("target");
+///
+/// let mut bb = Blackboard::new();
+/// bb.set_typed(TARGET, (3, 4));
+/// assert_eq!(bb.get_typed(TARGET), Some(&(3, 4)));
+/// assert_eq!(bb.get::<(i32, i32)>("target"), Some(&(3, 4)));
+/// ```
+pub struct TypedKey<T> {
+    name: &'static str,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> TypedKey<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _type: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for TypedKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TypedKey<T> {}
+
+impl<T> Debug for TypedKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TypedKey<{}>({:?})", type_name::<T>(), self.name)
+    }
+}
+
+impl<T> BlackboardKey for TypedKey<T> {
+    fn find(&self, interner: &Interner) -> Option<Symbol> {
+        interner.get(self.name)
+    }
+
+    fn intern(&self, interner: &mut Interner) -> Symbol {
+        interner.intern(self.name)
+    }
+}
+
+/// A storage of values of arbitrary types keyed by names, shared among nodes.
+///
+/// Put it in your payload (usually in a [RefCell](std::cell::RefCell)) so that nodes
+/// can exchange data without adding a field to your state struct for each of them.
+///
+/// ## Double buffering
+///
+/// A blackboard created by [Blackboard::double_buffered] does not make writes
+/// visible immediately.
+/// Instead, they are kept pending until you call [Blackboard::commit],
+/// which you would do once after each tick of the tree.
+/// This way, every node in a tick sees the same contents regardless of the
+/// order in which sibling subtrees are ticked.
+///
+/// ```
+/// # use tiny_behavior_tree::Blackboard;
+/// let mut bb = Blackboard::double_buffered();
+/// bb.set("target", 42);
+/// assert_eq!(bb.get::<i32>("target"), None);
+/// bb.commit();
+/// assert_eq!(bb.get::<i32>("target"), Some(&42));
+/// ```
+///
+/// ## Expiring entries
+///
+/// Transient facts, like the last seen position of an enemy, can be set with
+/// a lifetime so that they don't stay around and keep condition nodes acting
+/// on stale data.
+/// [Blackboard::set_for_generations] sets an entry that is removed after the
+/// given number of [Blackboard::advance_generation] calls, which you would call
+/// once per tick.
+/// [Blackboard::set_until] sets an entry that is removed by
+/// [Blackboard::purge_expired] after the given instant.
+/// In the double buffered mode, writes that expire before they are committed
+/// remove the entries when committed, so they don't bring back stale data.
+///
+/// ```
+/// # use tiny_behavior_tree::Blackboard;
+/// let mut bb = Blackboard::new();
+/// bb.set_for_generations("enemy_seen", (3, 4), 2);
+/// bb.advance_generation();
+/// assert!(bb.contains("enemy_seen"));
+/// bb.advance_generation();
+/// assert!(!bb.contains("enemy_seen"));
+/// ```
+///
+/// ## Snapshots
+///
+/// [Blackboard::snapshot] records the contents so that [Blackboard::diff]
+/// can tell which entries changed between two snapshots, e.g. before and
+/// after a tick that made the tree switch branches.
+/// Values of types registered with [Blackboard::register_debug] and some
+/// primitive types are recorded with their [Debug] representation.
+/// Other values are recorded only with their type names, so in-place
+/// modifications through [Blackboard::get_mut] are not detected for them.
+///
+/// ```
+/// # use tiny_behavior_tree::Blackboard;
+/// let mut bb = Blackboard::new();
+/// bb.register_debug::<(i32, i32)>();
+/// bb.set("enemy", (3, 4));
+/// let before = bb.snapshot();
+/// bb.set("enemy", (5, 4));
+/// bb.set("alerted", true);
+///
+/// let changes = Blackboard::diff(&before, &bb.snapshot());
+/// assert_eq!(changes.len(), 2);
+/// assert_eq!(changes[0].key, "alerted");
+/// assert_eq!(changes[0].before, None);
+/// assert_eq!(changes[0].after.as_deref(), Some("true"));
+/// assert_eq!(changes[1].to_string(), "enemy: (3, 4) -> (5, 4)");
+/// ```
+#[derive(Default)]
+pub struct Blackboard {
+    interner: Interner,
+    entries: HashMap<Symbol, Entry>,
+    /// Writes waiting for [Blackboard::commit], `None` in the immediate mode.
+    /// A `None` value is a pending removal.
+    pending: Option<HashMap<Symbol, Option<Entry>>>,
+    generation: u64,
+    /// The number of writes so far, used to tell entries that were set again apart.
+    revision: u64,
+    debug_fmts: HashMap<TypeId, DebugFmt>,
+    /// The scopes entered by [Blackboard::push_scope], the innermost last.
+    scopes: Vec<BlackboardScope>,
+}
+
+type DebugFmt = fn(&dyn Any) -> Option<String>;
+
+struct Entry {
+    value: Box<dyn Any>,
+    expiry: Option<Expiry>,
+    type_name: &'static str,
+    revision: u64,
+}
+
+/// Entries local to a subtree, which shadow the entries of the same names
+/// in the enclosing scopes while it is entered with [Blackboard::push_scope].
+///
+/// Reads of keys that the scope does not have fall back to the enclosing
+/// scopes, and writes go to the scope, except for the keys given to
+/// [BlackboardScope::share], which are written to the enclosing scope.
+/// This way, reused subtrees do not overwrite each other's entries.
+/// Writes to a scope take effect immediately even in the double buffered mode.
+///
+/// A scope holds the keys of the blackboard it was entered into, so it
+/// should not be used with another blackboard.
+/// [ScopeNode](crate::ScopeNode) enters a scope around the tick of its child.
+///
+/// ```
+/// # use tiny_behavior_tree::{Blackboard, BlackboardScope};
+/// let mut bb = Blackboard::new();
+/// bb.set("target", "door");
+/// bb.push_scope(BlackboardScope::new().share("done"));
+/// bb.set("target", "key");
+/// bb.set("done", true);
+/// assert_eq!(bb.get::<&str>("target"), Some(&"key"));
+/// let scope = bb.pop_scope().unwrap();
+///
+/// assert_eq!(bb.get::<&str>("target"), Some(&"door"));
+/// assert_eq!(bb.get::<bool>("done"), Some(&true));
+/// assert_eq!(scope.len(), 1);
+/// ```
+#[derive(Default)]
+pub struct BlackboardScope {
+    entries: HashMap<Symbol, Entry>,
+    shared: Vec<String>,
+    /// The keys of `shared`, interned by [Blackboard::push_scope].
+    shared_keys: Vec<Symbol>,
+}
+
+impl BlackboardScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes writes of `key` go to the enclosing scope instead of this one.
+    pub fn share(mut self, key: impl Into<String>) -> Self {
+        self.shared.push(key.into());
+        self
+    }
+
+    /// Returns the number of local entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all local entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn shares(&self, key: Symbol) -> bool {
+        self.shared_keys.contains(&key)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Expiry {
+    /// Expires when the generation reaches this value.
+    Generation(u64),
+    /// Expires at this instant.
+    Instant(Instant),
+}
+
+impl Blackboard {
+    /// Creates a blackboard whose writes are visible immediately.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a blackboard whose writes are visible only after [Blackboard::commit].
+    pub fn double_buffered() -> Self {
+        Self {
+            pending: Some(HashMap::new()),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_double_buffered(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Interns a key name so that it can be used for quick lookups.
+    pub fn key(&mut self, name: &str) -> Symbol {
+        self.interner.intern(name)
+    }
+
+    /// Returns the name of a key returned by [Blackboard::key].
+    pub fn key_name(&self, key: Symbol) -> Option<&str> {
+        self.interner.try_resolve(key)
+    }
+
+    /// Returns the value of an entry, or `None` if the entry does not exist
+    /// or has a different type.
+    pub fn get<T: 'static>(&self, key: impl BlackboardKey) -> Option<&T> {
+        let key = key.find(&self.interner)?;
+        self.lookup(key)?.value.downcast_ref()
+    }
+
+    /// Returns a mutable reference to the value of an entry.
+    ///
+    /// Note that modifying the value through this reference takes effect
+    /// immediately even in the double buffered mode.
+    pub fn get_mut<T: 'static>(&mut self, key: impl BlackboardKey) -> Option<&mut T> {
+        let key = key.find(&self.interner)?;
+        let entry = match self
+            .scopes
+            .iter()
+            .rposition(|scope| scope.entries.contains_key(&key))
+        {
+            Some(i) => self.scopes[i].entries.get_mut(&key),
+            None => self.entries.get_mut(&key),
+        };
+        entry?.value.downcast_mut()
+    }
+
+    /// Returns the value of an entry by a [TypedKey].
+    pub fn get_typed<T: 'static>(&self, key: TypedKey<T>) -> Option<&T> {
+        self.get(key)
+    }
+
+    /// Returns a mutable reference to the value of an entry by a [TypedKey].
+    pub fn get_typed_mut<T: 'static>(&mut self, key: TypedKey<T>) -> Option<&mut T> {
+        self.get_mut(key)
+    }
+
+    /// Sets the value of an entry by a [TypedKey].
+    pub fn set_typed<T: 'static>(&mut self, key: TypedKey<T>, value: T) {
+        self.set(key, value)
+    }
+
+    pub fn contains(&self, key: impl BlackboardKey) -> bool {
+        key.find(&self.interner)
+            .is_some_and(|key| self.lookup(key).is_some())
+    }
+
+    /// Returns the type of the value of an entry.
+    pub(crate) fn type_of(&self, key: &str) -> Option<(TypeId, &'static str)> {
+        let key = BlackboardKey::find(&key, &self.interner)?;
+        let entry = self.lookup(key)?;
+        Some((entry.value.as_ref().type_id(), entry.type_name))
+    }
+
+    /// Sets the value of an entry, replacing the existing value of any type.
+    pub fn set<T: 'static>(&mut self, key: impl BlackboardKey, value: T) {
+        self.insert(key, value, None);
+    }
+
+    /// Sets the value of an entry that is removed after `generations` calls
+    /// of [Blackboard::advance_generation].
+    pub fn set_for_generations<T: 'static>(
+        &mut self,
+        key: impl BlackboardKey,
+        value: T,
+        generations: u64,
+    ) {
+        let expiry = Expiry::Generation(self.generation + generations);
+        self.insert(key, value, Some(expiry));
+    }
+
+    /// Sets the value of an entry that is removed by [Blackboard::purge_expired]
+    /// at or after `deadline`.
+    pub fn set_until<T: 'static>(&mut self, key: impl BlackboardKey, value: T, deadline: Instant) {
+        self.insert(key, value, Some(Expiry::Instant(deadline)));
+    }
+
+    fn insert<T: 'static>(&mut self, key: impl BlackboardKey, value: T, expiry: Option<Expiry>) {
+        self.insert_boxed(key, Box::new(value), type_name::<T>(), expiry);
+    }
+
+    fn insert_boxed(
+        &mut self,
+        key: impl BlackboardKey,
+        value: Box<dyn Any>,
+        type_name: &'static str,
+        expiry: Option<Expiry>,
+    ) {
+        let key = key.intern(&mut self.interner);
+        self.revision += 1;
+        let entry = Entry {
+            value,
+            expiry,
+            type_name,
+            revision: self.revision,
+        };
+        if let Some(scope) = self.scope_for(key) {
+            scope.entries.insert(key, entry);
+            return;
+        }
+        match &mut self.pending {
+            Some(pending) => {
+                pending.insert(key, Some(entry));
+            }
+            None => {
+                self.entries.insert(key, entry);
+            }
+        }
+    }
+
+    /// Removes an entry.
+    pub fn remove(&mut self, key: impl BlackboardKey) {
+        let key = key.intern(&mut self.interner);
+        if let Some(scope) = self.scope_for(key) {
+            scope.entries.remove(&key);
+            return;
+        }
+        match &mut self.pending {
+            Some(pending) => {
+                pending.insert(key, None);
+            }
+            None => {
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    /// Returns the entries outside of scopes in the order of their names,
+    /// with the number of generations until they expire, for saving them.
+    #[cfg(feature = "serde")]
+    pub(crate) fn saved_entries(&self) -> Vec<(&str, &dyn Any, Option<u64>)> {
+        let mut entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| {
+                let expires_in = match entry.expiry {
+                    Some(Expiry::Generation(expiry)) => {
+                        Some(expiry.saturating_sub(self.generation))
+                    }
+                    _ => None,
+                };
+                (
+                    self.interner.resolve(*key),
+                    entry.value.as_ref(),
+                    expires_in,
+                )
+            })
+            .collect();
+        entries.sort_by_key(|(key, _, _)| *key);
+        entries
+    }
+
+    /// Sets an entry restored from a saved value.
+    #[cfg(feature = "serde")]
+    pub(crate) fn restore_entry(
+        &mut self,
+        key: &str,
+        value: Box<dyn Any>,
+        type_name: &'static str,
+        expires_in: Option<u64>,
+    ) {
+        let expiry =
+            expires_in.map(|generations| Expiry::Generation(self.generation + generations));
+        self.insert_boxed(key, value, type_name, expiry);
+    }
+
+    /// Enters `scope`, so that the following reads and writes see its entries
+    /// first, until [Blackboard::pop_scope] is called.
+    pub fn push_scope(&mut self, mut scope: BlackboardScope) {
+        scope.shared_keys = scope
+            .shared
+            .iter()
+            .map(|name| self.interner.intern(name))
+            .collect();
+        self.scopes.push(scope);
+    }
+
+    /// Leaves the innermost scope and returns it with the entries written in it.
+    pub fn pop_scope(&mut self) -> Option<BlackboardScope> {
+        self.scopes.pop()
+    }
+
+    /// Returns the entry of `key` in the innermost scope that has it.
+    fn lookup(&self, key: Symbol) -> Option<&Entry> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.entries.get(&key))
+            .or_else(|| self.entries.get(&key))
+    }
+
+    /// Returns the innermost scope that does not share `key`, or `None` if the
+    /// key should be written to the blackboard itself.
+    fn scope_for(&mut self, key: Symbol) -> Option<&mut BlackboardScope> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find(|scope| !scope.shares(key))
+    }
+
+    /// Returns true if there are writes that are not committed yet.
+    pub fn has_pending(&self) -> bool {
+        self.pending
+            .as_ref()
+            .is_some_and(|pending| !pending.is_empty())
+    }
+
+    /// Makes pending writes visible. It does nothing in the immediate mode.
+    pub fn commit(&mut self) {
+        if let Some(pending) = &mut self.pending {
+            for (key, value) in pending.drain() {
+                match value {
+                    Some(value) => self.entries.insert(key, value),
+                    None => self.entries.remove(&key),
+                };
+            }
+        }
+    }
+
+    /// Returns the current generation, which starts from 0.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Advances the generation and removes entries that expired by it.
+    pub fn advance_generation(&mut self) {
+        self.generation += 1;
+        let generation = self.generation;
+        self.retain_all(|entry| match entry.expiry {
+            Some(Expiry::Generation(expiry)) => generation < expiry,
+            _ => true,
+        });
+    }
+
+    /// Removes entries whose deadline given by [Blackboard::set_until] is
+    /// at or before `now`.
+    ///
+    /// Expired entries are visible until this method is called, so call it
+    /// before ticking the tree.
+    pub fn purge_expired(&mut self, now: Instant) {
+        self.retain_all(|entry| match entry.expiry {
+            Some(Expiry::Instant(deadline)) => now < deadline,
+            _ => true,
+        });
+    }
+
+    /// Keeps the entries for which `f` returns true, including those in the
+    /// entered scopes.
+    ///
+    /// Pending writes of the other entries become pending removals, so that
+    /// committing them does not bring back expired values.
+    fn retain_all(&mut self, f: impl Fn(&Entry) -> bool) {
+        self.entries.retain(|_, entry| f(entry));
+        for scope in &mut self.scopes {
+            scope.entries.retain(|_, entry| f(entry));
+        }
+        if let Some(pending) = &mut self.pending {
+            for value in pending.values_mut() {
+                if value.as_ref().is_some_and(|entry| !f(entry)) {
+                    *value = None;
+                }
+            }
+        }
+    }
+
+    /// Returns the number of visible entries.
+    pub fn len(&self) -> usize {
+        self.visible().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the visible entries, with those in inner scopes shadowing outer ones.
+    fn visible(&self) -> HashMap<Symbol, &Entry> {
+        let mut entries: HashMap<Symbol, &Entry> =
+            self.entries.iter().map(|(k, e)| (*k, e)).collect();
+        for scope in &self.scopes {
+            entries.extend(scope.entries.iter().map(|(k, e)| (*k, e)));
+        }
+        entries
+    }
+
+    /// Makes snapshots record values of type `T` with their [Debug] representation.
+    pub fn register_debug<T: Debug + 'static>(&mut self) {
+        self.debug_fmts.insert(TypeId::of::<T>(), debug_fmt::<T>);
+    }
+
+    /// Records the visible entries, see [Blackboard::diff].
+    pub fn snapshot(&self) -> BlackboardSnapshot {
+        let entries = self
+            .visible()
+            .into_iter()
+            .map(|(key, entry)| {
+                let value = entry.value.as_ref();
+                let debug = self
+                    .debug_fmts
+                    .get(&value.type_id())
+                    .and_then(|fmt| fmt(value))
+                    .or_else(|| builtin_debug(value));
+                let name = self.interner.resolve(key).to_owned();
+                let snapshot = SnapshotEntry {
+                    debug,
+                    type_name: entry.type_name,
+                    revision: entry.revision,
+                };
+                (name, snapshot)
+            })
+            .collect();
+        BlackboardSnapshot { entries }
+    }
+
+    /// Returns the entries that were added, removed or changed from `before`
+    /// to `after`, in the order of their names.
+    ///
+    /// Both snapshots should be taken from the same blackboard.
+    /// An entry that was set again to an equal value counts as a change only
+    /// if it has no [Debug] representation.
+    pub fn diff(before: &BlackboardSnapshot, after: &BlackboardSnapshot) -> Vec<BlackboardChange> {
+        let mut keys: Vec<&String> = before.entries.keys().chain(after.entries.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .filter_map(|key| {
+                let old = before.entries.get(key);
+                let new = after.entries.get(key);
+                let changed = match (old, new) {
+                    (Some(old), Some(new)) => match (&old.debug, &new.debug) {
+                        (Some(old_debug), Some(new_debug)) => old_debug != new_debug,
+                        _ => old.revision != new.revision,
+                    },
+                    (None, None) => false,
+                    _ => true,
+                };
+                changed.then(|| BlackboardChange {
+                    key: key.clone(),
+                    before: old.map(SnapshotEntry::describe),
+                    after: new.map(SnapshotEntry::describe),
+                })
+            })
+            .collect()
+    }
+}
+
+fn debug_fmt<T: Debug + 'static>(value: &dyn Any) -> Option<String> {
+    value
+        .downcast_ref::<T>()
+        .map(|value| format!("{:?}", value))
+}
+
+fn builtin_debug(value: &dyn Any) -> Option<String> {
+    macro_rules! try_types {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = value.downcast_ref::<$ty>() {
+                    return Some(format!("{:?}", value));
+                }
+            )*
+        };
+    }
+    try_types!(
+        bool,
+        char,
+        i8,
+        i16,
+        i32,
+        i64,
+        isize,
+        u8,
+        u16,
+        u32,
+        u64,
+        usize,
+        f32,
+        f64,
+        String,
+        &'static str
+    );
+    None
+}
+
+/// The contents of a [Blackboard] recorded by [Blackboard::snapshot].
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BlackboardSnapshot {
+    entries: BTreeMap<String, SnapshotEntry>,
+}
+
+impl BlackboardSnapshot {
+    /// Returns the recorded names of the entries in order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Returns the recorded representation of an entry, see [BlackboardChange].
+    pub fn describe(&self, key: &str) -> Option<String> {
+        self.entries.get(key).map(SnapshotEntry::describe)
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct SnapshotEntry {
+    debug: Option<String>,
+    type_name: &'static str,
+    revision: u64,
+}
+
+impl SnapshotEntry {
+    fn describe(&self) -> String {
+        match &self.debug {
+            Some(debug) => debug.clone(),
+            None => format!("<{}>", self.type_name),
+        }
+    }
+}
+
+/// An entry changed between two snapshots, returned by [Blackboard::diff].
+///
+/// The values are the [Debug] representations, or the type names in angle
+/// brackets for types without one, and `None` if the entry did not exist.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlackboardChange {
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl fmt::Display for BlackboardChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let describe = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.key,
+            describe(&self.before),
+            describe(&self.after)
+        )
+    }
+}
+
+/// A payload that passes a [Blackboard] to every node alongside the state of
+/// the application, so that nodes can exchange data through the blackboard
+/// while reading the state directly.
+///
+/// It is [Copy] if the state is, e.g. a shared reference, so control nodes can
+/// pass it down to their children.
+/// Use [MapPayloadNode](crate::MapPayloadNode) to give a subtree only one of them.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::RefCell;
+/// struct World {
+///     enemy: Option<(i32, i32)>,
+/// }
+///
+/// const TARGET: TypedKey<(i32, i32)> = TypedKey::new("target");
+///
+/// struct PickTarget;
+///
+/// impl<'a> BehaviorNodeBase<WithBlackboard<'a, &'a World>, (), ()> for PickTarget {
+///     fn tick(&mut self, ctx: WithBlackboard<'a, &'a World>) -> BehaviorResult<(), ()> {
+///         match ctx.state.enemy {
+///             Some(enemy) => {
+///                 ctx.blackboard.borrow_mut().set_typed(TARGET, enemy);
+///                 BehaviorResult::Success(())
+///             }
+///             None => BehaviorResult::Failure(()),
+///         }
+///     }
+/// }
+///
+/// let world = World { enemy: Some((3, 4)) };
+/// let bb = RefCell::new(Blackboard::new());
+/// let mut node = PickTarget;
+/// assert_eq!(node.tick(WithBlackboard::new(&world, &bb)), BehaviorResult::Success(()));
+/// assert_eq!(bb.borrow().get_typed(TARGET), Some(&(3, 4)));
+/// ```
+pub struct WithBlackboard<'a, S> {
+    pub state: S,
+    pub blackboard: &'a RefCell<Blackboard>,
+}
+
+impl<'a, S> WithBlackboard<'a, S> {
+    pub fn new(state: S, blackboard: &'a RefCell<Blackboard>) -> Self {
+        Self { state, blackboard }
+    }
+}
+
+impl<S: Clone> Clone for WithBlackboard<'_, S> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            blackboard: self.blackboard,
+        }
+    }
+}
+
+impl<S: Copy> Copy for WithBlackboard<'_, S> {}
+
+/// A payload that gives access to a [Blackboard], used by nodes that work on
+/// the blackboard regardless of the rest of the payload, like
+/// [ScopeNode](crate::ScopeNode).
+pub trait HasBlackboard<'a> {
+    fn blackboard(&self) -> &'a RefCell<Blackboard>;
+}
+
+impl<'a> HasBlackboard<'a> for &'a RefCell<Blackboard> {
+    fn blackboard(&self) -> &'a RefCell<Blackboard> {
+        self
+    }
+}
+
+impl<'a, S> HasBlackboard<'a> for WithBlackboard<'a, S> {
+    fn blackboard(&self) -> &'a RefCell<Blackboard> {
+        self.blackboard
+    }
+}
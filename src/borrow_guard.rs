@@ -0,0 +1,116 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, BtErrorKind, HaltReport};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// A `RefCell` borrow conflict caught by [CatchBorrowConflict].
+#[derive(Clone, PartialEq, Debug)]
+pub struct BorrowConflict {
+    /// The path given to [CatchBorrowConflict::new].
+    pub path: String,
+    /// The panic message, which tells whether a mutable borrow conflicted with
+    /// a shared borrow ("already borrowed") or the other way around
+    /// ("already mutably borrowed").
+    pub message: String,
+}
+
+impl fmt::Display for BorrowConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Borrow conflict in \"{}\": {}", self.path, self.message)
+    }
+}
+
+impl From<BorrowConflict> for String {
+    fn from(conflict: BorrowConflict) -> Self {
+        conflict.to_string()
+    }
+}
+
+/// A decorator that catches panics of conflicting `RefCell` borrows in the
+/// child node and turns them into a failure that names the node.
+///
+/// Borrowing a `RefCell` in the payload while another node holds a borrow
+/// panics, and the panic does not tell which node did it.
+/// Wrap suspicious subtrees with this decorator while debugging to find out.
+/// The failure value is made from [BorrowConflict], so `F` needs to implement
+/// `From<BorrowConflict>`.
+/// [try_tick](BehaviorNodeBase::try_tick) returns [BtErrorKind::Borrow] instead.
+///
+/// Other panics are propagated as they are.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::RefCell;
+/// struct Increment;
+///
+/// impl BehaviorNodeBase<&RefCell<i32>, (), String> for Increment {
+///     fn tick(&mut self, payload: &RefCell<i32>) -> BehaviorResult<(), String> {
+///         *payload.borrow_mut() += 1;
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// let payload = RefCell::new(0);
+/// let mut node = CatchBorrowConflict::new("increment", Increment);
+/// let _borrow = payload.borrow();
+/// let BehaviorResult::Failure(message) = node.tick(&payload) else { panic!() };
+/// assert!(message.starts_with("Borrow conflict in \"increment\""));
+/// ```
+pub struct CatchBorrowConflict<T> {
+    path: String,
+    node: T,
+}
+
+impl<T> CatchBorrowConflict<T> {
+    pub fn new(path: impl Into<String>, node: T) -> Self {
+        Self {
+            path: path.into(),
+            node,
+        }
+    }
+
+    fn catch<U>(&mut self, f: impl FnOnce(&mut T) -> U) -> Result<U, BorrowConflict> {
+        let node = &mut self.node;
+        panic::catch_unwind(AssertUnwindSafe(|| f(node))).map_err(|payload| {
+            let message = payload
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| payload.downcast_ref::<&str>().copied());
+            match message {
+                Some(message)
+                    if message.contains("already borrowed")
+                        || message.contains("already mutably borrowed") =>
+                {
+                    BorrowConflict {
+                        path: self.path.clone(),
+                        message: message.to_owned(),
+                    }
+                }
+                _ => panic::resume_unwind(payload),
+            }
+        })
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for CatchBorrowConflict<T>
+where
+    F: From<BorrowConflict>,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.catch(|node| node.tick(payload))
+            .unwrap_or_else(|conflict| BehaviorResult::Failure(conflict.into()))
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.catch(|node| node.try_tick(payload))
+            .unwrap_or_else(|_| Err(BtError::new(BtErrorKind::Borrow)))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
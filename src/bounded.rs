@@ -0,0 +1,158 @@
+//! A tree builder that bounds the depth of the tree at compile time.
+//!
+//! Every control node ticks its children in a nested call, so the depth of
+//! the tree is the depth of the call stack while ticking it.
+//! [BoundedTree] builds a tree through closures whose argument types encode
+//! the depth, and adding a node deeper than the maximum given as a const
+//! generic parameter fails to compile.
+//!
+//! ```
+//! # use tiny_behavior_tree::*;
+//! # use tiny_behavior_tree::testing::*;
+//! use tiny_behavior_tree::bounded::BoundedTree;
+//!
+//! let mut tree = BoundedTree::<3>::fallback(|b| {
+//!     b.sequence(|b| {
+//!         b.leaf(AlwaysSuccess(()));
+//!         b.leaf(AlwaysFailure(()));
+//!     });
+//!     b.leaf(AlwaysSuccess(()));
+//! });
+//! assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+//! ```
+//!
+//! A node at the fourth level does not compile.
+//!
+//! ```compile_fail
+//! # use tiny_behavior_tree::*;
+//! # use tiny_behavior_tree::testing::*;
+//! use tiny_behavior_tree::bounded::BoundedTree;
+//!
+//! let tree: Box<dyn BehaviorNodeBase<(), (), ()>> = BoundedTree::<3>::fallback(|b| {
+//!     b.sequence(|b| {
+//!         b.sequence(|b| {
+//!             b.leaf(AlwaysSuccess(()));
+//!         });
+//!     });
+//! });
+//! ```
+//!
+//! Note that the depth of the nodes passed to [DepthBuilder::leaf] is not
+//! checked, so do not pass control nodes or decorators there.
+//! The check happens when the code is built, so `cargo check` does not report it.
+use crate::{BehaviorNodeBase, FallbackNode, SequenceNode};
+use std::marker::PhantomData;
+
+/// The depth of a node encoded in a type.
+pub trait Depth {
+    const DEPTH: usize;
+}
+
+/// The depth of the root node.
+pub struct Root;
+
+impl Depth for Root {
+    const DEPTH: usize = 1;
+}
+
+/// The depth of the children of nodes at depth `D`.
+pub struct Nested<D>(PhantomData<D>);
+
+impl<D: Depth> Depth for Nested<D> {
+    const DEPTH: usize = D::DEPTH + 1;
+}
+
+struct Check<D, const MAX: usize>(PhantomData<D>);
+
+impl<D: Depth, const MAX: usize> Check<D, MAX> {
+    const OK: () = assert!(D::DEPTH <= MAX, "the tree is deeper than the maximum depth");
+}
+
+/// The entry point to build a tree whose depth is at most `MAX`.
+pub struct BoundedTree<const MAX: usize>;
+
+type Node<Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F>>;
+
+impl<const MAX: usize> BoundedTree<MAX> {
+    /// Builds a tree with a [SequenceNode] at the root.
+    pub fn sequence<Payload, R, F>(
+        build: impl FnOnce(&mut DepthBuilder<Payload, R, F, Root, MAX>),
+    ) -> Node<Payload, R, F>
+    where
+        Payload: Clone + 'static,
+        R: Default + 'static,
+        F: 'static,
+    {
+        let () = Check::<Root, MAX>::OK;
+        Box::new(SequenceNode::new(DepthBuilder::collect(build)))
+    }
+
+    /// Builds a tree with a [FallbackNode] at the root.
+    pub fn fallback<Payload, R, F>(
+        build: impl FnOnce(&mut DepthBuilder<Payload, R, F, Root, MAX>),
+    ) -> Node<Payload, R, F>
+    where
+        Payload: Clone + 'static,
+        R: 'static,
+        F: Default + 'static,
+    {
+        let () = Check::<Root, MAX>::OK;
+        Box::new(FallbackNode::new(DepthBuilder::collect(build)))
+    }
+}
+
+/// A builder of the children of a control node at depth `D`.
+pub struct DepthBuilder<Payload, R, F, D, const MAX: usize> {
+    children: Vec<Node<Payload, R, F>>,
+    _depth: PhantomData<D>,
+}
+
+impl<Payload, R, F, D: Depth, const MAX: usize> DepthBuilder<Payload, R, F, D, MAX> {
+    fn collect(build: impl FnOnce(&mut Self)) -> Vec<Node<Payload, R, F>> {
+        let mut builder = Self {
+            children: vec![],
+            _depth: PhantomData,
+        };
+        build(&mut builder);
+        builder.children
+    }
+
+    /// Adds a leaf node.
+    pub fn leaf(&mut self, node: impl BehaviorNodeBase<Payload, R, F> + 'static) -> &mut Self {
+        let () = Check::<Nested<D>, MAX>::OK;
+        self.children.push(Box::new(node));
+        self
+    }
+
+    /// Adds a [SequenceNode] whose children are added by `build`.
+    pub fn sequence(
+        &mut self,
+        build: impl FnOnce(&mut DepthBuilder<Payload, R, F, Nested<D>, MAX>),
+    ) -> &mut Self
+    where
+        Payload: Clone + 'static,
+        R: Default + 'static,
+        F: 'static,
+    {
+        let () = Check::<Nested<D>, MAX>::OK;
+        let children = DepthBuilder::collect(build);
+        self.children.push(Box::new(SequenceNode::new(children)));
+        self
+    }
+
+    /// Adds a [FallbackNode] whose children are added by `build`.
+    pub fn fallback(
+        &mut self,
+        build: impl FnOnce(&mut DepthBuilder<Payload, R, F, Nested<D>, MAX>),
+    ) -> &mut Self
+    where
+        Payload: Clone + 'static,
+        R: 'static,
+        F: Default + 'static,
+    {
+        let () = Check::<Nested<D>, MAX>::OK;
+        let children = DepthBuilder::collect(build);
+        self.children.push(Box::new(FallbackNode::new(children)));
+        self
+    }
+}
@@ -0,0 +1,39 @@
+/// Returns early from `tick` unless a [BehaviorResult](crate::BehaviorResult)
+/// is `Success`, like the `?` operator for `Result`.
+///
+/// `Success(r)` evaluates to `r`, while `Failure`, `Running` and `Idle` are
+/// returned as they are, with the failure converted by `From` as `?` does.
+/// It is handy to tick children in sequence in a custom control node.
+///
+/// ```
+/// # use tiny_behavior_tree::{bt_try, testing::AlwaysSuccess, *};
+/// struct SumNode<A, B>(A, B);
+///
+/// impl<A, B> BehaviorNodeBase<(), i32, String> for SumNode<A, B>
+/// where
+///     A: BehaviorNodeBase<(), i32, &'static str>,
+///     B: BehaviorNodeBase<(), i32, &'static str>,
+/// {
+///     fn tick(&mut self, _: ()) -> BehaviorResult<i32, String> {
+///         let a = bt_try!(self.0.tick(()));
+///         let b = bt_try!(self.1.tick(()));
+///         BehaviorResult::Success(a + b)
+///     }
+/// }
+///
+/// let mut node = SumNode(AlwaysSuccess(1), AlwaysSuccess(2));
+/// assert_eq!(node.tick(()), BehaviorResult::Success(3));
+/// ```
+#[macro_export]
+macro_rules! bt_try {
+    ($result:expr) => {
+        match $result {
+            $crate::BehaviorResult::Success(r) => r,
+            $crate::BehaviorResult::Failure(f) => {
+                return $crate::BehaviorResult::Failure(::std::convert::From::from(f))
+            }
+            $crate::BehaviorResult::Running => return $crate::BehaviorResult::Running,
+            $crate::BehaviorResult::Idle => return $crate::BehaviorResult::Idle,
+        }
+    };
+}
@@ -0,0 +1,169 @@
+//! Loading tree files of BehaviorTree.CPP, enabled by the `xml` feature.
+//!
+//! The XML is converted into a [TreeDef], so the nodes are created through a
+//! [NodeFactory] with the names of the elements, e.g. `<Sequence>` or
+//! `<OpenDoor>`, or with the `ID` attribute of the explicit form like
+//! `<Action ID="OpenDoor"/>`.
+//! The other attributes are given to the constructors as parameters, except
+//! for `name`, and blackboard references like `{goal}` are given as they are.
+//!
+//! `<SubTree ID="..."/>` is replaced by the root of the `<BehaviorTree>` with
+//! the ID, so the subtree shares the blackboard of the parent.
+//! Remapping the ports of a subtree is not supported yet.
+//!
+//! The `guard` attribute of any node, including `<SubTree>`, is a guard
+//! expression wrapping the node, see
+//! [NodeFactory::enable_guards](crate::NodeFactory::enable_guards) with the
+//! `stdlib` feature.
+//!
+//! ```
+//! # use tiny_behavior_tree::{testing::AlwaysSuccess, *};
+//! let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+//! factory.register("OpenDoor", |_| Ok(AlwaysSuccess(())));
+//! factory.register("EnterRoom", |_| Ok(AlwaysSuccess(())));
+//!
+//! let mut tree = factory
+//!     .load_btcpp_xml(
+//!         r#"
+//! <root BTCPP_format="4" main_tree_to_execute="MainTree">
+//!   <BehaviorTree ID="MainTree">
+//!     <Sequence name="enter">
+//!       <SubTree ID="Door"/>
+//!       <EnterRoom speed="{speed}"/>
+//!     </Sequence>
+//!   </BehaviorTree>
+//!   <BehaviorTree ID="Door">
+//!     <Action ID="OpenDoor" force="10"/>
+//!   </BehaviorTree>
+//! </root>"#,
+//!     )
+//!     .unwrap();
+//! assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+//! ```
+use crate::loader::{LoadError, NodeDef, TreeDef};
+use crate::{BehaviorNodeBase, NodeFactory};
+use roxmltree::{Document, Node};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Converts a BehaviorTree.CPP XML document into a definition of the main tree.
+///
+/// The main tree is given by the `main_tree_to_execute` attribute of `<root>`,
+/// or it is the only `<BehaviorTree>` in the document.
+pub fn parse(xml: &str) -> Result<TreeDef, LoadError> {
+    let doc = Document::parse(xml).map_err(|e| LoadError::Xml(e.to_string()))?;
+    let root = doc.root_element();
+    if root.tag_name().name() != "root" {
+        return Err(error("The document element must be <root>"));
+    }
+
+    let trees: BTreeMap<&str, Node> = root
+        .children()
+        .filter(|node| node.has_tag_name("BehaviorTree"))
+        .map(|node| (node.attribute("ID").unwrap_or(""), node))
+        .collect();
+    let main = match root.attribute("main_tree_to_execute") {
+        Some(id) => *trees
+            .get(id)
+            .ok_or_else(|| error(format!("No <BehaviorTree> with ID \"{}\"", id)))?,
+        None if trees.len() == 1 => *trees.values().next().unwrap(),
+        None => {
+            return Err(error(
+                "main_tree_to_execute is required with more than one <BehaviorTree>",
+            ))
+        }
+    };
+
+    let mut stack = vec![main.attribute("ID").unwrap_or("")];
+    Ok(TreeDef::new(convert(tree_root(main)?, &trees, &mut stack)?))
+}
+
+fn error(message: impl Into<String>) -> LoadError {
+    LoadError::Xml(message.into())
+}
+
+fn elements<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(Node::is_element)
+}
+
+/// Returns the only child element of a `<BehaviorTree>`.
+fn tree_root<'a, 'input>(tree: Node<'a, 'input>) -> Result<Node<'a, 'input>, LoadError> {
+    let mut children = elements(tree);
+    match (children.next(), children.next()) {
+        (Some(root), None) => Ok(root),
+        _ => Err(error(format!(
+            "<BehaviorTree ID=\"{}\"> must have exactly one child",
+            tree.attribute("ID").unwrap_or("")
+        ))),
+    }
+}
+
+fn convert<'a>(
+    node: Node<'a, '_>,
+    trees: &BTreeMap<&'a str, Node<'a, '_>>,
+    stack: &mut Vec<&'a str>,
+) -> Result<NodeDef, LoadError> {
+    let tag = node.tag_name().name();
+    let id = || {
+        node.attribute("ID")
+            .ok_or_else(|| error(format!("<{}> requires the ID attribute", tag)))
+    };
+
+    if tag == "SubTree" {
+        let id = id()?;
+        if let Some(attr) = node
+            .attributes()
+            .find(|attr| !matches!(attr.name(), "ID" | "name" | "_autoremap" | "guard"))
+        {
+            return Err(error(format!(
+                "Remapping port \"{}\" of <SubTree ID=\"{}\"> is not supported",
+                attr.name(),
+                id
+            )));
+        }
+        if stack.contains(&id) {
+            return Err(error(format!("<SubTree ID=\"{}\"> includes itself", id)));
+        }
+        let tree = trees
+            .get(id)
+            .ok_or_else(|| error(format!("No <BehaviorTree> with ID \"{}\"", id)))?;
+        stack.push(id);
+        let mut def = convert(tree_root(*tree)?, trees, stack)?;
+        stack.pop();
+        if let Some(guard) = node.attribute("guard") {
+            // The guard of the subtree holds along with the one of its root.
+            let guard = match def.params.get("guard").and_then(Value::as_str) {
+                Some(inner) => format!("{} && {}", guard, inner),
+                None => guard.to_owned(),
+            };
+            def.params.insert("guard".to_owned(), Value::String(guard));
+        }
+        return Ok(def);
+    }
+
+    let explicit = matches!(tag, "Action" | "Condition" | "Decorator" | "Control");
+    let mut def = NodeDef::new(if explicit { id()? } else { tag });
+    for attr in node.attributes() {
+        if attr.name() == "name" || explicit && attr.name() == "ID" {
+            continue;
+        }
+        def.params.insert(
+            attr.name().to_owned(),
+            Value::String(attr.value().to_owned()),
+        );
+    }
+    for child in elements(node) {
+        def.children.push(convert(child, trees, stack)?);
+    }
+    Ok(def)
+}
+
+impl<Payload: 'static, R: 'static, F: 'static> NodeFactory<Payload, R, F> {
+    /// Builds the main tree of a BehaviorTree.CPP XML document, see [parse].
+    pub fn load_btcpp_xml(
+        &self,
+        xml: &str,
+    ) -> Result<Box<dyn BehaviorNodeBase<Payload, R, F>>, LoadError> {
+        self.build(&parse(xml)?)
+    }
+}
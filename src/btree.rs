@@ -0,0 +1,122 @@
+/// Declares a tree inline, building the boxed nodes without writing
+/// `Box::new` and the type annotations for each of them.
+///
+/// A control node is written as its name without the `Node` suffix followed
+/// by its children in brackets, e.g. `Sequence [ ... ]`.
+/// The supported names are `Sequence`, `Fallback`, `SequenceWithMemory`,
+/// `FallbackWithMemory`, `ReactiveSequence`, `ReactiveFallback` and
+/// `RoundRobin`, and `Parallel(n)` with the success threshold `n`.
+/// `SequenceRef` and `FallbackRef` create [SequenceNodeRef](crate::SequenceNodeRef)
+/// and [FallbackNodeRef](crate::FallbackNodeRef), whose children do not need
+/// to be `'static`.
+/// Any other item is an expression of a node, like `OpenDoor` or
+/// `InverterNode::new(IsDoorOpen)`, which can take a nested `btree!` as
+/// the child since boxed nodes are nodes too.
+///
+/// The macro returns a `Box<dyn BehaviorNodeBase<Payload, R, F>>`, whose
+/// type arguments are inferred from the nodes.
+/// The items are separated by the commas outside of brackets and parentheses,
+/// so an expression with a comma in the generic arguments, like
+/// `Node::<A, B>::new()`, should be wrapped in parentheses.
+///
+/// ```
+/// # use tiny_behavior_tree::prelude::*;
+/// struct IsDoorOpen;
+///
+/// impl BehaviorNodeBase<&bool, (), ()> for IsDoorOpen {
+///     fn tick(&mut self, open: &bool) -> BehaviorResult<(), ()> {
+///         if *open {
+///             BehaviorResult::Success(())
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+/// }
+///
+/// struct Log(&'static str);
+///
+/// impl BehaviorNodeBase<&bool, (), ()> for Log {
+///     fn tick(&mut self, _: &bool) -> BehaviorResult<(), ()> {
+///         eprintln!("{}", self.0);
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// let mut tree = btree!(Sequence [
+///     Fallback [
+///         IsDoorOpen,
+///         Sequence [
+///             InverterNode::new(IsDoorOpen),
+///             Log("Door opened"),
+///         ],
+///     ],
+///     Log("Entered the room"),
+/// ]);
+/// assert_eq!(tree.tick(&false), BehaviorResult::Success(()));
+/// ```
+#[macro_export]
+macro_rules! btree {
+    (Sequence [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::SequenceNode::new($crate::btree!(@list [] [] $($children)*)))
+    };
+    (Fallback [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::FallbackNode::new($crate::btree!(@list [] [] $($children)*)))
+    };
+    (SequenceWithMemory [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::SequenceNodeWithMemory::new(
+            $crate::btree!(@list [] [] $($children)*)
+        ))
+    };
+    (FallbackWithMemory [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::FallbackNodeWithMemory::new(
+            $crate::btree!(@list [] [] $($children)*)
+        ))
+    };
+    (ReactiveSequence [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::ReactiveSequenceNode::new(
+            $crate::btree!(@list [] [] $($children)*)
+        ))
+    };
+    (ReactiveFallback [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::ReactiveFallbackNode::new(
+            $crate::btree!(@list [] [] $($children)*)
+        ))
+    };
+    (SequenceRef [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::SequenceNodeRef::new($crate::btree!(@list [] [] $($children)*)))
+    };
+    (FallbackRef [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::FallbackNodeRef::new($crate::btree!(@list [] [] $($children)*)))
+    };
+    (RoundRobin [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::RoundRobinNode::new($crate::btree!(@list [] [] $($children)*)))
+    };
+    (Parallel($threshold:expr) [$($children:tt)*]) => {
+        $crate::btree!(@node $crate::ParallelNode::new(
+            $crate::btree!(@list [] [] $($children)*),
+            $threshold
+        ))
+    };
+
+    // Splits the children at the commas, collecting the tokens of each child
+    // in the second brackets and the finished children in the first.
+    (@list [$(($($done:tt)*))*] []) => {
+        ::std::vec![$($crate::btree!($($done)*)),*]
+    };
+    (@list [$($done:tt)*] [$($current:tt)+]) => {
+        $crate::btree!(@list [$($done)* ($($current)+)] [])
+    };
+    (@list [$($done:tt)*] [$($current:tt)*] , $($rest:tt)*) => {
+        $crate::btree!(@list [$($done)* ($($current)*)] [] $($rest)*)
+    };
+    (@list [$($done:tt)*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::btree!(@list [$($done)*] [$($current)* $next] $($rest)*)
+    };
+
+    (@node $node:expr) => {
+        ::std::boxed::Box::new($node) as ::std::boxed::Box<dyn $crate::BehaviorNodeBase<_, _, _> + '_>
+    };
+    ($node:expr) => {
+        $crate::btree!(@node $node)
+    };
+}
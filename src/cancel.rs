@@ -0,0 +1,95 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+/// A flag shared with the work started by a running action, which is set
+/// when the action is halted, so that the work can stop instead of leaking.
+///
+/// It can be cloned and sent to other threads and tasks.
+/// The work checks [CancellationToken::is_cancelled] or awaits
+/// [CancellationToken::cancelled], which wakes up the task when it is cancelled.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::time::Duration;
+/// let token = CancellationToken::new();
+/// let worker = std::thread::spawn({
+///     let token = token.clone();
+///     move || {
+///         while !token.is_cancelled() {
+///             std::thread::sleep(Duration::from_millis(1));
+///         }
+///     }
+/// });
+/// token.cancel();
+/// worker.join().unwrap();
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken {
+    inner: Arc<TokenInner>,
+}
+
+#[derive(Default, Debug)]
+struct TokenInner {
+    cancelled: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the work, waking up the tasks awaiting [CancellationToken::cancelled].
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        for waker in self.lock_wakers().drain(..) {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Returns a future that resolves when the token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+        }
+    }
+
+    fn lock_wakers(&self) -> std::sync::MutexGuard<'_, Vec<Waker>> {
+        self.inner
+            .wakers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The future returned by [CancellationToken::cancelled].
+pub struct Cancelled {
+    token: CancellationToken,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        let mut wakers = self.token.lock_wakers();
+        // Checks again under the lock, since cancel may have drained the
+        // wakers after the first check.
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
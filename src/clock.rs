@@ -0,0 +1,148 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// A source of the current time for time-based nodes like [TimeoutNode](crate::TimeoutNode),
+/// [DelayNode](crate::DelayNode), [Cooldown](crate::Cooldown) and `stdlib::Wait`.
+///
+/// Nodes use [SystemClock] by default.
+/// Give them a [ManualClock] instead to control the time in simulations and tests,
+/// or a [ScaledClock] to slow down or speed up their timing.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// The clock that returns [Instant::now].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only advances when told to.
+///
+/// Cloning it makes another handle to the same time, so you can keep one
+/// to advance the clock given to the nodes.
+///
+/// ```
+/// # use tiny_behavior_tree::{Clock, ManualClock};
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+/// clock.advance(Duration::from_secs(3));
+/// assert_eq!(clock.now() - start, Duration::from_secs(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Creates a clock that starts at the current time.
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A clock that runs at a multiple of the speed of another clock, for
+/// slow-motion or fast-forward gameplay and accelerated simulations.
+///
+/// Cloning it makes another handle to the same clock, so give clones of
+/// one clock to all time-based nodes of a tree, or of all trees, to apply
+/// the same time scale to them.
+/// Changing the scale does not make the time jump; only the time after the
+/// change advances at the new speed.
+///
+/// ```
+/// # use tiny_behavior_tree::{Clock, ManualClock, ScaledClock};
+/// # use std::time::Duration;
+/// let real = ManualClock::new();
+/// let clock = ScaledClock::new(real.clone());
+/// let start = clock.now();
+/// clock.set_scale(0.5);
+/// real.advance(Duration::from_secs(2));
+/// assert_eq!(clock.now() - start, Duration::from_secs(1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScaledClock<C = SystemClock> {
+    inner: C,
+    state: Arc<Mutex<ScaleState>>,
+}
+
+#[derive(Debug)]
+struct ScaleState {
+    scale: f64,
+    /// The time of the inner clock when the scale was last changed.
+    inner_since: Instant,
+    /// The time of this clock when the scale was last changed.
+    since: Instant,
+}
+
+impl<C: Clock> ScaledClock<C> {
+    /// Creates a clock that runs at the same speed as `inner` until the scale is changed.
+    pub fn new(inner: C) -> Self {
+        let now = inner.now();
+        Self {
+            inner,
+            state: Arc::new(Mutex::new(ScaleState {
+                scale: 1.,
+                inner_since: now,
+                since: now,
+            })),
+        }
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.lock().scale
+    }
+
+    /// Sets the speed relative to the inner clock, e.g. 0.5 for the half speed.
+    ///
+    /// Panics if `scale` is negative or not finite.
+    pub fn set_scale(&self, scale: f64) {
+        assert!(
+            scale.is_finite() && 0. <= scale,
+            "time scale must be a non-negative finite number"
+        );
+        let inner_now = self.inner.now();
+        let mut state = self.lock();
+        state.since = Self::scaled(&state, inner_now);
+        state.inner_since = inner_now;
+        state.scale = scale;
+    }
+
+    fn scaled(state: &ScaleState, inner_now: Instant) -> Instant {
+        state.since + (inner_now - state.inner_since).mul_f64(state.scale)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ScaleState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<C: Clock> Clock for ScaledClock<C> {
+    fn now(&self) -> Instant {
+        let inner_now = self.inner.now();
+        Self::scaled(&self.lock(), inner_now)
+    }
+}
@@ -0,0 +1,522 @@
+use crate::report;
+use crate::{
+    BehaviorNodeBase, BehaviorResult, BtError, HaltReport, MergeResult, ShortCircuitCallback,
+};
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+/// Annotations for a child of a control node, like those written by a
+/// designer in an editor.
+///
+/// The built-in control nodes store them without changing how they tick,
+/// so that tools can read them back through [BehaviorNodeBase::child_meta]
+/// and write them out again.
+/// With the `serde` feature, they are saved in definition files as the
+/// `meta` of the children, see [NodeDef](crate::loader::NodeDef).
+#[derive(Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChildMeta {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub weight: Option<f64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub priority: Option<i32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub comment: Option<String>,
+}
+
+/// Implements methods to manage the children of a control node, given the
+/// path to the field of the children vector and the type of a child.
+macro_rules! children_vec_methods {
+    ($($field:ident).+, $child:ty) => {
+        /// Reserves capacity for at least `additional` more children.
+        pub fn reserve(&mut self, additional: usize) {
+            self.$($field).+.reserve(additional);
+        }
+
+        /// Shrinks the capacity of the children as much as possible.
+        pub fn shrink_to_fit(&mut self) {
+            self.$($field).+.shrink_to_fit();
+        }
+
+        /// Appends children, e.g. one for each waypoint discovered at runtime.
+        pub fn extend(&mut self, children: impl IntoIterator<Item = $child>) {
+            self.$($field).+.extend(children);
+        }
+    };
+}
+
+pub(crate) use children_vec_methods;
+
+/// Implements methods to annotate the children of a control node, given the
+/// paths to the fields of the children vector and the map of [ChildMeta].
+macro_rules! child_meta_methods {
+    ($($children:ident).+, $($meta:ident).+) => {
+        /// Sets the annotations of the child at `index`, replacing existing ones.
+        ///
+        /// Panics if there is no child at `index`.
+        pub fn set_child_meta(&mut self, index: usize, meta: $crate::ChildMeta) {
+            assert!(
+                index < self.$($children).+.len(),
+                "no child at index {}",
+                index
+            );
+            self.$($meta).+.insert(index, meta);
+        }
+
+        /// Sets the annotations of the child at `index`, see `set_child_meta`.
+        pub fn with_child_meta(mut self, index: usize, meta: $crate::ChildMeta) -> Self {
+            self.set_child_meta(index, meta);
+            self
+        }
+    };
+}
+
+pub(crate) use child_meta_methods;
+
+/// Implements the introspection methods of [BehaviorNodeBase] for a control
+/// node with a [Composite] in the field `inner`, given the payload type of
+/// the children.
+macro_rules! composite_node_introspection {
+    ($payload:ty) => {
+        fn as_any_mut(&mut self) -> Option<&mut dyn ::std::any::Any>
+        where
+            Self: 'static,
+        {
+            Some(self)
+        }
+
+        fn children_mut(
+            &mut self,
+        ) -> Vec<&mut (dyn $crate::BehaviorNodeBase<$payload, R, F> + 'static)>
+        where
+            Self: 'static,
+        {
+            self.inner
+                .children
+                .iter_mut()
+                .map(|node| node.as_mut() as _)
+                .collect()
+        }
+
+        fn push_child(
+            &mut self,
+            child: Box<dyn $crate::BehaviorNodeBase<$payload, R, F>>,
+        ) -> Result<(), Box<dyn $crate::BehaviorNodeBase<$payload, R, F>>>
+        where
+            Self: 'static,
+        {
+            self.inner.children.push(child);
+            Ok(())
+        }
+
+        fn child_meta(&self, index: usize) -> Option<&$crate::ChildMeta> {
+            self.inner.child_meta.get(&index)
+        }
+
+        fn set_child_meta(
+            &mut self,
+            index: usize,
+            meta: $crate::ChildMeta,
+        ) -> Result<(), $crate::ChildMeta> {
+            if self.inner.children.len() <= index {
+                return Err(meta);
+            }
+            self.inner.child_meta.insert(index, meta);
+            Ok(())
+        }
+    };
+}
+
+pub(crate) use composite_node_introspection;
+
+type Child<'c, Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F> + 'c>;
+
+/// The common part of the Sequence and Fallback node variants.
+///
+/// `Acc` is the type of the result accumulated over children, i.e. `R` for
+/// sequences and `F` for fallbacks.
+pub(crate) struct Composite<'c, Payload, R, F, MR, Acc> {
+    pub(crate) children: Vec<Child<'c, Payload, R, F>>,
+    pub(crate) child_meta: BTreeMap<usize, ChildMeta>,
+    merge_result: Option<MR>,
+    short_circuit: Option<ShortCircuitCallback<'c, R, F>>,
+    max_children_per_tick: Option<usize>,
+    /// Whether to resume from a running child in the next tick.
+    memory: bool,
+    /// The position of the child to resume from and the result accumulated so far.
+    resume: Option<(usize, Acc)>,
+    /// The index of the child that returned `Running` in the last tick.
+    running: Option<usize>,
+    /// The order to tick the children in, if not the order of `children`.
+    order: Option<Vec<usize>>,
+}
+
+impl<'c, Payload, R, F, MR, Acc> Composite<'c, Payload, R, F, MR, Acc> {
+    pub(crate) fn new(children: Vec<Child<'c, Payload, R, F>>, merge_result: Option<MR>) -> Self {
+        Self {
+            children,
+            child_meta: BTreeMap::new(),
+            merge_result,
+            short_circuit: None,
+            max_children_per_tick: None,
+            memory: false,
+            resume: None,
+            running: None,
+            order: None,
+        }
+    }
+
+    pub(crate) fn set_short_circuit(&mut self, callback: ShortCircuitCallback<'c, R, F>) {
+        self.short_circuit = Some(callback);
+    }
+
+    pub(crate) fn set_max_children_per_tick(&mut self, max: usize) {
+        assert!(0 < max, "max_children_per_tick must be positive");
+        self.max_children_per_tick = Some(max);
+    }
+
+    pub(crate) fn set_memory(&mut self) {
+        self.memory = true;
+    }
+
+    /// Sets the order to tick the children in, as a permutation of their indices.
+    pub(crate) fn set_order(&mut self, order: Vec<usize>) {
+        self.order = Some(order);
+    }
+
+    pub(crate) fn order(&self) -> Option<&[usize]> {
+        self.order.as_deref()
+    }
+
+    /// Returns true if a tick is going to resume from a running child.
+    pub(crate) fn is_resuming(&self) -> bool {
+        self.resume.is_some()
+    }
+
+    /// Returns the index of the child at `position` in the ticking order.
+    fn child_at(&self, position: usize) -> usize {
+        self.order
+            .as_ref()
+            .and_then(|order| order.get(position))
+            .copied()
+            .unwrap_or(position)
+    }
+
+    pub(crate) fn halt(&mut self) -> HaltReport {
+        self.resume = None;
+        self.running = None;
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    pub(crate) fn reset(&mut self) -> HaltReport {
+        self.resume = None;
+        self.running = None;
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+
+    /// Ticks the child at `i`, forgetting that it was running in the last
+    /// tick since it reports its state again.
+    fn tick_tracked_child<E>(
+        &mut self,
+        i: usize,
+        payload: Payload,
+        tick_child: &mut impl FnMut(
+            usize,
+            &mut Child<'c, Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E> {
+        if self.running == Some(i) {
+            self.running = None;
+        }
+        report::tick_child(i, || tick_child(i, &mut self.children[i], payload))
+    }
+
+    /// Halts the child that was running in the last tick if it was not
+    /// ticked in this tick, which means it comes after the child that
+    /// stopped this tick.
+    fn halt_skipped_running(&mut self) {
+        if let Some(running) = self.running.take() {
+            self.children[running].halt();
+        }
+    }
+
+    /// Returns true if the children from `start` to `i` have used up the budget of this tick.
+    fn budget_exhausted(&self, start: usize, i: usize) -> bool {
+        self.max_children_per_tick
+            .is_some_and(|max| start + max <= i)
+    }
+}
+
+impl<'c, Payload, R, F, MR> Composite<'c, Payload, R, F, MR, R>
+where
+    Payload: Clone,
+    R: Default,
+    MR: MergeResult<R>,
+{
+    pub(crate) fn tick_sequence(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run_sequence(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    pub(crate) fn try_tick_sequence(
+        &mut self,
+        payload: Payload,
+    ) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run_sequence(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    /// Ticks children with `tick_child`, which calls either `tick` or `try_tick` of the child.
+    fn run_sequence<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut Child<'c, Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E> {
+        let (start, mut last_success) = self.resume.take().unwrap_or_default();
+        for position in start..self.children.len() {
+            if self.budget_exhausted(start, position) {
+                self.resume = Some((position, last_success));
+                return Ok(BehaviorResult::Running);
+            }
+            let i = self.child_at(position);
+            match self.tick_tracked_child(i, payload.clone(), &mut tick_child)? {
+                BehaviorResult::Success(r) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_success, r)
+                    } else {
+                        last_success = r
+                    }
+                }
+                res @ BehaviorResult::Failure(_) => {
+                    self.halt_skipped_running();
+                    report::short_circuit(i);
+                    if let Some(ref mut short_circuit) = self.short_circuit {
+                        short_circuit(i, &res);
+                    }
+                    return Ok(res);
+                }
+                BehaviorResult::Running => {
+                    self.halt_skipped_running();
+                    self.running = Some(i);
+                    if self.memory {
+                        self.resume = Some((position, last_success));
+                    }
+                    return Ok(BehaviorResult::Running);
+                }
+                BehaviorResult::Idle => (),
+            }
+        }
+        self.halt_skipped_running();
+        Ok(BehaviorResult::Success(last_success))
+    }
+
+    pub(crate) fn tick_reactive_sequence(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run_reactive_sequence(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    pub(crate) fn try_tick_reactive_sequence(
+        &mut self,
+        payload: Payload,
+    ) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run_reactive_sequence(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    /// Ticks children from the first one every time, stopping at a running child.
+    fn run_reactive_sequence<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut Child<'c, Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E> {
+        let mut last_success = R::default();
+        for position in 0..self.children.len() {
+            let i = self.child_at(position);
+            match self.tick_tracked_child(i, payload.clone(), &mut tick_child)? {
+                BehaviorResult::Success(r) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_success, r)
+                    } else {
+                        last_success = r
+                    }
+                }
+                BehaviorResult::Running => {
+                    self.halt_skipped_running();
+                    self.running = Some(i);
+                    return Ok(BehaviorResult::Running);
+                }
+                res @ BehaviorResult::Failure(_) => {
+                    self.halt_skipped_running();
+                    report::short_circuit(i);
+                    if let Some(ref mut short_circuit) = self.short_circuit {
+                        short_circuit(i, &res);
+                    }
+                    return Ok(res);
+                }
+                BehaviorResult::Idle => (),
+            }
+        }
+        self.running = None;
+        Ok(BehaviorResult::Success(last_success))
+    }
+}
+
+impl<'c, Payload, R, F, MR> Composite<'c, Payload, R, F, MR, F>
+where
+    Payload: Clone,
+    F: Default,
+    MR: MergeResult<F>,
+{
+    pub(crate) fn tick_fallback(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run_fallback(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    pub(crate) fn try_tick_fallback(
+        &mut self,
+        payload: Payload,
+    ) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run_fallback(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    /// Ticks children with `tick_child`, which calls either `tick` or `try_tick` of the child.
+    fn run_fallback<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut Child<'c, Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E> {
+        let (start, mut last_failure) = self.resume.take().unwrap_or_default();
+        for position in start..self.children.len() {
+            if self.budget_exhausted(start, position) {
+                self.resume = Some((position, last_failure));
+                return Ok(BehaviorResult::Running);
+            }
+            let i = self.child_at(position);
+            match self.tick_tracked_child(i, payload.clone(), &mut tick_child)? {
+                res @ BehaviorResult::Success(_) => {
+                    self.halt_skipped_running();
+                    report::short_circuit(i);
+                    if let Some(ref mut short_circuit) = self.short_circuit {
+                        short_circuit(i, &res);
+                    }
+                    return Ok(res);
+                }
+                BehaviorResult::Failure(f) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_failure, f)
+                    } else {
+                        last_failure = f
+                    }
+                }
+                BehaviorResult::Running => {
+                    self.halt_skipped_running();
+                    self.running = Some(i);
+                    if self.memory {
+                        self.resume = Some((position, last_failure));
+                    }
+                    return Ok(BehaviorResult::Running);
+                }
+                BehaviorResult::Idle => (),
+            }
+        }
+        self.halt_skipped_running();
+        Ok(BehaviorResult::Failure(last_failure))
+    }
+
+    pub(crate) fn tick_reactive_fallback(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run_reactive_fallback(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    pub(crate) fn try_tick_reactive_fallback(
+        &mut self,
+        payload: Payload,
+    ) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run_reactive_fallback(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    /// Ticks children from the first one every time, stopping at a running child.
+    fn run_reactive_fallback<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut Child<'c, Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E> {
+        let mut last_failure = F::default();
+        for position in 0..self.children.len() {
+            let i = self.child_at(position);
+            match self.tick_tracked_child(i, payload.clone(), &mut tick_child)? {
+                res @ BehaviorResult::Success(_) => {
+                    self.halt_skipped_running();
+                    report::short_circuit(i);
+                    if let Some(ref mut short_circuit) = self.short_circuit {
+                        short_circuit(i, &res);
+                    }
+                    return Ok(res);
+                }
+                BehaviorResult::Running => {
+                    self.halt_skipped_running();
+                    self.running = Some(i);
+                    return Ok(BehaviorResult::Running);
+                }
+                BehaviorResult::Failure(f) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_failure, f)
+                    } else {
+                        last_failure = f
+                    }
+                }
+                BehaviorResult::Idle => (),
+            }
+        }
+        self.running = None;
+        Ok(BehaviorResult::Failure(last_failure))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{BehaviorNodeBase, BehaviorResult};
+
+/// A leaf node that succeeds if the predicate holds for the payload, and
+/// fails otherwise.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Agent {
+///     health: u32,
+/// }
+///
+/// let mut alive = ConditionNode::new(|agent: &Agent| 0 < agent.health);
+/// assert_eq!(alive.tick(&Agent { health: 3 }), BehaviorResult::Success(()));
+/// assert_eq!(alive.tick(&Agent { health: 0 }), BehaviorResult::Failure(()));
+/// ```
+pub struct ConditionNode<C> {
+    predicate: C,
+}
+
+impl<C> ConditionNode<C> {
+    pub fn new(predicate: C) -> Self {
+        Self { predicate }
+    }
+}
+
+impl<Payload, C> BehaviorNodeBase<Payload, (), ()> for ConditionNode<C>
+where
+    C: FnMut(Payload) -> bool,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<(), ()> {
+        if (self.predicate)(payload) {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
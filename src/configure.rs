@@ -0,0 +1,168 @@
+use crate::{BehaviorNodeBase, EnableSwitch};
+use std::any::type_name;
+use std::fmt;
+
+/// The error type returned by [Configure::configure].
+#[derive(PartialEq, Debug)]
+pub enum ConfigureError {
+    /// The path could not be parsed as slash-separated child indices.
+    InvalidPath(String),
+    /// There is no node at the path.
+    NotFound(String),
+    /// The node at the path does not expose itself through
+    /// [BehaviorNodeBase::as_any_mut].
+    NotConfigurable(String),
+    /// The node at the path is not of the requested type.
+    TypeMismatch {
+        path: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ConfigureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidPath(path) => write!(f, "Invalid node path \"{}\"", path),
+            Self::NotFound(path) => write!(f, "No node at path \"{}\"", path),
+            Self::NotConfigurable(path) => {
+                write!(f, "Node at path \"{}\" is not configurable", path)
+            }
+            Self::TypeMismatch { path, expected } => {
+                write!(f, "Node at path \"{}\" is not a {}", path, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigureError {}
+
+/// Runtime reconfiguration of nodes in a tree.
+///
+/// This trait is implemented for every node that is `'static`, so you can
+/// call it on the root of your tree, either boxed or not.
+///
+/// A node is addressed by a path of child indices separated by slashes,
+/// e.g. `"0/2"` is the third child of the first child of the root.
+/// An empty path refers to the root itself.
+/// Only children returned by [BehaviorNodeBase::children_mut] can be reached.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::any::Any;
+/// struct Threshold(i32);
+///
+/// impl BehaviorNodeBase<i32, (), ()> for Threshold {
+///     fn tick(&mut self, payload: i32) -> BehaviorResult<(), ()> {
+///         if self.0 <= payload {
+///             BehaviorResult::Success(())
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+///
+///     fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+///         Some(self)
+///     }
+/// }
+///
+/// let mut tree = SequenceNode::<i32, (), (), _>::new([
+///     Box::new(Threshold(10)) as Box<dyn BehaviorNodeBase<i32, (), ()>>,
+/// ]);
+/// assert_eq!(tree.tick(5), BehaviorResult::Failure(()));
+///
+/// tree.configure::<Threshold>("0", |node| node.0 = 3).unwrap();
+/// assert_eq!(tree.tick(5), BehaviorResult::Success(()));
+/// ```
+pub trait Configure<Payload, R, F> {
+    /// Finds the node at `path`, downcasts it to `T` and applies `f` to it.
+    fn configure<T: 'static>(
+        &mut self,
+        path: &str,
+        f: impl FnOnce(&mut T),
+    ) -> Result<(), ConfigureError>;
+
+    /// Enables or disables the [EnableSwitch] at `path`.
+    ///
+    /// Disabling it halts the wrapped node, whose report is discarded.
+    fn set_enabled(&mut self, path: &str, enabled: bool) -> Result<(), ConfigureError>
+    where
+        Payload: 'static,
+        R: 'static,
+        F: 'static,
+    {
+        self.configure::<EnableSwitch<Payload, R, F>>(path, |switch| {
+            switch.set_enabled(enabled);
+        })
+    }
+}
+
+impl<N, Payload, R, F> Configure<Payload, R, F> for N
+where
+    N: BehaviorNodeBase<Payload, R, F> + ?Sized + 'static,
+    Payload: 'static,
+    R: 'static,
+    F: 'static,
+{
+    fn configure<T: 'static>(
+        &mut self,
+        path: &str,
+        f: impl FnOnce(&mut T),
+    ) -> Result<(), ConfigureError> {
+        let indices =
+            parse_path(path).ok_or_else(|| ConfigureError::InvalidPath(path.to_owned()))?;
+        configure_at(self, path, &indices, f)
+    }
+}
+
+/// Returns the path of the child at `index` of the node at `path`.
+pub(crate) fn child_path(path: &str, index: usize) -> String {
+    if path.is_empty() {
+        index.to_string()
+    } else {
+        format!("{}/{}", path, index)
+    }
+}
+
+/// Parses a node path of slash-separated child indices.
+pub(crate) fn parse_path(path: &str) -> Option<Vec<usize>> {
+    if path.is_empty() {
+        return Some(vec![]);
+    }
+    path.split('/').map(|s| s.parse().ok()).collect()
+}
+
+fn configure_at<N, Payload, R, F, T: 'static>(
+    node: &mut N,
+    path: &str,
+    indices: &[usize],
+    f: impl FnOnce(&mut T),
+) -> Result<(), ConfigureError>
+where
+    N: BehaviorNodeBase<Payload, R, F> + ?Sized + 'static,
+    Payload: 'static,
+    R: 'static,
+    F: 'static,
+{
+    match indices.split_first() {
+        None => {
+            let any = node
+                .as_any_mut()
+                .ok_or_else(|| ConfigureError::NotConfigurable(path.to_owned()))?;
+            let node = any
+                .downcast_mut::<T>()
+                .ok_or_else(|| ConfigureError::TypeMismatch {
+                    path: path.to_owned(),
+                    expected: type_name::<T>(),
+                })?;
+            f(node);
+            Ok(())
+        }
+        Some((&index, rest)) => {
+            let mut children = node.children_mut();
+            if children.len() <= index {
+                return Err(ConfigureError::NotFound(path.to_owned()));
+            }
+            configure_at(children.swap_remove(index), path, rest, f)
+        }
+    }
+}
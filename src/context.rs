@@ -0,0 +1,220 @@
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, HaltReport};
+
+/// A variant of [BehaviorNodeBase] whose nodes are given a context besides
+/// the payload, like a clock, a random number generator, a logger or a
+/// blackboard, so that long-lived services do not have to be put in the payload.
+///
+/// The context is borrowed mutably for each tick, so the nodes can use it
+/// without [RefCell](std::cell::RefCell), while the payload is the data of
+/// the tick as in [BehaviorNodeBase].
+/// Existing nodes can be used in a context tree by wrapping them in [IgnoreContext].
+///
+/// [ContextSequenceNode] and [ContextFallbackNode] pass the context down to
+/// their children, and [WithContext] owns the context to turn a context tree
+/// into a [BehaviorNodeBase], e.g. to give it to a [Tree](crate::Tree).
+///
+/// ```
+/// # use tiny_behavior_tree::{testing::AlwaysSuccess, *};
+/// struct Services {
+///     log: Vec<String>,
+/// }
+///
+/// struct Greet;
+///
+/// impl BehaviorNode<Services, &str, (), ()> for Greet {
+///     fn tick(&mut self, ctx: &mut Services, name: &str) -> BehaviorResult<(), ()> {
+///         ctx.log.push(format!("Hello, {}!", name));
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// type Node = Box<dyn BehaviorNode<Services, &'static str, (), ()>>;
+///
+/// let mut tree = WithContext::new(
+///     Services { log: vec![] },
+///     ContextSequenceNode::new([
+///         Box::new(IgnoreContext(AlwaysSuccess(()))) as Node,
+///         Box::new(Greet),
+///     ]),
+/// );
+/// assert_eq!(tree.tick("world"), BehaviorResult::Success(()));
+/// assert_eq!(tree.ctx().log, ["Hello, world!"]);
+/// ```
+pub trait BehaviorNode<Ctx, Payload, R, F> {
+    fn tick(&mut self, ctx: &mut Ctx, payload: Payload) -> BehaviorResult<R, F>;
+
+    /// Stops any ongoing action of this node and its descendants, see
+    /// [BehaviorNodeBase::halt].
+    ///
+    /// The default implementation does nothing and reports a clean stop.
+    fn halt(&mut self, _ctx: &mut Ctx) -> HaltReport {
+        HaltReport::clean()
+    }
+}
+
+/// Uses a [BehaviorNodeBase] as a [BehaviorNode] for any context, which it
+/// ignores, so that existing leaves can be used in a context tree.
+pub struct IgnoreContext<T>(pub T);
+
+impl<Ctx, Payload, R, F, T> BehaviorNode<Ctx, Payload, R, F> for IgnoreContext<T>
+where
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, _ctx: &mut Ctx, payload: Payload) -> BehaviorResult<R, F> {
+        self.0.tick(payload)
+    }
+
+    fn halt(&mut self, _ctx: &mut Ctx) -> HaltReport {
+        self.0.halt()
+    }
+}
+
+type Child<Ctx, Payload, R, F> = Box<dyn BehaviorNode<Ctx, Payload, R, F>>;
+
+/// A sequence of [BehaviorNode]s, which passes the context down to the children.
+///
+/// It ticks the children like [SequenceNode](crate::SequenceNode), starting
+/// over from the first child in the next tick after a running child, and
+/// returns the last result in `R`.
+pub struct ContextSequenceNode<Ctx, Payload, R, F> {
+    children: Vec<Child<Ctx, Payload, R, F>>,
+}
+
+impl<Ctx, Payload, R, F> ContextSequenceNode<Ctx, Payload, R, F> {
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Child<Ctx, Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+        }
+    }
+}
+
+impl<Ctx, Payload, R, F> BehaviorNode<Ctx, Payload, R, F>
+    for ContextSequenceNode<Ctx, Payload, R, F>
+where
+    Payload: Clone,
+    R: Default,
+{
+    fn tick(&mut self, ctx: &mut Ctx, payload: Payload) -> BehaviorResult<R, F> {
+        let mut last_success = R::default();
+        for (i, child) in self.children.iter_mut().enumerate() {
+            match tick_child(i, child, ctx, payload.clone()) {
+                BehaviorResult::Success(r) => last_success = r,
+                res @ BehaviorResult::Failure(_) => {
+                    report::short_circuit(i);
+                    return res;
+                }
+                BehaviorResult::Running => return BehaviorResult::Running,
+                BehaviorResult::Idle => (),
+            }
+        }
+        BehaviorResult::Success(last_success)
+    }
+
+    fn halt(&mut self, ctx: &mut Ctx) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt(ctx)))
+    }
+}
+
+/// A fallback of [BehaviorNode]s, which passes the context down to the children.
+///
+/// It ticks the children like [FallbackNode](crate::FallbackNode), starting
+/// over from the first child in the next tick after a running child, and
+/// returns the last result in `F`.
+pub struct ContextFallbackNode<Ctx, Payload, R, F> {
+    children: Vec<Child<Ctx, Payload, R, F>>,
+}
+
+impl<Ctx, Payload, R, F> ContextFallbackNode<Ctx, Payload, R, F> {
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Child<Ctx, Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+        }
+    }
+}
+
+impl<Ctx, Payload, R, F> BehaviorNode<Ctx, Payload, R, F>
+    for ContextFallbackNode<Ctx, Payload, R, F>
+where
+    Payload: Clone,
+    F: Default,
+{
+    fn tick(&mut self, ctx: &mut Ctx, payload: Payload) -> BehaviorResult<R, F> {
+        let mut last_failure = F::default();
+        for (i, child) in self.children.iter_mut().enumerate() {
+            match tick_child(i, child, ctx, payload.clone()) {
+                res @ BehaviorResult::Success(_) => {
+                    report::short_circuit(i);
+                    return res;
+                }
+                BehaviorResult::Failure(f) => last_failure = f,
+                BehaviorResult::Running => return BehaviorResult::Running,
+                BehaviorResult::Idle => (),
+            }
+        }
+        BehaviorResult::Failure(last_failure)
+    }
+
+    fn halt(&mut self, ctx: &mut Ctx) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt(ctx)))
+    }
+}
+
+fn tick_child<Ctx, Payload, R, F>(
+    i: usize,
+    child: &mut Child<Ctx, Payload, R, F>,
+    ctx: &mut Ctx,
+    payload: Payload,
+) -> BehaviorResult<R, F> {
+    match report::tick_child(i, || {
+        Ok::<_, std::convert::Infallible>(child.tick(ctx, payload))
+    }) {
+        Ok(res) => res,
+        Err(e) => match e {},
+    }
+}
+
+/// Owns the context of a [BehaviorNode] tree, so that the tree can be
+/// ticked as a [BehaviorNodeBase] with the payload alone.
+pub struct WithContext<Ctx, T> {
+    ctx: Ctx,
+    node: T,
+}
+
+impl<Ctx, T> WithContext<Ctx, T> {
+    pub fn new(ctx: Ctx, node: T) -> Self {
+        Self { ctx, node }
+    }
+
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
+    pub fn ctx_mut(&mut self) -> &mut Ctx {
+        &mut self.ctx
+    }
+
+    /// Returns the context and the root node.
+    pub fn into_inner(self) -> (Ctx, T) {
+        (self.ctx, self.node)
+    }
+}
+
+impl<Ctx, Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for WithContext<Ctx, T>
+where
+    T: BehaviorNode<Ctx, Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.node.tick(&mut self.ctx, payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt(&mut self.ctx)
+    }
+}
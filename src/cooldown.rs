@@ -0,0 +1,188 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, Clock, HaltReport, SystemClock};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Named cooldowns shared among [Cooldown] decorators, possibly in different
+/// trees and threads.
+///
+/// Cloning a registry makes another handle to the same cooldowns.
+/// Use [CooldownRegistry::global] for limits that apply to the whole process,
+/// like "at most one taunt bark per 10 seconds across all enemies".
+#[derive(Clone, Default, Debug)]
+pub struct CooldownRegistry {
+    ready_at: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl CooldownRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the process-wide registry.
+    pub fn global() -> &'static CooldownRegistry {
+        static GLOBAL: OnceLock<CooldownRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(CooldownRegistry::new)
+    }
+
+    /// Returns true if the cooldown named `key` is not active at `now`.
+    pub fn is_ready(&self, key: &str, now: Instant) -> bool {
+        self.lock().get(key).is_none_or(|ready_at| *ready_at <= now)
+    }
+
+    /// Activates the cooldown named `key` until `ready_at`.
+    pub fn trigger(&self, key: &str, ready_at: Instant) {
+        self.lock().insert(key.to_owned(), ready_at);
+    }
+
+    /// Clears the cooldown named `key`.
+    pub fn reset(&self, key: &str) {
+        self.lock().remove(key);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Instant>> {
+        // The map is never left inconsistent, so a poisoned lock is fine to use.
+        self.ready_at.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+enum CooldownState {
+    Local(Option<Instant>),
+    Shared {
+        registry: CooldownRegistry,
+        key: String,
+    },
+}
+
+/// A decorator that fails without ticking the child node for a while after
+/// the child succeeded.
+///
+/// The cooldown can be local to this node, or shared by name with other
+/// `Cooldown` nodes through a [CooldownRegistry].
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// # use std::time::Duration;
+/// let registry = CooldownRegistry::new();
+/// let mut enemy1 = Cooldown::shared(&registry, "taunt", Duration::from_secs(10), AlwaysSuccess(()));
+/// let mut enemy2 = Cooldown::shared(&registry, "taunt", Duration::from_secs(10), AlwaysSuccess(()));
+///
+/// let res: BehaviorResult<(), ()> = enemy1.tick(());
+/// assert_eq!(res, BehaviorResult::Success(()));
+/// let res: BehaviorResult<(), ()> = enemy2.tick(());
+/// assert_eq!(res, BehaviorResult::Failure(()));
+/// ```
+pub struct Cooldown<T, C = SystemClock> {
+    duration: Duration,
+    state: CooldownState,
+    node: T,
+    clock: C,
+}
+
+impl<T> Cooldown<T> {
+    /// Creates a decorator with its own cooldown.
+    pub fn new(duration: Duration, node: T) -> Self {
+        Self {
+            duration,
+            state: CooldownState::Local(None),
+            node,
+            clock: SystemClock,
+        }
+    }
+
+    /// Creates a decorator that uses the cooldown named `key` in `registry`.
+    pub fn shared(
+        registry: &CooldownRegistry,
+        key: impl Into<String>,
+        duration: Duration,
+        node: T,
+    ) -> Self {
+        Self {
+            duration,
+            state: CooldownState::Shared {
+                registry: registry.clone(),
+                key: key.into(),
+            },
+            node,
+            clock: SystemClock,
+        }
+    }
+}
+
+impl<T, C: Clock> Cooldown<T, C> {
+    /// Measures the cooldown with `clock` instead of the system clock.
+    ///
+    /// All decorators sharing a cooldown through a [CooldownRegistry] should use the same clock.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> Cooldown<T, C2> {
+        Cooldown {
+            duration: self.duration,
+            state: self.state,
+            node: self.node,
+            clock,
+        }
+    }
+
+    fn is_ready(&self, now: Instant) -> bool {
+        match &self.state {
+            CooldownState::Local(ready_at) => ready_at.is_none_or(|ready_at| ready_at <= now),
+            CooldownState::Shared { registry, key } => registry.is_ready(key, now),
+        }
+    }
+
+    fn trigger(&mut self, now: Instant) {
+        let ready_at = now + self.duration;
+        match &mut self.state {
+            CooldownState::Local(local) => *local = Some(ready_at),
+            CooldownState::Shared { registry, key } => registry.trigger(key, ready_at),
+        }
+    }
+
+    fn after_tick<R, F>(&mut self, res: &BehaviorResult<R, F>) {
+        if let BehaviorResult::Success(_) = res {
+            self.trigger(self.clock.now());
+        }
+    }
+}
+
+impl<Payload, R, F, T, C> BehaviorNodeBase<Payload, R, F> for Cooldown<T, C>
+where
+    F: Default,
+    T: BehaviorNodeBase<Payload, R, F>,
+    C: Clock,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        if !self.is_ready(self.clock.now()) {
+            return BehaviorResult::Failure(F::default());
+        }
+        let res = self.node.tick(payload);
+        self.after_tick(&res);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        if !self.is_ready(self.clock.now()) {
+            return Ok(BehaviorResult::Failure(F::default()));
+        }
+        let res = self.node.try_tick(payload)?;
+        self.after_tick(&res);
+        Ok(res)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    /// Resets the child and a local cooldown, but not a shared one, which may be
+    /// used by other nodes.
+    fn reset(&mut self) -> HaltReport {
+        if let CooldownState::Local(ready_at) = &mut self.state {
+            *ready_at = None;
+        }
+        self.node.reset()
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![("duration", format!("{:?}", self.duration))]
+    }
+}
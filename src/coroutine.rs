@@ -0,0 +1,161 @@
+use crate::{BehaviorNodeBase, BehaviorResult, CancellationToken, HaltReport};
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+/// The handle given to the coroutine of a [CoroutineActionNode], which waits
+/// for the next tick.
+pub struct Coroutine<Payload> {
+    payload: Rc<Cell<Option<Payload>>>,
+    token: CancellationToken,
+}
+
+impl<Payload> Clone for Coroutine<Payload> {
+    fn clone(&self) -> Self {
+        Self {
+            payload: self.payload.clone(),
+            token: self.token.clone(),
+        }
+    }
+}
+
+impl<Payload> Coroutine<Payload> {
+    /// Returns the token that is cancelled when the node is halted.
+    ///
+    /// The coroutine itself is dropped by the halt, so give clones of the
+    /// token to the threads and tasks it started, to stop them as well.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Returns a future that makes the node return `Running` and resolves to
+    /// the payload of the next tick.
+    pub fn next_tick(&self) -> NextTick<Payload> {
+        self.payload.set(None);
+        NextTick {
+            payload: self.payload.clone(),
+        }
+    }
+}
+
+/// The future returned by [Coroutine::next_tick].
+pub struct NextTick<Payload> {
+    payload: Rc<Cell<Option<Payload>>>,
+}
+
+impl<Payload> Future for NextTick<Payload> {
+    type Output = Payload;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Payload> {
+        match self.payload.take() {
+            Some(payload) => Poll::Ready(payload),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// A leaf node that runs an action written as straight-line async code,
+/// polling it one step per tick instead of hand-rolling a state machine.
+///
+/// The first tick calls `start` with the payload and a [Coroutine] handle to
+/// create the future, which the node polls in every tick while it is
+/// pending, returning `Running`.
+/// The coroutine awaits [Coroutine::next_tick] to end the step of the tick and
+/// receive the payload of the next one.
+/// When the future resolves, the node returns `Success` for `Ok` and
+/// `Failure` for `Err`, and the next tick starts a new coroutine.
+/// Halting the node cancels the [CancellationToken] of the coroutine and
+/// drops the future, so the coroutine is not resumed.
+///
+/// The futures are polled without an executor, so they can await other
+/// futures that are woken by the ticks alone, like [Coroutine::next_tick]; a
+/// pending future of another runtime just keeps the node `Running`.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// let mut node = CoroutineActionNode::new(|speed: u32, co: Coroutine<u32>| async move {
+///     let mut distance = speed;
+///     while distance < 10 {
+///         distance += co.next_tick().await;
+///     }
+///     Ok::<_, ()>(distance)
+/// });
+///
+/// assert_eq!(node.tick(4), BehaviorResult::Running);
+/// assert_eq!(node.tick(4), BehaviorResult::Running);
+/// assert_eq!(node.tick(4), BehaviorResult::Success(12));
+/// ```
+pub struct CoroutineActionNode<Payload, S, Fut> {
+    start: S,
+    running: Option<Pin<Box<Fut>>>,
+    payload: Rc<Cell<Option<Payload>>>,
+    token: CancellationToken,
+}
+
+impl<Payload, S, Fut> CoroutineActionNode<Payload, S, Fut>
+where
+    S: FnMut(Payload, Coroutine<Payload>) -> Fut,
+    Fut: Future,
+{
+    pub fn new(start: S) -> Self {
+        Self {
+            start,
+            running: None,
+            payload: Rc::new(Cell::new(None)),
+            token: CancellationToken::new(),
+        }
+    }
+
+    /// Returns true if the coroutine has started and not completed yet.
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+}
+
+impl<Payload, R, F, S, Fut> BehaviorNodeBase<Payload, R, F> for CoroutineActionNode<Payload, S, Fut>
+where
+    S: FnMut(Payload, Coroutine<Payload>) -> Fut,
+    Fut: Future<Output = Result<R, F>>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        let running = match &mut self.running {
+            Some(running) => {
+                self.payload.set(Some(payload));
+                running
+            }
+            None => {
+                self.token = CancellationToken::new();
+                let co = Coroutine {
+                    payload: self.payload.clone(),
+                    token: self.token.clone(),
+                };
+                self.running.insert(Box::pin((self.start)(payload, co)))
+            }
+        };
+        match running
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Pending => BehaviorResult::Running,
+            Poll::Ready(result) => {
+                self.running = None;
+                self.payload.set(None);
+                match result {
+                    Ok(r) => BehaviorResult::Success(r),
+                    Err(f) => BehaviorResult::Failure(f),
+                }
+            }
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        if self.running.is_some() {
+            self.token.cancel();
+            self.running = None;
+        }
+        self.payload.set(None);
+        HaltReport::clean()
+    }
+}
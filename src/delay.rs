@@ -0,0 +1,96 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, Clock, HaltReport, SystemClock};
+use std::time::{Duration, Instant};
+
+/// A decorator that returns `Running` until the given duration has passed
+/// since its first tick, then ticks the child node.
+///
+/// The delay starts over after the child completed or the node was halted,
+/// with the time measured by the [Clock] given to [DelayNode::with_clock].
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let mut node = DelayNode::with_clock(Duration::from_secs(2), clock.clone(), AlwaysSuccess(()));
+///
+/// let res: BehaviorResult<(), ()> = node.tick(());
+/// assert_eq!(res, BehaviorResult::Running);
+/// clock.advance(Duration::from_secs(2));
+/// let res: BehaviorResult<(), ()> = node.tick(());
+/// assert_eq!(res, BehaviorResult::Success(()));
+/// ```
+pub struct DelayNode<T, C = SystemClock> {
+    node: T,
+    duration: Duration,
+    clock: C,
+    started: Option<Instant>,
+}
+
+impl<T> DelayNode<T> {
+    pub fn new(duration: Duration, node: T) -> Self {
+        Self::with_clock(duration, SystemClock, node)
+    }
+}
+
+impl<T, C: Clock> DelayNode<T, C> {
+    pub fn with_clock(duration: Duration, clock: C, node: T) -> Self {
+        Self {
+            node,
+            duration,
+            clock,
+            started: None,
+        }
+    }
+
+    /// Returns true if the delay has passed, starting the measurement if it has not started.
+    fn elapsed(&mut self) -> bool {
+        let now = self.clock.now();
+        let started = *self.started.get_or_insert(now);
+        self.duration <= now - started
+    }
+
+    fn after_tick<R, F>(&mut self, res: &BehaviorResult<R, F>) {
+        if !matches!(res, BehaviorResult::Running) {
+            self.started = None;
+        }
+    }
+}
+
+impl<Payload, R, F, T, C> BehaviorNodeBase<Payload, R, F> for DelayNode<T, C>
+where
+    T: BehaviorNodeBase<Payload, R, F>,
+    C: Clock,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        if !self.elapsed() {
+            return BehaviorResult::Running;
+        }
+        let res = self.node.tick(payload);
+        self.after_tick(&res);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        if !self.elapsed() {
+            return Ok(BehaviorResult::Running);
+        }
+        let res = self.node.try_tick(payload)?;
+        self.after_tick(&res);
+        Ok(res)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.started = None;
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.started = None;
+        self.node.reset()
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![("duration", format!("{:?}", self.duration))]
+    }
+}
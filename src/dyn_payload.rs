@@ -0,0 +1,267 @@
+use crate::{BehaviorNodeBase, BehaviorResult};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A value in [DynPayload].
+#[derive(Clone, PartialEq, Debug)]
+pub enum DynValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl DynValue {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a float, converting an integer.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Int(i) => Some(*i as f64),
+            Self::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Values of the same type are ordered, and integers and floats are
+/// compared as floats. Other combinations are not comparable.
+impl PartialOrd for DynValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Bool(a), Self::Bool(b)) => a.partial_cmp(b),
+            (Self::Int(a), Self::Int(b)) => a.partial_cmp(b),
+            (Self::Str(a), Self::Str(b)) => a.partial_cmp(b),
+            _ => self.as_float()?.partial_cmp(&other.as_float()?),
+        }
+    }
+}
+
+impl From<bool> for DynValue {
+    fn from(b: bool) -> Self {
+        Self::Bool(b)
+    }
+}
+
+impl From<i64> for DynValue {
+    fn from(i: i64) -> Self {
+        Self::Int(i)
+    }
+}
+
+impl From<i32> for DynValue {
+    fn from(i: i32) -> Self {
+        Self::Int(i.into())
+    }
+}
+
+impl From<f64> for DynValue {
+    fn from(f: f64) -> Self {
+        Self::Float(f)
+    }
+}
+
+impl From<&str> for DynValue {
+    fn from(s: &str) -> Self {
+        Self::Str(s.to_owned())
+    }
+}
+
+impl From<String> for DynValue {
+    fn from(s: String) -> Self {
+        Self::Str(s)
+    }
+}
+
+/// A payload of values keyed by names, for trees whose data is defined at
+/// runtime, e.g. by mods, rather than by payload structs compiled in.
+///
+/// The leaves in this module, [DynCondition] and [DynSet], operate on
+/// `&RefCell<DynPayload>`, so a tree can be made of them without any
+/// application specific code.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::RefCell;
+/// type Node<'a> = Box<dyn BehaviorNodeBase<&'a RefCell<DynPayload>, (), ()> + 'a>;
+///
+/// let mut payload = DynPayload::new();
+/// payload.set("health", 30);
+/// let payload = RefCell::new(payload);
+///
+/// let mut tree = SequenceNodeRef::new([
+///     Box::new(DynCondition::new("health", Comparison::Lt, 50)) as Node,
+///     Box::new(DynSet::new("state", "flee")),
+/// ]);
+/// assert_eq!(tree.tick(&payload), BehaviorResult::Success(()));
+/// assert_eq!(payload.borrow().get("state"), Some(&DynValue::from("flee")));
+/// ```
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct DynPayload {
+    values: HashMap<String, DynValue>,
+}
+
+impl DynPayload {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&DynValue> {
+        self.values.get(key)
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<DynValue>) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<DynValue> {
+        self.values.remove(key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+}
+
+/// A comparison operator used by [DynCondition].
+///
+/// It can be parsed from `==`, `!=`, `<`, `<=`, `>` and `>=`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    /// Returns true if `lhs` and `rhs` satisfy this comparison.
+    ///
+    /// Values that are not comparable satisfy only [Comparison::Ne].
+    pub fn test(self, lhs: &DynValue, rhs: &DynValue) -> bool {
+        let Some(ordering) = lhs.partial_cmp(rhs) else {
+            return self == Self::Ne;
+        };
+        match self {
+            Self::Eq => ordering == Ordering::Equal,
+            Self::Ne => ordering != Ordering::Equal,
+            Self::Lt => ordering == Ordering::Less,
+            Self::Le => ordering != Ordering::Greater,
+            Self::Gt => ordering == Ordering::Greater,
+            Self::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// The error type returned when parsing a [Comparison] fails.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseComparisonError(String);
+
+impl fmt::Display for ParseComparisonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown comparison operator \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ParseComparisonError {}
+
+impl FromStr for Comparison {
+    type Err = ParseComparisonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "==" => Self::Eq,
+            "!=" => Self::Ne,
+            "<" => Self::Lt,
+            "<=" => Self::Le,
+            ">" => Self::Gt,
+            ">=" => Self::Ge,
+            _ => return Err(ParseComparisonError(s.to_owned())),
+        })
+    }
+}
+
+/// Returns `Success` if the value of the entry satisfies the comparison with
+/// the given value, otherwise `Failure`, including when the entry is missing.
+pub struct DynCondition {
+    key: String,
+    op: Comparison,
+    value: DynValue,
+}
+
+impl DynCondition {
+    pub fn new(key: impl Into<String>, op: Comparison, value: impl Into<DynValue>) -> Self {
+        Self {
+            key: key.into(),
+            op,
+            value: value.into(),
+        }
+    }
+}
+
+impl<'a, R, F> BehaviorNodeBase<&'a RefCell<DynPayload>, R, F> for DynCondition
+where
+    R: Default,
+    F: Default,
+{
+    fn tick(&mut self, payload: &'a RefCell<DynPayload>) -> BehaviorResult<R, F> {
+        let payload = payload.borrow();
+        match payload.get(&self.key) {
+            Some(value) if self.op.test(value, &self.value) => {
+                BehaviorResult::Success(R::default())
+            }
+            _ => BehaviorResult::Failure(F::default()),
+        }
+    }
+}
+
+/// Sets the given value to the entry and returns `Success`.
+pub struct DynSet {
+    key: String,
+    value: DynValue,
+}
+
+impl DynSet {
+    pub fn new(key: impl Into<String>, value: impl Into<DynValue>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl<'a, R, F> BehaviorNodeBase<&'a RefCell<DynPayload>, R, F> for DynSet
+where
+    R: Default,
+{
+    fn tick(&mut self, payload: &'a RefCell<DynPayload>) -> BehaviorResult<R, F> {
+        payload
+            .borrow_mut()
+            .set(self.key.as_str(), self.value.clone());
+        BehaviorResult::Success(R::default())
+    }
+}
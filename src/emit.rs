@@ -0,0 +1,54 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError};
+use std::sync::mpsc::Sender;
+
+/// A leaf node that sends an event to a channel and succeeds.
+///
+/// The event is built from the payload by a closure.
+/// It is the sanctioned way for a tree to command external systems,
+/// like animation or audio, without mutating shared state.
+/// If the receiver has been dropped, the node fails, or
+/// [try_tick](BehaviorNodeBase::try_tick) returns [BtErrorKind::Disconnected](crate::BtErrorKind::Disconnected).
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::sync::mpsc::channel;
+/// #[derive(Debug, PartialEq)]
+/// enum Sound {
+///     Footstep(f64),
+/// }
+///
+/// let (sender, receiver) = channel();
+/// let mut node = EmitEvent::new(sender, |speed: f64| Sound::Footstep(speed * 0.1));
+/// assert_eq!(
+///     BehaviorNodeBase::<_, (), ()>::tick(&mut node, 5.),
+///     BehaviorResult::Success(())
+/// );
+/// assert_eq!(receiver.try_recv(), Ok(Sound::Footstep(0.5)));
+/// ```
+pub struct EmitEvent<T, B> {
+    sender: Sender<T>,
+    build: B,
+}
+
+impl<T, B> EmitEvent<T, B> {
+    pub fn new(sender: Sender<T>, build: B) -> Self {
+        Self { sender, build }
+    }
+}
+
+impl<Payload, R, F, T, B> BehaviorNodeBase<Payload, R, F> for EmitEvent<T, B>
+where
+    R: Default,
+    F: Default,
+    B: FnMut(Payload) -> T,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.try_tick(payload)
+            .unwrap_or_else(|_| BehaviorResult::Failure(F::default()))
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.sender.send((self.build)(payload))?;
+        Ok(BehaviorResult::Success(R::default()))
+    }
+}
@@ -0,0 +1,116 @@
+use std::cell::{BorrowError, BorrowMutError};
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc::SendError;
+use std::sync::PoisonError;
+
+/// An error of the infrastructure that a node depends on, returned by
+/// [BehaviorNodeBase::try_tick](crate::BehaviorNodeBase::try_tick).
+///
+/// Unlike [BehaviorResult::Failure](crate::BehaviorResult::Failure), it means
+/// that the node could not run at all, not that its behavior did not succeed.
+#[derive(Debug)]
+pub struct BtError {
+    path: String,
+    kind: BtErrorKind,
+}
+
+/// The cause of a [BtError].
+#[derive(Debug)]
+pub enum BtErrorKind {
+    /// A lock was poisoned by a thread that panicked while holding it.
+    PoisonedLock,
+    /// A `RefCell` in the payload was already borrowed.
+    Borrow,
+    /// The receiving end of a channel was dropped.
+    Disconnected,
+    /// Any other error reported by a node.
+    Other(Box<dyn Error + Send + Sync>),
+}
+
+impl BtError {
+    pub fn new(kind: BtErrorKind) -> Self {
+        Self {
+            path: String::new(),
+            kind,
+        }
+    }
+
+    /// The path of the node that reported the error, relative to the node
+    /// that was ticked, as slash-separated child indices.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn kind(&self) -> &BtErrorKind {
+        &self.kind
+    }
+
+    /// Prefixes the path with the index of the child that returned this error.
+    ///
+    /// Control nodes call it when they propagate errors from their children.
+    pub fn in_child(mut self, index: usize) -> Self {
+        self.path = if self.path.is_empty() {
+            index.to_string()
+        } else {
+            format!("{}/{}", index, self.path)
+        };
+        self
+    }
+}
+
+impl fmt::Display for BtErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PoisonedLock => write!(f, "Lock was poisoned"),
+            Self::Borrow => write!(f, "Payload was already borrowed"),
+            Self::Disconnected => write!(f, "Channel was disconnected"),
+            Self::Other(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for BtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Node at path \"{}\": {}", self.path, self.kind)
+    }
+}
+
+impl Error for BtError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            BtErrorKind::Other(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<BtErrorKind> for BtError {
+    fn from(kind: BtErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl From<BorrowError> for BtError {
+    fn from(_: BorrowError) -> Self {
+        Self::new(BtErrorKind::Borrow)
+    }
+}
+
+impl From<BorrowMutError> for BtError {
+    fn from(_: BorrowMutError) -> Self {
+        Self::new(BtErrorKind::Borrow)
+    }
+}
+
+impl<T> From<PoisonError<T>> for BtError {
+    fn from(_: PoisonError<T>) -> Self {
+        Self::new(BtErrorKind::PoisonedLock)
+    }
+}
+
+impl<T> From<SendError<T>> for BtError {
+    fn from(_: SendError<T>) -> Self {
+        Self::new(BtErrorKind::Disconnected)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{BehaviorNodeBase, BehaviorResult, HaltReport};
+use std::sync::{Arc, Mutex, PoisonError};
+
+enum ActionState<R, F> {
+    Idle,
+    Requested,
+    Completed(Result<R, F>),
+}
+
+/// A handle to complete an [ExternalActionNode] from outside of the tree.
+///
+/// It can be cloned and sent to other threads, e.g. to be called back when
+/// an animation finished.
+pub struct ActionHandle<R, F> {
+    state: Arc<Mutex<ActionState<R, F>>>,
+}
+
+impl<R, F> Clone for ActionHandle<R, F> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<R, F> ActionHandle<R, F> {
+    /// Completes the action with `Ok` for success and `Err` for failure,
+    /// which the node returns in the next tick.
+    pub fn complete(&self, result: Result<R, F>) {
+        *self.lock() = ActionState::Completed(result);
+    }
+
+    /// Returns true if the node has been ticked and waits for completion.
+    ///
+    /// It turns false when the node is halted, so that the external system
+    /// can cancel its work.
+    pub fn is_requested(&self) -> bool {
+        matches!(*self.lock(), ActionState::Requested)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ActionState<R, F>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A leaf node that returns `Running` until external code completes it
+/// through an [ActionHandle].
+///
+/// After returning the result, the node starts over and waits for the next
+/// completion.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// let mut node = ExternalActionNode::<&str, ()>::new();
+/// let handle = node.handle();
+///
+/// assert_eq!(node.tick(()), BehaviorResult::Running);
+/// assert!(handle.is_requested());
+/// handle.complete(Ok("animation finished"));
+/// assert_eq!(node.tick(()), BehaviorResult::Success("animation finished"));
+/// ```
+pub struct ExternalActionNode<R, F> {
+    handle: ActionHandle<R, F>,
+}
+
+impl<R, F> ExternalActionNode<R, F> {
+    pub fn new() -> Self {
+        Self {
+            handle: ActionHandle {
+                state: Arc::new(Mutex::new(ActionState::Idle)),
+            },
+        }
+    }
+
+    pub fn handle(&self) -> ActionHandle<R, F> {
+        self.handle.clone()
+    }
+}
+
+impl<R, F> Default for ExternalActionNode<R, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for ExternalActionNode<R, F> {
+    fn tick(&mut self, _payload: Payload) -> BehaviorResult<R, F> {
+        let mut state = self.handle.lock();
+        match std::mem::replace(&mut *state, ActionState::Requested) {
+            ActionState::Completed(result) => {
+                *state = ActionState::Idle;
+                match result {
+                    Ok(r) => BehaviorResult::Success(r),
+                    Err(f) => BehaviorResult::Failure(f),
+                }
+            }
+            ActionState::Idle | ActionState::Requested => BehaviorResult::Running,
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        *self.handle.lock() = ActionState::Idle;
+        HaltReport::clean()
+    }
+}
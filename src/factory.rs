@@ -0,0 +1,419 @@
+#[cfg(all(feature = "stdlib", feature = "serde"))]
+use crate::stdlib::ParseGuardError;
+use crate::{
+    BehaviorNodeBase, BehaviorResult, BtError, ChildMeta, FallbackNode, FallbackNodeWithMemory,
+    HaltReport, ParallelNode, Port, ReactiveFallbackNode, ReactiveSequenceNode, RoundRobinNode,
+    SequenceNode, SequenceNodeWithMemory,
+};
+use std::any::{type_name, Any};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::str::FromStr;
+
+type Node<Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F>>;
+type Constructor<Payload, R, F> =
+    Box<dyn Fn(&NodeConfig) -> Result<Node<Payload, R, F>, ParamError>>;
+#[cfg(all(feature = "stdlib", feature = "serde"))]
+pub(crate) type GuardWrapper<Payload, R, F> =
+    Box<dyn Fn(&str, Node<Payload, R, F>) -> Result<Node<Payload, R, F>, ParseGuardError>>;
+
+/// The parameters given to a constructor registered in a [NodeFactory],
+/// as strings keyed by names, e.g. the attributes of a node in a definition file.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct NodeConfig {
+    params: BTreeMap<String, String>,
+}
+
+impl NodeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a parameter.
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.params.insert(name.into(), value.into());
+    }
+
+    /// Removes a parameter, returning its raw value.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.params.remove(name)
+    }
+
+    /// Returns the raw value of a parameter.
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    /// Parses a required parameter.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, ParamError> {
+        self.get_opt(name)?.ok_or_else(|| ParamError::missing(name))
+    }
+
+    /// Parses a parameter, or returns `default` if it is not given.
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, ParamError> {
+        Ok(self.get_opt(name)?.unwrap_or(default))
+    }
+
+    fn get_opt<T: FromStr>(&self, name: &str) -> Result<Option<T>, ParamError> {
+        self.get_str(name)
+            .map(|value| {
+                value.parse().map_err(|_| {
+                    ParamError::invalid(
+                        name,
+                        format!("\"{}\" is not a valid {}", value, type_name::<T>()),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Returns the parameters in the order of their names.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// A problem with a parameter in a [NodeConfig], returned by a constructor.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParamError {
+    pub param: String,
+    pub message: String,
+}
+
+impl ParamError {
+    pub fn missing(param: &str) -> Self {
+        Self {
+            param: param.to_owned(),
+            message: "missing".to_owned(),
+        }
+    }
+
+    pub fn invalid(param: &str, message: impl Into<String>) -> Self {
+        Self {
+            param: param.to_owned(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Parameter \"{}\": {}", self.param, self.message)
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// The error type returned by [NodeFactory::create].
+#[derive(Clone, PartialEq, Debug)]
+pub enum FactoryError {
+    /// No constructor is registered with the name.
+    UnknownType(String),
+    /// The constructor rejected a parameter.
+    Param {
+        node_type: String,
+        error: ParamError,
+    },
+    /// The constructor panicked, with the panic message if it was a string.
+    Panicked { node_type: String, message: String },
+}
+
+impl fmt::Display for FactoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownType(name) => write!(f, "Unknown node type \"{}\"", name),
+            Self::Param { node_type, error } => write!(f, "{} in \"{}\"", error, node_type),
+            Self::Panicked { node_type, message } => {
+                write!(f, "Constructor of \"{}\" panicked: {}", node_type, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FactoryError {}
+
+/// A set of node types that a crate registers into a [NodeFactory] at once,
+/// see [NodeFactory::install].
+pub trait NodePlugin<Payload, R, F> {
+    fn register(&self, factory: &mut NodeFactory<Payload, R, F>);
+}
+
+/// A registry of node constructors keyed by type names, so that nodes can be
+/// created from names and parameters in a definition file.
+///
+/// Constructors create nodes without children; control nodes get their
+/// children through [BehaviorNodeBase::push_child] afterwards, as in
+/// [Tree::from_flat](crate::Tree::from_flat).
+/// [NodeFactory::with_builtins] registers the built-in control nodes.
+///
+/// The created nodes remember their type names and parameters, which
+/// [BehaviorNodeBase::definition] returns, so that a tree can be saved back.
+///
+/// A panic in a constructor is caught and returned as
+/// [FactoryError::Panicked], so that an editor or a server loading trees can
+/// keep running.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct OpenDoor {
+///     force: f64,
+/// }
+///
+/// impl BehaviorNodeBase<(), (), ()> for OpenDoor {
+///     fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+///         if 10. <= self.force {
+///             BehaviorResult::Success(())
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+/// }
+///
+/// let mut factory = NodeFactory::with_builtins();
+/// factory.register("OpenDoor", |cfg| {
+///     Ok(OpenDoor {
+///         force: cfg.get_or("force", 5.)?,
+///     })
+/// });
+///
+/// let mut door = factory.create("OpenDoor", &NodeConfig::new().with("force", "20")).unwrap();
+/// assert_eq!(door.tick(()), BehaviorResult::Success(()));
+///
+/// let err = factory.create("OpenDoor", &NodeConfig::new().with("force", "hard"));
+/// assert_eq!(
+///     err.err().unwrap().to_string(),
+///     "Parameter \"force\": \"hard\" is not a valid f64 in \"OpenDoor\""
+/// );
+/// ```
+pub struct NodeFactory<Payload, R, F> {
+    constructors: BTreeMap<String, Constructor<Payload, R, F>>,
+    #[cfg(all(feature = "stdlib", feature = "serde"))]
+    pub(crate) guard: Option<GuardWrapper<Payload, R, F>>,
+    #[cfg(feature = "serde")]
+    pub(crate) migrations: BTreeMap<u32, crate::loader::Migration>,
+}
+
+impl<Payload, R, F> Default for NodeFactory<Payload, R, F> {
+    fn default() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+            #[cfg(all(feature = "stdlib", feature = "serde"))]
+            guard: None,
+            #[cfg(feature = "serde")]
+            migrations: BTreeMap::new(),
+        }
+    }
+}
+
+impl<Payload, R, F> NodeFactory<Payload, R, F> {
+    /// Creates a factory without any node types.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a constructor under `name`, replacing the one registered before.
+    pub fn register<N>(
+        &mut self,
+        name: impl Into<String>,
+        constructor: impl Fn(&NodeConfig) -> Result<N, ParamError> + 'static,
+    ) where
+        N: BehaviorNodeBase<Payload, R, F> + 'static,
+    {
+        let name = name.into();
+        let node_type = name.clone();
+        self.constructors.insert(
+            name,
+            Box::new(move |cfg| {
+                Ok(Box::new(FactoryNode {
+                    node_type: node_type.clone(),
+                    config: cfg.clone(),
+                    node: constructor(cfg)?,
+                }) as _)
+            }),
+        );
+    }
+
+    /// Registers the node types of a plugin.
+    pub fn install(&mut self, plugin: &impl NodePlugin<Payload, R, F>) {
+        plugin.register(self);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.contains_key(name)
+    }
+
+    /// Returns the registered names in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+
+    /// Creates a node of the type registered under `name`.
+    pub fn create(
+        &self,
+        name: &str,
+        cfg: &NodeConfig,
+    ) -> Result<Node<Payload, R, F>, FactoryError> {
+        let constructor = self
+            .constructors
+            .get(name)
+            .ok_or_else(|| FactoryError::UnknownType(name.to_owned()))?;
+        match catch_unwind(AssertUnwindSafe(|| constructor(cfg))) {
+            Ok(Ok(node)) => Ok(node),
+            Ok(Err(error)) => Err(FactoryError::Param {
+                node_type: name.to_owned(),
+                error,
+            }),
+            Err(panic) => {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                Err(FactoryError::Panicked {
+                    node_type: name.to_owned(),
+                    message,
+                })
+            }
+        }
+    }
+
+    /// Wraps a node created from `cfg` in the guard given by `expr`, see
+    /// [NodeFactory::enable_guards].
+    ///
+    /// The returned node keeps `cfg` with the `guard` parameter as its definition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the guards are not enabled.
+    #[cfg(all(feature = "stdlib", feature = "serde"))]
+    pub(crate) fn guard(
+        &self,
+        node_type: &str,
+        cfg: NodeConfig,
+        expr: &str,
+        node: Node<Payload, R, F>,
+    ) -> Result<Node<Payload, R, F>, ParseGuardError>
+    where
+        Payload: 'static,
+        R: 'static,
+        F: 'static,
+    {
+        let guard = self.guard.as_ref().expect("the guards are not enabled");
+        let node = guard(expr, node)?;
+        Ok(Box::new(FactoryNode {
+            node_type: node_type.to_owned(),
+            config: cfg,
+            node,
+        }))
+    }
+}
+
+/// A node created by a [NodeFactory], with its type name and parameters.
+struct FactoryNode<N> {
+    node_type: String,
+    config: NodeConfig,
+    node: N,
+}
+
+impl<Payload, R, F, N> BehaviorNodeBase<Payload, R, F> for FactoryNode<N>
+where
+    N: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.node.tick(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.node.try_tick(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        self.node.as_any_mut()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.node.children_mut()
+    }
+
+    fn push_child(&mut self, child: Node<Payload, R, F>) -> Result<(), Node<Payload, R, F>>
+    where
+        Self: 'static,
+    {
+        self.node.push_child(child)
+    }
+
+    fn child_meta(&self, index: usize) -> Option<&ChildMeta> {
+        self.node.child_meta(index)
+    }
+
+    fn set_child_meta(&mut self, index: usize, meta: ChildMeta) -> Result<(), ChildMeta> {
+        self.node.set_child_meta(index, meta)
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        self.node.describe()
+    }
+
+    fn ports(&self) -> Vec<Port> {
+        self.node.ports()
+    }
+
+    fn definition(&self) -> Option<(&str, &NodeConfig)> {
+        Some((&self.node_type, &self.config))
+    }
+}
+
+impl<Payload, R, F> NodeFactory<Payload, R, F>
+where
+    Payload: Clone + 'static,
+    R: Default + 'static,
+    F: Default + 'static,
+{
+    /// Creates a factory with the built-in control nodes registered under
+    /// their names without the `Node` suffix, e.g. `Sequence` and
+    /// `ReactiveFallback`.
+    ///
+    /// `Parallel` takes the parameter `success_threshold`.
+    pub fn with_builtins() -> Self {
+        let mut factory = Self::new();
+        factory.register("Sequence", |_| Ok(SequenceNode::new(vec![])));
+        factory.register("Fallback", |_| Ok(FallbackNode::new(vec![])));
+        factory.register("SequenceWithMemory", |_| {
+            Ok(SequenceNodeWithMemory::new(vec![]))
+        });
+        factory.register("FallbackWithMemory", |_| {
+            Ok(FallbackNodeWithMemory::new(vec![]))
+        });
+        factory.register("ReactiveSequence", |_| {
+            Ok(ReactiveSequenceNode::new(vec![]))
+        });
+        factory.register("ReactiveFallback", |_| {
+            Ok(ReactiveFallbackNode::new(vec![]))
+        });
+        factory.register("Parallel", |cfg| {
+            Ok(ParallelNode::new(vec![], cfg.get("success_threshold")?))
+        });
+        factory.register("RoundRobin", |_| Ok(RoundRobinNode::new(vec![])));
+        factory
+    }
+}
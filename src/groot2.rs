@@ -0,0 +1,208 @@
+//! Live monitoring with Groot2, enabled by the `groot2` feature.
+//!
+//! [Groot2Publisher] answers the requests of Groot2 over ZeroMQ in the same
+//! protocol as the publisher of BehaviorTree.CPP, so a tree can be watched
+//! in the same visualizer.
+//! It sends the structure of the tree and the statuses of its nodes, which
+//! are taken from the [TickReport]s of [Tree::tick_with_report](crate::Tree::tick_with_report).
+//! Blackboards, breakpoints and recordings are not supported yet, and their
+//! requests are answered with errors.
+//!
+//! ```no_run
+//! # use tiny_behavior_tree::{groot2::Groot2Publisher, testing::AlwaysSuccess, *};
+//! let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+//! factory.register("Succeed", |_| Ok(AlwaysSuccess(())));
+//! let mut root = factory.create("Sequence", &NodeConfig::new()).unwrap();
+//! assert!(root
+//!     .push_child(factory.create("Succeed", &NodeConfig::new()).unwrap())
+//!     .is_ok());
+//! let mut tree = Tree::new(root);
+//!
+//! let mut groot = Groot2Publisher::new(&mut tree, 1667).unwrap();
+//! loop {
+//!     let (_, report) = tree.tick_with_report(());
+//!     groot.update(&report);
+//!     groot.poll().unwrap();
+//!     # break;
+//! }
+//! ```
+use crate::configure::child_path;
+use crate::{BehaviorNodeBase, ResultKind, TickReport};
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::hash::{BuildHasher, Hasher};
+
+const PROTOCOL_ID: u8 = 2;
+const REQUEST_HEADER_SIZE: usize = 6;
+const FULLTREE: u8 = b'T';
+const STATUS: u8 = b'S';
+
+/// The names of the node types that Groot2 knows without models.
+const BUILTINS: &[&str] = &[
+    "Sequence",
+    "Fallback",
+    "SequenceWithMemory",
+    "ReactiveSequence",
+    "ReactiveFallback",
+    "Parallel",
+];
+
+/// A server that publishes the structure and the statuses of a tree to Groot2.
+///
+/// The nodes are named by their [BehaviorNodeBase::definition], so trees
+/// built through a [NodeFactory](crate::NodeFactory) show their type names
+/// and parameters, while other nodes are shown as `Node`.
+pub struct Groot2Publisher {
+    socket: zmq::Socket,
+    tree_id: [u8; 16],
+    xml: String,
+    /// The paths of the nodes, whose unique IDs are the indices plus one.
+    paths: Vec<String>,
+    statuses: Vec<u8>,
+}
+
+impl Groot2Publisher {
+    /// Starts serving the structure of `tree` on the TCP `port` of all interfaces.
+    ///
+    /// Groot2 connects to port 1667 by default.
+    pub fn new<Payload: 'static, R: 'static, F: 'static>(
+        tree: &mut (dyn BehaviorNodeBase<Payload, R, F> + 'static),
+        port: u16,
+    ) -> Result<Self, zmq::Error> {
+        let socket = zmq::Context::new().socket(zmq::REP)?;
+        socket.bind(&format!("tcp://*:{}", port))?;
+
+        let mut paths = vec![];
+        let mut models = BTreeSet::new();
+        let mut nodes = String::new();
+        write_node(tree, String::new(), &mut paths, &mut models, &mut nodes);
+        let mut xml = String::from("<root BTCPP_format=\"4\">");
+        write!(
+            xml,
+            "<BehaviorTree ID=\"MainTree\">{}</BehaviorTree>",
+            nodes
+        )
+        .unwrap();
+        xml.push_str("<TreeNodesModel>");
+        for (category, name) in models {
+            write!(xml, "<{} ID=\"{}\"/>", category, escape(&name)).unwrap();
+        }
+        xml.push_str("</TreeNodesModel></root>");
+
+        let mut tree_id = [0; 16];
+        for half in tree_id.chunks_mut(8) {
+            half.copy_from_slice(&RandomState::new().build_hasher().finish().to_le_bytes());
+        }
+
+        Ok(Self {
+            socket,
+            tree_id,
+            statuses: vec![0; paths.len()],
+            paths,
+            xml,
+        })
+    }
+
+    /// Returns the XML of the tree sent to Groot2, with the unique IDs of the nodes.
+    pub fn xml(&self) -> &str {
+        &self.xml
+    }
+
+    /// Updates the statuses of the nodes with the results of a tick.
+    ///
+    /// The nodes that were not ticked become idle, which Groot2 shows with
+    /// the faded color of their last status.
+    pub fn update(&mut self, report: &TickReport) {
+        for (path, status) in self.paths.iter().zip(&mut self.statuses) {
+            *status = match report.status(path) {
+                Some(ResultKind::Idle) | None if *status < 10 => 10 + *status,
+                Some(ResultKind::Idle) | None => *status,
+                Some(ResultKind::Running) => 1,
+                Some(ResultKind::Success) => 2,
+                Some(ResultKind::Failure) => 3,
+            };
+        }
+    }
+
+    /// Answers the pending requests without blocking.
+    pub fn poll(&mut self) -> Result<(), zmq::Error> {
+        while self.socket.poll(zmq::POLLIN, 0)? != 0 {
+            let request = self.socket.recv_multipart(0)?;
+            let reply = self.reply(&request);
+            self.socket.send_multipart(reply, 0)?;
+        }
+        Ok(())
+    }
+
+    fn reply(&self, request: &[Vec<u8>]) -> Vec<Vec<u8>> {
+        let header = match request.first() {
+            Some(header) if REQUEST_HEADER_SIZE <= header.len() => &header[..REQUEST_HEADER_SIZE],
+            _ => return error_reply("wrong request header"),
+        };
+        if header[0] != PROTOCOL_ID {
+            return error_reply("wrong protocol");
+        }
+        let mut reply_header = header.to_vec();
+        reply_header.extend_from_slice(&self.tree_id);
+        let body = match header[1] {
+            FULLTREE => self.xml.as_bytes().to_vec(),
+            STATUS => self
+                .statuses
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &status)| {
+                    let [lo, hi] = (i as u16 + 1).to_le_bytes();
+                    [lo, hi, status]
+                })
+                .collect(),
+            _ => return error_reply("unsupported request"),
+        };
+        vec![reply_header, body]
+    }
+}
+
+fn error_reply(message: &str) -> Vec<Vec<u8>> {
+    vec![b"error".to_vec(), message.as_bytes().to_vec()]
+}
+
+fn write_node<Payload: 'static, R: 'static, F: 'static>(
+    node: &mut (dyn BehaviorNodeBase<Payload, R, F> + 'static),
+    path: String,
+    paths: &mut Vec<String>,
+    models: &mut BTreeSet<(&'static str, String)>,
+    xml: &mut String,
+) {
+    paths.push(path.clone());
+    let uid = paths.len();
+    let (name, params) = match node.definition() {
+        Some((name, cfg)) => (
+            name.to_owned(),
+            cfg.iter()
+                .map(|(k, v)| format!(" {}=\"{}\"", escape(k), escape(v)))
+                .collect(),
+        ),
+        None => ("Node".to_owned(), String::new()),
+    };
+    write!(xml, "<{} _uid=\"{}\"{}>", escape(&name), uid, params).unwrap();
+    let children = node.children_mut();
+    let category = if children.is_empty() {
+        "Action"
+    } else {
+        "Control"
+    };
+    for (i, child) in children.into_iter().enumerate() {
+        write_node(child, child_path(&path, i), paths, models, xml);
+    }
+    write!(xml, "</{}>", escape(&name)).unwrap();
+    if !BUILTINS.contains(&name.as_str()) {
+        models.insert((category, name));
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,150 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::marker::PhantomData;
+
+/// A condition checked by [GuardNode] before ticking its child.
+///
+/// It is implemented for closures taking a reference to the payload, and
+/// for condition nodes wrapped in [NodeCondition].
+pub trait GuardCondition<Payload> {
+    fn check(&mut self, payload: &Payload) -> bool;
+
+    /// Called when the guard node is halted.
+    fn halt(&mut self) {}
+
+    /// Called when the guard node is reset. The default implementation calls
+    /// [GuardCondition::halt].
+    fn reset(&mut self) {
+        self.halt();
+    }
+}
+
+impl<Payload, C> GuardCondition<Payload> for C
+where
+    C: FnMut(&Payload) -> bool,
+{
+    fn check(&mut self, payload: &Payload) -> bool {
+        self(payload)
+    }
+}
+
+/// A condition node used as a [GuardCondition], which holds when the node succeeds.
+pub struct NodeCondition<N, R, F> {
+    node: N,
+    _result: PhantomData<fn() -> (R, F)>,
+}
+
+impl<N, R, F> NodeCondition<N, R, F> {
+    pub fn new(node: N) -> Self {
+        Self {
+            node,
+            _result: PhantomData,
+        }
+    }
+}
+
+impl<Payload, N, R, F> GuardCondition<Payload> for NodeCondition<N, R, F>
+where
+    Payload: Clone,
+    N: BehaviorNodeBase<Payload, R, F>,
+{
+    fn check(&mut self, payload: &Payload) -> bool {
+        matches!(self.node.tick(payload.clone()), BehaviorResult::Success(_))
+    }
+
+    fn halt(&mut self) {
+        self.node.halt();
+    }
+
+    fn reset(&mut self) {
+        self.node.reset();
+    }
+}
+
+/// A decorator that checks a condition before ticking the child node in
+/// every tick, and fails without ticking it if the condition does not hold.
+///
+/// If the condition stops holding while the child is running, the child is
+/// aborted by halting it, like the observer aborts of Unreal Engine.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysRunning;
+/// struct Npc {
+///     health: u32,
+/// }
+///
+/// let mut fight = GuardNode::new(|npc: &&Npc| 30 < npc.health, AlwaysRunning);
+/// let res: BehaviorResult<(), ()> = fight.tick(&Npc { health: 80 });
+/// assert_eq!(res, BehaviorResult::Running);
+/// // The fight is aborted.
+/// let res: BehaviorResult<(), ()> = fight.tick(&Npc { health: 20 });
+/// assert_eq!(res, BehaviorResult::Failure(()));
+/// ```
+pub struct GuardNode<C, T> {
+    condition: C,
+    node: T,
+    running: bool,
+}
+
+impl<C, T> GuardNode<C, T> {
+    /// Constructs a [GuardNode] with a closure or a [NodeCondition].
+    pub fn new(condition: C, node: T) -> Self {
+        Self {
+            condition,
+            node,
+            running: false,
+        }
+    }
+
+    /// Returns true if the condition holds, halting the running child otherwise.
+    fn check<Payload, R, F>(&mut self, payload: &Payload) -> bool
+    where
+        C: GuardCondition<Payload>,
+        T: BehaviorNodeBase<Payload, R, F>,
+    {
+        if self.condition.check(payload) {
+            return true;
+        }
+        if std::mem::take(&mut self.running) {
+            self.node.halt();
+        }
+        false
+    }
+}
+
+impl<Payload, R, F, C, T> BehaviorNodeBase<Payload, R, F> for GuardNode<C, T>
+where
+    F: Default,
+    C: GuardCondition<Payload>,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        if !self.check(&payload) {
+            return BehaviorResult::Failure(F::default());
+        }
+        let res = self.node.tick(payload);
+        self.running = matches!(res, BehaviorResult::Running);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        if !self.check(&payload) {
+            return Ok(BehaviorResult::Failure(F::default()));
+        }
+        let res = self.node.try_tick(payload)?;
+        self.running = matches!(res, BehaviorResult::Running);
+        Ok(res)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.running = false;
+        self.condition.halt();
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.running = false;
+        self.condition.reset();
+        self.node.reset()
+    }
+}
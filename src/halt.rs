@@ -0,0 +1,83 @@
+use std::fmt;
+
+/// A node that could not stop cleanly when it was halted.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HaltIssue {
+    /// The path of the node relative to the node that was halted,
+    /// as slash-separated child indices.
+    pub path: String,
+    /// The reason reported by the node, e.g. "irreversible motion in progress".
+    pub reason: String,
+}
+
+/// The result of [halting](crate::BehaviorNodeBase::halt) a node and its descendants.
+///
+/// An empty report means that everything stopped cleanly.
+/// Safety layers can inspect [HaltReport::issues] to learn which nodes
+/// are still acting on the world despite the abort.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct HaltReport {
+    issues: Vec<HaltIssue>,
+}
+
+impl HaltReport {
+    /// A report for a node that stopped cleanly.
+    pub fn clean() -> Self {
+        Self::default()
+    }
+
+    /// A report for a node that could not stop cleanly by itself.
+    pub fn incomplete(reason: impl Into<String>) -> Self {
+        Self {
+            issues: vec![HaltIssue {
+                path: String::new(),
+                reason: reason.into(),
+            }],
+        }
+    }
+
+    /// Combines reports of child nodes, prefixing the paths with the child indices.
+    pub fn from_children(children: impl IntoIterator<Item = HaltReport>) -> Self {
+        let mut ret = Self::default();
+        for (i, child) in children.into_iter().enumerate() {
+            ret.merge_child(i, child);
+        }
+        ret
+    }
+
+    /// Adds issues of the child at `index` to this report.
+    pub fn merge_child(&mut self, index: usize, child: HaltReport) {
+        self.issues
+            .extend(child.issues.into_iter().map(|issue| HaltIssue {
+                path: if issue.path.is_empty() {
+                    index.to_string()
+                } else {
+                    format!("{}/{}", index, issue.path)
+                },
+                reason: issue.reason,
+            }));
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn issues(&self) -> &[HaltIssue] {
+        &self.issues
+    }
+}
+
+impl fmt::Display for HaltReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_clean() {
+            return write!(f, "halted cleanly");
+        }
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "\"{}\": {}", issue.path, issue.reason)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, ResultKind};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::time::Instant;
+
+/// A result returned by a node recorded in [ResultHistory].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HistoryEntry {
+    pub timestamp: Instant,
+    pub kind: ResultKind,
+}
+
+type Buffer = Rc<RefCell<VecDeque<HistoryEntry>>>;
+
+/// Ring buffers of the last results of nodes, addressed by paths.
+///
+/// Wrap the nodes you are interested in with [ResultHistory::record], and you
+/// can look at what they returned recently when you notice a bug, without
+/// recording a full trace.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::*;
+/// type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+///
+/// let history = ResultHistory::new(2);
+/// let mut tree = FallbackNode::new([
+///     Box::new(history.record("0", AlwaysFailure(()))) as Node,
+///     Box::new(history.record("1", AlwaysSuccess(()))),
+/// ]);
+/// for _ in 0..3 {
+///     tree.tick(());
+/// }
+/// let kinds: Vec<_> = history.entries("1").iter().map(|entry| entry.kind).collect();
+/// assert_eq!(kinds, [ResultKind::Success, ResultKind::Success]);
+/// ```
+pub struct ResultHistory {
+    capacity: usize,
+    buffers: RefCell<HashMap<String, Buffer>>,
+}
+
+impl ResultHistory {
+    /// Creates a history that keeps the last `capacity` results of each node.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            buffers: RefCell::default(),
+        }
+    }
+
+    /// Wraps a node so that its results are recorded under `path`.
+    ///
+    /// Nodes recorded under the same path share the buffer.
+    pub fn record<T>(&self, path: impl Into<String>, node: T) -> Recorded<T> {
+        let buffer = self
+            .buffers
+            .borrow_mut()
+            .entry(path.into())
+            .or_insert_with(|| Rc::new(RefCell::new(VecDeque::with_capacity(self.capacity))))
+            .clone();
+        Recorded {
+            capacity: self.capacity,
+            buffer,
+            node,
+        }
+    }
+
+    /// Returns the recorded results of the node at `path`, oldest first.
+    pub fn entries(&self, path: &str) -> Vec<HistoryEntry> {
+        self.buffers
+            .borrow()
+            .get(path)
+            .map(|buffer| buffer.borrow().iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the paths of the recorded nodes.
+    pub fn paths(&self) -> Vec<String> {
+        self.buffers.borrow().keys().cloned().collect()
+    }
+}
+
+/// A decorator created by [ResultHistory::record].
+pub struct Recorded<T> {
+    capacity: usize,
+    buffer: Buffer,
+    node: T,
+}
+
+impl<T> Recorded<T> {
+    fn push<R, F>(&self, res: &BehaviorResult<R, F>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut buffer = self.buffer.borrow_mut();
+        if buffer.len() == self.capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(HistoryEntry {
+            timestamp: Instant::now(),
+            kind: res.kind(),
+        });
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for Recorded<T>
+where
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        let res = self.node.tick(payload);
+        self.push(&res);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        let res = self.node.try_tick(payload)?;
+        self.push(&res);
+        Ok(res)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
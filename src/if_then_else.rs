@@ -0,0 +1,174 @@
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::any::Any;
+use std::convert::Infallible;
+use std::iter::once;
+
+type Child<Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F>>;
+
+/// A control node that ticks the "then" child if the condition child
+/// succeeds, or the "else" child if it fails.
+///
+/// It returns the result of the ticked branch, or the failure of the
+/// condition if there is no "else" child.
+/// While a branch is running, the following ticks resume it without ticking
+/// the condition again.
+///
+/// The children are the condition, the "then" child and the "else" child in
+/// this order.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct IsArmed;
+///
+/// impl BehaviorNodeBase<bool, &'static str, &'static str> for IsArmed {
+///     fn tick(&mut self, armed: bool) -> BehaviorResult<&'static str, &'static str> {
+///         if armed {
+///             BehaviorResult::Success("armed")
+///         } else {
+///             BehaviorResult::Failure("unarmed")
+///         }
+///     }
+/// }
+///
+/// struct Say(&'static str);
+///
+/// impl BehaviorNodeBase<bool, &'static str, &'static str> for Say {
+///     fn tick(&mut self, _: bool) -> BehaviorResult<&'static str, &'static str> {
+///         BehaviorResult::Success(self.0)
+///     }
+/// }
+///
+/// let mut tree = IfThenElseNode::new(Box::new(IsArmed), Box::new(Say("attack")))
+///     .with_else(Box::new(Say("flee")));
+/// assert_eq!(tree.tick(true), BehaviorResult::Success("attack"));
+/// assert_eq!(tree.tick(false), BehaviorResult::Success("flee"));
+/// ```
+pub struct IfThenElseNode<Payload, R, F> {
+    condition: Child<Payload, R, F>,
+    then: Child<Payload, R, F>,
+    otherwise: Option<Child<Payload, R, F>>,
+    /// The index of the branch that returned `Running` in the last tick.
+    running: Option<usize>,
+}
+
+impl<Payload, R, F> IfThenElseNode<Payload, R, F> {
+    /// Constructs an [IfThenElseNode] without the "else" child.
+    pub fn new(condition: Child<Payload, R, F>, then: Child<Payload, R, F>) -> Self {
+        Self {
+            condition,
+            then,
+            otherwise: None,
+            running: None,
+        }
+    }
+
+    /// Sets the child that is ticked if the condition fails.
+    pub fn with_else(mut self, otherwise: Child<Payload, R, F>) -> Self {
+        self.otherwise = Some(otherwise);
+        self
+    }
+
+    fn children(&mut self) -> impl Iterator<Item = &mut Child<Payload, R, F>> {
+        once(&mut self.condition)
+            .chain(once(&mut self.then))
+            .chain(self.otherwise.as_mut())
+    }
+
+    fn branch(&mut self, index: usize) -> Option<&mut Child<Payload, R, F>> {
+        match index {
+            1 => Some(&mut self.then),
+            _ => self.otherwise.as_mut(),
+        }
+    }
+
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut Child<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        Payload: Clone,
+    {
+        let index = match self.running.take() {
+            Some(index) => index,
+            None => {
+                match report::tick_child(0, || tick_child(0, &mut self.condition, payload.clone()))?
+                {
+                    BehaviorResult::Success(_) => 1,
+                    res @ BehaviorResult::Failure(_) if self.otherwise.is_none() => return Ok(res),
+                    BehaviorResult::Failure(_) => 2,
+                    res => return Ok(res),
+                }
+            }
+        };
+        let branch = self.branch(index).expect("the branch should exist");
+        let res = report::tick_child(index, || tick_child(index, branch, payload))?;
+        if let BehaviorResult::Running = res {
+            self.running = Some(index);
+        }
+        Ok(res)
+    }
+}
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for IfThenElseNode<Payload, R, F>
+where
+    Payload: Clone,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.running = None;
+        HaltReport::from_children(self.children().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.running = None;
+        HaltReport::from_children(self.children().map(|node| node.reset()))
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children().map(|node| node.as_mut() as _).collect()
+    }
+
+    fn push_child(
+        &mut self,
+        child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+    where
+        Self: 'static,
+    {
+        if self.otherwise.is_some() {
+            return Err(child);
+        }
+        self.otherwise = Some(child);
+        Ok(())
+    }
+}
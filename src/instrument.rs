@@ -0,0 +1,102 @@
+//! Instrumentation with `tracing`, enabled by the `tracing` feature.
+//!
+//! The built-in control nodes tick each child in a `child` span with the
+//! index of the child, and emit a `TRACE` event with the status and the
+//! duration of the tick, so the spans of nested control nodes show the
+//! structure of the tree in the existing telemetry.
+//! Wrap the nodes of interest in [InstrumentedNode] to name them, which
+//! ticks them in a `node` span and emits `DEBUG` events.
+//!
+//! ```
+//! use tiny_behavior_tree::instrument::InstrumentedNode;
+//! # use tiny_behavior_tree::{testing::AlwaysSuccess, *};
+//! type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+//!
+//! let mut tree = SequenceNode::new([
+//!     Box::new(InstrumentedNode::new("OpenDoor", AlwaysSuccess(()))) as Node,
+//!     Box::new(InstrumentedNode::new("EnterRoom", AlwaysSuccess(()))),
+//! ]);
+//! // Install a subscriber, e.g. with `tracing_subscriber::fmt::init()`, to see the events.
+//! assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+//! ```
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::time::Instant;
+
+/// Ticks the child at `index` of a control node in a `child` span, emitting
+/// an event with its result.
+pub(crate) fn tick_child<R, F, E>(
+    index: usize,
+    tick: impl FnOnce() -> Result<BehaviorResult<R, F>, E>,
+) -> Result<BehaviorResult<R, F>, E> {
+    let _span = tracing::trace_span!("child", index).entered();
+    let start = Instant::now();
+    let res = tick();
+    if let Ok(res) = &res {
+        tracing::trace!(status = ?res.kind(), duration = ?start.elapsed(), "ticked");
+    }
+    res
+}
+
+/// A decorator that ticks the child node in a `node` span named by the given
+/// name, emitting `DEBUG` events with the status and the duration of each
+/// tick, and when the child is halted.
+pub struct InstrumentedNode<T> {
+    name: String,
+    node: T,
+}
+
+impl<T> InstrumentedNode<T> {
+    pub fn new(name: impl Into<String>, node: T) -> Self {
+        Self {
+            name: name.into(),
+            node,
+        }
+    }
+
+    fn instrument<R, F, E: std::fmt::Display>(
+        &mut self,
+        tick: impl FnOnce(&mut T) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E> {
+        let _span = tracing::debug_span!("node", name = %self.name).entered();
+        let start = Instant::now();
+        let res = tick(&mut self.node);
+        match &res {
+            Ok(res) => {
+                tracing::debug!(status = ?res.kind(), duration = ?start.elapsed(), "ticked")
+            }
+            Err(e) => tracing::debug!(error = %e, "tick failed"),
+        }
+        res
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for InstrumentedNode<T>
+where
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.instrument(|node| Ok::<_, std::convert::Infallible>(node.tick(payload))) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.instrument(|node| node.try_tick(payload))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        let _span = tracing::debug_span!("node", name = %self.name).entered();
+        let report = self.node.halt();
+        tracing::debug!(clean = report.is_clean(), "halted");
+        report
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![("name", self.name.clone())]
+    }
+}
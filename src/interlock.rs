@@ -0,0 +1,150 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::convert::Infallible;
+
+/// A decorator that consults an external safety predicate, like an
+/// emergency stop or a server kill switch, before and after ticking the
+/// child node in every tick.
+///
+/// The predicate returns true while it is safe to run.
+/// When it trips, the child is halted if it is running, and this node fails
+/// immediately, even if the child has just succeeded.
+/// A latching interlock stays tripped until [InterlockNode::reset] is called,
+/// even if the predicate returns true again.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysRunning;
+/// # use std::sync::atomic::{AtomicBool, Ordering};
+/// # use std::sync::Arc;
+/// let estop = Arc::new(AtomicBool::new(false));
+/// let estop2 = estop.clone();
+/// let mut drive = InterlockNode::new(move || !estop2.load(Ordering::SeqCst), AlwaysRunning)
+///     .latching();
+/// let res: BehaviorResult<(), ()> = drive.tick(());
+/// assert_eq!(res, BehaviorResult::Running);
+///
+/// estop.store(true, Ordering::SeqCst);
+/// let res: BehaviorResult<(), ()> = drive.tick(());
+/// assert_eq!(res, BehaviorResult::Failure(()));
+///
+/// // Releasing the button is not enough for a latching interlock.
+/// estop.store(false, Ordering::SeqCst);
+/// let res: BehaviorResult<(), ()> = drive.tick(());
+/// assert_eq!(res, BehaviorResult::Failure(()));
+/// drive.reset();
+/// let res: BehaviorResult<(), ()> = drive.tick(());
+/// assert_eq!(res, BehaviorResult::Running);
+/// ```
+pub struct InterlockNode<P, T> {
+    is_safe: P,
+    node: T,
+    latching: bool,
+    tripped: bool,
+    running: bool,
+}
+
+impl<P, T> InterlockNode<P, T>
+where
+    P: Fn() -> bool,
+{
+    pub fn new(is_safe: P, node: T) -> Self {
+        Self {
+            is_safe,
+            node,
+            latching: false,
+            tripped: false,
+            running: false,
+        }
+    }
+
+    /// Makes the interlock stay tripped until [InterlockNode::reset] is called.
+    pub fn latching(mut self) -> Self {
+        self.latching = true;
+        self
+    }
+
+    /// Returns true if the interlock was tripped in the last check.
+    pub fn is_tripped(&self) -> bool {
+        self.tripped
+    }
+
+    /// Releases a tripped latching interlock.
+    pub fn reset(&mut self) {
+        self.tripped = false;
+    }
+
+    /// Checks the predicate, halting the running child if it tripped.
+    fn check<Payload, R, F>(&mut self) -> bool
+    where
+        T: BehaviorNodeBase<Payload, R, F>,
+    {
+        if !(self.latching && self.tripped) {
+            self.tripped = !(self.is_safe)();
+        }
+        if !self.tripped {
+            return true;
+        }
+        if std::mem::take(&mut self.running) {
+            self.node.halt();
+        }
+        false
+    }
+
+    fn run<Payload, R, F, E>(
+        &mut self,
+        payload: Payload,
+        tick_child: impl FnOnce(&mut T, Payload) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        F: Default,
+        T: BehaviorNodeBase<Payload, R, F>,
+    {
+        if !self.check() {
+            return Ok(BehaviorResult::Failure(F::default()));
+        }
+        let res = tick_child(&mut self.node, payload)?;
+        self.running = matches!(res, BehaviorResult::Running);
+        if !self.check() {
+            return Ok(BehaviorResult::Failure(F::default()));
+        }
+        Ok(res)
+    }
+}
+
+impl<Payload, R, F, P, T> BehaviorNodeBase<Payload, R, F> for InterlockNode<P, T>
+where
+    F: Default,
+    P: Fn() -> bool,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |node, payload| node.try_tick(payload))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.running = false;
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.running = false;
+        self.tripped = false;
+        self.node.reset()
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("latching", self.latching.to_string()),
+            ("tripped", self.tripped.to_string()),
+        ]
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A small integer handle to a string stored in an [Interner].
+///
+/// Comparing and hashing a `Symbol` is as cheap as comparing a `u32`,
+/// so it is suitable as a key for node names, node paths and blackboard
+/// entries that are looked up in every tick.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the index of this symbol in the order of interning.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// An arena-backed string interner.
+///
+/// All interned strings are appended to a single buffer, so interning
+/// many short names does not allocate a separate string for each of them.
+/// A string is hashed only once when it is interned; after that you
+/// should pass around [Symbol]s and resolve them back to strings only
+/// when you need to display them.
+///
+/// ```
+/// # use tiny_behavior_tree::Interner;
+/// let mut interner = Interner::new();
+/// let door = interner.intern("door");
+/// assert_eq!(interner.intern("door"), door);
+/// assert_eq!(interner.resolve(door), "door");
+/// ```
+#[derive(Default)]
+pub struct Interner {
+    arena: String,
+    spans: Vec<(u32, u32)>,
+    buckets: HashMap<u64, Vec<Symbol>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the symbol for `s`, interning it if it was not seen before.
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        let hash = hash_str(s);
+        if let Some(sym) = self.find(hash, s) {
+            return sym;
+        }
+        let start = self.arena.len();
+        self.arena.push_str(s);
+        let sym = Symbol(self.spans.len() as u32);
+        self.spans.push((start as u32, s.len() as u32));
+        self.buckets.entry(hash).or_default().push(sym);
+        sym
+    }
+
+    /// Returns the symbol for `s` if it has already been interned.
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.find(hash_str(s), s)
+    }
+
+    /// Returns the string that `sym` refers to.
+    ///
+    /// Panics if `sym` was not created by this interner.
+    pub fn resolve(&self, sym: Symbol) -> &str {
+        self.try_resolve(sym)
+            .expect("Symbol was not created by this Interner")
+    }
+
+    /// Returns the string that `sym` refers to, or `None` if `sym` was not
+    /// created by this interner.
+    pub fn try_resolve(&self, sym: Symbol) -> Option<&str> {
+        let &(start, len) = self.spans.get(sym.index())?;
+        Some(&self.arena[start as usize..(start + len) as usize])
+    }
+
+    /// Returns an object that formats `sym` as its string, for use in
+    /// `format!` and friends.
+    pub fn display(&self, sym: Symbol) -> Resolved<'_> {
+        Resolved {
+            interner: self,
+            sym,
+        }
+    }
+
+    /// Returns the number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    fn find(&self, hash: u64, s: &str) -> Option<Symbol> {
+        self.buckets
+            .get(&hash)?
+            .iter()
+            .copied()
+            .find(|sym| self.try_resolve(*sym) == Some(s))
+    }
+}
+
+/// A [Symbol] paired with its [Interner], which implements [fmt::Display].
+pub struct Resolved<'a> {
+    interner: &'a Interner,
+    sym: Symbol,
+}
+
+impl<'a> fmt::Display for Resolved<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.interner.try_resolve(self.sym) {
+            Some(s) => write!(f, "{}", s),
+            None => write!(f, "<unknown symbol {}>", self.sym.index()),
+        }
+    }
+}
+
+fn hash_str(s: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,100 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+
+/// A decorator that swaps `Success` and `Failure` of the child node.
+///
+/// [InverterNode::new] passes the results through as they are, so the success
+/// type of the child becomes the failure type of this node and vice versa.
+/// Use [InverterNode::with_map] to convert them to other types.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+/// let mut not_found = InverterNode::new(AlwaysFailure("not found"));
+/// let res: BehaviorResult<&str, ()> = not_found.tick(());
+/// assert_eq!(res, BehaviorResult::Success("not found"));
+///
+/// let mut blocked = InverterNode::with_map(
+///     AlwaysSuccess(3),
+///     |distance: i32| format!("enemy within {distance}"),
+///     |_: ()| (),
+/// );
+/// let res: BehaviorResult<(), String> = blocked.tick(());
+/// assert_eq!(res, BehaviorResult::Failure("enemy within 3".to_string()));
+/// ```
+pub struct InverterNode<T, M = ()> {
+    node: T,
+    map: M,
+}
+
+impl<T> InverterNode<T> {
+    pub fn new(node: T) -> Self {
+        Self { node, map: () }
+    }
+}
+
+impl<T, CR, CF, R, F> InverterNode<T, (fn(CR) -> F, fn(CF) -> R)> {
+    /// Creates an inverter that converts the success of the child with
+    /// `to_failure` and the failure with `to_success`.
+    pub fn with_map(node: T, to_failure: fn(CR) -> F, to_success: fn(CF) -> R) -> Self {
+        Self {
+            node,
+            map: (to_failure, to_success),
+        }
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for InverterNode<T>
+where
+    T: BehaviorNodeBase<Payload, F, R>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        invert(self.node.tick(payload), |r| r, |f| f)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        Ok(invert(self.node.try_tick(payload)?, |r| r, |f| f))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
+
+impl<Payload, R, F, CR, CF, T> BehaviorNodeBase<Payload, R, F>
+    for InverterNode<T, (fn(CR) -> F, fn(CF) -> R)>
+where
+    T: BehaviorNodeBase<Payload, CR, CF>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        invert(self.node.tick(payload), self.map.0, self.map.1)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        Ok(invert(self.node.try_tick(payload)?, self.map.0, self.map.1))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
+
+fn invert<R, F, CR, CF>(
+    res: BehaviorResult<CR, CF>,
+    to_failure: impl FnOnce(CR) -> F,
+    to_success: impl FnOnce(CF) -> R,
+) -> BehaviorResult<R, F> {
+    match res {
+        BehaviorResult::Idle => BehaviorResult::Idle,
+        BehaviorResult::Running => BehaviorResult::Running,
+        BehaviorResult::Success(r) => BehaviorResult::Failure(to_failure(r)),
+        BehaviorResult::Failure(f) => BehaviorResult::Success(to_success(f)),
+    }
+}
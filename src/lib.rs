@@ -4,6 +4,9 @@
 //! which was in turn inspired by [BehaviorTreeCPP](https://github.com/BehaviorTree/BehaviorTree.CPP.git).
 //!
 //! The name was converted to snake_case to conform to Rust crate naming convention.
+//! The crate is `tiny_behavior_tree`, while the repository is named `rusty_tiny_behavior_tree`;
+//! import items from `tiny_behavior_tree`, usually through [prelude].
+//!
 //! ## Motivation
 //!
 //! For the motivation why we would like to experiment things like this, see [TinyBehaviorTree's README](https://github.com/msakuta/TinyBehaviorTree/blob/master/README.md).
@@ -184,13 +187,370 @@
 //! peel_node_def!(PeelLeftArmNode, Body, Arm, (), (), |payload: &'a Body| &payload.left_arm);
 //! peel_node_def!(PeelRightArmNode, Body, Arm, (), (), |payload: &'a Body| &payload.right_arm);
 //! ```
+mod blackboard;
+mod borrow_guard;
+mod bt_try;
+mod btree;
+mod cancel;
+mod clock;
+mod composite;
+mod condition_node;
+mod configure;
+mod context;
+mod cooldown;
+mod coroutine;
+mod delay;
+mod dyn_payload;
+mod emit;
+mod error;
+mod external;
+mod factory;
+mod guard;
+mod halt;
+mod history;
+mod if_then_else;
+mod interlock;
+mod intern;
+mod inverter;
+mod livelock;
+mod memory;
+mod mut_payload;
+mod outline;
+mod parallel;
+mod peel;
+mod ports;
+mod priority;
+mod random;
+mod reactive;
+mod repeat;
+mod report;
+mod retry;
+mod round_robin;
+mod run_once;
+mod scope;
+mod send;
+mod subtree;
+mod switch;
+mod switch_node;
+mod timeout;
+mod traced;
+mod tree;
+mod utility;
+mod while_do_else;
+
+pub mod bounded;
+pub mod mergers;
+pub mod prelude;
+pub mod testing;
+
+#[cfg(feature = "stdlib")]
+pub mod stdlib;
+
+#[cfg(feature = "alloc-counter")]
+pub mod alloc_counter;
+
+#[cfg(feature = "watchdog")]
+pub mod watchdog;
+
+#[cfg(feature = "xml")]
+pub mod btcpp;
+
+#[cfg(feature = "groot2")]
+pub mod groot2;
+
+#[cfg(feature = "tokio")]
+pub mod tokio_time;
+
+#[cfg(feature = "rayon")]
+pub mod parallel_rayon;
+
+#[cfg(feature = "tracing")]
+pub mod instrument;
+
+#[cfg(feature = "serde")]
+pub mod loader;
+
+#[cfg(feature = "serde")]
+pub mod manifest;
+
+#[cfg(feature = "serde")]
+pub mod persist;
+
+use crate::composite::{
+    child_meta_methods, children_vec_methods, composite_node_introspection, Composite,
+};
+use crate::mergers::Merge;
+use std::any::Any;
 use std::cmp::PartialEq;
 
+pub use crate::blackboard::{
+    Blackboard, BlackboardChange, BlackboardKey, BlackboardScope, BlackboardSnapshot,
+    HasBlackboard, TypedKey, WithBlackboard,
+};
+pub use crate::borrow_guard::{BorrowConflict, CatchBorrowConflict};
+pub use crate::cancel::{CancellationToken, Cancelled};
+pub use crate::clock::{Clock, ManualClock, ScaledClock, SystemClock};
+pub use crate::composite::ChildMeta;
+pub use crate::condition_node::ConditionNode;
+pub use crate::configure::{Configure, ConfigureError};
+pub use crate::context::{
+    BehaviorNode, ContextFallbackNode, ContextSequenceNode, IgnoreContext, WithContext,
+};
+pub use crate::cooldown::{Cooldown, CooldownRegistry};
+pub use crate::coroutine::{Coroutine, CoroutineActionNode, NextTick};
+pub use crate::delay::DelayNode;
+pub use crate::dyn_payload::{
+    Comparison, DynCondition, DynPayload, DynSet, DynValue, ParseComparisonError,
+};
+pub use crate::emit::EmitEvent;
+pub use crate::error::{BtError, BtErrorKind};
+pub use crate::external::{ActionHandle, ExternalActionNode};
+pub use crate::factory::{FactoryError, NodeConfig, NodeFactory, NodePlugin, ParamError};
+pub use crate::guard::{GuardCondition, GuardNode, NodeCondition};
+pub use crate::halt::{HaltIssue, HaltReport};
+pub use crate::history::{HistoryEntry, Recorded, ResultHistory};
+pub use crate::if_then_else::IfThenElseNode;
+pub use crate::interlock::InterlockNode;
+pub use crate::intern::{Interner, Resolved, Symbol};
+pub use crate::inverter::InverterNode;
+pub use crate::livelock::{Livelock, LivelockDetector};
+pub use crate::memory::{FallbackNodeWithMemory, SequenceNodeWithMemory};
+pub use crate::mergers::MergeResult;
+pub use crate::mut_payload::{ChildMut, FallbackNodeMut, SequenceNodeMut};
+pub use crate::outline::{OutlineNode, TreeOutline};
+pub use crate::parallel::ParallelNode;
+pub use crate::peel::{AdaptNode, MapPayloadNode, PeelOnce};
+pub use crate::ports::{Port, PortDirection, PortError, ValidatePorts};
+pub use crate::priority::PrioritySelectorNode;
+pub use crate::random::{
+    RandomContext, RandomFallbackNode, RandomSequenceNode, RandomSource, SplitMix64,
+};
+pub use crate::reactive::{ReactiveFallbackNode, ReactiveSequenceNode};
+pub use crate::repeat::RepeatNode;
+pub use crate::report::{NodeReport, TickReport};
+pub use crate::retry::{RetryNode, RotatingRetryNode};
+pub use crate::round_robin::RoundRobinNode;
+pub use crate::run_once::RunOnceNode;
+pub use crate::scope::ScopeNode;
+pub use crate::send::{ChildSend, FallbackNodeSend, SequenceNodeSend};
+pub use crate::subtree::{InstantiateError, SubtreeLibrary, SubtreeNode, SubtreeTemplate};
+pub use crate::switch::EnableSwitch;
+pub use crate::switch_node::SwitchNode;
+pub use crate::timeout::TimeoutNode;
+pub use crate::traced::{append_traced, Traced, TracedNode};
+pub use crate::tree::{Tree, TreeBuildError};
+pub use crate::utility::UtilitySelectorNode;
+pub use crate::while_do_else::WhileDoElseNode;
+
+/// Derives [BehaviorNodeBase] for a newtype wrapper by forwarding to the inner node.
+///
+/// Custom decorators are often a newtype around another node that only
+/// override some aspects of it. Deriving the trait keeps such wrappers
+/// working when [BehaviorNodeBase] gains new methods.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// #[derive(DelegateNode)]
+/// struct Named<T>(T);
+///
+/// struct Succeed;
+///
+/// impl BehaviorNodeBase<(), (), ()> for Succeed {
+///     fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// assert_eq!(Named(Succeed).tick(()), BehaviorResult::Success(()));
+/// ```
+///
+/// If the struct has more than one field, mark the inner node with `#[delegate]`.
+#[cfg(feature = "derive")]
+pub use tiny_behavior_tree_derive::DelegateNode;
+
+/// Derives [BehaviorNodeBase] for a condition that compares a value in the payload with a threshold.
+///
+/// Many condition nodes only compare a value from the payload with a
+/// parameter stored in the node. Describe the payload type, how to get the value
+/// and the comparison operator in `#[condition(...)]` attribute to generate
+/// such a node.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Agent {
+///     health: u32,
+/// }
+///
+/// #[derive(Condition)]
+/// #[condition(payload = "&Agent", get = "payload.health", op = ">=")]
+/// struct MinHealth {
+///     value: u32,
+/// }
+///
+/// let mut node = MinHealth { value: 50 };
+/// assert_eq!(node.tick(&Agent { health: 80 }), BehaviorResult::Success(()));
+/// assert_eq!(node.tick(&Agent { health: 20 }), BehaviorResult::Failure(()));
+/// ```
+///
+/// The threshold is the only field of the struct, or the field marked with `#[condition]`.
+/// `op` defaults to `==`.
+#[cfg(feature = "derive")]
+pub use tiny_behavior_tree_derive::Condition;
+
+/// Derives [BehaviorNodeBase] for a leaf node that calls a method, with a
+/// constructor and a name for [NodeFactory].
+///
+/// Describe the payload and the method in `#[behavior_node(...)]` and mark
+/// the parameters of the node with `#[port]`.
+/// The derived `from_config` parses the ports from a [NodeConfig], `register`
+/// registers it with `NODE_NAME`, which is the name of the struct unless
+/// `name` is given, and [BehaviorNodeBase::describe] lists the ports.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Door {
+///     locked: bool,
+/// }
+///
+/// #[derive(BehaviorNode)]
+/// #[behavior_node(payload = "&Door", tick = "open")]
+/// struct OpenDoor {
+///     #[port(default = "1")]
+///     tries: u32,
+///     attempts: u32,
+/// }
+///
+/// impl OpenDoor {
+///     fn open(&mut self, door: &Door) -> BehaviorResult<(), ()> {
+///         self.attempts += 1;
+///         if !door.locked {
+///             BehaviorResult::Success(())
+///         } else if self.attempts < self.tries {
+///             BehaviorResult::Running
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+/// }
+///
+/// let door = Door { locked: true };
+/// let mut factory = NodeFactory::new();
+/// OpenDoor::register(&mut factory);
+/// let mut node = factory.create("OpenDoor", &NodeConfig::new().with("tries", "2")).unwrap();
+/// assert_eq!(node.describe(), [("tries", "2".to_string())]);
+/// assert_eq!(node.tick(&door), BehaviorResult::Running);
+/// assert_eq!(node.tick(&door), BehaviorResult::Failure(()));
+/// ```
+///
+/// `success` and `failure` set the result types, which default to `()`, as does the payload.
+#[cfg(feature = "derive")]
+pub use tiny_behavior_tree_derive::BehaviorNode;
+
+/// Turns a function into an action node.
+///
+/// The function takes the payload, or nothing, and returns a [BehaviorResult].
+/// The attribute keeps the function and defines a unit struct named in
+/// CamelCase that calls it, with `NODE_NAME` and `register` like
+/// [BehaviorNode]. `#[behavior_action(name = "...")]` sets the `NODE_NAME`.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::RefCell;
+/// struct Door {
+///     open: bool,
+/// }
+///
+/// #[behavior_action]
+/// fn open_door(door: &RefCell<Door>) -> BehaviorResult<(), ()> {
+///     door.borrow_mut().open = true;
+///     BehaviorResult::Success(())
+/// }
+///
+/// let door = RefCell::new(Door { open: false });
+/// assert_eq!(OpenDoor.tick(&door), BehaviorResult::Success(()));
+/// assert!(door.borrow().open);
+///
+/// let mut factory = NodeFactory::new();
+/// OpenDoor::register(&mut factory);
+/// assert!(factory.contains("OpenDoor"));
+/// ```
+#[cfg(feature = "derive")]
+pub use tiny_behavior_tree_derive::behavior_action;
+
+/// Generates a method that lists the fields of a node for [BehaviorNodeBase::describe].
+///
+/// The derived `describe_fields` method returns the names of the fields with
+/// their [Debug](std::fmt::Debug) representations.
+/// Mark fields that should not be listed, like child nodes, with `#[describe(skip)]`.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// #[derive(Describe)]
+/// struct Patrol {
+///     speed: f32,
+///     waypoints: Vec<(i32, i32)>,
+///     #[describe(skip)]
+///     path_cache: Vec<u8>,
+/// }
+///
+/// impl BehaviorNodeBase<(), (), ()> for Patrol {
+///     fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+///         BehaviorResult::Running
+///     }
+///
+///     fn describe(&self) -> Vec<(&'static str, String)> {
+///         self.describe_fields()
+///     }
+/// }
+///
+/// let node: Box<dyn BehaviorNodeBase<(), (), ()>> = Box::new(Patrol {
+///     speed: 1.5,
+///     waypoints: vec![(0, 0), (3, 4)],
+///     path_cache: vec![],
+/// });
+/// assert_eq!(
+///     node.describe(),
+///     [("speed", "1.5".to_string()), ("waypoints", "[(0, 0), (3, 4)]".to_string())]
+/// );
+/// ```
+#[cfg(feature = "derive")]
+pub use tiny_behavior_tree_derive::Describe;
+
+/// Defines condition nodes for boolean fields and getters of a payload type.
+///
+/// Many other condition nodes only check a flag of the payload.
+/// List the fields, and the getters followed by `()`, to generate a node type
+/// named in CamelCase for each of them, which succeeds if the value is true.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Agent {
+///     has_key: bool,
+///     room: u32,
+/// }
+///
+/// impl Agent {
+///     fn in_room(&self) -> bool {
+///         self.room != 0
+///     }
+/// }
+///
+/// conditions_for!(Agent { has_key, in_room() });
+///
+/// let agent = Agent { has_key: true, room: 0 };
+/// assert_eq!(HasKey.tick(&agent), BehaviorResult::Success(()));
+/// assert_eq!(InRoom.tick(&agent), BehaviorResult::Failure(()));
+/// ```
+///
+/// Put a visibility before the payload type, e.g. `pub Agent { ... }`, to make the node types public.
+#[cfg(feature = "derive")]
+pub use tiny_behavior_tree_derive::conditions_for;
+
 /// The result type for behavior nodes.
 ///
 /// It is generic over result type `R` and `F`, which contains success and
 /// failure cases' results, respectively.
-#[derive(PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum BehaviorResult<R, F> {
     Idle,
     Running,
@@ -198,13 +558,251 @@ pub enum BehaviorResult<R, F> {
     Failure(F),
 }
 
+impl<R, F> BehaviorResult<R, F> {
+    /// Returns the variant of this result without the payload.
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::*;
+    /// let res = BehaviorResult::<String, ()>::Success("done".to_string());
+    /// assert_eq!(res.kind(), ResultKind::Success);
+    /// ```
+    pub fn kind(&self) -> ResultKind {
+        match self {
+            Self::Idle => ResultKind::Idle,
+            Self::Running => ResultKind::Running,
+            Self::Success(_) => ResultKind::Success,
+            Self::Failure(_) => ResultKind::Failure,
+        }
+    }
+}
+
+/// The variant of a [BehaviorResult] without the payload, returned by [BehaviorResult::kind].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ResultKind {
+    Idle,
+    Running,
+    Success,
+    Failure,
+}
+
 /// The basis of the behavior tree. Every behavior node implements this trait.
 pub trait BehaviorNodeBase<Payload, R, F> {
     fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F>;
+
+    /// Ticks this node, reporting errors of the infrastructure it depends on,
+    /// like poisoned locks or disconnected channels, separately from
+    /// behavioral failures.
+    ///
+    /// Nodes that can encounter such errors should implement it and make
+    /// [BehaviorNodeBase::tick] fall back to a reasonable result.
+    /// Control nodes propagate the first error from their children, so that
+    /// calling it on the root tells which node could not run.
+    ///
+    /// The default implementation calls [BehaviorNodeBase::tick] and never fails.
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        Ok(self.tick(payload))
+    }
+
+    /// Stops any ongoing action of this node and its descendants.
+    ///
+    /// A node that cannot stop cleanly, e.g. because an irreversible motion is in
+    /// progress, should return [HaltReport::incomplete] with the reason.
+    /// Control nodes halt all of their children and merge the reports, so that the
+    /// caller can see whether the abort actually took effect.
+    ///
+    /// The default implementation does nothing and reports a clean stop.
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::clean()
+    }
+
+    /// Brings this node and its descendants back to the state right after
+    /// construction, so that the tree can start over, e.g. in a new episode.
+    ///
+    /// Unlike [BehaviorNodeBase::halt], it also forgets the state that nodes
+    /// keep across activations, like the position of a
+    /// [RoundRobinNode] or the stored result of a [RunOnceNode].
+    /// Running actions are halted, and the merged [HaltReport] is returned.
+    /// Control nodes reset their children instead of halting them.
+    ///
+    /// The default implementation calls [BehaviorNodeBase::halt], which is
+    /// enough for nodes without such state.
+    fn reset(&mut self) -> HaltReport {
+        self.halt()
+    }
+
+    /// Returns this node as [Any] so that it can be downcast to its concrete type,
+    /// e.g. by [Configure::configure].
+    ///
+    /// The default implementation returns `None`, which means the node is opaque.
+    /// Nodes that want to be configurable at runtime can implement it as `Some(self)`.
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        None
+    }
+
+    /// Returns child nodes that share the payload type with this node.
+    ///
+    /// Control nodes return their children in order, so that a child can be
+    /// addressed by a path of indices.
+    /// Nodes that convert the payload, like peel nodes, cannot return their
+    /// children here, so they appear as leaves.
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        vec![]
+    }
+
+    /// Appends a child node if this is a control node that can have any number of children.
+    ///
+    /// Other nodes return the given node back as an error.
+    fn push_child(
+        &mut self,
+        child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+    where
+        Self: 'static,
+    {
+        Err(child)
+    }
+
+    /// Returns the annotations of the child at `index`, if this is a control
+    /// node that stores them.
+    ///
+    /// The default implementation returns `None`.
+    fn child_meta(&self, _index: usize) -> Option<&ChildMeta> {
+        None
+    }
+
+    /// Sets the annotations of the child at `index`, if this is a control
+    /// node that stores them, e.g. when a tree is loaded from a definition.
+    ///
+    /// Other nodes, and control nodes without a child at `index`, return the
+    /// given annotations back as an error.
+    fn set_child_meta(&mut self, _index: usize, meta: ChildMeta) -> Result<(), ChildMeta> {
+        Err(meta)
+    }
+
+    /// Returns the current values of the parameters and the state of this
+    /// node as names and [Debug](std::fmt::Debug) representations, so that
+    /// inspectors and status dumps can show e.g. `attempts=3, used=1`.
+    ///
+    /// The default implementation returns nothing.
+    /// `#[derive(Describe)]` generates a method that lists the fields of a
+    /// struct, which an implementation can return.
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+
+    /// Returns the blackboard entries that this node reads or writes, so that
+    /// [ValidatePorts::validate_ports] can check them before the tree runs.
+    ///
+    /// The default implementation returns nothing.
+    fn ports(&self) -> Vec<Port> {
+        vec![]
+    }
+
+    /// Returns the name of the node type and the parameters that a
+    /// [NodeFactory] created this node with, so that a tree can be saved
+    /// back to a definition file.
+    ///
+    /// The default implementation returns `None`.
+    fn definition(&self) -> Option<(&str, &NodeConfig)> {
+        None
+    }
 }
 
+/// Implements [BehaviorNodeBase] for a pointer type to a node `T` by
+/// forwarding all the methods to the node.
+macro_rules! forward_node_impl {
+    ($ptr:ty) => {
+        impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for $ptr
+        where
+            T: BehaviorNodeBase<Payload, R, F> + ?Sized,
+        {
+            fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+                (**self).tick(payload)
+            }
+
+            fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+                (**self).try_tick(payload)
+            }
+
+            fn halt(&mut self) -> HaltReport {
+                (**self).halt()
+            }
+
+            fn reset(&mut self) -> HaltReport {
+                (**self).reset()
+            }
+
+            fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+            where
+                Self: 'static,
+            {
+                (**self).as_any_mut()
+            }
+
+            fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+            where
+                Self: 'static,
+            {
+                (**self).children_mut()
+            }
+
+            fn push_child(
+                &mut self,
+                child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+            ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+            where
+                Self: 'static,
+            {
+                (**self).push_child(child)
+            }
+
+            fn child_meta(&self, index: usize) -> Option<&ChildMeta> {
+                (**self).child_meta(index)
+            }
+
+            fn set_child_meta(&mut self, index: usize, meta: ChildMeta) -> Result<(), ChildMeta> {
+                (**self).set_child_meta(index, meta)
+            }
+
+            fn describe(&self) -> Vec<(&'static str, String)> {
+                (**self).describe()
+            }
+
+            fn ports(&self) -> Vec<Port> {
+                (**self).ports()
+            }
+
+            fn definition(&self) -> Option<(&str, &NodeConfig)> {
+                (**self).definition()
+            }
+        }
+    };
+}
+
+// Boxed and borrowed nodes are nodes themselves, so that they can be given to
+// decorators and wrappers that take a node by value.
+forward_node_impl!(Box<T>);
+forward_node_impl!(&mut T);
+
+/// A callback to observe where a control node short-circuited,
+/// see [SequenceNode::on_short_circuit].
+pub type ShortCircuitCallback<'a, R, F> = Box<dyn FnMut(usize, &BehaviorResult<R, F>) + 'a>;
+
 /// Sequence returns success if all child nodes succeed, otherwise returns failure on first child node's failure.
 ///
+/// If a child returns `Running`, it returns `Running` without ticking the rest,
+/// and starts over from the first child in the next tick.
+/// If another child returns `Running` or decides the result in that tick,
+/// the child that was running is halted.
+/// Use [SequenceNodeWithMemory] to resume from the running child instead.
+/// Use [SequenceNodeSend] to move the tree to another thread.
+///
 /// It has a handful of generic parameters.
 ///
 /// * `Payload`: the type that is passed down to child nodes
@@ -229,9 +827,11 @@ pub trait BehaviorNodeBase<Payload, R, F> {
 /// ```ignore
 /// |result: &mut Vec<String>, mut merge: Vec<String>| result.append(&mut merge)
 /// ```
+///
+/// For `()`, `Vec<T>` and `Option<T>`, [SequenceNode::merged] merges the
+/// results without a merger function, see [Merge](mergers::Merge).
 pub struct SequenceNode<Payload, R, F, MR> {
-    children: Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>,
-    merge_result: Option<MR>,
+    inner: Composite<'static, Payload, R, F, MR, R>,
 }
 
 impl<Payload, R, F> SequenceNode<Payload, R, F, &dyn Fn(&mut R, R)> {
@@ -244,10 +844,25 @@ impl<Payload, R, F> SequenceNode<Payload, R, F, &dyn Fn(&mut R, R)> {
         T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
     {
         Self {
-            children: children.into(),
-            merge_result: None,
+            inner: Composite::new(children.into(), None),
         }
     }
+
+    /// Constructs a [SequenceNode] without children, with room for `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+}
+
+impl<Payload, R, F> SequenceNode<Payload, R, F, Merge> {
+    /// Constructs a [SequenceNode] with children nodes, merging their results in
+    /// `R` with [Merge].
+    pub fn merged<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self::new_with_merger(children, Merge)
+    }
 }
 
 impl<Payload, R, F, MR> SequenceNode<Payload, R, F, MR> {
@@ -257,35 +872,64 @@ impl<Payload, R, F, MR> SequenceNode<Payload, R, F, MR> {
         T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
     {
         Self {
-            children: children.into(),
-            merge_result: Some(merge_result),
+            inner: Composite::new(children.into(), Some(merge_result)),
         }
     }
+
+    children_vec_methods!(inner.children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when a child fails and ends this node,
+    /// with the index of the child and its result.
+    ///
+    /// It is called for the last child as well, but not for a running child.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'static,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+
+    /// Limits the number of children ticked in a single call to `max`.
+    ///
+    /// If there are more children to tick, this node returns
+    /// [BehaviorResult::Running] and resumes from the next child in the
+    /// following tick, which spreads the cost of a long list of children over
+    /// multiple frames.
+    /// Halting this node discards the progress.
+    ///
+    /// Panics if `max` is 0.
+    pub fn max_children_per_tick(mut self, max: usize) -> Self {
+        self.inner.set_max_children_per_tick(max);
+        self
+    }
 }
 
 impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F> for SequenceNode<Payload, R, F, MR>
 where
     R: Default,
     Payload: Clone,
-    MR: Fn(&mut R, R),
+    MR: MergeResult<R>,
 {
     fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
-        let mut last_success = R::default();
-        for node in &mut self.children {
-            match node.tick(payload.clone()) {
-                BehaviorResult::Success(r) => {
-                    if let Some(ref merge_result) = self.merge_result {
-                        merge_result(&mut last_success, r)
-                    } else {
-                        last_success = r
-                    }
-                }
-                BehaviorResult::Failure(f) => return BehaviorResult::Failure(f),
-                _ => (),
-            }
-        }
-        BehaviorResult::Success(last_success)
+        self.inner.tick_sequence(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.inner.try_tick_sequence(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
     }
+
+    composite_node_introspection!(Payload);
 }
 
 /// SequenceNode that takes reference to an argument object.
@@ -299,10 +943,9 @@ where
 ///
 /// This node will pass down shared reference, so you cannot mutate the
 /// referred object in the child nodes.
-/// If you want to do so, use [RefCell] as `Payload`.
+/// If you want to do so, use [SequenceNodeMut], or [RefCell] as `Payload`.
 pub struct SequenceNodeRef<'a, Payload, R, F, MR> {
-    children: Vec<Box<dyn BehaviorNodeBase<&'a Payload, R, F> + 'a>>,
-    merge_result: Option<MR>,
+    inner: Composite<'a, &'a Payload, R, F, MR, R>,
 }
 
 impl<'a, Payload, R, F> SequenceNodeRef<'a, Payload, R, F, &dyn Fn(&mut R, R)> {
@@ -315,10 +958,14 @@ impl<'a, Payload, R, F> SequenceNodeRef<'a, Payload, R, F, &dyn Fn(&mut R, R)> {
         T: Into<Vec<Box<dyn BehaviorNodeBase<&'a Payload, R, F> + 'a>>>,
     {
         Self {
-            children: children.into(),
-            merge_result: None,
+            inner: Composite::new(children.into(), None),
         }
     }
+
+    /// Constructs a [SequenceNodeRef] without children, with room for `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
 }
 
 impl<'a, Payload, R, F, MR> SequenceNodeRef<'a, Payload, R, F, MR> {
@@ -328,39 +975,77 @@ impl<'a, Payload, R, F, MR> SequenceNodeRef<'a, Payload, R, F, MR> {
         T: Into<Vec<Box<dyn BehaviorNodeBase<&'a Payload, R, F> + 'a>>>,
     {
         Self {
-            children: children.into(),
-            merge_result: Some(merge_result),
+            inner: Composite::new(children.into(), Some(merge_result)),
         }
     }
+
+    children_vec_methods!(
+        inner.children,
+        Box<dyn BehaviorNodeBase<&'a Payload, R, F> + 'a>
+    );
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when a child fails and ends this node,
+    /// with the index of the child and its result.
+    ///
+    /// It is called for the last child as well, but not for a running child.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'a,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+
+    /// Limits the number of children ticked in a single call to `max`.
+    ///
+    /// If there are more children to tick, this node returns
+    /// [BehaviorResult::Running] and resumes from the next child in the
+    /// following tick, which spreads the cost of a long list of children over
+    /// multiple frames.
+    /// Halting this node discards the progress.
+    ///
+    /// Panics if `max` is 0.
+    pub fn max_children_per_tick(mut self, max: usize) -> Self {
+        self.inner.set_max_children_per_tick(max);
+        self
+    }
 }
 
 impl<'a, Payload, R, F, MR> BehaviorNodeBase<&'a Payload, R, F>
     for SequenceNodeRef<'a, Payload, R, F, MR>
 where
     R: Default,
-    MR: Fn(&mut R, R),
+    MR: MergeResult<R>,
 {
     fn tick(&mut self, payload: &'a Payload) -> BehaviorResult<R, F> {
-        let mut last_success = R::default();
-        for node in &mut self.children {
-            match node.tick(payload) {
-                BehaviorResult::Success(r) => {
-                    if let Some(ref merge_result) = self.merge_result {
-                        merge_result(&mut last_success, r)
-                    } else {
-                        last_success = r
-                    }
-                }
-                BehaviorResult::Failure(f) => return BehaviorResult::Failure(f),
-                _ => (),
-            }
-        }
-        BehaviorResult::Success(last_success)
+        self.inner.tick_sequence(payload)
+    }
+
+    fn try_tick(&mut self, payload: &'a Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.inner.try_tick_sequence(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
     }
+
+    composite_node_introspection!(&'a Payload);
 }
 
 /// Fallback returns failure if all child nodes fail, otherwise returns success on first child node's success.
 ///
+/// If a child returns `Running`, it returns `Running` without ticking the rest,
+/// and starts over from the first child in the next tick.
+/// If another child returns `Running` or decides the result in that tick,
+/// the child that was running is halted.
+/// Use [FallbackNodeWithMemory] to resume from the running child instead.
+///
 /// It has a handful of generic parameters.
 ///
 /// * `Payload`: the type that is passed down to child nodes
@@ -385,9 +1070,11 @@ where
 /// ```ignore
 /// |result: &mut Vec<String>, mut merge: Vec<String>| result.append(&mut merge)
 /// ```
+///
+/// For `()`, `Vec<T>` and `Option<T>`, [FallbackNode::merged] merges the
+/// results without a merger function, see [Merge](mergers::Merge).
 pub struct FallbackNode<Payload, R, F, MR> {
-    children: Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>,
-    merge_result: Option<MR>,
+    inner: Composite<'static, Payload, R, F, MR, F>,
 }
 
 impl<Payload, R, F> FallbackNode<Payload, R, F, &dyn Fn(&mut F, F)> {
@@ -400,10 +1087,25 @@ impl<Payload, R, F> FallbackNode<Payload, R, F, &dyn Fn(&mut F, F)> {
         T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
     {
         Self {
-            children: children.into(),
-            merge_result: None,
+            inner: Composite::new(children.into(), None),
         }
     }
+
+    /// Constructs a [FallbackNode] without children, with room for `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+}
+
+impl<Payload, R, F> FallbackNode<Payload, R, F, Merge> {
+    /// Constructs a [FallbackNode] with children nodes, merging their results in
+    /// `F` with [Merge].
+    pub fn merged<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self::new_with_merger(children, Merge)
+    }
 }
 
 impl<Payload, R, F, MR> FallbackNode<Payload, R, F, MR> {
@@ -413,35 +1115,64 @@ impl<Payload, R, F, MR> FallbackNode<Payload, R, F, MR> {
         T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
     {
         Self {
-            children: children.into(),
-            merge_result: Some(merge_result),
+            inner: Composite::new(children.into(), Some(merge_result)),
         }
     }
+
+    children_vec_methods!(inner.children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when a child succeeds and ends this node,
+    /// with the index of the child and its result.
+    ///
+    /// It is called for the last child as well, but not for a running child.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'static,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+
+    /// Limits the number of children ticked in a single call to `max`.
+    ///
+    /// If there are more children to tick, this node returns
+    /// [BehaviorResult::Running] and resumes from the next child in the
+    /// following tick, which spreads the cost of a long list of children over
+    /// multiple frames.
+    /// Halting this node discards the progress.
+    ///
+    /// Panics if `max` is 0.
+    pub fn max_children_per_tick(mut self, max: usize) -> Self {
+        self.inner.set_max_children_per_tick(max);
+        self
+    }
 }
 
 impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F> for FallbackNode<Payload, R, F, MR>
 where
     F: Default,
     Payload: Clone,
-    MR: Fn(&mut F, F),
+    MR: MergeResult<F>,
 {
     fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
-        let mut last_failure = F::default();
-        for node in &mut self.children {
-            match node.tick(payload.clone()) {
-                BehaviorResult::Success(r) => return BehaviorResult::Success(r),
-                BehaviorResult::Failure(f) => {
-                    if let Some(ref merge_result) = self.merge_result {
-                        merge_result(&mut last_failure, f)
-                    } else {
-                        last_failure = f
-                    }
-                }
-                _ => (),
-            }
-        }
-        BehaviorResult::Failure(last_failure)
+        self.inner.tick_fallback(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.inner.try_tick_fallback(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
     }
+
+    composite_node_introspection!(Payload);
 }
 
 /// FallbackNode that takes reference to an argument object.
@@ -455,10 +1186,9 @@ where
 ///
 /// This node will pass down shared reference, so you cannot mutate the
 /// referred object in the child nodes.
-/// If you want to do so, use [RefCell] as `Payload`.
+/// If you want to do so, use [FallbackNodeMut], or [RefCell] as `Payload`.
 pub struct FallbackNodeRef<'a, Payload, R, F, MR> {
-    children: Vec<Box<dyn BehaviorNodeBase<&'a Payload, R, F> + 'a>>,
-    merge_result: Option<MR>,
+    inner: Composite<'a, &'a Payload, R, F, MR, F>,
 }
 
 impl<'a, Payload, R, F> FallbackNodeRef<'a, Payload, R, F, &dyn Fn(&mut F, F)> {
@@ -470,10 +1200,14 @@ impl<'a, Payload, R, F> FallbackNodeRef<'a, Payload, R, F, &dyn Fn(&mut F, F)> {
         T: Into<Vec<Box<dyn BehaviorNodeBase<&'a Payload, R, F> + 'a>>>,
     {
         Self {
-            children: children.into(),
-            merge_result: None,
+            inner: Composite::new(children.into(), None),
         }
     }
+
+    /// Constructs a [FallbackNodeRef] without children, with room for `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
 }
 
 impl<'a, Payload, R, F, MR> FallbackNodeRef<'a, Payload, R, F, MR> {
@@ -483,35 +1217,67 @@ impl<'a, Payload, R, F, MR> FallbackNodeRef<'a, Payload, R, F, MR> {
         T: Into<Vec<Box<dyn BehaviorNodeBase<&'a Payload, R, F> + 'a>>>,
     {
         Self {
-            children: children.into(),
-            merge_result: Some(merge_result),
+            inner: Composite::new(children.into(), Some(merge_result)),
         }
     }
+
+    children_vec_methods!(
+        inner.children,
+        Box<dyn BehaviorNodeBase<&'a Payload, R, F> + 'a>
+    );
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when a child succeeds and ends this node,
+    /// with the index of the child and its result.
+    ///
+    /// It is called for the last child as well, but not for a running child.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'a,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+
+    /// Limits the number of children ticked in a single call to `max`.
+    ///
+    /// If there are more children to tick, this node returns
+    /// [BehaviorResult::Running] and resumes from the next child in the
+    /// following tick, which spreads the cost of a long list of children over
+    /// multiple frames.
+    /// Halting this node discards the progress.
+    ///
+    /// Panics if `max` is 0.
+    pub fn max_children_per_tick(mut self, max: usize) -> Self {
+        self.inner.set_max_children_per_tick(max);
+        self
+    }
 }
 
 impl<'a, Payload, R, F, MR> BehaviorNodeBase<&'a Payload, R, F>
     for FallbackNodeRef<'a, Payload, R, F, MR>
 where
     F: Default,
-    MR: Fn(&mut F, F),
+    MR: MergeResult<F>,
 {
     fn tick(&mut self, payload: &'a Payload) -> BehaviorResult<R, F> {
-        let mut last_failure = F::default();
-        for node in &mut self.children {
-            match node.tick(payload) {
-                BehaviorResult::Success(r) => return BehaviorResult::Success(r),
-                BehaviorResult::Failure(f) => {
-                    if let Some(ref merge_result) = self.merge_result {
-                        merge_result(&mut last_failure, f)
-                    } else {
-                        last_failure = f
-                    }
-                }
-                _ => (),
-            }
-        }
-        BehaviorResult::Failure(last_failure)
+        self.inner.tick_fallback(payload)
+    }
+
+    fn try_tick(&mut self, payload: &'a Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.inner.try_tick_fallback(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
     }
+
+    composite_node_introspection!(&'a Payload);
 }
 
 /// A utility macro to define "peel nodes".
@@ -549,20 +1315,235 @@ where
 /// This is because the macro expands to a generic impl with <'a>.
 ///
 /// Interestingly, Rust's macros are hygienic about identifier names, but not about lifetimes.
+///
+/// # Visibility and generics
+///
+/// The name can be preceded by a visibility, which is given to the struct
+/// and its field, so that other modules can construct the node.
+/// It can be followed by type parameters with optional bounds, which the
+/// payload types and `peel` can use.
+/// The node implements [BehaviorNodeBase] for any of them, so the struct
+/// itself has only the type parameter of the child node.
+///
+/// ```
+/// mod nodes {
+///     # use tiny_behavior_tree::peel_node_def;
+///     pub struct Arm<G> {
+///         pub grip: G,
+///     }
+///
+///     pub struct Body<G> {
+///         pub left_arm: Arm<G>,
+///     }
+///
+///     peel_node_def!(pub PeelLeftArmNode<G: Clone>, Body<G>, Arm<G>, (), (), |payload: &'a Body<G>| &payload.left_arm);
+/// }
+///
+/// # use tiny_behavior_tree::*;
+/// use nodes::*;
+///
+/// struct CheckGrip;
+///
+/// impl<'a> BehaviorNodeBase<&'a Arm<f64>, (), ()> for CheckGrip {
+///     fn tick(&mut self, arm: &'a Arm<f64>) -> BehaviorResult<(), ()> {
+///         if 0.5 < arm.grip {
+///             BehaviorResult::Success(())
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+/// }
+///
+/// let body = Body { left_arm: Arm { grip: 0.8 } };
+/// assert_eq!(PeelLeftArmNode(CheckGrip).tick(&body), BehaviorResult::Success(()));
+/// ```
 #[macro_export]
 macro_rules! peel_node_def {
-    ($name:ident, $parent_payload:ty, $payload:ty, $r:ty, $f:ty, $peel:expr) => {
-        struct $name<T>(T);
+    (
+        $vis:vis $name:ident $(<$($param:ident $(: $bound:path)?),+>)?,
+        $parent_payload:ty, $payload:ty, $r:ty, $f:ty, $peel:expr
+    ) => {
+        $vis struct $name<PeelChild>($vis PeelChild);
 
-        impl<'a, T: tiny_behavior_tree::BehaviorNodeBase<&'a $payload, $r, $f>>
-            tiny_behavior_tree::BehaviorNodeBase<&'a $parent_payload, $r, $f> for $name<T>
+        impl<'a, $($($param $(: $bound)?,)+)? PeelChild: $crate::BehaviorNodeBase<&'a $payload, $r, $f>>
+            $crate::BehaviorNodeBase<&'a $parent_payload, $r, $f> for $name<PeelChild>
         {
-            fn tick(
+            fn tick(&mut self, payload: &'a $parent_payload) -> $crate::BehaviorResult<$r, $f> {
+                self.0.tick($peel(payload))
+            }
+
+            fn try_tick(
                 &mut self,
                 payload: &'a $parent_payload,
-            ) -> tiny_behavior_tree::BehaviorResult<$r, $f> {
+            ) -> ::std::result::Result<$crate::BehaviorResult<$r, $f>, $crate::BtError> {
+                self.0.try_tick($peel(payload))
+            }
+
+            fn halt(&mut self) -> $crate::HaltReport {
+                self.0.halt()
+            }
+
+            fn reset(&mut self) -> $crate::HaltReport {
+                self.0.reset()
+            }
+        }
+    };
+}
+
+/// A variant of [peel_node_def] for mutable references, which defines a
+/// peel node converting `&'a mut Parent` into `&'a mut Child`.
+///
+/// The arguments, including the visibility and the type parameters, are the
+/// same as [peel_node_def], but `peel` takes and
+/// returns mutable references, e.g. `|payload: &'a mut Body| &mut payload.left_arm`.
+/// The defined node can be a child of [SequenceNodeMut] and [FallbackNodeMut],
+/// so the state of a heterogeneous tree can be mutated without [RefCell](std::cell::RefCell).
+///
+/// # Example
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Arm {
+///     raised: bool,
+/// }
+///
+/// struct Body {
+///     left_arm: Arm,
+///     right_arm: Arm,
+/// }
+///
+/// peel_node_def_mut!(PeelLeftArmNode, Body, Arm, (), (), |payload: &'a mut Body| &mut payload.left_arm);
+///
+/// struct RaiseArmNode;
+///
+/// impl<'a> BehaviorNodeBase<&'a mut Arm, (), ()> for RaiseArmNode {
+///     fn tick(&mut self, arm: &'a mut Arm) -> BehaviorResult<(), ()> {
+///         arm.raised = true;
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// let mut body = Body {
+///     left_arm: Arm { raised: false },
+///     right_arm: Arm { raised: false },
+/// };
+/// let mut tree = PeelLeftArmNode(RaiseArmNode);
+/// assert_eq!(tree.tick(&mut body), BehaviorResult::Success(()));
+/// assert!(body.left_arm.raised);
+/// assert!(!body.right_arm.raised);
+/// ```
+#[macro_export]
+macro_rules! peel_node_def_mut {
+    (
+        $vis:vis $name:ident $(<$($param:ident $(: $bound:path)?),+>)?,
+        $parent_payload:ty, $payload:ty, $r:ty, $f:ty, $peel:expr
+    ) => {
+        $vis struct $name<PeelChild>($vis PeelChild);
+
+        impl<'a, $($($param $(: $bound)?,)+)? PeelChild: $crate::BehaviorNodeBase<&'a mut $payload, $r, $f>>
+            $crate::BehaviorNodeBase<&'a mut $parent_payload, $r, $f> for $name<PeelChild>
+        {
+            fn tick(&mut self, payload: &'a mut $parent_payload) -> $crate::BehaviorResult<$r, $f> {
                 self.0.tick($peel(payload))
             }
+
+            fn try_tick(
+                &mut self,
+                payload: &'a mut $parent_payload,
+            ) -> ::std::result::Result<$crate::BehaviorResult<$r, $f>, $crate::BtError> {
+                self.0.try_tick($peel(payload))
+            }
+
+            fn halt(&mut self) -> $crate::HaltReport {
+                self.0.halt()
+            }
+
+            fn reset(&mut self) -> $crate::HaltReport {
+                self.0.reset()
+            }
+        }
+    };
+}
+
+/// A variant of [peel_node_def] whose `peel` returns `Option<&'a Child>`,
+/// e.g. `|payload: &'a Body| payload.tool.as_ref()`, for a part of the
+/// payload that may not exist.
+///
+/// The defined node returns `Failure` with `Default::default()` of the
+/// failure type without ticking the child if `peel` returns `None`.
+/// The other arguments are the same as [peel_node_def].
+///
+/// # Example
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Tool {
+///     name: String,
+/// }
+///
+/// struct Body {
+///     tool: Option<Tool>,
+/// }
+///
+/// peel_node_def_opt!(PeelToolNode, Body, Tool, (), (), |payload: &'a Body| payload.tool.as_ref());
+///
+/// struct UseToolNode;
+///
+/// impl<'a> BehaviorNodeBase<&'a Tool, (), ()> for UseToolNode {
+///     fn tick(&mut self, tool: &'a Tool) -> BehaviorResult<(), ()> {
+///         println!("using {}", tool.name);
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// let mut tree = PeelToolNode(UseToolNode);
+/// let body = Body {
+///     tool: Some(Tool { name: "hammer".to_string() }),
+/// };
+/// assert_eq!(tree.tick(&body), BehaviorResult::Success(()));
+/// assert_eq!(tree.tick(&Body { tool: None }), BehaviorResult::Failure(()));
+/// ```
+#[macro_export]
+macro_rules! peel_node_def_opt {
+    (
+        $vis:vis $name:ident $(<$($param:ident $(: $bound:path)?),+>)?,
+        $parent_payload:ty, $payload:ty, $r:ty, $f:ty, $peel:expr
+    ) => {
+        $vis struct $name<PeelChild>($vis PeelChild);
+
+        impl<'a, $($($param $(: $bound)?,)+)? PeelChild: $crate::BehaviorNodeBase<&'a $payload, $r, $f>>
+            $crate::BehaviorNodeBase<&'a $parent_payload, $r, $f> for $name<PeelChild>
+        where
+            $f: ::std::default::Default,
+        {
+            fn tick(&mut self, payload: &'a $parent_payload) -> $crate::BehaviorResult<$r, $f> {
+                let peeled: ::std::option::Option<&'a $payload> = $peel(payload);
+                match peeled {
+                    ::std::option::Option::Some(payload) => self.0.tick(payload),
+                    ::std::option::Option::None => {
+                        $crate::BehaviorResult::Failure(::std::default::Default::default())
+                    }
+                }
+            }
+
+            fn try_tick(
+                &mut self,
+                payload: &'a $parent_payload,
+            ) -> ::std::result::Result<$crate::BehaviorResult<$r, $f>, $crate::BtError> {
+                let peeled: ::std::option::Option<&'a $payload> = $peel(payload);
+                match peeled {
+                    ::std::option::Option::Some(payload) => self.0.try_tick(payload),
+                    ::std::option::Option::None => ::std::result::Result::Ok(
+                        $crate::BehaviorResult::Failure(::std::default::Default::default()),
+                    ),
+                }
+            }
+
+            fn halt(&mut self) -> $crate::HaltReport {
+                self.0.halt()
+            }
+
+            fn reset(&mut self) -> $crate::HaltReport {
+                self.0.reset()
+            }
         }
     };
 }
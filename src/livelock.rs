@@ -0,0 +1,116 @@
+use crate::{ResultKind, TickReport};
+use std::fmt;
+
+/// Detects ticks that keep returning `Running` without any progress, which
+/// is a common symptom of a control node with memory resuming a child that
+/// never completes.
+///
+/// It compares the [TickReport]s of consecutive ticks, and reports a
+/// [Livelock] once the same nodes returned the same results, including
+/// `Running` ones, for more than `threshold` ticks in a row.
+/// Only the nodes that appear in the reports are compared, see [TickReport].
+///
+/// [Tree::detect_livelock](crate::Tree::detect_livelock) runs it on every
+/// tick of a tree in debug builds.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::{AlwaysRunning, AlwaysSuccess};
+/// type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+///
+/// let mut tree = Tree::new(Box::new(SequenceNodeWithMemory::new([
+///     Box::new(AlwaysSuccess(())) as Node,
+///     Box::new(AlwaysRunning),
+/// ])));
+/// let mut detector = LivelockDetector::new(2);
+/// let mut livelocks = vec![];
+/// for _ in 0..4 {
+///     let (_, report) = tree.tick_with_report(());
+///     livelocks.extend(detector.observe(&report));
+/// }
+/// assert_eq!(livelocks.len(), 1);
+/// assert_eq!(livelocks[0].paths, ["", "1"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LivelockDetector {
+    threshold: usize,
+    last: Vec<(String, ResultKind)>,
+    /// The number of consecutive ticks that returned the same results with `Running` in them.
+    repeats: usize,
+}
+
+/// Nodes that kept returning `Running` without any change in the tree,
+/// reported by [LivelockDetector].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Livelock {
+    /// The number of consecutive ticks without any change.
+    pub ticks: usize,
+    /// The paths of the nodes that returned `Running`, sorted.
+    pub paths: Vec<String>,
+}
+
+impl fmt::Display for Livelock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "No progress in {} ticks, running nodes: {}",
+            self.ticks,
+            self.paths
+                .iter()
+                .map(|path| format!("\"{}\"", path))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl LivelockDetector {
+    /// Constructs a [LivelockDetector] that reports after more than `threshold` ticks without change.
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            last: vec![],
+            repeats: 0,
+        }
+    }
+
+    /// Compares the report of a tick with the previous one, returning a
+    /// [Livelock] when the threshold is exceeded.
+    ///
+    /// A livelock is reported once until the tree changes.
+    pub fn observe(&mut self, report: &TickReport) -> Option<Livelock> {
+        let current: Vec<_> = report
+            .nodes()
+            .iter()
+            .map(|node| (node.path.clone(), node.kind))
+            .collect();
+        let running = current.iter().any(|(_, kind)| *kind == ResultKind::Running);
+        self.repeats = match (running, current == self.last) {
+            (false, _) => 0,
+            (true, true) => self.repeats + 1,
+            (true, false) => 1,
+        };
+        self.last = current;
+        if self.repeats != self.threshold + 1 {
+            return None;
+        }
+        let mut paths: Vec<_> = self
+            .last
+            .iter()
+            .filter(|(_, kind)| *kind == ResultKind::Running)
+            .map(|(path, _)| path.clone())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        Some(Livelock {
+            ticks: self.repeats,
+            paths,
+        })
+    }
+
+    /// Forgets the ticks observed so far, e.g. after halting the tree.
+    pub fn reset(&mut self) {
+        self.last.clear();
+        self.repeats = 0;
+    }
+}
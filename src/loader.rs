@@ -0,0 +1,494 @@
+//! Loading behavior trees from definition files, enabled by the `serde` feature.
+//!
+//! A definition is a [TreeDef], a tree of [NodeDef]s with the names of node
+//! types registered in a [NodeFactory], their parameters and their children.
+//! It implements `Serialize` and `Deserialize`, so it can be written in any
+//! format supported by serde, while [NodeFactory::load_json] reads JSON directly.
+//! With the `yaml` feature, [NodeFactory::load_yaml] reads the same definitions
+//! written in YAML, which is easier to write by hand for deep trees.
+//! [TreeDef::from_tree] goes the other way, saving a tree built through a
+//! factory, e.g. one generated by a program.
+//! With the `xml` feature, the `btcpp` module reads the tree files of BehaviorTree.CPP.
+//!
+//! ```
+//! # use tiny_behavior_tree::*;
+//! # use std::{cell::RefCell, rc::Rc};
+//! struct Say(String, Rc<RefCell<Vec<String>>>);
+//!
+//! impl BehaviorNodeBase<(), (), ()> for Say {
+//!     fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+//!         self.1.borrow_mut().push(self.0.clone());
+//!         BehaviorResult::Success(())
+//!     }
+//! }
+//!
+//! let log = Rc::new(RefCell::new(vec![]));
+//! let mut factory = NodeFactory::with_builtins();
+//! let log2 = log.clone();
+//! factory.register("Say", move |cfg| Ok(Say(cfg.get("text")?, log2.clone())));
+//!
+//! let mut tree = factory
+//!     .load_json(
+//!         r#"{
+//!             "format_version": 1,
+//!             "root": {
+//!                 "type": "Sequence",
+//!                 "children": [
+//!                     { "type": "Say", "params": { "text": "hello" } },
+//!                     { "type": "Say", "params": { "text": "world" } }
+//!                 ]
+//!             }
+//!         }"#,
+//!     )
+//!     .unwrap();
+//!
+//! assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+//! assert_eq!(*log.borrow(), ["hello", "world"]);
+//! ```
+use crate::configure::child_path;
+use crate::{BehaviorNodeBase, ChildMeta, FactoryError, NodeConfig, NodeFactory};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The version of the definition format that this release writes and reads.
+///
+/// Definitions in older versions are read through the migrations registered
+/// by [NodeFactory::register_migration].
+pub const FORMAT_VERSION: u32 = 1;
+
+/// A migration hook registered by [NodeFactory::register_migration].
+pub(crate) type Migration = Box<dyn Fn(&mut TreeDef)>;
+
+/// The root of a definition file, with the version of the format it was written in.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TreeDef {
+    pub format_version: u32,
+    pub root: NodeDef,
+}
+
+impl TreeDef {
+    /// Creates a definition in the current [FORMAT_VERSION].
+    pub fn new(root: NodeDef) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            root,
+        }
+    }
+
+    /// Creates a definition of a tree of nodes created by a [NodeFactory],
+    /// from their [BehaviorNodeBase::definition]s.
+    ///
+    /// The parameters are saved as strings, as they were given to the constructors.
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::{loader::TreeDef, testing::AlwaysSuccess, *};
+    /// let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+    /// factory.register("Succeed", |_| Ok(AlwaysSuccess(())));
+    ///
+    /// let mut tree = factory.create("Parallel", &NodeConfig::new().with("success_threshold", "1")).unwrap();
+    /// for _ in 0..2 {
+    ///     let child = factory.create("Succeed", &NodeConfig::new()).unwrap();
+    ///     assert!(tree.push_child(child).is_ok());
+    /// }
+    ///
+    /// let def = TreeDef::from_tree(tree.as_mut()).unwrap();
+    /// assert_eq!(
+    ///     serde_json::to_string(&def).unwrap(),
+    ///     r#"{"format_version":1,"root":{"type":"Parallel","params":{"success_threshold":"1"},"children":[{"type":"Succeed"},{"type":"Succeed"}]}}"#
+    /// );
+    /// ```
+    pub fn from_tree<Payload: 'static, R: 'static, F: 'static>(
+        node: &mut (dyn BehaviorNodeBase<Payload, R, F> + 'static),
+    ) -> Result<Self, UndefinedNodeError> {
+        Ok(Self::new(node_def(node, String::new())?))
+    }
+
+    /// Writes the definition in pretty-printed JSON, which [NodeFactory::load_json] reads.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a definition has only string keys")
+    }
+
+    /// Writes the definition in YAML, which [NodeFactory::load_yaml] reads.
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(self).expect("a definition has only string keys")
+    }
+}
+
+fn node_def<Payload: 'static, R: 'static, F: 'static>(
+    node: &mut (dyn BehaviorNodeBase<Payload, R, F> + 'static),
+    path: String,
+) -> Result<NodeDef, UndefinedNodeError> {
+    let mut def = match node.definition() {
+        Some((node_type, cfg)) => NodeDef {
+            node_type: node_type.to_owned(),
+            params: cfg
+                .iter()
+                .map(|(name, value)| (name.to_owned(), Value::String(value.to_owned())))
+                .collect(),
+            children: vec![],
+            meta: None,
+        },
+        None => return Err(UndefinedNodeError { path }),
+    };
+    let meta: Vec<_> = (0..node.children_mut().len())
+        .map(|i| node.child_meta(i).cloned())
+        .collect();
+    for (i, (child, meta)) in node.children_mut().into_iter().zip(meta).enumerate() {
+        let mut child = node_def(child, child_path(&path, i))?;
+        child.meta = meta;
+        def.children.push(child);
+    }
+    Ok(def)
+}
+
+/// The error type returned by [TreeDef::from_tree] when a node was not
+/// created by a [NodeFactory], with the path of the node.
+#[derive(Clone, PartialEq, Debug)]
+pub struct UndefinedNodeError {
+    pub path: String,
+}
+
+impl fmt::Display for UndefinedNodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Node at path \"{}\" was not created by a NodeFactory",
+            self.path
+        )
+    }
+}
+
+impl std::error::Error for UndefinedNodeError {}
+
+/// The definition of a node.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct NodeDef {
+    /// The name of the node type registered in the [NodeFactory].
+    #[serde(rename = "type")]
+    pub node_type: String,
+    /// The parameters given to the constructor through [NodeConfig].
+    ///
+    /// Strings are given as they are, and other values as JSON text,
+    /// e.g. `2` or `true`.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub params: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<NodeDef>,
+    /// The annotations of this node as a child of its parent, which are given
+    /// to [BehaviorNodeBase::set_child_meta] of the parent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ChildMeta>,
+}
+
+impl NodeDef {
+    pub fn new(node_type: impl Into<String>) -> Self {
+        Self {
+            node_type: node_type.into(),
+            params: Map::new(),
+            children: vec![],
+            meta: None,
+        }
+    }
+
+    /// Adds a parameter.
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Adds a child.
+    pub fn with_child(mut self, child: NodeDef) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Sets the annotations of this node as a child.
+    pub fn with_meta(mut self, meta: ChildMeta) -> Self {
+        self.meta = Some(meta);
+        self
+    }
+
+    /// Calls `f` with this node and all of its descendants in the depth-first
+    /// order, e.g. to rename a node type in a migration.
+    pub fn visit_mut(&mut self, f: &mut impl FnMut(&mut NodeDef)) {
+        f(self);
+        for child in &mut self.children {
+            child.visit_mut(f);
+        }
+    }
+
+    fn config(&self) -> NodeConfig {
+        let mut cfg = NodeConfig::new();
+        for (name, value) in &self.params {
+            match value {
+                Value::String(s) => cfg.insert(name, s.as_str()),
+                _ => cfg.insert(name, value.to_string()),
+            }
+        }
+        cfg
+    }
+}
+
+/// The error type returned by [NodeFactory::load_json] and [NodeFactory::build].
+///
+/// Errors about nodes carry the path of the node, in the same form as
+/// [Tree::from_flat](crate::Tree::from_flat).
+#[derive(Debug)]
+pub enum LoadError {
+    /// The document is not a valid definition.
+    Json(serde_json::Error),
+    /// The YAML document is not a valid definition.
+    #[cfg(feature = "yaml")]
+    Yaml(serde_yaml::Error),
+    /// The XML document is not a valid tree file of BehaviorTree.CPP.
+    #[cfg(feature = "xml")]
+    Xml(String),
+    /// The definition was written in a newer format than [FORMAT_VERSION], or
+    /// in an older one without the migrations from it.
+    UnsupportedVersion(u32),
+    /// The node could not be created, including when its constructor panicked.
+    Node { path: String, error: FactoryError },
+    /// The node has children, but it does not accept them.
+    NotComposite { path: String, node_type: String },
+    /// The child has annotations, but its parent does not store them.
+    NoChildMeta { path: String, node_type: String },
+    /// The `guard` parameter of the node is not a valid expression.
+    #[cfg(feature = "stdlib")]
+    Guard {
+        path: String,
+        error: crate::stdlib::ParseGuardError,
+    },
+    /// The node has the `guard` parameter, but the guards are not enabled by
+    /// `NodeFactory::enable_guards`, which needs the `stdlib` feature.
+    GuardsDisabled { path: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Json(error) => write!(f, "Invalid definition: {}", error),
+            #[cfg(feature = "yaml")]
+            Self::Yaml(error) => write!(f, "Invalid definition: {}", error),
+            #[cfg(feature = "xml")]
+            Self::Xml(message) => write!(f, "Invalid tree file: {}", message),
+            Self::UnsupportedVersion(version) if FORMAT_VERSION < *version => write!(
+                f,
+                "Unsupported format version {} (supported up to {})",
+                version, FORMAT_VERSION
+            ),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported format version {} (no migrations to {})",
+                version, FORMAT_VERSION
+            ),
+            Self::Node { path, error } => write!(f, "Node at path \"{}\": {}", path, error),
+            Self::NotComposite { path, node_type } => write!(
+                f,
+                "Node at path \"{}\" has children, but \"{}\" does not accept them",
+                path, node_type
+            ),
+            Self::NoChildMeta { path, node_type } => write!(
+                f,
+                "Node at path \"{}\" has meta, but \"{}\" does not store it",
+                path, node_type
+            ),
+            #[cfg(feature = "stdlib")]
+            Self::Guard { path, error } => write!(f, "Node at path \"{}\": {}", path, error),
+            Self::GuardsDisabled { path } => write!(
+                f,
+                "Node at path \"{}\" has a guard, but the guards are not enabled",
+                path
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Json(error) => Some(error),
+            #[cfg(feature = "yaml")]
+            Self::Yaml(error) => Some(error),
+            Self::Node { error, .. } => Some(error),
+            #[cfg(feature = "stdlib")]
+            Self::Guard { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<Payload, R, F> NodeFactory<Payload, R, F> {
+    /// Registers a hook that migrates definitions in `from_version` to the
+    /// next version, replacing the one registered before for the version.
+    ///
+    /// [NodeFactory::build] runs the hooks from the version of a definition up
+    /// to [FORMAT_VERSION], so that trees authored against older releases, or
+    /// with older versions of your node types, keep loading.
+    /// The hooks are chained, so each one only handles the changes of a single
+    /// version.
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::{loader::TreeDef, testing::AlwaysSuccess, *};
+    /// let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+    /// factory.register("Succeed", |_| Ok(AlwaysSuccess(())));
+    /// // Version 0 called fallbacks "Selector".
+    /// factory.register_migration(0, |def: &mut TreeDef| {
+    ///     def.root.visit_mut(&mut |node| {
+    ///         if node.node_type == "Selector" {
+    ///             node.node_type = "Fallback".to_owned();
+    ///         }
+    ///     })
+    /// });
+    /// assert_eq!(factory.supported_versions(), 0..=1);
+    ///
+    /// let mut tree = factory
+    ///     .load_json(
+    ///         r#"{
+    ///             "format_version": 0,
+    ///             "root": { "type": "Selector", "children": [{ "type": "Succeed" }] }
+    ///         }"#,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    /// ```
+    pub fn register_migration(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(&mut TreeDef) + 'static,
+    ) {
+        self.migrations.insert(from_version, Box::new(migrate));
+    }
+
+    /// Returns the versions of the definitions that [NodeFactory::build]
+    /// reads, which go down from [FORMAT_VERSION] as far as the migrations
+    /// are chained.
+    pub fn supported_versions(&self) -> RangeInclusive<u32> {
+        let mut oldest = FORMAT_VERSION;
+        while oldest > 0 && self.migrations.contains_key(&(oldest - 1)) {
+            oldest -= 1;
+        }
+        oldest..=FORMAT_VERSION
+    }
+
+    /// Migrates a definition to [FORMAT_VERSION].
+    fn migrate(&self, mut def: TreeDef) -> Result<TreeDef, LoadError> {
+        if !self.supported_versions().contains(&def.format_version) {
+            return Err(LoadError::UnsupportedVersion(def.format_version));
+        }
+        while def.format_version < FORMAT_VERSION {
+            self.migrations[&def.format_version](&mut def);
+            def.format_version += 1;
+        }
+        Ok(def)
+    }
+}
+
+impl<Payload: 'static, R: 'static, F: 'static> NodeFactory<Payload, R, F> {
+    /// Builds a tree from a JSON document of a [TreeDef].
+    pub fn load_json(
+        &self,
+        json: &str,
+    ) -> Result<Box<dyn BehaviorNodeBase<Payload, R, F>>, LoadError> {
+        let def: TreeDef = serde_json::from_str(json).map_err(LoadError::Json)?;
+        self.build(&def)
+    }
+
+    /// Builds a tree from a YAML document of a [TreeDef], enabled by the `yaml` feature.
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::{testing::AlwaysSuccess, *};
+    /// let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+    /// factory.register("Succeed", |_| Ok(AlwaysSuccess(())));
+    ///
+    /// let mut tree = factory
+    ///     .load_yaml(
+    ///         "
+    /// format_version: 1
+    /// root:
+    ///   type: Parallel
+    ///   params:
+    ///     success_threshold: 2
+    ///   children:
+    ///     - type: Succeed
+    ///     - type: Succeed
+    /// ",
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn load_yaml(
+        &self,
+        yaml: &str,
+    ) -> Result<Box<dyn BehaviorNodeBase<Payload, R, F>>, LoadError> {
+        let def: TreeDef = serde_yaml::from_str(yaml).map_err(LoadError::Yaml)?;
+        self.build(&def)
+    }
+
+    /// Builds a tree from a definition.
+    ///
+    /// A definition in an older version is migrated to [FORMAT_VERSION] first,
+    /// see [NodeFactory::register_migration].
+    pub fn build(
+        &self,
+        def: &TreeDef,
+    ) -> Result<Box<dyn BehaviorNodeBase<Payload, R, F>>, LoadError> {
+        if def.format_version == FORMAT_VERSION {
+            self.build_node(&def.root, String::new())
+        } else {
+            let def = self.migrate(def.clone())?;
+            self.build_node(&def.root, String::new())
+        }
+    }
+
+    fn build_node(
+        &self,
+        def: &NodeDef,
+        path: String,
+    ) -> Result<Box<dyn BehaviorNodeBase<Payload, R, F>>, LoadError> {
+        let mut cfg = def.config();
+        let guard = cfg.remove("guard");
+        #[cfg(feature = "stdlib")]
+        let guards_enabled = self.guard.is_some();
+        #[cfg(not(feature = "stdlib"))]
+        let guards_enabled = false;
+        if guard.is_some() && !guards_enabled {
+            return Err(LoadError::GuardsDisabled { path });
+        }
+        let mut node = self
+            .create(&def.node_type, &cfg)
+            .map_err(|error| LoadError::Node {
+                path: path.clone(),
+                error,
+            })?;
+        for (i, child) in def.children.iter().enumerate() {
+            let meta = child.meta.clone();
+            let child = self.build_node(child, child_path(&path, i))?;
+            if node.push_child(child).is_err() {
+                return Err(LoadError::NotComposite {
+                    path,
+                    node_type: def.node_type.clone(),
+                });
+            }
+            if let Some(meta) = meta {
+                if node.set_child_meta(i, meta).is_err() {
+                    return Err(LoadError::NoChildMeta {
+                        path: child_path(&path, i),
+                        node_type: def.node_type.clone(),
+                    });
+                }
+            }
+        }
+        #[cfg(feature = "stdlib")]
+        if let Some(expr) = guard {
+            // The guard wraps the node after its children, since it does not accept them.
+            node = self
+                .guard(&def.node_type, def.config(), &expr, node)
+                .map_err(|error| LoadError::Guard { path, error })?;
+        }
+        Ok(node)
+    }
+}
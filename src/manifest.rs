@@ -0,0 +1,141 @@
+//! Registry manifests, enabled by the `serde` feature.
+//!
+//! A [Manifest] lists the node types that a program registers in its
+//! [NodeFactory], with their kinds and their required parameters, so that
+//! tools can check definition files without the program.
+//! [Manifest::register] registers stand-in nodes for them, which check the
+//! parameters and the number of children like the real ones, but fail when
+//! ticked.
+//!
+//! ```
+//! # use tiny_behavior_tree::{manifest::Manifest, *};
+//! let manifest = Manifest::from_json(
+//!     r#"{
+//!         "nodes": [
+//!             { "type": "OpenDoor", "kind": "action", "params": ["force"] },
+//!             { "type": "IsLocked", "kind": "condition" }
+//!         ]
+//!     }"#,
+//! )
+//! .unwrap();
+//! let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+//! manifest.register(&mut factory);
+//!
+//! let open = factory.create("OpenDoor", &NodeConfig::new().with("force", "10"));
+//! assert_eq!(open.unwrap().tick(()), BehaviorResult::Failure(()));
+//! let err = factory.create("OpenDoor", &NodeConfig::new());
+//! assert_eq!(
+//!     err.err().unwrap().to_string(),
+//!     "Parameter \"force\": missing in \"OpenDoor\""
+//! );
+//! ```
+use crate::{BehaviorNodeBase, BehaviorResult, NodeFactory, ParamError};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+/// The node types registered by a program, usually read from a JSON file.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub nodes: Vec<NodeSpec>,
+}
+
+/// A node type in a [Manifest].
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct NodeSpec {
+    /// The name the node type is registered under.
+    #[serde(rename = "type")]
+    pub node_type: String,
+    pub kind: NodeKind,
+    /// The names of the required parameters.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<String>,
+}
+
+/// The kind of a node type, which decides how many children it accepts.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    /// A leaf node.
+    Action,
+    /// A leaf node.
+    Condition,
+    /// A node with exactly one child.
+    Decorator,
+    /// A node with any number of children.
+    Control,
+}
+
+impl Manifest {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Registers a stand-in node for each node type, replacing the ones
+    /// registered before under the same names.
+    pub fn register<Payload, R, F>(&self, factory: &mut NodeFactory<Payload, R, F>)
+    where
+        Payload: 'static,
+        R: 'static,
+        F: Default + 'static,
+    {
+        for spec in &self.nodes {
+            let spec = spec.clone();
+            factory.register(spec.node_type.clone(), move |cfg| {
+                for param in &spec.params {
+                    if cfg.get_str(param).is_none() {
+                        return Err(ParamError::missing(param));
+                    }
+                }
+                Ok(StandIn {
+                    kind: spec.kind,
+                    children: vec![],
+                })
+            });
+        }
+    }
+}
+
+/// A node registered by [Manifest::register], which only holds its children.
+struct StandIn<Payload, R, F> {
+    kind: NodeKind,
+    children: Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>,
+}
+
+impl<Payload, R, F: Default> BehaviorNodeBase<Payload, R, F> for StandIn<Payload, R, F> {
+    fn tick(&mut self, _payload: Payload) -> BehaviorResult<R, F> {
+        BehaviorResult::Failure(F::default())
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children
+            .iter_mut()
+            .map(|node| node.as_mut() as _)
+            .collect()
+    }
+
+    fn push_child(
+        &mut self,
+        child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+    where
+        Self: 'static,
+    {
+        match self.kind {
+            NodeKind::Action | NodeKind::Condition => return Err(child),
+            NodeKind::Decorator if !self.children.is_empty() => return Err(child),
+            _ => (),
+        }
+        self.children.push(child);
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+use crate::composite::{
+    child_meta_methods, children_vec_methods, composite_node_introspection, Composite,
+};
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, MergeResult};
+
+/// SequenceWithMemory is a Sequence that remembers the child that returned
+/// `Running` and resumes from it in the next tick.
+///
+/// Children that succeeded before the running one are not ticked again until
+/// this node succeeds or fails, or it is halted.
+///
+/// The generic parameters and the result merger function are the same as
+/// [SequenceNode](crate::SequenceNode), and results of children that
+/// succeeded in earlier ticks are merged as well.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Countdown(u32);
+///
+/// impl BehaviorNodeBase<(), Vec<u32>, ()> for Countdown {
+///     fn tick(&mut self, _payload: ()) -> BehaviorResult<Vec<u32>, ()> {
+///         if self.0 == 0 {
+///             BehaviorResult::Success(vec![])
+///         } else {
+///             self.0 -= 1;
+///             BehaviorResult::Running
+///         }
+///     }
+/// }
+///
+/// struct Once(u32, bool);
+///
+/// impl BehaviorNodeBase<(), Vec<u32>, ()> for Once {
+///     fn tick(&mut self, _payload: ()) -> BehaviorResult<Vec<u32>, ()> {
+///         if self.1 {
+///             BehaviorResult::Failure(())
+///         } else {
+///             self.1 = true;
+///             BehaviorResult::Success(vec![self.0])
+///         }
+///     }
+/// }
+///
+/// type Node = Box<dyn BehaviorNodeBase<(), Vec<u32>, ()>>;
+///
+/// let mut tree = SequenceNodeWithMemory::new_with_merger(
+///     [Box::new(Once(1, false)) as Node, Box::new(Countdown(1)), Box::new(Once(2, false))],
+///     |acc: &mut Vec<u32>, mut v: Vec<u32>| acc.append(&mut v),
+/// );
+/// assert_eq!(tree.tick(()), BehaviorResult::Running);
+/// // The first child is not ticked again, otherwise it would fail
+/// assert_eq!(tree.tick(()), BehaviorResult::Success(vec![1, 2]));
+/// ```
+pub struct SequenceNodeWithMemory<Payload, R, F, MR> {
+    inner: Composite<'static, Payload, R, F, MR, R>,
+}
+
+impl<Payload, R, F> SequenceNodeWithMemory<Payload, R, F, &dyn Fn(&mut R, R)> {
+    /// Constructs a [SequenceNodeWithMemory] with children nodes.
+    ///
+    /// If multiple child nodes return results in `R`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        let mut inner = Composite::new(children.into(), None);
+        inner.set_memory();
+        Self { inner }
+    }
+
+    /// Constructs a [SequenceNodeWithMemory] without children, with room for `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+}
+
+impl<Payload, R, F, MR> SequenceNodeWithMemory<Payload, R, F, MR> {
+    /// Constructs a [SequenceNodeWithMemory] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        let mut inner = Composite::new(children.into(), Some(merge_result));
+        inner.set_memory();
+        Self { inner }
+    }
+
+    children_vec_methods!(inner.children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when a child fails and ends this node,
+    /// with the index of the child and its result.
+    ///
+    /// It is called for the last child as well, but not for a running child.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'static,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+
+    /// Limits the number of children ticked in a single call to `max`,
+    /// see [SequenceNode::max_children_per_tick](crate::SequenceNode::max_children_per_tick).
+    pub fn max_children_per_tick(mut self, max: usize) -> Self {
+        self.inner.set_max_children_per_tick(max);
+        self
+    }
+}
+
+impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F>
+    for SequenceNodeWithMemory<Payload, R, F, MR>
+where
+    R: Default,
+    Payload: Clone,
+    MR: MergeResult<R>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.inner.tick_sequence(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.inner.try_tick_sequence(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
+    }
+
+    composite_node_introspection!(Payload);
+}
+
+/// FallbackWithMemory is a Fallback that remembers the child that returned
+/// `Running` and resumes from it in the next tick.
+///
+/// Children that failed before the running one are not tried again until
+/// this node succeeds or fails, or it is halted.
+///
+/// The generic parameters and the result merger function are the same as
+/// [FallbackNode](crate::FallbackNode), and results of children that
+/// failed in earlier ticks are merged as well.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::*;
+/// # use std::cell::Cell;
+/// # use std::rc::Rc;
+/// struct CountTicks(Rc<Cell<u32>>);
+///
+/// impl BehaviorNodeBase<(), (), ()> for CountTicks {
+///     fn tick(&mut self, _payload: ()) -> BehaviorResult<(), ()> {
+///         self.0.set(self.0.get() + 1);
+///         BehaviorResult::Failure(())
+///     }
+/// }
+///
+/// type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+///
+/// let ticks = Rc::new(Cell::new(0));
+/// let mut tree = FallbackNodeWithMemory::new([
+///     Box::new(CountTicks(ticks.clone())) as Node,
+///     Box::new(AlwaysRunning),
+/// ]);
+/// assert_eq!(tree.tick(()), BehaviorResult::Running);
+/// assert_eq!(tree.tick(()), BehaviorResult::Running);
+/// assert_eq!(ticks.get(), 1);
+/// ```
+pub struct FallbackNodeWithMemory<Payload, R, F, MR> {
+    inner: Composite<'static, Payload, R, F, MR, F>,
+}
+
+impl<Payload, R, F> FallbackNodeWithMemory<Payload, R, F, &dyn Fn(&mut F, F)> {
+    /// Constructs a [FallbackNodeWithMemory] with children nodes.
+    ///
+    /// If multiple child nodes return results in `F`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        let mut inner = Composite::new(children.into(), None);
+        inner.set_memory();
+        Self { inner }
+    }
+
+    /// Constructs a [FallbackNodeWithMemory] without children, with room for `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+}
+
+impl<Payload, R, F, MR> FallbackNodeWithMemory<Payload, R, F, MR> {
+    /// Constructs a [FallbackNodeWithMemory] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        let mut inner = Composite::new(children.into(), Some(merge_result));
+        inner.set_memory();
+        Self { inner }
+    }
+
+    children_vec_methods!(inner.children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when a child succeeds and ends this node,
+    /// with the index of the child and its result.
+    ///
+    /// It is called for the last child as well, but not for a running child.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'static,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+
+    /// Limits the number of children ticked in a single call to `max`,
+    /// see [FallbackNode::max_children_per_tick](crate::FallbackNode::max_children_per_tick).
+    pub fn max_children_per_tick(mut self, max: usize) -> Self {
+        self.inner.set_max_children_per_tick(max);
+        self
+    }
+}
+
+impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F>
+    for FallbackNodeWithMemory<Payload, R, F, MR>
+where
+    F: Default,
+    Payload: Clone,
+    MR: MergeResult<F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.inner.tick_fallback(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.inner.try_tick_fallback(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
+    }
+
+    composite_node_introspection!(Payload);
+}
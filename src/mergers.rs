@@ -0,0 +1,180 @@
+//! Ready-made merger functions for the `new_with_merger` constructors of the
+//! control nodes, like [SequenceNode::new_with_merger](crate::SequenceNode::new_with_merger).
+//!
+//! A merger function combines the result of a child into the result
+//! accumulated so far, which starts from `Default::default()`.
+//!
+//! ```
+//! # use tiny_behavior_tree::*;
+//! # use tiny_behavior_tree::testing::*;
+//! use tiny_behavior_tree::mergers;
+//!
+//! type Node = Box<dyn BehaviorNodeBase<(), Vec<&'static str>, ()>>;
+//!
+//! let mut tree = SequenceNode::new_with_merger(
+//!     [
+//!         Box::new(AlwaysSuccess(vec!["sword"])) as Node,
+//!         Box::new(AlwaysSuccess(vec!["shield"])),
+//!     ],
+//!     mergers::append,
+//! );
+//! assert_eq!(tree.tick(()), BehaviorResult::Success(vec!["sword", "shield"]));
+//! ```
+//!
+//! Mergers are [MergeResult]s, which any function with the signature
+//! `Fn(&mut R, R)` is.
+//! [Merge] is a merger for the result types with an obvious way to merge,
+//! which the `merged` constructors like [SequenceNode::merged](crate::SequenceNode::merged)
+//! use, so it does not need to be passed.
+
+use std::ops::{AddAssign, Div, Mul};
+
+pub use crate::traced::append_traced;
+
+/// Combines the result of a child into the result accumulated so far.
+pub trait MergeResult<R> {
+    fn merge(&self, acc: &mut R, v: R);
+}
+
+impl<R, M: Fn(&mut R, R)> MergeResult<R> for M {
+    fn merge(&self, acc: &mut R, v: R) {
+        self(acc, v)
+    }
+}
+
+/// The merger for `()`, `Vec<T>`, which appends the elements like [append],
+/// and `Option<T>`, which keeps the last `Some`.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::*;
+/// type Node = Box<dyn BehaviorNodeBase<(), Option<&'static str>, ()>>;
+///
+/// let mut tree = SequenceNode::merged([
+///     Box::new(AlwaysSuccess(Some("sword"))) as Node,
+///     Box::new(AlwaysSuccess(None)),
+/// ]);
+/// assert_eq!(tree.tick(()), BehaviorResult::Success(Some("sword")));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Merge;
+
+impl MergeResult<()> for Merge {
+    fn merge(&self, _acc: &mut (), _v: ()) {}
+}
+
+impl<T> MergeResult<Vec<T>> for Merge {
+    fn merge(&self, acc: &mut Vec<T>, v: Vec<T>) {
+        append(acc, v);
+    }
+}
+
+impl<T> MergeResult<Option<T>> for Merge {
+    fn merge(&self, acc: &mut Option<T>, v: Option<T>) {
+        if v.is_some() {
+            *acc = v;
+        }
+    }
+}
+
+/// Appends the elements of `v` to `acc`.
+pub fn append<T>(acc: &mut Vec<T>, mut v: Vec<T>) {
+    acc.append(&mut v);
+}
+
+/// Extends `acc` with the elements of `v`, e.g. to collect entries into a map.
+///
+/// An entry of a map replaces an earlier entry with the same key.
+pub fn extend<C>(acc: &mut C, v: C)
+where
+    C: Extend<C::Item> + IntoIterator,
+{
+    acc.extend(v);
+}
+
+/// Adds `v` to `acc`, e.g. to sum up the scores or the costs of the children.
+pub fn sum<T: AddAssign>(acc: &mut T, v: T) {
+    *acc += v;
+}
+
+/// Keeps the greatest value, where `None` means there is no value yet.
+pub fn max<T: Ord>(acc: &mut Option<T>, v: Option<T>) {
+    if v > *acc {
+        *acc = v;
+    }
+}
+
+/// Keeps the least value, where `None` means there is no value yet.
+pub fn min<T: Ord>(acc: &mut Option<T>, v: Option<T>) {
+    match (&*acc, &v) {
+        (_, None) => (),
+        (Some(a), Some(b)) if a <= b => (),
+        _ => *acc = v,
+    }
+}
+
+/// Counts the results, ignoring their values.
+pub fn count(acc: &mut usize, _v: usize) {
+    *acc += 1;
+}
+
+/// A weighted mean of values, which children return with
+/// [WeightedMean::new] and [sum] merges.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::*;
+/// use tiny_behavior_tree::mergers::{self, WeightedMean};
+///
+/// type Node = Box<dyn BehaviorNodeBase<(), WeightedMean, ()>>;
+///
+/// let mut utility = ParallelNode::new_with_merger(
+///     [
+///         Box::new(AlwaysSuccess(WeightedMean::new(3., 1.))) as Node,
+///         Box::new(AlwaysSuccess(WeightedMean::new(1., 5.))),
+///     ],
+///     2,
+///     mergers::sum,
+/// );
+/// let BehaviorResult::Success(mean) = utility.tick(()) else { panic!() };
+/// assert_eq!(mean.mean(), Some(2.));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct WeightedMean<T = f64> {
+    weight: T,
+    weighted_sum: T,
+}
+
+impl<T: Copy + Mul<Output = T>> WeightedMean<T> {
+    pub fn new(weight: T, value: T) -> Self {
+        Self {
+            weight,
+            weighted_sum: weight * value,
+        }
+    }
+}
+
+impl<T> WeightedMean<T>
+where
+    T: Copy + Default + PartialEq + Div<Output = T>,
+{
+    /// Returns the mean, or `None` if the total weight is zero.
+    pub fn mean(&self) -> Option<T> {
+        if self.weight == T::default() {
+            None
+        } else {
+            Some(self.weighted_sum / self.weight)
+        }
+    }
+
+    pub fn weight(&self) -> T {
+        self.weight
+    }
+}
+
+impl<T: AddAssign> AddAssign for WeightedMean<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.weight += rhs.weight;
+        self.weighted_sum += rhs.weighted_sum;
+    }
+}
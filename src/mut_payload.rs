@@ -0,0 +1,257 @@
+use crate::composite::children_vec_methods;
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, MergeResult};
+use std::convert::Infallible;
+
+/// A child of [SequenceNodeMut] or [FallbackNodeMut], which accepts the
+/// mutable reference to the payload for any lifetime.
+pub type ChildMut<Payload, R, F> = Box<dyn for<'p> BehaviorNodeBase<&'p mut Payload, R, F>>;
+
+/// A sequence that passes down a mutable reference to the payload, so that
+/// the children can mutate it without [RefCell](std::cell::RefCell).
+///
+/// The reference is reborrowed for each child, so the children have to
+/// implement `BehaviorNodeBase<&'p mut Payload, R, F>` for any lifetime `'p`,
+/// which is usually written as `impl<'p> BehaviorNodeBase<&'p mut Door, (), ()>`.
+/// It ticks the children like [SequenceNode](crate::SequenceNode), starting
+/// over from the first child in the next tick after a running child.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Door {
+///     open: bool,
+/// }
+///
+/// struct OpenDoor;
+///
+/// impl<'p> BehaviorNodeBase<&'p mut Door, (), ()> for OpenDoor {
+///     fn tick(&mut self, door: &'p mut Door) -> BehaviorResult<(), ()> {
+///         door.open = true;
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// struct IsDoorOpen;
+///
+/// impl<'p> BehaviorNodeBase<&'p mut Door, (), ()> for IsDoorOpen {
+///     fn tick(&mut self, door: &'p mut Door) -> BehaviorResult<(), ()> {
+///         if door.open {
+///             BehaviorResult::Success(())
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+/// }
+///
+/// let mut tree = SequenceNodeMut::new([
+///     Box::new(OpenDoor) as ChildMut<Door, (), ()>,
+///     Box::new(IsDoorOpen),
+/// ]);
+/// let mut door = Door { open: false };
+/// assert_eq!(tree.tick(&mut door), BehaviorResult::Success(()));
+/// assert!(door.open);
+/// ```
+pub struct SequenceNodeMut<Payload, R, F, MR> {
+    children: Vec<ChildMut<Payload, R, F>>,
+    merge_result: Option<MR>,
+}
+
+impl<Payload, R, F> SequenceNodeMut<Payload, R, F, &dyn Fn(&mut R, R)> {
+    /// Constructs a [SequenceNodeMut] with children nodes.
+    ///
+    /// If multiple child nodes return results in `R`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<ChildMut<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: None,
+        }
+    }
+}
+
+impl<Payload, R, F, MR> SequenceNodeMut<Payload, R, F, MR> {
+    /// Constructs a [SequenceNodeMut] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<ChildMut<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: Some(merge_result),
+        }
+    }
+
+    children_vec_methods!(children, ChildMut<Payload, R, F>);
+
+    fn run<E>(
+        &mut self,
+        payload: &mut Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut ChildMut<Payload, R, F>,
+            &mut Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        R: Default,
+        MR: MergeResult<R>,
+    {
+        let mut last_success = R::default();
+        for (i, child) in self.children.iter_mut().enumerate() {
+            match report::tick_child(i, || tick_child(i, child, payload))? {
+                BehaviorResult::Success(r) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_success, r)
+                    } else {
+                        last_success = r
+                    }
+                }
+                res @ BehaviorResult::Failure(_) => {
+                    report::short_circuit(i);
+                    return Ok(res);
+                }
+                BehaviorResult::Running => return Ok(BehaviorResult::Running),
+                BehaviorResult::Idle => (),
+            }
+        }
+        Ok(BehaviorResult::Success(last_success))
+    }
+}
+
+impl<'a, Payload, R, F, MR> BehaviorNodeBase<&'a mut Payload, R, F>
+    for SequenceNodeMut<Payload, R, F, MR>
+where
+    R: Default,
+    MR: MergeResult<R>,
+{
+    fn tick(&mut self, payload: &'a mut Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: &'a mut Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+}
+
+/// A fallback that passes down a mutable reference to the payload, the
+/// counterpart of [SequenceNodeMut].
+///
+/// It ticks the children like [FallbackNode](crate::FallbackNode), starting
+/// over from the first child in the next tick after a running child.
+pub struct FallbackNodeMut<Payload, R, F, MR> {
+    children: Vec<ChildMut<Payload, R, F>>,
+    merge_result: Option<MR>,
+}
+
+impl<Payload, R, F> FallbackNodeMut<Payload, R, F, &dyn Fn(&mut F, F)> {
+    /// Constructs a [FallbackNodeMut] with children nodes.
+    ///
+    /// If multiple child nodes return results in `F`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<ChildMut<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: None,
+        }
+    }
+}
+
+impl<Payload, R, F, MR> FallbackNodeMut<Payload, R, F, MR> {
+    /// Constructs a [FallbackNodeMut] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<ChildMut<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: Some(merge_result),
+        }
+    }
+
+    children_vec_methods!(children, ChildMut<Payload, R, F>);
+
+    fn run<E>(
+        &mut self,
+        payload: &mut Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut ChildMut<Payload, R, F>,
+            &mut Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        F: Default,
+        MR: MergeResult<F>,
+    {
+        let mut last_failure = F::default();
+        for (i, child) in self.children.iter_mut().enumerate() {
+            match report::tick_child(i, || tick_child(i, child, payload))? {
+                res @ BehaviorResult::Success(_) => {
+                    report::short_circuit(i);
+                    return Ok(res);
+                }
+                BehaviorResult::Failure(f) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_failure, f)
+                    } else {
+                        last_failure = f
+                    }
+                }
+                BehaviorResult::Running => return Ok(BehaviorResult::Running),
+                BehaviorResult::Idle => (),
+            }
+        }
+        Ok(BehaviorResult::Failure(last_failure))
+    }
+}
+
+impl<'a, Payload, R, F, MR> BehaviorNodeBase<&'a mut Payload, R, F>
+    for FallbackNodeMut<Payload, R, F, MR>
+where
+    F: Default,
+    MR: MergeResult<F>,
+{
+    fn tick(&mut self, payload: &'a mut Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: &'a mut Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+}
@@ -0,0 +1,226 @@
+use crate::configure::child_path;
+use crate::{BehaviorNodeBase, ResultKind, TickReport};
+use std::fmt;
+
+/// The hierarchy of a tree with the names and the last statuses of the
+/// nodes, which is displayed as indented ASCII for debugging in terminal logs.
+///
+/// The nodes are named by their [BehaviorNodeBase::definition] with the
+/// parameters, or `Node` if they were not created by a
+/// [NodeFactory](crate::NodeFactory).
+/// The statuses are taken from a [TickReport], so nodes that are not reported
+/// are shown without one.
+///
+/// ```
+/// # use tiny_behavior_tree::{testing::{AlwaysFailure, AlwaysSuccess}, *};
+/// let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+/// factory.register("Open", |_| Ok(AlwaysFailure(())));
+/// factory.register("Smash", |_| Ok(AlwaysSuccess(())));
+///
+/// let mut root = factory.create("Fallback", &NodeConfig::new()).unwrap();
+/// for (name, force) in [("Open", "1"), ("Smash", "10"), ("Smash", "20")] {
+///     let cfg = NodeConfig::new().with("force", force);
+///     assert!(root.push_child(factory.create(name, &cfg).unwrap()).is_ok());
+/// }
+/// let mut tree = Tree::new(root);
+/// tree.tick_with_report(());
+///
+/// assert_eq!(
+///     tree.outline().to_string(),
+///     "\
+/// Fallback [Success]
+/// |-- Open force=1 [Failure]
+/// |-- Smash force=10 [Success]
+/// `-- Smash force=20
+/// "
+/// );
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct TreeOutline {
+    pub nodes: Vec<OutlineNode>,
+}
+
+/// A node in a [TreeOutline], in the depth-first order.
+#[derive(Clone, PartialEq, Debug)]
+pub struct OutlineNode {
+    pub path: String,
+    pub depth: usize,
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub status: Option<ResultKind>,
+}
+
+impl TreeOutline {
+    /// Collects the nodes of a tree with their statuses in `report`, if given.
+    pub fn new<Payload: 'static, R: 'static, F: 'static>(
+        node: &mut (dyn BehaviorNodeBase<Payload, R, F> + 'static),
+        report: Option<&TickReport>,
+    ) -> Self {
+        let mut outline = Self { nodes: vec![] };
+        outline.push(node, String::new(), 0, report);
+        outline
+    }
+
+    fn push<Payload: 'static, R: 'static, F: 'static>(
+        &mut self,
+        node: &mut (dyn BehaviorNodeBase<Payload, R, F> + 'static),
+        path: String,
+        depth: usize,
+        report: Option<&TickReport>,
+    ) {
+        let (name, params) = match node.definition() {
+            Some((name, cfg)) => (
+                name.to_owned(),
+                cfg.iter()
+                    .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                    .collect(),
+            ),
+            None => ("Node".to_owned(), vec![]),
+        };
+        self.nodes.push(OutlineNode {
+            status: report.and_then(|report| report.status(&path)),
+            path: path.clone(),
+            depth,
+            name,
+            params,
+        });
+        for (i, child) in node.children_mut().into_iter().enumerate() {
+            self.push(child, child_path(&path, i), depth + 1, report);
+        }
+    }
+}
+
+impl TreeOutline {
+    /// Renders the tree as a Graphviz DOT digraph, with the parameters and
+    /// the statuses in the labels.
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::{testing::AlwaysSuccess, *};
+    /// let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+    /// factory.register("Open", |_| Ok(AlwaysSuccess(())));
+    /// let mut root = factory.create("Sequence", &NodeConfig::new()).unwrap();
+    /// let open = factory.create("Open", &NodeConfig::new().with("force", "1")).unwrap();
+    /// assert!(root.push_child(open).is_ok());
+    ///
+    /// assert_eq!(
+    ///     TreeOutline::new(root.as_mut(), None).to_dot(),
+    ///     "\
+    /// digraph tree {
+    ///     n0 [label=\"Sequence\"];
+    ///     n1 [label=\"Open\\nforce=1\"];
+    ///     n0 -> n1;
+    /// }
+    /// "
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph tree {\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let label: Vec<_> = Self::label(node)
+                .iter()
+                .map(|line| line.replace('\\', "\\\\").replace('"', "\\\""))
+                .collect();
+            let label = label.join("\\n");
+            dot += &format!("    n{} [label=\"{}\"];\n", i, label);
+        }
+        for (parent, child) in self.edges() {
+            dot += &format!("    n{} -> n{};\n", parent, child);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the tree as a Mermaid flowchart from the top down, with the
+    /// parameters and the statuses in the labels.
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::{testing::AlwaysSuccess, *};
+    /// let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+    /// factory.register("Open", |_| Ok(AlwaysSuccess(())));
+    /// let mut root = factory.create("Sequence", &NodeConfig::new()).unwrap();
+    /// let open = factory.create("Open", &NodeConfig::new().with("force", "1")).unwrap();
+    /// assert!(root.push_child(open).is_ok());
+    ///
+    /// assert_eq!(
+    ///     TreeOutline::new(root.as_mut(), None).to_mermaid(),
+    ///     "\
+    /// flowchart TD
+    ///     n0[\"Sequence\"]
+    ///     n1[\"Open<br/>force=1\"]
+    ///     n0 --> n1
+    /// "
+    /// );
+    /// ```
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart TD\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let label = Self::label(node).join("<br/>").replace('"', "#quot;");
+            mermaid += &format!("    n{}[\"{}\"]\n", i, label);
+        }
+        for (parent, child) in self.edges() {
+            mermaid += &format!("    n{} --> n{}\n", parent, child);
+        }
+        mermaid
+    }
+
+    /// Returns the lines of the label of a node: the name, the parameters
+    /// and the status.
+    fn label(node: &OutlineNode) -> Vec<String> {
+        let mut lines = vec![node.name.clone()];
+        lines.extend(
+            node.params
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value)),
+        );
+        if let Some(status) = node.status {
+            lines.push(format!("[{:?}]", status));
+        }
+        lines
+    }
+
+    /// Returns the pairs of the indices of the parents and the children.
+    fn edges(&self) -> Vec<(usize, usize)> {
+        let mut ancestors: Vec<usize> = vec![];
+        let mut edges = vec![];
+        for (i, node) in self.nodes.iter().enumerate() {
+            ancestors.truncate(node.depth);
+            if let Some(&parent) = ancestors.last() {
+                edges.push((parent, i));
+            }
+            ancestors.push(i);
+        }
+        edges
+    }
+}
+
+impl fmt::Display for TreeOutline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Whether the ancestors at each depth are the last children, which
+        // decides whether their vertical lines continue.
+        let mut last = vec![];
+        for (i, node) in self.nodes.iter().enumerate() {
+            let is_last = self.nodes[i + 1..]
+                .iter()
+                .find(|next| next.depth <= node.depth)
+                .is_none_or(|next| next.depth < node.depth);
+            last.truncate(node.depth);
+            if 0 < node.depth {
+                for &ancestor_last in &last[1..] {
+                    f.write_str(if ancestor_last { "    " } else { "|   " })?;
+                }
+                f.write_str(if is_last { "`-- " } else { "|-- " })?;
+            }
+            last.push(is_last);
+
+            f.write_str(&node.name)?;
+            for (name, value) in &node.params {
+                write!(f, " {}={}", name, value)?;
+            }
+            if let Some(status) = node.status {
+                write!(f, " [{:?}]", status)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use crate::composite::{child_meta_methods, children_vec_methods};
+use crate::mergers::Merge;
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, ChildMeta, HaltReport, MergeResult};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+/// Parallel ticks all child nodes every tick and succeeds if at least
+/// `success_threshold` of them succeed.
+///
+/// It fails as soon as so many children fail that the threshold cannot be
+/// reached, and returns `Running` otherwise.
+/// When it completes, it halts the children that are still running.
+///
+/// It does not remember the results of the children: the children that
+/// completed in an earlier tick are ticked again while this node is running,
+/// and only the results of the latest tick count.
+/// This suits conditions monitored while an action runs, since a condition
+/// that stops holding is noticed, but an action that should not run again
+/// after it completes has to keep its result by itself.
+///
+/// The generic parameters are the same as [SequenceNode](crate::SequenceNode),
+/// and the merger function combines results of succeeded children.
+/// On failure, it returns the result of the last failed child.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::*;
+/// type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+///
+/// // Keep moving while the monitor condition holds
+/// let mut tree = ParallelNode::new(
+///     [Box::new(AlwaysSuccess(())) as Node, Box::new(AlwaysRunning)],
+///     2,
+/// );
+/// assert_eq!(tree.tick(()), BehaviorResult::Running);
+/// ```
+pub struct ParallelNode<Payload, R, F, MR> {
+    children: Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>,
+    child_meta: BTreeMap<usize, ChildMeta>,
+    merge_result: Option<MR>,
+    success_threshold: usize,
+}
+
+impl<Payload, R, F> ParallelNode<Payload, R, F, &dyn Fn(&mut R, R)> {
+    /// Constructs a [ParallelNode] with children nodes.
+    ///
+    /// If multiple child nodes return results in `R`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T, success_threshold: usize) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            children: children.into(),
+            child_meta: BTreeMap::new(),
+            merge_result: None,
+            success_threshold,
+        }
+    }
+}
+
+impl<Payload, R, F> ParallelNode<Payload, R, F, Merge> {
+    /// Constructs a [ParallelNode] with children nodes, merging their results
+    /// in `R` with [Merge].
+    pub fn merged<T>(children: T, success_threshold: usize) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self::new_with_merger(children, success_threshold, Merge)
+    }
+}
+
+impl<Payload, R, F, MR> ParallelNode<Payload, R, F, MR> {
+    /// Constructs a [ParallelNode] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, success_threshold: usize, merge_result: MR) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            children: children.into(),
+            child_meta: BTreeMap::new(),
+            merge_result: Some(merge_result),
+            success_threshold,
+        }
+    }
+
+    children_vec_methods!(children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    child_meta_methods!(children, child_meta);
+}
+
+impl<Payload, R, F, MR> ParallelNode<Payload, R, F, MR>
+where
+    R: Default,
+    F: Default,
+    Payload: Clone,
+    MR: MergeResult<R>,
+{
+    /// Ticks children with `tick_child`, which calls either `tick` or `try_tick` of the child.
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut dyn BehaviorNodeBase<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E> {
+        let mut last_success = R::default();
+        let mut last_failure = F::default();
+        let mut successes = 0;
+        let mut failures = 0;
+        let mut running = vec![];
+        for (i, node) in self.children.iter_mut().enumerate() {
+            match report::tick_child(i, || tick_child(i, node.as_mut(), payload.clone()))? {
+                BehaviorResult::Success(r) => {
+                    successes += 1;
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_success, r)
+                    } else {
+                        last_success = r
+                    }
+                }
+                BehaviorResult::Failure(f) => {
+                    failures += 1;
+                    last_failure = f;
+                }
+                BehaviorResult::Running => running.push(i),
+                BehaviorResult::Idle => (),
+            }
+        }
+
+        let res = if self.success_threshold <= successes {
+            BehaviorResult::Success(last_success)
+        } else if self.children.len() < self.success_threshold + failures {
+            BehaviorResult::Failure(last_failure)
+        } else {
+            return Ok(BehaviorResult::Running);
+        };
+        for i in running {
+            self.children[i].halt();
+        }
+        Ok(res)
+    }
+}
+
+impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F> for ParallelNode<Payload, R, F, MR>
+where
+    R: Default,
+    F: Default,
+    Payload: Clone,
+    MR: MergeResult<R>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![("success_threshold", self.success_threshold.to_string())]
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children
+            .iter_mut()
+            .map(|node| node.as_mut() as _)
+            .collect()
+    }
+
+    fn push_child(
+        &mut self,
+        child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+    where
+        Self: 'static,
+    {
+        self.children.push(child);
+        Ok(())
+    }
+
+    fn child_meta(&self, index: usize) -> Option<&ChildMeta> {
+        self.child_meta.get(&index)
+    }
+
+    fn set_child_meta(&mut self, index: usize, meta: ChildMeta) -> Result<(), ChildMeta> {
+        if self.children.len() <= index {
+            return Err(meta);
+        }
+        self.child_meta.insert(index, meta);
+        Ok(())
+    }
+}
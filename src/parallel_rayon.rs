@@ -0,0 +1,240 @@
+//! A parallel node that ticks its children on threads, enabled by the `rayon` feature.
+//!
+//! [ParallelRayonNode] works like [ParallelNode](crate::ParallelNode), but
+//! ticks the children concurrently on a rayon thread pool, which pays off
+//! when a tree evaluates many independent and expensive conditions per tick.
+//! The children have to be [Send], and the payload [Sync], since each child
+//! gets a clone of the payload on its thread.
+//!
+//! ```
+//! use tiny_behavior_tree::parallel_rayon::ParallelRayonNode;
+//! # use tiny_behavior_tree::*;
+//! struct IsVisible(u32);
+//!
+//! impl BehaviorNodeBase<&[u32], (), ()> for IsVisible {
+//!     fn tick(&mut self, obstacles: &[u32]) -> BehaviorResult<(), ()> {
+//!         if obstacles.contains(&self.0) {
+//!             BehaviorResult::Failure(())
+//!         } else {
+//!             BehaviorResult::Success(())
+//!         }
+//!     }
+//! }
+//!
+//! let mut tree = ParallelRayonNode::new(
+//!     (0..8)
+//!         .map(|i| Box::new(IsVisible(i)) as ChildSend<&[u32], _, _>)
+//!         .collect::<Vec<_>>(),
+//!     7,
+//! );
+//! assert_eq!(tree.tick(&[3][..]), BehaviorResult::Success(()));
+//! assert_eq!(tree.tick(&[3, 5][..]), BehaviorResult::Failure(()));
+//! ```
+use crate::composite::children_vec_methods;
+use crate::mergers::Merge;
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, ChildSend, HaltReport, MergeResult};
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use std::any::Any;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A parallel node that ticks all children concurrently on a rayon thread
+/// pool and succeeds if at least `success_threshold` of them succeed.
+///
+/// The results are collected in the order of the children, then merged
+/// and counted the same way as [ParallelNode](crate::ParallelNode), so the
+/// result does not depend on the scheduling of the threads.
+/// The children run on the global pool of rayon unless another pool is given
+/// by [ParallelRayonNode::in_pool].
+///
+/// The children are reported in a [TickReport](crate::TickReport), but their
+/// descendants are not, since they are ticked on other threads.
+pub struct ParallelRayonNode<Payload, R, F, MR> {
+    children: Vec<ChildSend<Payload, R, F>>,
+    merge_result: Option<MR>,
+    success_threshold: usize,
+    pool: Option<Arc<ThreadPool>>,
+}
+
+impl<Payload, R, F> ParallelRayonNode<Payload, R, F, &dyn Fn(&mut R, R)> {
+    /// Constructs a [ParallelRayonNode] with children nodes.
+    ///
+    /// If multiple child nodes return results in `R`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T, success_threshold: usize) -> Self
+    where
+        T: Into<Vec<ChildSend<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: None,
+            success_threshold,
+            pool: None,
+        }
+    }
+}
+
+impl<Payload, R, F> ParallelRayonNode<Payload, R, F, Merge> {
+    /// Constructs a [ParallelRayonNode] with children nodes, merging their
+    /// results in `R` with [Merge].
+    pub fn merged<T>(children: T, success_threshold: usize) -> Self
+    where
+        T: Into<Vec<ChildSend<Payload, R, F>>>,
+    {
+        Self::new_with_merger(children, success_threshold, Merge)
+    }
+}
+
+impl<Payload, R, F, MR> ParallelRayonNode<Payload, R, F, MR> {
+    /// Constructs a [ParallelRayonNode] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, success_threshold: usize, merge_result: MR) -> Self
+    where
+        T: Into<Vec<ChildSend<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: Some(merge_result),
+            success_threshold,
+            pool: None,
+        }
+    }
+
+    /// Ticks the children on `pool` instead of the global pool of rayon.
+    pub fn in_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    children_vec_methods!(children, ChildSend<Payload, R, F>);
+}
+
+impl<Payload, R, F, MR> ParallelRayonNode<Payload, R, F, MR>
+where
+    R: Default + Send,
+    F: Default + Send,
+    Payload: Clone + Sync,
+    MR: MergeResult<R>,
+{
+    /// Ticks children with `tick_child`, which calls either `tick` or `try_tick` of the child.
+    fn run<E: Send>(
+        &mut self,
+        payload: Payload,
+        tick_child: impl Fn(
+                usize,
+                &mut (dyn BehaviorNodeBase<Payload, R, F> + Send),
+                Payload,
+            ) -> Result<BehaviorResult<R, F>, E>
+            + Sync,
+    ) -> Result<BehaviorResult<R, F>, E> {
+        let children = &mut self.children;
+        let (payload, tick_child) = (&payload, &tick_child);
+        let mut tick_all = move || {
+            children
+                .par_iter_mut()
+                .enumerate()
+                .map(|(i, node)| {
+                    let start = Instant::now();
+                    let res = tick_child(i, node.as_mut(), payload.clone());
+                    (res, start.elapsed())
+                })
+                .collect::<Vec<_>>()
+        };
+        let results = match &self.pool {
+            Some(pool) => pool.install(tick_all),
+            None => tick_all(),
+        };
+
+        let mut last_success = R::default();
+        let mut last_failure = F::default();
+        let mut successes = 0;
+        let mut failures = 0;
+        let mut running = vec![];
+        for (i, (res, duration)) in results.into_iter().enumerate() {
+            let res = res?;
+            report::child_ticked(i, res.kind(), duration);
+            match res {
+                BehaviorResult::Success(r) => {
+                    successes += 1;
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_success, r)
+                    } else {
+                        last_success = r
+                    }
+                }
+                BehaviorResult::Failure(f) => {
+                    failures += 1;
+                    last_failure = f;
+                }
+                BehaviorResult::Running => running.push(i),
+                BehaviorResult::Idle => (),
+            }
+        }
+
+        let res = if self.success_threshold <= successes {
+            BehaviorResult::Success(last_success)
+        } else if self.children.len() < self.success_threshold + failures {
+            BehaviorResult::Failure(last_failure)
+        } else {
+            return Ok(BehaviorResult::Running);
+        };
+        for i in running {
+            self.children[i].halt();
+        }
+        Ok(res)
+    }
+}
+
+impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F> for ParallelRayonNode<Payload, R, F, MR>
+where
+    R: Default + Send,
+    F: Default + Send,
+    Payload: Clone + Sync,
+    MR: MergeResult<R>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![("success_threshold", self.success_threshold.to_string())]
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children
+            .iter_mut()
+            .map(|node| node.as_mut() as _)
+            .collect()
+    }
+}
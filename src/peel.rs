@@ -0,0 +1,245 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::marker::PhantomData;
+
+/// A peel node defined by a projection function instead of [peel_node_def](crate::peel_node_def).
+///
+/// The projection runs once per tick, and its result is passed to the child node.
+/// When the child is a control node, all of its children share the same
+/// projected value, so wrapping the control node with a single `PeelOnce`
+/// is cheaper than wrapping each of its children with a peel node if the
+/// projection is expensive, e.g. a query to a spatial index.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::Cell;
+/// struct Arm {
+///     name: String,
+/// }
+///
+/// struct Body {
+///     arms: Vec<Arm>,
+///     lookups: Cell<usize>,
+/// }
+///
+/// fn longest_arm(body: &Body) -> &Arm {
+///     body.lookups.set(body.lookups.get() + 1);
+///     body.arms.iter().max_by_key(|arm| arm.name.len()).unwrap()
+/// }
+///
+/// struct PrintArmNode;
+///
+/// impl BehaviorNodeBase<&Arm, (), ()> for PrintArmNode {
+///     fn tick(&mut self, arm: &Arm) -> BehaviorResult<(), ()> {
+///         println!("arm: {}", arm.name);
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// let body = Body {
+///     arms: vec![Arm { name: "leftArm".to_string() }, Arm { name: "rightArm".to_string() }],
+///     lookups: Cell::new(0),
+/// };
+/// let mut tree = PeelOnce::new(
+///     longest_arm,
+///     SequenceNodeRef::<Arm, (), (), _>::new([
+///         Box::new(PrintArmNode) as Box<dyn BehaviorNodeBase<&Arm, (), ()>>,
+///         Box::new(PrintArmNode),
+///     ]),
+/// );
+/// assert_eq!(tree.tick(&body), BehaviorResult::Success(()));
+/// assert_eq!(body.lookups.get(), 1);
+/// ```
+pub struct PeelOnce<P, T> {
+    peel: P,
+    node: T,
+}
+
+impl<P, T> PeelOnce<P, T> {
+    pub fn new(peel: P, node: T) -> Self {
+        Self { peel, node }
+    }
+}
+
+impl<ParentPayload, Payload, R, F, P, T> BehaviorNodeBase<ParentPayload, R, F> for PeelOnce<P, T>
+where
+    P: FnMut(ParentPayload) -> Payload,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: ParentPayload) -> BehaviorResult<R, F> {
+        let payload = (self.peel)(payload);
+        self.node.tick(payload)
+    }
+
+    fn try_tick(&mut self, payload: ParentPayload) -> Result<BehaviorResult<R, F>, BtError> {
+        let payload = (self.peel)(payload);
+        self.node.try_tick(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
+
+/// A decorator that computes an owned payload for its subtree from the
+/// parent payload once per tick.
+///
+/// It is the same node as [PeelOnce], named for the case where the projection
+/// builds a new value, like a navigation context from the world state,
+/// rather than borrowing a part of the parent payload.
+/// If the child is a control node, the payload is cloned for each of its
+/// children, so wrap it in an [Rc](std::rc::Rc) if it is expensive to clone.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::rc::Rc;
+/// struct World {
+///     obstacles: Vec<(i32, i32)>,
+/// }
+///
+/// struct NavContext {
+///     blocked: Vec<i32>,
+/// }
+///
+/// struct IsClear(i32);
+///
+/// impl BehaviorNodeBase<Rc<NavContext>, (), ()> for IsClear {
+///     fn tick(&mut self, nav: Rc<NavContext>) -> BehaviorResult<(), ()> {
+///         if nav.blocked.contains(&self.0) {
+///             BehaviorResult::Failure(())
+///         } else {
+///             BehaviorResult::Success(())
+///         }
+///     }
+/// }
+///
+/// type Node = Box<dyn BehaviorNodeBase<Rc<NavContext>, (), ()>>;
+///
+/// let mut tree = MapPayloadNode::new(
+///     |world: &World| {
+///         Rc::new(NavContext {
+///             blocked: world.obstacles.iter().map(|(x, _)| *x).collect(),
+///         })
+///     },
+///     SequenceNode::new([Box::new(IsClear(1)) as Node, Box::new(IsClear(2))]),
+/// );
+/// let world = World {
+///     obstacles: vec![(3, 0)],
+/// };
+/// assert_eq!(tree.tick(&world), BehaviorResult::Success(()));
+/// ```
+pub type MapPayloadNode<M, T> = PeelOnce<M, T>;
+
+/// A peel node that also converts the results of the child, so that a
+/// subtree with its own result types can be mounted under a parent
+/// expecting different ones.
+///
+/// The payload is converted by `peel` as [PeelOnce] does, and the results
+/// by `map_success` and `map_failure` on the way back.
+/// The conversions are closures stored in the node, so they can capture
+/// variables, e.g. a unit or a label of the subtree.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct Robot {
+///     battery: u32,
+/// }
+///
+/// /// A node from a navigation library that reports the cost of the path.
+/// struct Navigate;
+///
+/// impl BehaviorNodeBase<u32, f64, &'static str> for Navigate {
+///     fn tick(&mut self, battery: u32) -> BehaviorResult<f64, &'static str> {
+///         if 10 <= battery {
+///             BehaviorResult::Success(4.5)
+///         } else {
+///             BehaviorResult::Failure("low battery")
+///         }
+///     }
+/// }
+///
+/// let name = "navigation".to_string();
+/// let mut tree = AdaptNode::new(
+///     |robot: &Robot| robot.battery,
+///     |_cost: f64| (),
+///     move |reason: &str| format!("{}: {}", name, reason),
+///     Navigate,
+/// );
+/// assert_eq!(tree.tick(&Robot { battery: 50 }), BehaviorResult::Success(()));
+/// assert_eq!(
+///     tree.tick(&Robot { battery: 5 }),
+///     BehaviorResult::Failure("navigation: low battery".to_string())
+/// );
+/// ```
+///
+/// `CR` and `CF` are the result types of the child, which the conversions take.
+pub struct AdaptNode<P, T, MS, MF, CR, CF> {
+    peel: P,
+    node: T,
+    map_success: MS,
+    map_failure: MF,
+    marker: PhantomData<fn(CR, CF)>,
+}
+
+impl<P, T, MS, MF, CR, CF> AdaptNode<P, T, MS, MF, CR, CF> {
+    pub fn new(peel: P, map_success: MS, map_failure: MF, node: T) -> Self {
+        Self {
+            peel,
+            node,
+            map_success,
+            map_failure,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<ParentPayload, Payload, R, F, CR, CF, P, T, MS, MF> BehaviorNodeBase<ParentPayload, R, F>
+    for AdaptNode<P, T, MS, MF, CR, CF>
+where
+    P: FnMut(ParentPayload) -> Payload,
+    T: BehaviorNodeBase<Payload, CR, CF>,
+    MS: FnMut(CR) -> R,
+    MF: FnMut(CF) -> F,
+{
+    fn tick(&mut self, payload: ParentPayload) -> BehaviorResult<R, F> {
+        let payload = (self.peel)(payload);
+        adapt(
+            self.node.tick(payload),
+            &mut self.map_success,
+            &mut self.map_failure,
+        )
+    }
+
+    fn try_tick(&mut self, payload: ParentPayload) -> Result<BehaviorResult<R, F>, BtError> {
+        let payload = (self.peel)(payload);
+        Ok(adapt(
+            self.node.try_tick(payload)?,
+            &mut self.map_success,
+            &mut self.map_failure,
+        ))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
+
+fn adapt<R, F, CR, CF>(
+    res: BehaviorResult<CR, CF>,
+    map_success: impl FnOnce(CR) -> R,
+    map_failure: impl FnOnce(CF) -> F,
+) -> BehaviorResult<R, F> {
+    match res {
+        BehaviorResult::Idle => BehaviorResult::Idle,
+        BehaviorResult::Running => BehaviorResult::Running,
+        BehaviorResult::Success(r) => BehaviorResult::Success(map_success(r)),
+        BehaviorResult::Failure(f) => BehaviorResult::Failure(map_failure(f)),
+    }
+}
@@ -0,0 +1,204 @@
+//! Saving and restoring the contents of a [Blackboard], enabled by the `serde` feature.
+//!
+//! The values in a blackboard have arbitrary types, so the types to save are
+//! registered in a [SerdeRegistry] with stable tags, which are written along
+//! with the values so that they can be restored in another run of the
+//! program, e.g. for save games or bug reports.
+//! [SavedBlackboard] implements `Serialize` and `Deserialize`, so it can be
+//! written in any format supported by serde.
+//!
+//! ```
+//! use tiny_behavior_tree::persist::SerdeRegistry;
+//! # use tiny_behavior_tree::Blackboard;
+//! let mut types = SerdeRegistry::new();
+//! types.register::<(i32, i32)>("position");
+//! types.register::<String>("string");
+//!
+//! let mut bb = Blackboard::new();
+//! bb.set("enemy", (3, 4));
+//! bb.set("target", "door".to_string());
+//! let json = serde_json::to_string(&types.save(&bb).unwrap()).unwrap();
+//!
+//! let mut restored = Blackboard::new();
+//! types.restore(&mut restored, &serde_json::from_str(&json).unwrap()).unwrap();
+//! assert_eq!(restored.get::<(i32, i32)>("enemy"), Some(&(3, 4)));
+//! assert_eq!(restored.get::<String>("target").map(String::as_str), Some("door"));
+//! ```
+use crate::Blackboard;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The types of blackboard values that can be saved, with their tags.
+#[derive(Default)]
+pub struct SerdeRegistry {
+    tags: HashMap<TypeId, String>,
+    codecs: HashMap<String, Codec>,
+}
+
+struct Codec {
+    type_name: &'static str,
+    save: fn(&dyn Any) -> serde_json::Result<Value>,
+    load: fn(Value) -> serde_json::Result<Box<dyn Any>>,
+}
+
+impl SerdeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` with `tag`, which identifies the type in saved data.
+    ///
+    /// Registering another type with the same tag replaces the former, and
+    /// registering the same type with another tag replaces the former tag, so
+    /// that data saved with it cannot be restored anymore.
+    pub fn register<T: Serialize + DeserializeOwned + 'static>(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        self.tags.retain(|_, registered| *registered != tag);
+        if let Some(former) = self.tags.insert(TypeId::of::<T>(), tag.clone()) {
+            self.codecs.remove(&former);
+        }
+        self.codecs.insert(
+            tag,
+            Codec {
+                type_name: type_name::<T>(),
+                save: save::<T>,
+                load: load::<T>,
+            },
+        );
+    }
+
+    /// Saves the entries of registered types outside of scopes.
+    ///
+    /// The keys of entries of other types are listed in
+    /// [SavedBlackboard::skipped].
+    /// Entries set by [Blackboard::set_for_generations] keep the remaining
+    /// generations, while deadlines given by [Blackboard::set_until] are not
+    /// saved since they are relative to the running program.
+    pub fn save(&self, blackboard: &Blackboard) -> Result<SavedBlackboard, SaveError> {
+        let mut saved = SavedBlackboard::default();
+        for (key, value, expires_in) in blackboard.saved_entries() {
+            let Some(tag) = self.tags.get(&value.type_id()) else {
+                saved.skipped.push(key.to_owned());
+                continue;
+            };
+            let value = (self.codecs[tag].save)(value).map_err(|error| SaveError {
+                key: key.to_owned(),
+                error,
+            })?;
+            saved.entries.push(SavedEntry {
+                key: key.to_owned(),
+                tag: tag.clone(),
+                value,
+                expires_in,
+            });
+        }
+        Ok(saved)
+    }
+
+    /// Sets the saved entries to `blackboard`.
+    ///
+    /// Nothing is set if any of the entries cannot be restored.
+    pub fn restore(
+        &self,
+        blackboard: &mut Blackboard,
+        saved: &SavedBlackboard,
+    ) -> Result<(), RestoreError> {
+        let mut values = vec![];
+        for entry in &saved.entries {
+            let codec = self
+                .codecs
+                .get(&entry.tag)
+                .ok_or_else(|| RestoreError::UnknownTag {
+                    key: entry.key.clone(),
+                    tag: entry.tag.clone(),
+                })?;
+            let value =
+                (codec.load)(entry.value.clone()).map_err(|error| RestoreError::Invalid {
+                    key: entry.key.clone(),
+                    error,
+                })?;
+            values.push((entry, value, codec.type_name));
+        }
+        for (entry, value, type_name) in values {
+            blackboard.restore_entry(&entry.key, value, type_name, entry.expires_in);
+        }
+        Ok(())
+    }
+}
+
+fn save<T: Serialize + 'static>(value: &dyn Any) -> serde_json::Result<Value> {
+    let value = value
+        .downcast_ref::<T>()
+        .expect("the codec is chosen by the type id");
+    serde_json::to_value(value)
+}
+
+fn load<T: DeserializeOwned + 'static>(value: Value) -> serde_json::Result<Box<dyn Any>> {
+    Ok(Box::new(serde_json::from_value::<T>(value)?))
+}
+
+/// The contents of a [Blackboard] saved by [SerdeRegistry::save].
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SavedBlackboard {
+    pub entries: Vec<SavedEntry>,
+    /// The keys of the entries whose types are not registered.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SavedEntry {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub tag: String,
+    pub value: Value,
+    /// The number of generations until the entry expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<u64>,
+}
+
+/// The error type returned by [SerdeRegistry::save].
+#[derive(Debug)]
+pub struct SaveError {
+    pub key: String,
+    pub error: serde_json::Error,
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cannot save entry \"{}\": {}", self.key, self.error)
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// The error type returned by [SerdeRegistry::restore].
+#[derive(Debug)]
+pub enum RestoreError {
+    /// No type is registered with the tag of the entry.
+    UnknownTag { key: String, tag: String },
+    /// The value does not match the type registered with the tag.
+    Invalid {
+        key: String,
+        error: serde_json::Error,
+    },
+}
+
+impl fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownTag { key, tag } => {
+                write!(f, "Unknown type \"{}\" of entry \"{}\"", tag, key)
+            }
+            Self::Invalid { key, error } => {
+                write!(f, "Cannot restore entry \"{}\": {}", key, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RestoreError {}
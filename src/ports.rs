@@ -0,0 +1,231 @@
+use crate::{BehaviorNodeBase, Blackboard, TypedKey};
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Whether a node reads or writes the blackboard entry of a [Port].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortDirection {
+    Input,
+    Output,
+    InOut,
+}
+
+impl PortDirection {
+    fn reads(self) -> bool {
+        matches!(self, Self::Input | Self::InOut)
+    }
+
+    fn writes(self) -> bool {
+        matches!(self, Self::Output | Self::InOut)
+    }
+}
+
+/// A blackboard entry that a node reads or writes, returned by
+/// [BehaviorNodeBase::ports].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Port {
+    name: &'static str,
+    direction: PortDirection,
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+impl Port {
+    pub fn input<T: 'static>(key: TypedKey<T>) -> Self {
+        Self::new(key, PortDirection::Input)
+    }
+
+    pub fn output<T: 'static>(key: TypedKey<T>) -> Self {
+        Self::new(key, PortDirection::Output)
+    }
+
+    pub fn in_out<T: 'static>(key: TypedKey<T>) -> Self {
+        Self::new(key, PortDirection::InOut)
+    }
+
+    fn new<T: 'static>(key: TypedKey<T>, direction: PortDirection) -> Self {
+        Self {
+            name: key.name(),
+            direction,
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn direction(&self) -> PortDirection {
+        self.direction
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+/// A problem found by [ValidatePorts::validate_ports].
+#[derive(PartialEq, Debug)]
+pub enum PortError {
+    /// The node at `path` declares the port `key` with a different type than
+    /// another node or the existing entry in the blackboard.
+    TypeMismatch {
+        path: String,
+        key: &'static str,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The node at `path` reads `key`, but no node writes it and the
+    /// blackboard does not have it.
+    Unprovided { path: String, key: &'static str },
+}
+
+impl fmt::Display for PortError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TypeMismatch {
+                path,
+                key,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Node at path \"{}\" uses \"{}\" as {}, but it is {}",
+                path, key, found, expected
+            ),
+            Self::Unprovided { path, key } => write!(
+                f,
+                "Node at path \"{}\" reads \"{}\", which nothing provides",
+                path, key
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PortError {}
+
+/// Validation of the ports declared by the nodes in a tree, so that a wrong
+/// blackboard key or type is found when the tree is built instead of when
+/// a node reads it.
+///
+/// Like [Configure](crate::Configure), it is implemented for every node that
+/// is `'static` and visits the children returned by
+/// [BehaviorNodeBase::children_mut].
+/// An input port is provided if any other port in the tree writes the same
+/// key, or if `blackboard` already has an entry of the same type.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::RefCell;
+/// const TARGET: TypedKey<(i32, i32)> = TypedKey::new("target");
+///
+/// struct MoveTo;
+///
+/// impl<'a> BehaviorNodeBase<&'a RefCell<Blackboard>, (), ()> for MoveTo {
+///     fn tick(&mut self, bb: &'a RefCell<Blackboard>) -> BehaviorResult<(), ()> {
+///         match bb.borrow().get_typed(TARGET) {
+///             Some(_) => BehaviorResult::Success(()),
+///             None => BehaviorResult::Failure(()),
+///         }
+///     }
+///
+///     fn ports(&self) -> Vec<Port> {
+///         vec![Port::input(TARGET)]
+///     }
+/// }
+///
+/// let mut tree = MoveTo;
+/// let mut bb = Blackboard::new();
+/// let errors = ValidatePorts::<&RefCell<Blackboard>, (), ()>::validate_ports(&mut tree, &bb);
+/// assert_eq!(errors.unwrap_err()[0].to_string(), "Node at path \"\" reads \"target\", which nothing provides");
+///
+/// bb.set_typed(TARGET, (3, 4));
+/// assert!(ValidatePorts::<&RefCell<Blackboard>, (), ()>::validate_ports(&mut tree, &bb).is_ok());
+/// ```
+pub trait ValidatePorts<Payload, R, F> {
+    /// Checks the ports of all nodes, returning every problem found.
+    fn validate_ports(&mut self, blackboard: &Blackboard) -> Result<(), Vec<PortError>>;
+}
+
+impl<N, Payload, R, F> ValidatePorts<Payload, R, F> for N
+where
+    N: BehaviorNodeBase<Payload, R, F> + ?Sized + 'static,
+    Payload: 'static,
+    R: 'static,
+    F: 'static,
+{
+    fn validate_ports(&mut self, blackboard: &Blackboard) -> Result<(), Vec<PortError>> {
+        let mut ports = vec![];
+        collect_ports(self, &mut vec![], &mut ports);
+
+        let mut errors = vec![];
+        // The type of each key, taken from the blackboard or the first node that declares it.
+        let mut types: HashMap<&str, (TypeId, &'static str)> = HashMap::new();
+        for (path, port) in &ports {
+            let (type_id, expected) = *types.entry(port.name).or_insert_with(|| {
+                blackboard
+                    .type_of(port.name)
+                    .unwrap_or((port.type_id, port.type_name))
+            });
+            if type_id != port.type_id {
+                errors.push(PortError::TypeMismatch {
+                    path: path.clone(),
+                    key: port.name,
+                    expected,
+                    found: port.type_name,
+                });
+            }
+        }
+
+        for (i, (path, port)) in ports.iter().enumerate() {
+            if !port.direction.reads() || blackboard.type_of(port.name).is_some() {
+                continue;
+            }
+            // An in-out port needs a value before it writes one.
+            let written = ports.iter().enumerate().any(|(j, (_, other))| {
+                i != j && other.name == port.name && other.direction.writes()
+            });
+            if !written {
+                errors.push(PortError::Unprovided {
+                    path: path.clone(),
+                    key: port.name,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn collect_ports<N, Payload, R, F>(
+    node: &mut N,
+    path: &mut Vec<usize>,
+    ports: &mut Vec<(String, Port)>,
+) where
+    N: BehaviorNodeBase<Payload, R, F> + ?Sized + 'static,
+    Payload: 'static,
+    R: 'static,
+    F: 'static,
+{
+    let path_string = path
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+    ports.extend(
+        node.ports()
+            .into_iter()
+            .map(|port| (path_string.clone(), port)),
+    );
+    for (i, child) in node.children_mut().into_iter().enumerate() {
+        path.push(i);
+        collect_ports(child, path, ports);
+        path.pop();
+    }
+}
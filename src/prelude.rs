@@ -0,0 +1,20 @@
+//! The commonly used traits, control nodes, result types and macros.
+//!
+//! ```
+//! use tiny_behavior_tree::prelude::*;
+//! # use tiny_behavior_tree::testing::AlwaysSuccess;
+//!
+//! type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+//!
+//! let mut tree = SequenceNode::<(), (), (), _>::new([Box::new(AlwaysSuccess(())) as Node]);
+//! assert_eq!(tree.tick(()).kind(), ResultKind::Success);
+//! ```
+pub use crate::{bt_try, btree, peel_node_def, peel_node_def_mut, peel_node_def_opt};
+#[cfg(feature = "derive")]
+pub use crate::{conditions_for, Condition, DelegateNode, Describe};
+pub use crate::{
+    BehaviorNodeBase, BehaviorResult, ConditionNode, Configure, FallbackNode, FallbackNodeMut,
+    FallbackNodeRef, FallbackNodeSend, FallbackNodeWithMemory, InverterNode, MergeResult,
+    ParallelNode, ReactiveFallbackNode, ReactiveSequenceNode, RepeatNode, ResultKind, RetryNode,
+    SequenceNode, SequenceNodeMut, SequenceNodeRef, SequenceNodeSend, SequenceNodeWithMemory, Tree,
+};
@@ -0,0 +1,128 @@
+use crate::composite::{
+    child_meta_methods, children_vec_methods, composite_node_introspection, Composite,
+};
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, MergeResult};
+use std::cmp::Ordering;
+
+/// A [ReactiveFallbackNode](crate::ReactiveFallbackNode) whose priorities of
+/// the children are recomputed in every tick by a comparator over the payload.
+///
+/// The comparator receives the payload and the indices of two children, and
+/// the child ordered first has the higher priority.
+/// Children with equal priorities keep their order.
+/// As in [ReactiveFallbackNode](crate::ReactiveFallbackNode), the children are
+/// tried from the highest priority in every tick, and if a higher priority
+/// child succeeds or starts running, a lower priority child that was running
+/// is halted.
+/// The order used in the last tick is available from [PrioritySelectorNode::order].
+///
+/// The generic parameters and the result merger function are the same as
+/// [FallbackNode](crate::FallbackNode), and `C` is the comparator.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// # use std::cmp::Ordering;
+/// type Node = Box<dyn BehaviorNodeBase<&'static [u32], usize, ()>>;
+///
+/// // Prefer the child with the least cost given in the payload.
+/// let mut tree = PrioritySelectorNode::new(
+///     [Box::new(AlwaysSuccess(0_usize)) as Node, Box::new(AlwaysSuccess(1))],
+///     |costs: &&[u32], a: usize, b: usize| costs[a].cmp(&costs[b]),
+/// );
+/// assert_eq!(tree.tick(&[5, 3]), BehaviorResult::Success(1));
+/// assert_eq!(tree.tick(&[2, 3]), BehaviorResult::Success(0));
+/// ```
+pub struct PrioritySelectorNode<Payload, R, F, C, MR> {
+    inner: Composite<'static, Payload, R, F, MR, F>,
+    compare: C,
+}
+
+impl<Payload, R, F, C> PrioritySelectorNode<Payload, R, F, C, &dyn Fn(&mut F, F)>
+where
+    C: Fn(&Payload, usize, usize) -> Ordering,
+{
+    /// Constructs a [PrioritySelectorNode] with children nodes and a comparator.
+    ///
+    /// If multiple child nodes return results in `F`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T, compare: C) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            inner: Composite::new(children.into(), None),
+            compare,
+        }
+    }
+}
+
+impl<Payload, R, F, C, MR> PrioritySelectorNode<Payload, R, F, C, MR>
+where
+    C: Fn(&Payload, usize, usize) -> Ordering,
+{
+    /// Constructs a [PrioritySelectorNode] with children nodes, a comparator and a merger funtion.
+    pub fn new_with_merger<T>(children: T, compare: C, merge_result: MR) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            inner: Composite::new(children.into(), Some(merge_result)),
+            compare,
+        }
+    }
+
+    children_vec_methods!(inner.children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when this node stops ticking children
+    /// because of a success, with the index of the child and its result.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'static,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+
+    /// Returns the indices of the children in the order of priority used in the last tick.
+    pub fn order(&self) -> Option<&[usize]> {
+        self.inner.order()
+    }
+
+    fn prioritize(&mut self, payload: &Payload) {
+        let mut order: Vec<usize> = (0..self.inner.children.len()).collect();
+        order.sort_by(|a, b| (self.compare)(payload, *a, *b));
+        self.inner.set_order(order);
+    }
+}
+
+impl<Payload, R, F, C, MR> BehaviorNodeBase<Payload, R, F>
+    for PrioritySelectorNode<Payload, R, F, C, MR>
+where
+    F: Default,
+    Payload: Clone,
+    C: Fn(&Payload, usize, usize) -> Ordering,
+    MR: MergeResult<F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.prioritize(&payload);
+        self.inner.tick_reactive_fallback(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.prioritize(&payload);
+        self.inner.try_tick_reactive_fallback(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
+    }
+
+    composite_node_introspection!(Payload);
+}
@@ -0,0 +1,371 @@
+use crate::composite::{
+    child_meta_methods, children_vec_methods, composite_node_introspection, Composite,
+};
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, MergeResult};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// A source of random numbers for the randomized control nodes.
+///
+/// Implement it for the random number generator of your game, or use
+/// [SplitMix64] with a fixed seed to make the order of the children
+/// reproducible in tests and replays.
+pub trait RandomSource {
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a number in `0..n`, where `n` must be positive.
+    fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+/// A small and fast seedable random number generator.
+///
+/// It is not suitable for cryptography.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator that always produces the same numbers for the same `seed`.
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Creates a generator with a seed that differs every time.
+    pub fn from_entropy() -> Self {
+        Self::seed_from_u64(RandomState::new().build_hasher().finish())
+    }
+}
+
+impl Default for SplitMix64 {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// A seed from which each randomized node derives its own [SplitMix64]
+/// stream by its path in the tree.
+///
+/// Seeding every node from the same generator makes the random choices of a
+/// node depend on how many random numbers the other nodes drew before it, so
+/// adding an unrelated random node changes the behavior of the existing ones.
+/// A stream derived from the path depends only on the seed and the path,
+/// which keeps a bug report reproducible with the seed across builds.
+/// The derivation uses a fixed hash function and does not change between
+/// versions of this crate.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+///
+/// let context = RandomContext::new(1234);
+/// let mut patrol = RandomSequenceNode::new([
+///     Box::new(AlwaysSuccess(())) as Node,
+///     Box::new(AlwaysSuccess(())),
+/// ])
+/// .with_rng(context.stream("0/2"));
+/// # patrol.tick(());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RandomContext {
+    seed: u64,
+}
+
+impl RandomContext {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Creates a context with a seed that differs every time, which can be
+    /// logged with [RandomContext::seed] to reproduce the run later.
+    pub fn from_entropy() -> Self {
+        Self::new(SplitMix64::from_entropy().next_u64())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the random stream of the node at `path`, given as slash-separated child indices
+    /// like [Configure](crate::Configure) or any other name unique in the tree.
+    pub fn stream(&self, path: &str) -> SplitMix64 {
+        // FNV-1a, which is stable unlike the hasher of the standard library.
+        let hash = path.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        let mut mixer = SplitMix64::seed_from_u64(self.seed ^ hash);
+        SplitMix64::seed_from_u64(mixer.next_u64())
+    }
+}
+
+/// Chooses a random order of `len` children with the Fisher-Yates shuffle.
+fn shuffle(rng: &mut impl RandomSource, len: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    for i in (1..len).rev() {
+        order.swap(i, rng.below(i + 1));
+    }
+    order
+}
+
+/// Implements the methods shared by the randomized control nodes.
+macro_rules! random_node_methods {
+    ($name:ident) => {
+        /// Replaces the random number generator, e.g. with a seeded one in tests.
+        pub fn with_rng<G2: RandomSource>(self, rng: G2) -> $name<Payload, R, F, MR, G2> {
+            $name {
+                inner: self.inner,
+                rng,
+            }
+        }
+
+        /// Returns the order of the children chosen in the current or the
+        /// last activation, as their indices.
+        pub fn order(&self) -> Option<&[usize]> {
+            self.inner.order()
+        }
+
+        children_vec_methods!(inner.children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+        child_meta_methods!(inner.children, inner.child_meta);
+
+        /// Sets a callback that is called when a child ends this node, i.e. fails
+        /// in a sequence or succeeds in a fallback, with the index of the child
+        /// and its result.
+        ///
+        /// It is called for the last child as well, but not for a running child.
+        pub fn on_short_circuit(
+            mut self,
+            callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'static,
+        ) -> Self {
+            self.inner.set_short_circuit(Box::new(callback));
+            self
+        }
+
+        /// Shuffles the children if a new activation begins.
+        fn prepare(&mut self)
+        where
+            G: RandomSource,
+        {
+            if !self.inner.is_resuming() {
+                let order = shuffle(&mut self.rng, self.inner.children.len());
+                report::order(&order);
+                self.inner.set_order(order);
+            }
+        }
+    };
+}
+
+/// A [SequenceNodeWithMemory](crate::SequenceNodeWithMemory) that ticks the
+/// children in a random order, e.g. to visit waypoints in a different order
+/// every time.
+///
+/// The order is chosen when an activation begins and kept until this node
+/// succeeds or fails, or it is halted, so a running child is resumed as in
+/// [SequenceNodeWithMemory](crate::SequenceNodeWithMemory).
+/// The chosen order is available from [RandomSequenceNode::order] and
+/// [TickReport::order](crate::TickReport::order).
+///
+/// The generic parameters and the result merger function are the same as
+/// [SequenceNode](crate::SequenceNode), and `G` is the [RandomSource].
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// type Node = Box<dyn BehaviorNodeBase<(), Vec<i32>, ()>>;
+///
+/// let mut tree = RandomSequenceNode::new_with_merger(
+///     [
+///         Box::new(AlwaysSuccess(vec![1])) as Node,
+///         Box::new(AlwaysSuccess(vec![2])),
+///         Box::new(AlwaysSuccess(vec![3])),
+///     ],
+///     |acc: &mut Vec<i32>, mut v: Vec<i32>| acc.append(&mut v),
+/// )
+/// .with_rng(SplitMix64::seed_from_u64(42));
+/// let BehaviorResult::Success(visited) = tree.tick(()) else { panic!() };
+/// let order: Vec<_> = tree.order().unwrap().iter().map(|i| *i as i32 + 1).collect();
+/// assert_eq!(visited, order);
+/// ```
+pub struct RandomSequenceNode<Payload, R, F, MR, G = SplitMix64> {
+    inner: Composite<'static, Payload, R, F, MR, R>,
+    rng: G,
+}
+
+impl<Payload, R, F> RandomSequenceNode<Payload, R, F, &dyn Fn(&mut R, R)> {
+    /// Constructs a [RandomSequenceNode] with children nodes and a random seed.
+    ///
+    /// If multiple child nodes return results in `R`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        let mut inner = Composite::new(children.into(), None);
+        inner.set_memory();
+        Self {
+            inner,
+            rng: SplitMix64::from_entropy(),
+        }
+    }
+}
+
+impl<Payload, R, F, MR> RandomSequenceNode<Payload, R, F, MR> {
+    /// Constructs a [RandomSequenceNode] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        let mut inner = Composite::new(children.into(), Some(merge_result));
+        inner.set_memory();
+        Self {
+            inner,
+            rng: SplitMix64::from_entropy(),
+        }
+    }
+}
+
+impl<Payload, R, F, MR, G> RandomSequenceNode<Payload, R, F, MR, G> {
+    random_node_methods!(RandomSequenceNode);
+}
+
+impl<Payload, R, F, MR, G> BehaviorNodeBase<Payload, R, F>
+    for RandomSequenceNode<Payload, R, F, MR, G>
+where
+    R: Default,
+    Payload: Clone,
+    MR: MergeResult<R>,
+    G: RandomSource,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.prepare();
+        self.inner.tick_sequence(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.prepare();
+        self.inner.try_tick_sequence(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
+    }
+
+    composite_node_introspection!(Payload);
+}
+
+/// A [FallbackNodeWithMemory](crate::FallbackNodeWithMemory) that tries the
+/// children in a random order, so that an NPC does not always pick the first
+/// viable option.
+///
+/// The order is chosen when an activation begins and kept until this node
+/// succeeds or fails, or it is halted, so a running child is resumed as in
+/// [FallbackNodeWithMemory](crate::FallbackNodeWithMemory).
+/// The chosen order is available from [RandomFallbackNode::order] and
+/// [TickReport::order](crate::TickReport::order).
+///
+/// The generic parameters and the result merger function are the same as
+/// [FallbackNode](crate::FallbackNode), and `G` is the [RandomSource].
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// type Node = Box<dyn BehaviorNodeBase<(), &'static str, ()>>;
+///
+/// let mut idle = RandomFallbackNode::new([
+///     Box::new(AlwaysSuccess("whistle")) as Node,
+///     Box::new(AlwaysSuccess("stretch")),
+///     Box::new(AlwaysSuccess("look around")),
+/// ])
+/// .with_rng(SplitMix64::seed_from_u64(7));
+/// let BehaviorResult::Success(action) = idle.tick(()) else { panic!() };
+/// let first = idle.order().unwrap()[0];
+/// assert_eq!(action, ["whistle", "stretch", "look around"][first]);
+/// ```
+pub struct RandomFallbackNode<Payload, R, F, MR, G = SplitMix64> {
+    inner: Composite<'static, Payload, R, F, MR, F>,
+    rng: G,
+}
+
+impl<Payload, R, F> RandomFallbackNode<Payload, R, F, &dyn Fn(&mut F, F)> {
+    /// Constructs a [RandomFallbackNode] with children nodes and a random seed.
+    ///
+    /// If multiple child nodes return results in `F`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        let mut inner = Composite::new(children.into(), None);
+        inner.set_memory();
+        Self {
+            inner,
+            rng: SplitMix64::from_entropy(),
+        }
+    }
+}
+
+impl<Payload, R, F, MR> RandomFallbackNode<Payload, R, F, MR> {
+    /// Constructs a [RandomFallbackNode] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        let mut inner = Composite::new(children.into(), Some(merge_result));
+        inner.set_memory();
+        Self {
+            inner,
+            rng: SplitMix64::from_entropy(),
+        }
+    }
+}
+
+impl<Payload, R, F, MR, G> RandomFallbackNode<Payload, R, F, MR, G> {
+    random_node_methods!(RandomFallbackNode);
+}
+
+impl<Payload, R, F, MR, G> BehaviorNodeBase<Payload, R, F>
+    for RandomFallbackNode<Payload, R, F, MR, G>
+where
+    F: Default,
+    Payload: Clone,
+    MR: MergeResult<F>,
+    G: RandomSource,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.prepare();
+        self.inner.tick_fallback(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.prepare();
+        self.inner.try_tick_fallback(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
+    }
+
+    composite_node_introspection!(Payload);
+}
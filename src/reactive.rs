@@ -0,0 +1,231 @@
+use crate::composite::{
+    child_meta_methods, children_vec_methods, composite_node_introspection, Composite,
+};
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, MergeResult};
+
+/// ReactiveSequence is a Sequence that ticks child nodes from the first one
+/// in every tick.
+///
+/// It returns `Running` as soon as a child node returns `Running`, so that
+/// conditions before a long running action, like "battery ok", are checked
+/// again in every tick while the action is running.
+/// If such a condition fails, the running action is halted.
+///
+/// The generic parameters and the result merger function are the same as
+/// [SequenceNode](crate::SequenceNode).
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::*;
+/// # use std::cell::Cell;
+/// struct BatteryOk;
+///
+/// impl BehaviorNodeBase<&Cell<i32>, (), ()> for BatteryOk {
+///     fn tick(&mut self, battery: &Cell<i32>) -> BehaviorResult<(), ()> {
+///         if 20 < battery.get() {
+///             BehaviorResult::Success(())
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+/// }
+///
+/// type Node = Box<dyn BehaviorNodeBase<&'static Cell<i32>, (), ()>>;
+///
+/// let battery: &'static Cell<i32> = Box::leak(Box::new(Cell::new(50)));
+/// let mut tree = ReactiveSequenceNode::new([
+///     Box::new(BatteryOk) as Node,
+///     Box::new(AlwaysRunning),
+/// ]);
+/// assert_eq!(tree.tick(battery), BehaviorResult::Running);
+/// battery.set(10);
+/// assert_eq!(tree.tick(battery), BehaviorResult::Failure(()));
+/// ```
+pub struct ReactiveSequenceNode<Payload, R, F, MR> {
+    inner: Composite<'static, Payload, R, F, MR, R>,
+}
+
+impl<Payload, R, F> ReactiveSequenceNode<Payload, R, F, &dyn Fn(&mut R, R)> {
+    /// Constructs a [ReactiveSequenceNode] with children nodes.
+    ///
+    /// If multiple child nodes return results in `R`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            inner: Composite::new(children.into(), None),
+        }
+    }
+
+    /// Constructs a [ReactiveSequenceNode] without children, with room for `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+}
+
+impl<Payload, R, F, MR> ReactiveSequenceNode<Payload, R, F, MR> {
+    /// Constructs a [ReactiveSequenceNode] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            inner: Composite::new(children.into(), Some(merge_result)),
+        }
+    }
+
+    children_vec_methods!(inner.children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when this node stops ticking children
+    /// because of a failure, with the index of the child and its result.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'static,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+}
+
+impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F> for ReactiveSequenceNode<Payload, R, F, MR>
+where
+    R: Default,
+    Payload: Clone,
+    MR: MergeResult<R>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.inner.tick_reactive_sequence(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.inner.try_tick_reactive_sequence(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
+    }
+
+    composite_node_introspection!(Payload);
+}
+
+/// ReactiveFallback is a Fallback that ticks child nodes from the first one
+/// in every tick.
+///
+/// It returns `Running` as soon as a child node returns `Running`, so that
+/// higher priority children are tried again in every tick while a lower
+/// priority one is running.
+/// If one of them succeeds or starts running, the lower priority child is
+/// halted, e.g. "if an enemy is visible, abandon patrol".
+///
+/// The generic parameters and the result merger function are the same as
+/// [FallbackNode](crate::FallbackNode).
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::*;
+/// # use std::cell::Cell;
+/// struct EnemyVisible;
+///
+/// impl BehaviorNodeBase<&Cell<bool>, (), ()> for EnemyVisible {
+///     fn tick(&mut self, visible: &Cell<bool>) -> BehaviorResult<(), ()> {
+///         if visible.get() {
+///             BehaviorResult::Success(())
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+/// }
+///
+/// type Node = Box<dyn BehaviorNodeBase<&'static Cell<bool>, (), ()>>;
+///
+/// let visible: &'static Cell<bool> = Box::leak(Box::new(Cell::new(false)));
+/// let mut tree = ReactiveFallbackNode::new([
+///     Box::new(EnemyVisible) as Node,
+///     Box::new(AlwaysRunning), // patrol
+/// ]);
+/// assert_eq!(tree.tick(visible), BehaviorResult::Running);
+/// visible.set(true);
+/// assert_eq!(tree.tick(visible), BehaviorResult::Success(()));
+/// ```
+pub struct ReactiveFallbackNode<Payload, R, F, MR> {
+    inner: Composite<'static, Payload, R, F, MR, F>,
+}
+
+impl<Payload, R, F> ReactiveFallbackNode<Payload, R, F, &dyn Fn(&mut F, F)> {
+    /// Constructs a [ReactiveFallbackNode] with children nodes.
+    ///
+    /// If multiple child nodes return results in `F`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            inner: Composite::new(children.into(), None),
+        }
+    }
+
+    /// Constructs a [ReactiveFallbackNode] without children, with room for `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+}
+
+impl<Payload, R, F, MR> ReactiveFallbackNode<Payload, R, F, MR> {
+    /// Constructs a [ReactiveFallbackNode] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            inner: Composite::new(children.into(), Some(merge_result)),
+        }
+    }
+
+    children_vec_methods!(inner.children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    child_meta_methods!(inner.children, inner.child_meta);
+
+    /// Sets a callback that is called when this node stops ticking children
+    /// because of a success, with the index of the child and its result.
+    pub fn on_short_circuit(
+        mut self,
+        callback: impl FnMut(usize, &BehaviorResult<R, F>) + 'static,
+    ) -> Self {
+        self.inner.set_short_circuit(Box::new(callback));
+        self
+    }
+}
+
+impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F> for ReactiveFallbackNode<Payload, R, F, MR>
+where
+    F: Default,
+    Payload: Clone,
+    MR: MergeResult<F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.inner.tick_reactive_fallback(payload)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.inner.try_tick_reactive_fallback(payload)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.inner.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.inner.reset()
+    }
+
+    composite_node_introspection!(Payload);
+}
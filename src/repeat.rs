@@ -0,0 +1,142 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, MergeResult};
+use std::convert::Infallible;
+
+/// A decorator that ticks the child node a number of times in a single tick,
+/// stopping early when the child fails.
+///
+/// It returns the last success of the child, or the results merged by a
+/// merger function given to [RepeatNode::new_with_merger], in the same way
+/// as [SequenceNode](crate::SequenceNode).
+/// If the child returns `Running`, this node returns `Running` as well and
+/// continues the repetition in the next tick.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::Cell;
+/// struct ReadSensor;
+///
+/// impl BehaviorNodeBase<&Cell<i32>, Vec<i32>, ()> for ReadSensor {
+///     fn tick(&mut self, sensor: &Cell<i32>) -> BehaviorResult<Vec<i32>, ()> {
+///         sensor.set(sensor.get() + 1);
+///         BehaviorResult::Success(vec![sensor.get()])
+///     }
+/// }
+///
+/// let mut poll = RepeatNode::new_with_merger(
+///     5,
+///     ReadSensor,
+///     |readings: &mut Vec<i32>, mut reading: Vec<i32>| readings.append(&mut reading),
+/// );
+/// let sensor = Cell::new(0);
+/// assert_eq!(poll.tick(&sensor), BehaviorResult::Success(vec![1, 2, 3, 4, 5]));
+/// ```
+pub struct RepeatNode<T, R, MR> {
+    node: T,
+    times: Option<usize>,
+    merge_result: Option<MR>,
+    /// The number of successes so far and the result accumulated from them,
+    /// if the child was running in the last tick.
+    progress: Option<(usize, R)>,
+}
+
+impl<T, R> RepeatNode<T, R, &dyn Fn(&mut R, R)> {
+    /// Constructs a [RepeatNode] that ticks `node` `times` times per tick.
+    pub fn new(times: usize, node: T) -> Self {
+        Self::with_times(Some(times), node, None)
+    }
+
+    /// Constructs a [RepeatNode] that ticks `node` until it fails or returns `Running`.
+    ///
+    /// A child that always succeeds makes the tick of this node never return.
+    pub fn forever(node: T) -> Self {
+        Self::with_times(None, node, None)
+    }
+}
+
+impl<T, R, MR> RepeatNode<T, R, MR> {
+    /// Constructs a [RepeatNode] that ticks `node` `times` times per tick,
+    /// merging the results with a merger function.
+    pub fn new_with_merger(times: usize, node: T, merge_result: MR) -> Self {
+        Self::with_times(Some(times), node, Some(merge_result))
+    }
+
+    fn with_times(times: Option<usize>, node: T, merge_result: Option<MR>) -> Self {
+        Self {
+            node,
+            times,
+            merge_result,
+            progress: None,
+        }
+    }
+
+    fn run<Payload, F, E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(&mut T, Payload) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        Payload: Clone,
+        R: Default,
+        MR: MergeResult<R>,
+    {
+        let (mut done, mut last_success) = self.progress.take().unwrap_or_default();
+        while self.times.is_none_or(|times| done < times) {
+            match tick_child(&mut self.node, payload.clone())? {
+                BehaviorResult::Success(r) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_success, r)
+                    } else {
+                        last_success = r
+                    }
+                    done += 1;
+                }
+                res @ BehaviorResult::Failure(_) => return Ok(res),
+                res @ (BehaviorResult::Running | BehaviorResult::Idle) => {
+                    self.progress = Some((done, last_success));
+                    return Ok(res);
+                }
+            }
+        }
+        Ok(BehaviorResult::Success(last_success))
+    }
+}
+
+impl<Payload, R, F, T, MR> BehaviorNodeBase<Payload, R, F> for RepeatNode<T, R, MR>
+where
+    Payload: Clone,
+    R: Default,
+    T: BehaviorNodeBase<Payload, R, F>,
+    MR: MergeResult<R>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |node, payload| node.try_tick(payload))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.progress = None;
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.progress = None;
+        self.node.reset()
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        let times = match self.times {
+            Some(times) => times.to_string(),
+            None => "forever".to_string(),
+        };
+        let done = self.progress.as_ref().map_or(0, |(done, _)| *done);
+        vec![("times", times), ("done", done.to_string())]
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{BehaviorResult, ResultKind};
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+/// What happened in a tick of a [Tree](crate::Tree), returned by
+/// [Tree::tick_with_report](crate::Tree::tick_with_report).
+///
+/// The built-in control nodes report the results of their children, so a
+/// node appears in the report if it is the root or a child of such a node.
+/// Nodes are addressed by slash-separated child indices, the same as
+/// [Configure](crate::Configure), and the root is the empty path.
+#[derive(Clone, Default, Debug)]
+pub struct TickReport {
+    nodes: Vec<NodeReport>,
+    short_circuits: Vec<String>,
+    orders: Vec<(String, Vec<usize>)>,
+}
+
+/// The result of a node in a [TickReport].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NodeReport {
+    pub path: String,
+    pub kind: ResultKind,
+    pub duration: Duration,
+}
+
+impl TickReport {
+    /// Returns the results of the ticked nodes in the order they completed,
+    /// which means children come before their parents.
+    ///
+    /// A node that was ticked more than once in the tick appears as many times.
+    pub fn nodes(&self) -> &[NodeReport] {
+        &self.nodes
+    }
+
+    /// Returns the last result of the node at `path`, or `None` if it was not ticked.
+    pub fn status(&self, path: &str) -> Option<ResultKind> {
+        self.find(path).last().map(|node| node.kind)
+    }
+
+    /// Returns the total time spent in the node at `path`, including its descendants.
+    pub fn duration(&self, path: &str) -> Option<Duration> {
+        let mut nodes = self.find(path).peekable();
+        nodes.peek()?;
+        Some(nodes.map(|node| node.duration).sum())
+    }
+
+    /// Returns the paths of the children at which control nodes stopped
+    /// ticking the rest of their children.
+    pub fn short_circuits(&self) -> &[String] {
+        &self.short_circuits
+    }
+
+    /// Returns the order of the children chosen in this tick by the randomized
+    /// control node at `path`, like [RandomSequenceNode](crate::RandomSequenceNode),
+    /// or `None` if it did not choose one.
+    pub fn order(&self, path: &str) -> Option<&[usize]> {
+        self.orders
+            .iter()
+            .rev()
+            .find(|(p, _)| p == path)
+            .map(|(_, order)| order.as_slice())
+    }
+
+    fn find<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a NodeReport> {
+        self.nodes.iter().filter(move |node| node.path == path)
+    }
+}
+
+struct Recorder {
+    path: Vec<usize>,
+    report: TickReport,
+}
+
+impl Recorder {
+    fn path_string(&self, last: Option<usize>) -> String {
+        self.path
+            .iter()
+            .chain(last.as_ref())
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Runs `tick` for the root of a tree, collecting the reports of the control nodes in it.
+pub(crate) fn record<R, F>(
+    tick: impl FnOnce() -> BehaviorResult<R, F>,
+) -> (BehaviorResult<R, F>, TickReport) {
+    match record_try(|| Ok::<_, Infallible>(tick())) {
+        (Ok(res), report) => (res, report.unwrap_or_default()),
+        (Err(e), _) => match e {},
+    }
+}
+
+/// Runs `tick` for the root of a tree like [record], without a report if it returns an error.
+pub(crate) fn record_try<R, F, E>(
+    tick: impl FnOnce() -> Result<BehaviorResult<R, F>, E>,
+) -> (Result<BehaviorResult<R, F>, E>, Option<TickReport>) {
+    let recorder = Recorder {
+        path: vec![],
+        report: TickReport::default(),
+    };
+    // Keep the recorder of an outer tree, in case this tree is ticked by a node in it.
+    let outer = RECORDER.with(|cell| cell.borrow_mut().replace(recorder));
+    let start = Instant::now();
+    let res = tick();
+    let duration = start.elapsed();
+    let recorder = RECORDER.with(|cell| std::mem::replace(&mut *cell.borrow_mut(), outer));
+    let Ok(kind) = res.as_ref().map(|res| res.kind()) else {
+        return (res, None);
+    };
+    let mut report = recorder.map(|recorder| recorder.report).unwrap_or_default();
+    report.nodes.push(NodeReport {
+        path: String::new(),
+        kind,
+        duration,
+    });
+    (res, Some(report))
+}
+
+/// Ticks the child at `index` with `tick`, reporting its result if a report is being recorded.
+pub(crate) fn tick_child<R, F, E>(
+    index: usize,
+    tick: impl FnOnce() -> Result<BehaviorResult<R, F>, E>,
+) -> Result<BehaviorResult<R, F>, E> {
+    #[cfg(feature = "tracing")]
+    let tick = move || crate::instrument::tick_child(index, tick);
+    let recording = RECORDER.with(|cell| match &mut *cell.borrow_mut() {
+        Some(recorder) => {
+            recorder.path.push(index);
+            true
+        }
+        None => false,
+    });
+    if !recording {
+        return tick();
+    }
+    // Pops the index even if the child panics, e.g. inside CatchBorrowConflict.
+    let _pop = PopPath;
+    let start = Instant::now();
+    let res = tick();
+    let duration = start.elapsed();
+    if let Ok(res) = &res {
+        RECORDER.with(|cell| {
+            if let Some(recorder) = &mut *cell.borrow_mut() {
+                let path = recorder.path_string(None);
+                recorder.report.nodes.push(NodeReport {
+                    path,
+                    kind: res.kind(),
+                    duration,
+                });
+            }
+        });
+    }
+    res
+}
+
+/// Reports the result of the child at `index` that was ticked outside of
+/// [tick_child], e.g. on another thread, where the recorder is not available.
+#[cfg(feature = "rayon")]
+pub(crate) fn child_ticked(index: usize, kind: ResultKind, duration: Duration) {
+    RECORDER.with(|cell| {
+        if let Some(recorder) = &mut *cell.borrow_mut() {
+            let path = recorder.path_string(Some(index));
+            recorder.report.nodes.push(NodeReport {
+                path,
+                kind,
+                duration,
+            });
+        }
+    });
+}
+
+struct PopPath;
+
+impl Drop for PopPath {
+    fn drop(&mut self) {
+        RECORDER.with(|cell| {
+            if let Some(recorder) = &mut *cell.borrow_mut() {
+                recorder.path.pop();
+            }
+        });
+    }
+}
+
+/// Reports that a control node stopped ticking its children at the child at `index`.
+pub(crate) fn short_circuit(index: usize) {
+    RECORDER.with(|cell| {
+        if let Some(recorder) = &mut *cell.borrow_mut() {
+            let path = recorder.path_string(Some(index));
+            recorder.report.short_circuits.push(path);
+        }
+    });
+}
+
+/// Reports that the node being ticked chose to tick its children in `order`.
+pub(crate) fn order(order: &[usize]) {
+    RECORDER.with(|cell| {
+        if let Some(recorder) = &mut *cell.borrow_mut() {
+            let path = recorder.path_string(None);
+            recorder.report.orders.push((path, order.to_vec()));
+        }
+    });
+}
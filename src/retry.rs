@@ -0,0 +1,278 @@
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::any::Any;
+use std::convert::Infallible;
+
+/// A decorator that ticks the child node again when it fails, up to a
+/// number of attempts in total.
+///
+/// It returns the success of the child as it is, or the last failure if all
+/// attempts failed.
+/// If the child returns `Running`, this node returns `Running` as well and
+/// keeps counting the attempts in the following ticks.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// struct OpenDoor {
+///     stuck: u32,
+/// }
+///
+/// impl BehaviorNodeBase<(), &'static str, u32> for OpenDoor {
+///     fn tick(&mut self, _: ()) -> BehaviorResult<&'static str, u32> {
+///         if 0 < self.stuck {
+///             self.stuck -= 1;
+///             BehaviorResult::Failure(self.stuck)
+///         } else {
+///             BehaviorResult::Success("opened")
+///         }
+///     }
+/// }
+///
+/// let mut open = RetryNode::new(3, OpenDoor { stuck: 2 });
+/// assert_eq!(open.tick(()), BehaviorResult::Success("opened"));
+///
+/// let mut open = RetryNode::new(3, OpenDoor { stuck: 5 });
+/// assert_eq!(open.tick(()), BehaviorResult::Failure(2));
+/// ```
+pub struct RetryNode<T> {
+    node: T,
+    max_attempts: usize,
+    /// The number of failed attempts so far, if the child was running in the last tick.
+    failed: usize,
+}
+
+impl<T> RetryNode<T> {
+    /// Constructs a [RetryNode] that ticks `node` up to `max_attempts` times.
+    ///
+    /// Panics if `max_attempts` is 0.
+    pub fn new(max_attempts: usize, node: T) -> Self {
+        assert!(0 < max_attempts, "max_attempts must be positive");
+        Self {
+            node,
+            max_attempts,
+            failed: 0,
+        }
+    }
+
+    fn run<Payload, R, F, E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(&mut T, Payload) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        Payload: Clone,
+    {
+        loop {
+            match tick_child(&mut self.node, payload.clone()) {
+                Ok(BehaviorResult::Failure(f)) => {
+                    self.failed += 1;
+                    if self.max_attempts <= self.failed {
+                        self.failed = 0;
+                        return Ok(BehaviorResult::Failure(f));
+                    }
+                }
+                res @ Ok(BehaviorResult::Running | BehaviorResult::Idle) => return res,
+                res => {
+                    self.failed = 0;
+                    return res;
+                }
+            }
+        }
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for RetryNode<T>
+where
+    Payload: Clone,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |node, payload| node.try_tick(payload))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.failed = 0;
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.failed = 0;
+        self.node.reset()
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("attempts", self.max_attempts.to_string()),
+            ("used", self.failed.to_string()),
+        ]
+    }
+}
+
+/// A retry decorator over alternative child nodes, which moves on to the next
+/// child on each failed attempt, going back to the first after the last one.
+///
+/// The attempts are shared by all children, e.g. "try path A, then B, then C,
+/// then A again, 5 attempts in total".
+/// As with [RetryNode], it returns the success of a child as it is or the
+/// last failure, and a child that returns `Running` is ticked again in the
+/// next tick.
+/// The next activation after a success or a failure starts over from the
+/// first child.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysFailure;
+/// let mut tree = RotatingRetryNode::new(
+///     [
+///         Box::new(AlwaysFailure("path A is blocked")) as Box<dyn BehaviorNodeBase<(), &str, &str>>,
+///         Box::new(AlwaysFailure("path B is blocked")),
+///     ],
+///     3,
+/// );
+/// assert_eq!(tree.tick(()), BehaviorResult::Failure("path A is blocked"));
+/// ```
+pub struct RotatingRetryNode<Payload, R, F> {
+    children: Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>,
+    max_attempts: usize,
+    /// The index of the child to tick next.
+    current: usize,
+    failed: usize,
+}
+
+impl<Payload, R, F> RotatingRetryNode<Payload, R, F> {
+    /// Constructs a [RotatingRetryNode] that ticks `children` in turn up to
+    /// `max_attempts` times in total.
+    ///
+    /// Panics if `max_attempts` is 0.
+    pub fn new<T>(children: T, max_attempts: usize) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        assert!(0 < max_attempts, "max_attempts must be positive");
+        Self {
+            children: children.into(),
+            max_attempts,
+            current: 0,
+            failed: 0,
+        }
+    }
+
+    fn restart(&mut self) {
+        self.current = 0;
+        self.failed = 0;
+    }
+
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut dyn BehaviorNodeBase<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        Payload: Clone,
+        F: Default,
+    {
+        if self.children.is_empty() {
+            return Ok(BehaviorResult::Failure(F::default()));
+        }
+        loop {
+            let i = self.current;
+            match report::tick_child(i, || {
+                tick_child(i, self.children[i].as_mut(), payload.clone())
+            }) {
+                Ok(BehaviorResult::Failure(f)) => {
+                    self.failed += 1;
+                    if self.max_attempts <= self.failed {
+                        self.restart();
+                        return Ok(BehaviorResult::Failure(f));
+                    }
+                    self.current = (i + 1) % self.children.len();
+                }
+                res @ Ok(BehaviorResult::Running | BehaviorResult::Idle) => return res,
+                res => {
+                    self.restart();
+                    return res;
+                }
+            }
+        }
+    }
+}
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for RotatingRetryNode<Payload, R, F>
+where
+    Payload: Clone,
+    F: Default,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.restart();
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.restart();
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("attempts", self.max_attempts.to_string()),
+            ("used", self.failed.to_string()),
+            ("current", self.current.to_string()),
+        ]
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children
+            .iter_mut()
+            .map(|node| node.as_mut() as _)
+            .collect()
+    }
+
+    fn push_child(
+        &mut self,
+        child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+    where
+        Self: 'static,
+    {
+        self.children.push(child);
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use crate::composite::children_vec_methods;
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::any::Any;
+use std::convert::Infallible;
+
+/// A control node that ticks one child per activation, moving on to the
+/// next child each time, e.g. to visit patrol points in turn.
+///
+/// It returns the result of the current child as it is.
+/// A running child is ticked again in the next tick, and the next child is
+/// selected once it succeeds or fails, going back to the first after the
+/// last one.
+/// Halting this node keeps the position, so an interrupted patrol resumes at
+/// the same point.
+/// Without children, it returns `Failure(F::default())`.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// let mut patrol = RoundRobinNode::new([
+///     Box::new(AlwaysSuccess("gate")) as Box<dyn BehaviorNodeBase<(), &str, ()>>,
+///     Box::new(AlwaysSuccess("tower")),
+/// ]);
+/// assert_eq!(patrol.tick(()), BehaviorResult::Success("gate"));
+/// assert_eq!(patrol.tick(()), BehaviorResult::Success("tower"));
+/// assert_eq!(patrol.tick(()), BehaviorResult::Success("gate"));
+/// ```
+pub struct RoundRobinNode<Payload, R, F> {
+    children: Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>,
+    /// The index of the child to tick next.
+    current: usize,
+}
+
+impl<Payload, R, F> RoundRobinNode<Payload, R, F> {
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<Box<dyn BehaviorNodeBase<Payload, R, F>>>>,
+    {
+        Self {
+            children: children.into(),
+            current: 0,
+        }
+    }
+
+    children_vec_methods!(children, Box<dyn BehaviorNodeBase<Payload, R, F>>);
+
+    /// Returns the index of the child to be ticked in the next tick.
+    pub fn position(&self) -> usize {
+        self.current
+    }
+
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut dyn BehaviorNodeBase<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        F: Default,
+    {
+        if self.children.is_empty() {
+            return Ok(BehaviorResult::Failure(F::default()));
+        }
+        let i = self.current;
+        let res = report::tick_child(i, || tick_child(i, self.children[i].as_mut(), payload))?;
+        self.current = match res {
+            BehaviorResult::Success(_) | BehaviorResult::Failure(_) => {
+                (i + 1) % self.children.len()
+            }
+            _ => i,
+        };
+        Ok(res)
+    }
+}
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for RoundRobinNode<Payload, R, F>
+where
+    F: Default,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.current = 0;
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![("position", self.current.to_string())]
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children
+            .iter_mut()
+            .map(|node| node.as_mut() as _)
+            .collect()
+    }
+
+    fn push_child(
+        &mut self,
+        child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+    where
+        Self: 'static,
+    {
+        self.children.push(child);
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+
+/// A decorator that ticks the child node until it completes once, then
+/// returns the same result forever without ticking the child.
+///
+/// It is useful for one-shot initialization branches.
+/// Call [RunOnceNode::reset] to run the child again.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::Cell;
+/// struct Initialize;
+///
+/// impl BehaviorNodeBase<&Cell<u32>, (), ()> for Initialize {
+///     fn tick(&mut self, count: &Cell<u32>) -> BehaviorResult<(), ()> {
+///         count.set(count.get() + 1);
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// let count = Cell::new(0);
+/// let mut init = RunOnceNode::new(Initialize);
+/// assert_eq!(init.tick(&count), BehaviorResult::Success(()));
+/// assert_eq!(init.tick(&count), BehaviorResult::Success(()));
+/// assert_eq!(count.get(), 1);
+///
+/// init.reset();
+/// init.tick(&count);
+/// assert_eq!(count.get(), 2);
+/// ```
+pub struct RunOnceNode<T, R, F> {
+    node: T,
+    result: Option<BehaviorResult<R, F>>,
+}
+
+impl<T, R, F> RunOnceNode<T, R, F> {
+    pub fn new(node: T) -> Self {
+        Self { node, result: None }
+    }
+
+    /// Returns the stored result if the child has completed.
+    pub fn result(&self) -> Option<&BehaviorResult<R, F>> {
+        self.result.as_ref()
+    }
+
+    /// Discards the stored result, so that the child is ticked again in the next tick.
+    pub fn reset(&mut self) {
+        self.result = None;
+    }
+
+    fn after_tick(&mut self, res: &BehaviorResult<R, F>)
+    where
+        R: Clone,
+        F: Clone,
+    {
+        if matches!(res, BehaviorResult::Success(_) | BehaviorResult::Failure(_)) {
+            self.result = Some(res.clone());
+        }
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for RunOnceNode<T, R, F>
+where
+    R: Clone,
+    F: Clone,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        if let Some(res) = &self.result {
+            return res.clone();
+        }
+        let res = self.node.tick(payload);
+        self.after_tick(&res);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        if let Some(res) = &self.result {
+            return Ok(res.clone());
+        }
+        let res = self.node.try_tick(payload)?;
+        self.after_tick(&res);
+        Ok(res)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.result = None;
+        self.node.reset()
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    BehaviorNodeBase, BehaviorResult, Blackboard, BlackboardScope, BtError, HaltReport,
+    HasBlackboard,
+};
+use std::any::Any;
+use std::cell::RefCell;
+
+/// A decorator that gives the child node its own [BlackboardScope], so that
+/// the entries written by the subtree are local to this instance.
+///
+/// The subtree still reads the entries of the enclosing scopes that it does
+/// not have, and keys given to [ScopeNode::share] are written to the
+/// enclosing scope, e.g. the result of the subtree.
+/// The local entries are kept across ticks, and cleared by
+/// [BehaviorNodeBase::reset].
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use std::cell::RefCell;
+/// struct Count;
+///
+/// impl<'a> BehaviorNodeBase<&'a RefCell<Blackboard>, (), ()> for Count {
+///     fn tick(&mut self, bb: &'a RefCell<Blackboard>) -> BehaviorResult<(), ()> {
+///         let mut bb = bb.borrow_mut();
+///         let count = bb.get::<i32>("count").copied().unwrap_or(0) + 1;
+///         bb.set("count", count);
+///         bb.set("last", count);
+///         BehaviorResult::Success(())
+///     }
+/// }
+///
+/// type Node<'a> = Box<dyn BehaviorNodeBase<&'a RefCell<Blackboard>, (), ()>>;
+/// let bb = RefCell::new(Blackboard::new());
+/// let mut tree = SequenceNode::new([
+///     Box::new(ScopeNode::new(Count).share("last")) as Node,
+///     Box::new(ScopeNode::new(Count).share("last")),
+/// ]);
+/// tree.tick(&bb);
+/// tree.tick(&bb);
+/// assert_eq!(bb.borrow().get::<i32>("last"), Some(&2));
+/// assert_eq!(bb.borrow().get::<i32>("count"), None);
+/// ```
+pub struct ScopeNode<T> {
+    node: T,
+    scope: Option<BlackboardScope>,
+}
+
+impl<T> ScopeNode<T> {
+    pub fn new(node: T) -> Self {
+        Self {
+            node,
+            scope: Some(BlackboardScope::new()),
+        }
+    }
+
+    /// Makes writes of `key` in the subtree go to the enclosing scope.
+    pub fn share(mut self, key: impl Into<String>) -> Self {
+        self.scope = self.scope.map(|scope| scope.share(key));
+        self
+    }
+
+    /// Runs `tick` with the scope of this node entered.
+    fn enter<R>(&mut self, blackboard: &RefCell<Blackboard>, tick: impl FnOnce(&mut T) -> R) -> R {
+        let scope = self.scope.take().unwrap_or_default();
+        blackboard.borrow_mut().push_scope(scope);
+        let res = tick(&mut self.node);
+        self.scope = blackboard.borrow_mut().pop_scope();
+        res
+    }
+}
+
+impl<'a, Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for ScopeNode<T>
+where
+    Payload: HasBlackboard<'a>,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        let blackboard = payload.blackboard();
+        self.enter(blackboard, |node| node.tick(payload))
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        let blackboard = payload.blackboard();
+        self.enter(blackboard, |node| node.try_tick(payload))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        if let Some(scope) = &mut self.scope {
+            scope.clear();
+        }
+        self.node.reset()
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+}
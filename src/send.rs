@@ -0,0 +1,271 @@
+use crate::composite::children_vec_methods;
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, MergeResult};
+use std::any::Any;
+use std::convert::Infallible;
+
+/// A child of [SequenceNodeSend] or [FallbackNodeSend], which can be moved
+/// to another thread.
+pub type ChildSend<Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F> + Send>;
+
+/// A sequence whose children are [Send], so that the whole tree can be moved
+/// into a worker thread or an async task.
+///
+/// The children of [SequenceNode](crate::SequenceNode) are boxed trait
+/// objects without the `Send` bound, which makes the tree `!Send` even if all
+/// of the nodes are `Send`.
+/// This node boxes them as [ChildSend] instead, and it is [Send] itself if
+/// the merger function is.
+/// It ticks the children like [SequenceNode](crate::SequenceNode), starting
+/// over from the first child in the next tick after a running child.
+///
+/// ```
+/// # use tiny_behavior_tree::{testing::AlwaysSuccess, *};
+/// let mut tree = SequenceNodeSend::new([
+///     Box::new(AlwaysSuccess(())) as ChildSend<(), (), ()>,
+///     Box::new(FallbackNodeSend::new([
+///         Box::new(AlwaysSuccess(())) as ChildSend<(), (), ()>,
+///     ])),
+/// ]);
+/// let res = std::thread::spawn(move || tree.tick(())).join().unwrap();
+/// assert_eq!(res, BehaviorResult::Success(()));
+/// ```
+pub struct SequenceNodeSend<Payload, R, F, MR> {
+    children: Vec<ChildSend<Payload, R, F>>,
+    merge_result: Option<MR>,
+}
+
+impl<Payload, R, F> SequenceNodeSend<Payload, R, F, fn(&mut R, R)> {
+    /// Constructs a [SequenceNodeSend] with children nodes.
+    ///
+    /// If multiple child nodes return results in `R`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<ChildSend<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: None,
+        }
+    }
+}
+
+impl<Payload, R, F, MR> SequenceNodeSend<Payload, R, F, MR> {
+    /// Constructs a [SequenceNodeSend] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<ChildSend<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: Some(merge_result),
+        }
+    }
+
+    children_vec_methods!(children, ChildSend<Payload, R, F>);
+
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut ChildSend<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        Payload: Clone,
+        R: Default,
+        MR: MergeResult<R>,
+    {
+        let mut last_success = R::default();
+        for (i, child) in self.children.iter_mut().enumerate() {
+            match report::tick_child(i, || tick_child(i, child, payload.clone()))? {
+                BehaviorResult::Success(r) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_success, r)
+                    } else {
+                        last_success = r
+                    }
+                }
+                res @ BehaviorResult::Failure(_) => {
+                    report::short_circuit(i);
+                    return Ok(res);
+                }
+                BehaviorResult::Running => return Ok(BehaviorResult::Running),
+                BehaviorResult::Idle => (),
+            }
+        }
+        Ok(BehaviorResult::Success(last_success))
+    }
+}
+
+impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F> for SequenceNodeSend<Payload, R, F, MR>
+where
+    Payload: Clone,
+    R: Default,
+    MR: MergeResult<R>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children
+            .iter_mut()
+            .map(|node| node.as_mut() as _)
+            .collect()
+    }
+}
+
+/// A fallback whose children are [Send], the counterpart of [SequenceNodeSend].
+///
+/// It ticks the children like [FallbackNode](crate::FallbackNode), starting
+/// over from the first child in the next tick after a running child.
+pub struct FallbackNodeSend<Payload, R, F, MR> {
+    children: Vec<ChildSend<Payload, R, F>>,
+    merge_result: Option<MR>,
+}
+
+impl<Payload, R, F> FallbackNodeSend<Payload, R, F, fn(&mut F, F)> {
+    /// Constructs a [FallbackNodeSend] with children nodes.
+    ///
+    /// If multiple child nodes return results in `F`, this node will return
+    /// the last one.
+    pub fn new<T>(children: T) -> Self
+    where
+        T: Into<Vec<ChildSend<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: None,
+        }
+    }
+}
+
+impl<Payload, R, F, MR> FallbackNodeSend<Payload, R, F, MR> {
+    /// Constructs a [FallbackNodeSend] with children nodes and a merger funtion.
+    pub fn new_with_merger<T>(children: T, merge_result: MR) -> Self
+    where
+        T: Into<Vec<ChildSend<Payload, R, F>>>,
+    {
+        Self {
+            children: children.into(),
+            merge_result: Some(merge_result),
+        }
+    }
+
+    children_vec_methods!(children, ChildSend<Payload, R, F>);
+
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut ChildSend<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        Payload: Clone,
+        F: Default,
+        MR: MergeResult<F>,
+    {
+        let mut last_failure = F::default();
+        for (i, child) in self.children.iter_mut().enumerate() {
+            match report::tick_child(i, || tick_child(i, child, payload.clone()))? {
+                res @ BehaviorResult::Success(_) => {
+                    report::short_circuit(i);
+                    return Ok(res);
+                }
+                BehaviorResult::Failure(f) => {
+                    if let Some(ref merge_result) = self.merge_result {
+                        merge_result.merge(&mut last_failure, f)
+                    } else {
+                        last_failure = f
+                    }
+                }
+                BehaviorResult::Running => return Ok(BehaviorResult::Running),
+                BehaviorResult::Idle => (),
+            }
+        }
+        Ok(BehaviorResult::Failure(last_failure))
+    }
+}
+
+impl<Payload, R, F, MR> BehaviorNodeBase<Payload, R, F> for FallbackNodeSend<Payload, R, F, MR>
+where
+    Payload: Clone,
+    F: Default,
+    MR: MergeResult<F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        HaltReport::from_children(self.children.iter_mut().map(|node| node.reset()))
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children
+            .iter_mut()
+            .map(|node| node.as_mut() as _)
+            .collect()
+    }
+}
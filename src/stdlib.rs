@@ -0,0 +1,557 @@
+//! A library of generic leaf nodes, enabled by the `stdlib` feature.
+//!
+//! These nodes don't know anything about your application, so a freshly
+//! designed tree can run end-to-end before you implement your own nodes.
+//!
+//! Besides the constant leaves [AlwaysSuccess], [AlwaysFailure] and
+//! [AlwaysRunning], there are plumbing nodes like [SetBlackboard] and
+//! [CompareBlackboard].
+//! Nodes that access the [Blackboard] work with any payload that implements
+//! [BlackboardPayload], such as `&RefCell<Blackboard>` and
+//! [crate::WithBlackboard].
+//!
+//! [NodeFactory::with_stdlib] registers them for the loaders, see [Stdlib].
+pub use crate::emit::EmitEvent;
+pub use crate::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use crate::{
+    BehaviorNodeBase, BehaviorResult, Blackboard, BtError, ChildMeta, Clock, Comparison, DynValue,
+    HaltReport, HasBlackboard, NodeFactory, NodePlugin, ParamError, SystemClock,
+};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+/// A payload that gives access to a [Blackboard].
+///
+/// It is implemented for every payload that implements [HasBlackboard].
+/// Implement it for your payload type to use the blackboard nodes in this module.
+pub trait BlackboardPayload {
+    fn with_blackboard<T>(&self, f: impl FnOnce(&mut Blackboard) -> T) -> T;
+}
+
+impl<'a, P: HasBlackboard<'a>> BlackboardPayload for P {
+    fn with_blackboard<T>(&self, f: impl FnOnce(&mut Blackboard) -> T) -> T {
+        f(&mut self.blackboard().borrow_mut())
+    }
+}
+
+/// Returns `Running` until the given duration has passed since the first tick,
+/// then returns `Success` once and starts over.
+///
+/// Halting it also starts over, so the next tick waits for the whole duration again.
+pub struct Wait<C = SystemClock> {
+    duration: Duration,
+    started: Option<Instant>,
+    clock: C,
+}
+
+impl Wait {
+    pub fn new(duration: Duration) -> Self {
+        Self::with_clock(duration, SystemClock)
+    }
+}
+
+impl<C: Clock> Wait<C> {
+    /// Creates a node that measures the time with `clock`.
+    pub fn with_clock(duration: Duration, clock: C) -> Self {
+        Self {
+            duration,
+            started: None,
+            clock,
+        }
+    }
+}
+
+impl<Payload, R: Default, F, C: Clock> BehaviorNodeBase<Payload, R, F> for Wait<C> {
+    fn tick(&mut self, _payload: Payload) -> BehaviorResult<R, F> {
+        let now = self.clock.now();
+        let started = *self.started.get_or_insert(now);
+        if self.duration <= now - started {
+            self.started = None;
+            BehaviorResult::Success(R::default())
+        } else {
+            BehaviorResult::Running
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.started = None;
+        HaltReport::clean()
+    }
+}
+
+/// Prints a message to stderr and returns `Success`.
+pub struct Log(pub String);
+
+impl<Payload, R: Default, F> BehaviorNodeBase<Payload, R, F> for Log {
+    fn tick(&mut self, _payload: Payload) -> BehaviorResult<R, F> {
+        eprintln!("{}", self.0);
+        BehaviorResult::Success(R::default())
+    }
+}
+
+/// Sets a clone of the given value to the blackboard and returns `Success`.
+pub struct SetBlackboard<T> {
+    key: String,
+    value: T,
+}
+
+impl<T> SetBlackboard<T> {
+    pub fn new(key: impl Into<String>, value: T) -> Self {
+        Self {
+            key: key.into(),
+            value,
+        }
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for SetBlackboard<T>
+where
+    Payload: BlackboardPayload,
+    R: Default,
+    T: Clone + 'static,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        payload.with_blackboard(|bb| bb.set(self.key.as_str(), self.value.clone()));
+        BehaviorResult::Success(R::default())
+    }
+}
+
+/// Returns `Success` if the blackboard entry equals the given value,
+/// otherwise `Failure`, including when the entry is missing or has another type.
+pub struct CompareBlackboard<T> {
+    key: String,
+    value: T,
+}
+
+impl<T> CompareBlackboard<T> {
+    pub fn new(key: impl Into<String>, value: T) -> Self {
+        Self {
+            key: key.into(),
+            value,
+        }
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for CompareBlackboard<T>
+where
+    Payload: BlackboardPayload,
+    R: Default,
+    F: Default,
+    T: PartialEq + 'static,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        let key = self.key.as_str();
+        if payload.with_blackboard(|bb| bb.get::<T>(key) == Some(&self.value)) {
+            BehaviorResult::Success(R::default())
+        } else {
+            BehaviorResult::Failure(F::default())
+        }
+    }
+}
+
+/// A decorator that prints the changes to the blackboard made during each
+/// tick of the child node to stderr, see [Blackboard::diff].
+///
+/// Wrap the root of your tree with it to see what data changed in a tick.
+pub struct LogBlackboardChanges<T>(pub T);
+
+impl<T> LogBlackboardChanges<T> {
+    fn log<Payload: BlackboardPayload, Res>(payload: &Payload, tick: impl FnOnce() -> Res) -> Res {
+        let before = payload.with_blackboard(|bb| bb.snapshot());
+        let res = tick();
+        let after = payload.with_blackboard(|bb| bb.snapshot());
+        for change in Blackboard::diff(&before, &after) {
+            eprintln!("{}", change);
+        }
+        res
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for LogBlackboardChanges<T>
+where
+    Payload: BlackboardPayload + Clone,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        Self::log(&payload.clone(), || self.0.tick(payload))
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        Self::log(&payload.clone(), || self.0.try_tick(payload))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.0.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.0.reset()
+    }
+}
+
+/// A condition over blackboard entries, parsed from an expression like
+/// `door.open == false && alert_level < 3`.
+///
+/// The expression is terms joined by `&&`, where each term is either a
+/// comparison of an entry with a literal, an entry name, which means
+/// `== true`, or an entry name prefixed with `!`, which means `== false`.
+/// Literals are `true`, `false`, numbers and double-quoted strings.
+/// Entries of type `bool`, `i32`, `i64`, `u32`, `f32`, `f64`, `String` and
+/// `&'static str` can be compared, and comparisons with missing entries or
+/// entries of other types don't hold, except for `!=`.
+///
+/// ```
+/// # use tiny_behavior_tree::Blackboard;
+/// # use tiny_behavior_tree::stdlib::GuardExpr;
+/// let guard: GuardExpr = "door.open == false && alert_level < 3".parse().unwrap();
+/// let mut bb = Blackboard::new();
+/// bb.set("door.open", false);
+/// bb.set("alert_level", 1);
+/// assert!(guard.test(&bb));
+/// bb.set("alert_level", 5);
+/// assert!(!guard.test(&bb));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct GuardExpr {
+    terms: Vec<(String, Comparison, DynValue)>,
+}
+
+impl GuardExpr {
+    /// Returns true if all terms of the expression hold.
+    pub fn test(&self, bb: &Blackboard) -> bool {
+        self.terms
+            .iter()
+            .all(|(key, op, rhs)| match entry_value(bb, key) {
+                Some(lhs) => op.test(&lhs, rhs),
+                None => *op == Comparison::Ne,
+            })
+    }
+}
+
+fn entry_value(bb: &Blackboard, key: &str) -> Option<DynValue> {
+    macro_rules! try_types {
+        ($($ty:ty => $conv:expr),*) => {
+            $(
+                if let Some(value) = bb.get::<$ty>(key) {
+                    return Some($conv(value));
+                }
+            )*
+        };
+    }
+    try_types!(
+        bool => |b: &bool| DynValue::Bool(*b),
+        i32 => |i: &i32| DynValue::Int((*i).into()),
+        i64 => |i: &i64| DynValue::Int(*i),
+        u32 => |i: &u32| DynValue::Int((*i).into()),
+        f32 => |f: &f32| DynValue::Float((*f).into()),
+        f64 => |f: &f64| DynValue::Float(*f),
+        String => |s: &String| DynValue::Str(s.clone()),
+        &'static str => |s: &&str| DynValue::Str(s.to_string())
+    );
+    None
+}
+
+/// The error type returned when parsing a [GuardExpr] fails, with the term that could not be parsed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseGuardError(String);
+
+impl fmt::Display for ParseGuardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid guard term \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ParseGuardError {}
+
+impl FromStr for GuardExpr {
+    type Err = ParseGuardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = s
+            .split("&&")
+            .map(|term| {
+                parse_term(term.trim()).ok_or_else(|| ParseGuardError(term.trim().to_owned()))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { terms })
+    }
+}
+
+fn parse_term(term: &str) -> Option<(String, Comparison, DynValue)> {
+    // Longer operators first, so that "<=" is not taken as "<".
+    for op in ["==", "!=", "<=", ">=", "<", ">"] {
+        if let Some((key, literal)) = term.split_once(op) {
+            let key = parse_key(key.trim())?;
+            let value = parse_literal(literal.trim())?;
+            return Some((key, op.parse().ok()?, value));
+        }
+    }
+    match term.strip_prefix('!') {
+        Some(key) => Some((
+            parse_key(key.trim())?,
+            Comparison::Eq,
+            DynValue::Bool(false),
+        )),
+        None => Some((parse_key(term)?, Comparison::Eq, DynValue::Bool(true))),
+    }
+}
+
+fn parse_key(key: &str) -> Option<String> {
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+    valid.then(|| key.to_owned())
+}
+
+fn parse_literal(literal: &str) -> Option<DynValue> {
+    match literal {
+        "true" => return Some(DynValue::Bool(true)),
+        "false" => return Some(DynValue::Bool(false)),
+        _ => (),
+    }
+    if let Some(s) = literal
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        return Some(DynValue::Str(s.to_owned()));
+    }
+    if let Ok(i) = literal.parse() {
+        return Some(DynValue::Int(i));
+    }
+    literal.parse().ok().map(DynValue::Float)
+}
+
+/// A decorator that ticks the child node only while the [GuardExpr] holds,
+/// and fails otherwise.
+///
+/// If the guard stops holding while the child is running, the child is halted.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::stdlib::{AlwaysSuccess, Guard};
+/// # use std::cell::RefCell;
+/// let mut open_door = Guard::new("door.open == false".parse().unwrap(), AlwaysSuccess(()));
+/// let bb = RefCell::new(Blackboard::new());
+/// bb.borrow_mut().set("door.open", true);
+/// let res: BehaviorResult<(), ()> = open_door.tick(&bb);
+/// assert_eq!(res, BehaviorResult::Failure(()));
+/// ```
+pub struct Guard<T> {
+    expr: GuardExpr,
+    node: T,
+    running: bool,
+}
+
+impl<T> Guard<T> {
+    pub fn new(expr: GuardExpr, node: T) -> Self {
+        Self {
+            expr,
+            node,
+            running: false,
+        }
+    }
+
+    /// Returns true if the guard holds, halting the running child otherwise.
+    fn check<Payload: BlackboardPayload, R, F>(&mut self, payload: &Payload) -> bool
+    where
+        T: BehaviorNodeBase<Payload, R, F>,
+    {
+        if payload.with_blackboard(|bb| self.expr.test(bb)) {
+            return true;
+        }
+        if std::mem::take(&mut self.running) {
+            self.node.halt();
+        }
+        false
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for Guard<T>
+where
+    Payload: BlackboardPayload,
+    F: Default,
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        if !self.check(&payload) {
+            return BehaviorResult::Failure(F::default());
+        }
+        let res = self.node.tick(payload);
+        self.running = matches!(res, BehaviorResult::Running);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        if !self.check(&payload) {
+            return Ok(BehaviorResult::Failure(F::default()));
+        }
+        let res = self.node.try_tick(payload)?;
+        self.running = matches!(res, BehaviorResult::Running);
+        Ok(res)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.running = false;
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.running = false;
+        self.node.reset()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.node.children_mut()
+    }
+
+    fn child_meta(&self, index: usize) -> Option<&ChildMeta> {
+        self.node.child_meta(index)
+    }
+
+    fn set_child_meta(&mut self, index: usize, meta: ChildMeta) -> Result<(), ChildMeta> {
+        self.node.set_child_meta(index, meta)
+    }
+}
+
+/// The leaves of this module as a [NodePlugin], registered under the names
+/// of their types with these parameters, so that a definition file can use
+/// them before any custom nodes exist:
+///
+/// * `Wait`: `duration` in seconds.
+/// * `Log`: `message`.
+/// * `SetBlackboard` and `CompareBlackboard`: `key` and `value`, which is
+///   `true`, `false`, a number or a double-quoted string as in [GuardExpr],
+///   or a string as it is otherwise.
+///   `CompareBlackboard` compares the value like a [GuardExpr] does.
+/// * `AlwaysSuccess`, `AlwaysFailure` and `AlwaysRunning`: no parameters.
+/// * `EmitEvent`: `event`, sent as a `String` to the channel given to
+///   [Stdlib::with_events]. It is registered only with a channel.
+#[derive(Clone, Default, Debug)]
+pub struct Stdlib {
+    events: Option<Sender<String>>,
+}
+
+impl Stdlib {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `EmitEvent` as well, sending the events to `sender`.
+    pub fn with_events(sender: Sender<String>) -> Self {
+        Self {
+            events: Some(sender),
+        }
+    }
+}
+
+/// Parses the `value` parameter of the blackboard leaves registered by [Stdlib].
+fn parse_value(value: &str) -> DynValue {
+    parse_literal(value).unwrap_or_else(|| DynValue::Str(value.to_owned()))
+}
+
+impl<Payload, R, F> NodePlugin<Payload, R, F> for Stdlib
+where
+    Payload: BlackboardPayload + 'static,
+    R: Default + Clone + 'static,
+    F: Default + Clone + 'static,
+{
+    fn register(&self, factory: &mut NodeFactory<Payload, R, F>) {
+        factory.register("Wait", |cfg| {
+            let duration = cfg.get("duration")?;
+            Duration::try_from_secs_f64(duration)
+                .map(Wait::new)
+                .map_err(|e| ParamError::invalid("duration", e.to_string()))
+        });
+        factory.register("Log", |cfg| Ok(Log(cfg.get("message")?)));
+        factory.register("SetBlackboard", |cfg| {
+            let key: String = cfg.get("key")?;
+            let node: Box<dyn BehaviorNodeBase<Payload, R, F>> =
+                match parse_value(&cfg.get::<String>("value")?) {
+                    DynValue::Bool(b) => Box::new(SetBlackboard::new(key, b)),
+                    DynValue::Int(i) => Box::new(SetBlackboard::new(key, i)),
+                    DynValue::Float(f) => Box::new(SetBlackboard::new(key, f)),
+                    DynValue::Str(s) => Box::new(SetBlackboard::new(key, s)),
+                };
+            Ok(node)
+        });
+        factory.register("CompareBlackboard", |cfg| {
+            let expr = GuardExpr {
+                terms: vec![(
+                    cfg.get("key")?,
+                    Comparison::Eq,
+                    parse_value(&cfg.get::<String>("value")?),
+                )],
+            };
+            Ok(Guard::new(expr, AlwaysSuccess(R::default())))
+        });
+        factory.register("AlwaysSuccess", |_| Ok(AlwaysSuccess(R::default())));
+        factory.register("AlwaysFailure", |_| Ok(AlwaysFailure(F::default())));
+        factory.register("AlwaysRunning", |_| Ok(AlwaysRunning));
+        if let Some(sender) = &self.events {
+            let sender = sender.clone();
+            factory.register("EmitEvent", move |cfg| {
+                let event: String = cfg.get("event")?;
+                Ok(EmitEvent::new(sender.clone(), move |_| event.clone()))
+            });
+        }
+    }
+}
+
+impl<Payload, R, F> NodeFactory<Payload, R, F>
+where
+    Payload: BlackboardPayload + Clone + 'static,
+    R: Default + Clone + 'static,
+    F: Default + Clone + 'static,
+{
+    /// Creates a factory with the built-in control nodes, see
+    /// [NodeFactory::with_builtins], and the leaves of this module, see [Stdlib].
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::*;
+    /// # use std::cell::RefCell;
+    /// let factory = NodeFactory::<&'static RefCell<Blackboard>, (), ()>::with_stdlib();
+    /// let mut root = factory.create("Sequence", &NodeConfig::new()).unwrap();
+    /// for (name, cfg) in [
+    ///     ("SetBlackboard", NodeConfig::new().with("key", "door").with("value", "open")),
+    ///     ("CompareBlackboard", NodeConfig::new().with("key", "door").with("value", "open")),
+    /// ] {
+    ///     assert!(root.push_child(factory.create(name, &cfg).unwrap()).is_ok());
+    /// }
+    /// let bb: &'static RefCell<Blackboard> = Box::leak(Box::new(RefCell::new(Blackboard::new())));
+    /// assert_eq!(root.tick(bb), BehaviorResult::Success(()));
+    /// assert_eq!(bb.borrow().get::<String>("door").unwrap(), "open");
+    /// ```
+    pub fn with_stdlib() -> Self {
+        let mut factory = Self::with_builtins();
+        factory.install(&Stdlib::new());
+        factory
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Payload, R, F> NodeFactory<Payload, R, F>
+where
+    Payload: BlackboardPayload + 'static,
+    R: 'static,
+    F: Default + 'static,
+{
+    /// Makes the loaders wrap the nodes with the `guard` parameter in a
+    /// [Guard] with the [GuardExpr] in it, e.g. `guard="door.open == false"`
+    /// in a BehaviorTree.CPP file.
+    ///
+    /// The expressions are parsed when the tree is loaded, and the `guard`
+    /// parameter is not given to the constructors.
+    /// Without this, the `guard` parameter of a node is a load error, so that
+    /// a guard is never silently ignored.
+    pub fn enable_guards(&mut self) {
+        self.guard = Some(Box::new(|expr, node| {
+            Ok(Box::new(Guard::new(expr.parse()?, node)) as _)
+        }));
+    }
+}
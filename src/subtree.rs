@@ -0,0 +1,297 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport, Tree};
+use std::any::{type_name, Any};
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+type Node<Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F>>;
+
+/// A subtree that can be instantiated multiple times with different
+/// parameters of type `Params`, e.g. a `GoTo { target, speed }` struct.
+///
+/// The constructor closure builds a new subtree from the parameters for
+/// each instance, so the nodes in it can capture them.
+/// Cloning a template shares the closure.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// struct GoTo {
+///     target: (f32, f32),
+///     speed: f32,
+/// }
+///
+/// type Node = Box<dyn BehaviorNodeBase<(), (f32, f32), ()>>;
+///
+/// let go_to = SubtreeTemplate::new("GoTo", |params: GoTo| {
+///     // A real subtree would move towards the target by the speed in each tick.
+///     Box::new(AlwaysSuccess(params.target)) as Node
+/// });
+/// let mut tree = SequenceNode::new([
+///     go_to.instantiate(GoTo { target: (1., 2.), speed: 2. }),
+///     go_to.instantiate(GoTo { target: (3., 4.), speed: 1. }),
+/// ]);
+/// assert_eq!(tree.tick(()), BehaviorResult::Success((3., 4.)));
+/// ```
+pub struct SubtreeTemplate<Params, Payload, R, F> {
+    name: String,
+    build: Rc<dyn Fn(Params) -> Node<Payload, R, F>>,
+}
+
+impl<Params, Payload, R, F> SubtreeTemplate<Params, Payload, R, F> {
+    pub fn new(
+        name: impl Into<String>,
+        build: impl Fn(Params) -> Node<Payload, R, F> + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            build: Rc::new(build),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Builds a new instance of the subtree with `params`.
+    pub fn instantiate(&self, params: Params) -> Node<Payload, R, F> {
+        (self.build)(params)
+    }
+}
+
+impl<Params, Payload, R, F> Clone for SubtreeTemplate<Params, Payload, R, F> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            build: self.build.clone(),
+        }
+    }
+}
+
+/// The error type returned by [SubtreeLibrary::instantiate].
+#[derive(PartialEq, Debug)]
+pub enum InstantiateError {
+    /// No template was registered with the name.
+    UnknownTemplate(String),
+    /// The template takes parameters of another type, whose name is given.
+    ParamsMismatch {
+        name: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for InstantiateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownTemplate(name) => write!(f, "Unknown subtree template \"{}\"", name),
+            Self::ParamsMismatch { name, expected } => write!(
+                f,
+                "Subtree template \"{}\" takes parameters of type {}",
+                name, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InstantiateError {}
+
+/// A collection of [SubtreeTemplate]s with different parameter types, looked up by name.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// let mut library = SubtreeLibrary::<(), f32, ()>::new();
+/// library.register(SubtreeTemplate::new("Wait", |seconds: f32| {
+///     Box::new(AlwaysSuccess(seconds)) as Box<dyn BehaviorNodeBase<_, _, _>>
+/// }));
+///
+/// let mut wait = library.instantiate("Wait", 1.5_f32).unwrap();
+/// assert_eq!(wait.tick(()), BehaviorResult::Success(1.5));
+/// assert!(library.instantiate("Wait", 1.5_f64).is_err());
+/// ```
+pub struct SubtreeLibrary<Payload, R, F> {
+    templates: HashMap<String, Template>,
+    marker: PhantomData<fn(Payload) -> (R, F)>,
+}
+
+/// A [SubtreeTemplate] with its parameter type erased.
+struct Template {
+    template: Box<dyn Any>,
+    params: &'static str,
+}
+
+impl<Payload, R, F> Default for SubtreeLibrary<Payload, R, F> {
+    fn default() -> Self {
+        Self {
+            templates: HashMap::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Payload: 'static, R: 'static, F: 'static> SubtreeLibrary<Payload, R, F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a template by its name, replacing a template with the same name.
+    pub fn register<Params: 'static>(&mut self, template: SubtreeTemplate<Params, Payload, R, F>) {
+        self.templates.insert(
+            template.name.clone(),
+            Template {
+                template: Box::new(template),
+                params: type_name::<Params>(),
+            },
+        );
+    }
+
+    /// Returns the template with `name` if it takes parameters of type `Params`.
+    pub fn get<Params: 'static>(
+        &self,
+        name: &str,
+    ) -> Result<&SubtreeTemplate<Params, Payload, R, F>, InstantiateError> {
+        let template = self
+            .templates
+            .get(name)
+            .ok_or_else(|| InstantiateError::UnknownTemplate(name.to_owned()))?;
+        template
+            .template
+            .downcast_ref()
+            .ok_or_else(|| InstantiateError::ParamsMismatch {
+                name: name.to_owned(),
+                expected: template.params,
+            })
+    }
+
+    /// Builds a new instance of the template with `name` with `params`.
+    pub fn instantiate<Params: 'static>(
+        &self,
+        name: &str,
+        params: Params,
+    ) -> Result<Node<Payload, R, F>, InstantiateError> {
+        Ok(self.get(name)?.instantiate(params))
+    }
+
+    /// Returns the names of the registered templates in arbitrary order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+}
+
+/// A leaf node that embeds a separately built [Tree] with its own payload
+/// and result types.
+///
+/// The payload of the parent is converted for the subtree by a closure in
+/// each tick, like [MapPayloadNode](crate::MapPayloadNode).
+/// The results of the subtree are passed through as they are, or collapsed
+/// into the result types of the parent by [SubtreeNode::summarize], which
+/// receives the detailed result first, so it can record it, e.g. into a
+/// blackboard or an observer, before it is lost.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysFailure;
+/// // A navigation tree built elsewhere with its own types.
+/// let navigation: Tree<(i32, i32), (), String> =
+///     Tree::new(Box::new(AlwaysFailure("no path to (3, 4)".to_string())));
+///
+/// let mut log = vec![];
+/// let mut go_home = SubtreeNode::new(navigation, |agent: &(i32, i32)| *agent).summarize(
+///     |res: BehaviorResult<(), String>| {
+///         match res {
+///             BehaviorResult::Success(()) => BehaviorResult::Success(()),
+///             BehaviorResult::Failure(reason) => {
+///                 log.push(reason);
+///                 BehaviorResult::Failure(())
+///             }
+///             BehaviorResult::Running => BehaviorResult::Running,
+///             BehaviorResult::Idle => BehaviorResult::Idle,
+///         }
+///     },
+/// );
+/// assert_eq!(go_home.tick(&(3, 4)), BehaviorResult::Failure(()));
+/// drop(go_home);
+/// assert_eq!(log, ["no path to (3, 4)"]);
+/// ```
+pub struct SubtreeNode<SP, SR, SF, M, S> {
+    tree: Tree<SP, SR, SF>,
+    map_payload: M,
+    summarize: S,
+}
+
+type Identity<SR, SF> = fn(BehaviorResult<SR, SF>) -> BehaviorResult<SR, SF>;
+
+impl<SP, SR, SF, M> SubtreeNode<SP, SR, SF, M, Identity<SR, SF>> {
+    /// Constructs a [SubtreeNode] that converts the parent payload with `map_payload`.
+    pub fn new(tree: Tree<SP, SR, SF>, map_payload: M) -> Self {
+        Self {
+            tree,
+            map_payload,
+            summarize: |res| res,
+        }
+    }
+}
+
+impl<SP, SR, SF, M, S> SubtreeNode<SP, SR, SF, M, S> {
+    /// Sets a closure that converts the results of the subtree into the
+    /// result types of the parent.
+    pub fn summarize<R, F, S2>(self, summarize: S2) -> SubtreeNode<SP, SR, SF, M, S2>
+    where
+        S2: FnMut(BehaviorResult<SR, SF>) -> BehaviorResult<R, F>,
+    {
+        SubtreeNode {
+            tree: self.tree,
+            map_payload: self.map_payload,
+            summarize,
+        }
+    }
+
+    pub fn tree(&self) -> &Tree<SP, SR, SF> {
+        &self.tree
+    }
+
+    pub fn tree_mut(&mut self) -> &mut Tree<SP, SR, SF> {
+        &mut self.tree
+    }
+
+    pub fn into_tree(self) -> Tree<SP, SR, SF> {
+        self.tree
+    }
+}
+
+impl<Payload, R, F, SP, SR, SF, M, S> BehaviorNodeBase<Payload, R, F>
+    for SubtreeNode<SP, SR, SF, M, S>
+where
+    M: FnMut(Payload) -> SP,
+    S: FnMut(BehaviorResult<SR, SF>) -> BehaviorResult<R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        let res = self.tree.tick((self.map_payload)(payload));
+        (self.summarize)(res)
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        let res = self.tree.try_tick((self.map_payload)(payload))?;
+        Ok((self.summarize)(res))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.tree.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.tree.reset()
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        self.tree.describe()
+    }
+}
@@ -0,0 +1,120 @@
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::any::Any;
+use std::convert::Infallible;
+
+/// A decorator that can be switched off at runtime, e.g. to disable a
+/// misbehaving branch in production without a redeploy.
+///
+/// While disabled, it returns a fixed result without ticking the child node.
+/// The result is `Failure` with the default value unless set by
+/// [EnableSwitch::with_disabled_result].
+///
+/// The child is the only child of this node in the introspection, so the
+/// wrapped node at path `"1"` is reachable at `"1/0"`.
+/// Use [Configure::set_enabled](crate::Configure::set_enabled) to switch it by path.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+///
+/// let mut tree = FallbackNode::<(), (), (), _>::new([
+///     Box::new(EnableSwitch::new(Box::new(AlwaysSuccess(())) as Node)) as Node,
+/// ]);
+/// assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+///
+/// tree.set_enabled("0", false).unwrap();
+/// assert_eq!(tree.tick(()), BehaviorResult::Failure(()));
+/// ```
+pub struct EnableSwitch<Payload, R, F> {
+    node: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    enabled: bool,
+    disabled_result: BehaviorResult<R, F>,
+}
+
+impl<Payload, R, F: Default> EnableSwitch<Payload, R, F> {
+    /// Wraps `node` with a switch that is enabled.
+    pub fn new(node: Box<dyn BehaviorNodeBase<Payload, R, F>>) -> Self {
+        Self {
+            node,
+            enabled: true,
+            disabled_result: BehaviorResult::Failure(F::default()),
+        }
+    }
+}
+
+impl<Payload, R, F> EnableSwitch<Payload, R, F> {
+    /// Sets the result returned while this node is disabled.
+    pub fn with_disabled_result(mut self, result: BehaviorResult<R, F>) -> Self {
+        self.disabled_result = result;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables this node.
+    ///
+    /// Disabling the node halts the child, in case it was running.
+    pub fn set_enabled(&mut self, enabled: bool) -> HaltReport {
+        let was_enabled = std::mem::replace(&mut self.enabled, enabled);
+        if was_enabled && !enabled {
+            HaltReport::from_children([self.node.halt()])
+        } else {
+            HaltReport::clean()
+        }
+    }
+}
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for EnableSwitch<Payload, R, F>
+where
+    R: Clone,
+    F: Clone,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        if self.enabled {
+            match report::tick_child(0, || Ok::<_, Infallible>(self.node.tick(payload))) {
+                Ok(res) => res,
+                Err(e) => match e {},
+            }
+        } else {
+            self.disabled_result.clone()
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        if self.enabled {
+            report::tick_child(0, || self.node.try_tick(payload).map_err(|e| e.in_child(0)))
+        } else {
+            Ok(self.disabled_result.clone())
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        HaltReport::from_children([self.node.halt()])
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        HaltReport::from_children([self.node.reset()])
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![("enabled", self.enabled.to_string())]
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        vec![self.node.as_mut()]
+    }
+}
@@ -0,0 +1,181 @@
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::any::Any;
+use std::convert::Infallible;
+
+type Child<Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F>>;
+
+/// A control node that ticks the child registered for a key derived from the payload.
+///
+/// The key is computed by a closure in every tick, and the first case with
+/// an equal key is ticked, or the default child if there is no such case.
+/// Without the default child, it fails with the default value.
+/// If the selected child changes while another child is running, the
+/// running child is halted.
+///
+/// The children are the cases in the order they were added, followed by the default child.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// #[derive(PartialEq)]
+/// enum Order {
+///     Attack,
+///     Retreat,
+///     Hold,
+/// }
+///
+/// type Node = Box<dyn BehaviorNodeBase<&'static Order, &'static str, ()>>;
+///
+/// let mut tree = SwitchNode::new(|order: &&Order| match order {
+///     Order::Attack => 0,
+///     Order::Retreat => 1,
+///     Order::Hold => 2,
+/// })
+/// .case(0, Box::new(AlwaysSuccess("charge")) as Node)
+/// .case(1, Box::new(AlwaysSuccess("run")))
+/// .default(Box::new(AlwaysSuccess("wait")));
+/// assert_eq!(tree.tick(&Order::Retreat), BehaviorResult::Success("run"));
+/// assert_eq!(tree.tick(&Order::Hold), BehaviorResult::Success("wait"));
+/// ```
+pub struct SwitchNode<Payload, R, F, K, KF> {
+    key: KF,
+    cases: Vec<(K, Child<Payload, R, F>)>,
+    default: Option<Child<Payload, R, F>>,
+    /// The index of the child that returned `Running` in the last tick.
+    running: Option<usize>,
+}
+
+impl<Payload, R, F, K, KF> SwitchNode<Payload, R, F, K, KF>
+where
+    K: PartialEq,
+    KF: Fn(&Payload) -> K,
+{
+    /// Constructs a [SwitchNode] without children, which selects them by the
+    /// key returned by `key`.
+    pub fn new(key: KF) -> Self {
+        Self {
+            key,
+            cases: vec![],
+            default: None,
+            running: None,
+        }
+    }
+
+    /// Adds a child that is ticked when the key equals `key`.
+    pub fn case(mut self, key: K, child: Child<Payload, R, F>) -> Self {
+        self.cases.push((key, child));
+        self
+    }
+
+    /// Sets the child that is ticked when no case matches the key.
+    pub fn default(mut self, child: Child<Payload, R, F>) -> Self {
+        self.default = Some(child);
+        self
+    }
+
+    fn child(&mut self, index: usize) -> Option<&mut Child<Payload, R, F>> {
+        match self.cases.get_mut(index) {
+            Some((_, child)) => Some(child),
+            None => self.default.as_mut(),
+        }
+    }
+
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut Child<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        F: Default,
+    {
+        let key = (self.key)(&payload);
+        let index = self
+            .cases
+            .iter()
+            .position(|(case, _)| *case == key)
+            .unwrap_or(self.cases.len());
+        if let Some(running) = self.running.take() {
+            if running != index {
+                if let Some(child) = self.child(running) {
+                    child.halt();
+                }
+            }
+        }
+        let Some(child) = self.child(index) else {
+            return Ok(BehaviorResult::Failure(F::default()));
+        };
+        let res = report::tick_child(index, || tick_child(index, child, payload))?;
+        if let BehaviorResult::Running = res {
+            self.running = Some(index);
+        }
+        Ok(res)
+    }
+}
+
+impl<Payload, R, F, K, KF> BehaviorNodeBase<Payload, R, F> for SwitchNode<Payload, R, F, K, KF>
+where
+    F: Default,
+    K: PartialEq,
+    KF: Fn(&Payload) -> K,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.running = None;
+        HaltReport::from_children(
+            self.cases
+                .iter_mut()
+                .map(|(_, child)| child)
+                .chain(self.default.as_mut())
+                .map(|child| child.halt()),
+        )
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.running = None;
+        HaltReport::from_children(
+            self.cases
+                .iter_mut()
+                .map(|(_, child)| child)
+                .chain(self.default.as_mut())
+                .map(|child| child.reset()),
+        )
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.cases
+            .iter_mut()
+            .map(|(_, child)| child)
+            .chain(self.default.as_mut())
+            .map(|child| child.as_mut() as _)
+            .collect()
+    }
+}
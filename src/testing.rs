@@ -0,0 +1,41 @@
+//! Trivial leaf nodes that are handy for testing control nodes.
+//!
+//! ```
+//! # use tiny_behavior_tree::*;
+//! # use tiny_behavior_tree::testing::*;
+//! type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+//!
+//! let mut tree = FallbackNode::<(), (), (), _>::new([
+//!     Box::new(AlwaysFailure(())) as Node,
+//!     Box::new(AlwaysSuccess(())),
+//! ]);
+//! assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+//! ```
+use crate::{BehaviorNodeBase, BehaviorResult};
+
+/// Always returns `Success` with a clone of the given value.
+pub struct AlwaysSuccess<R>(pub R);
+
+impl<Payload, R: Clone, F> BehaviorNodeBase<Payload, R, F> for AlwaysSuccess<R> {
+    fn tick(&mut self, _payload: Payload) -> BehaviorResult<R, F> {
+        BehaviorResult::Success(self.0.clone())
+    }
+}
+
+/// Always returns `Failure` with a clone of the given value.
+pub struct AlwaysFailure<F>(pub F);
+
+impl<Payload, R, F: Clone> BehaviorNodeBase<Payload, R, F> for AlwaysFailure<F> {
+    fn tick(&mut self, _payload: Payload) -> BehaviorResult<R, F> {
+        BehaviorResult::Failure(self.0.clone())
+    }
+}
+
+/// Always returns `Running`.
+pub struct AlwaysRunning;
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for AlwaysRunning {
+    fn tick(&mut self, _payload: Payload) -> BehaviorResult<R, F> {
+        BehaviorResult::Running
+    }
+}
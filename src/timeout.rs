@@ -0,0 +1,99 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, Clock, HaltReport, SystemClock};
+use std::time::{Duration, Instant};
+
+/// A decorator that halts the child node and fails if the child keeps
+/// returning `Running` for longer than the given duration.
+///
+/// The time is measured from the first tick of the child after it completed
+/// or was halted, with the [Clock] given to [TimeoutNode::with_clock].
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysRunning;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let mut node = TimeoutNode::with_clock(Duration::from_secs(5), clock.clone(), AlwaysRunning);
+///
+/// let res: BehaviorResult<(), ()> = node.tick(());
+/// assert_eq!(res, BehaviorResult::Running);
+/// clock.advance(Duration::from_secs(5));
+/// let res: BehaviorResult<(), ()> = node.tick(());
+/// assert_eq!(res, BehaviorResult::Failure(()));
+/// ```
+pub struct TimeoutNode<T, C = SystemClock> {
+    node: T,
+    duration: Duration,
+    clock: C,
+    started: Option<Instant>,
+}
+
+impl<T> TimeoutNode<T> {
+    pub fn new(duration: Duration, node: T) -> Self {
+        Self::with_clock(duration, SystemClock, node)
+    }
+}
+
+impl<T, C: Clock> TimeoutNode<T, C> {
+    pub fn with_clock(duration: Duration, clock: C, node: T) -> Self {
+        Self {
+            node,
+            duration,
+            clock,
+            started: None,
+        }
+    }
+
+    /// Returns true if the time is up, starting the measurement if it has not started.
+    fn expired(&mut self) -> bool {
+        let now = self.clock.now();
+        let started = *self.started.get_or_insert(now);
+        self.duration <= now - started
+    }
+
+    fn after_tick<R, F>(&mut self, res: &BehaviorResult<R, F>) {
+        if !matches!(res, BehaviorResult::Running) {
+            self.started = None;
+        }
+    }
+}
+
+impl<Payload, R, F, T, C> BehaviorNodeBase<Payload, R, F> for TimeoutNode<T, C>
+where
+    F: Default,
+    T: BehaviorNodeBase<Payload, R, F>,
+    C: Clock,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        if self.expired() {
+            self.halt();
+            return BehaviorResult::Failure(F::default());
+        }
+        let res = self.node.tick(payload);
+        self.after_tick(&res);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        if self.expired() {
+            self.halt();
+            return Ok(BehaviorResult::Failure(F::default()));
+        }
+        let res = self.node.try_tick(payload)?;
+        self.after_tick(&res);
+        Ok(res)
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.started = None;
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.started = None;
+        self.node.reset()
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![("duration", format!("{:?}", self.duration))]
+    }
+}
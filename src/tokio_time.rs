@@ -0,0 +1,94 @@
+//! Time-based nodes driven by `tokio::time`, enabled by the `tokio` feature.
+//!
+//! [sleep] creates a leaf node that awaits `tokio::time::sleep` in a
+//! [CoroutineActionNode], so it is woken by the timers of the tokio runtime.
+//! [timeout], [delay] and [cooldown] create the time-based decorators of this
+//! crate, which are still ticked synchronously, but measure the time with
+//! [TokioClock], which follows the clock of the tokio runtime.
+//!
+//! When a tree runs in a tokio task, e.g. ticked in a loop with
+//! `tokio::time::interval`, the timers need no hand-rolled clock, and tests
+//! with paused time (`tokio::time::pause` and `tokio::time::advance`) control
+//! the nodes as well as the rest of the application.
+//!
+//! The nodes are not ticked by the runtime, though: a tree still returns
+//! `Running` until the next tick after a timer fires, so the ticks should
+//! yield to the runtime in between, e.g. by awaiting the interval.
+//!
+//! ```
+//! use tiny_behavior_tree::tokio_time;
+//! # use tiny_behavior_tree::{testing::AlwaysSuccess, *};
+//! # use std::time::Duration;
+//! # #[tokio::main(flavor = "current_thread", start_paused = true)]
+//! # async fn main() {
+//! let mut tree = SequenceNode::new(vec![
+//!     Box::new(tokio_time::sleep(Duration::from_secs(1))) as Box<dyn BehaviorNodeBase<(), (), ()>>,
+//!     Box::new(tokio_time::timeout(Duration::from_secs(5), AlwaysSuccess(()))),
+//! ]);
+//! let mut interval = tokio::time::interval(Duration::from_millis(600));
+//! let mut ticks = 0;
+//! loop {
+//!     interval.tick().await;
+//!     ticks += 1;
+//!     if tree.tick(()) != BehaviorResult::Running {
+//!         break;
+//!     }
+//! }
+//! assert_eq!(ticks, 3);
+//! # }
+//! ```
+use crate::{BehaviorNodeBase, Clock, CoroutineActionNode};
+use std::time::{Duration, Instant};
+
+/// The clock that returns `tokio::time::Instant::now`, which stops while the
+/// time of the runtime is paused.
+///
+/// Outside a runtime, or without paused time, it is the same as [SystemClock](crate::SystemClock).
+#[derive(Clone, Copy, Default, Debug)]
+pub struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+}
+
+/// [crate::TimeoutNode] measured with [TokioClock], created by [timeout].
+pub type TimeoutNode<T> = crate::TimeoutNode<T, TokioClock>;
+
+/// [crate::DelayNode] measured with [TokioClock], created by [delay].
+pub type DelayNode<T> = crate::DelayNode<T, TokioClock>;
+
+/// [crate::Cooldown] measured with [TokioClock], created by [cooldown].
+pub type CooldownNode<T> = crate::Cooldown<T, TokioClock>;
+
+/// Creates a leaf node that returns `Running` until `duration` has passed
+/// since its first tick, awaiting `tokio::time::sleep`, then returns `Success`
+/// once and starts over.
+///
+/// Halting it drops the timer, so the next tick waits for the whole duration again.
+///
+/// # Panics
+///
+/// Panics if it is ticked outside a tokio runtime with the time driver enabled.
+pub fn sleep<Payload, R: Default, F>(duration: Duration) -> impl BehaviorNodeBase<Payload, R, F> {
+    CoroutineActionNode::new(move |_, _| async move {
+        tokio::time::sleep(duration).await;
+        Ok(R::default())
+    })
+}
+
+/// Creates a [crate::TimeoutNode] measured with [TokioClock].
+pub fn timeout<T>(duration: Duration, node: T) -> TimeoutNode<T> {
+    crate::TimeoutNode::with_clock(duration, TokioClock, node)
+}
+
+/// Creates a [crate::DelayNode] measured with [TokioClock].
+pub fn delay<T>(duration: Duration, node: T) -> DelayNode<T> {
+    crate::DelayNode::with_clock(duration, TokioClock, node)
+}
+
+/// Creates a [crate::Cooldown] with its own cooldown measured with [TokioClock].
+pub fn cooldown<T>(duration: Duration, node: T) -> CooldownNode<T> {
+    crate::Cooldown::new(duration, node).with_clock(TokioClock)
+}
@@ -0,0 +1,105 @@
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::time::Instant;
+
+/// A result value with its provenance, i.e. which node produced it and when.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Traced<T> {
+    pub path: String,
+    pub timestamp: Instant,
+    pub value: T,
+}
+
+impl<T> Traced<T> {
+    /// Creates a traced value with the current time as the timestamp.
+    pub fn new(path: impl Into<String>, value: T) -> Self {
+        Self {
+            path: path.into(),
+            timestamp: Instant::now(),
+            value,
+        }
+    }
+}
+
+/// A decorator that wraps the results of the child node in [Traced].
+///
+/// The results become a `Vec<Traced<_>>` with a single element, so that
+/// control nodes can collect them from multiple children with [append_traced].
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::*;
+/// type Node = Box<dyn BehaviorNodeBase<(), Vec<Traced<i32>>, Vec<Traced<()>>>>;
+///
+/// let mut tree = SequenceNode::new_with_merger(
+///     [
+///         Box::new(TracedNode::new("first", AlwaysSuccess(1))) as Node,
+///         Box::new(TracedNode::new("second", AlwaysSuccess(2))),
+///     ],
+///     append_traced,
+/// );
+/// let BehaviorResult::Success(results) = tree.tick(()) else { panic!() };
+/// let paths: Vec<_> = results.iter().map(|r| (r.path.as_str(), r.value)).collect();
+/// assert_eq!(paths, [("first", 1), ("second", 2)]);
+/// ```
+pub struct TracedNode<T> {
+    path: String,
+    node: T,
+}
+
+impl<T> TracedNode<T> {
+    pub fn new(path: impl Into<String>, node: T) -> Self {
+        Self {
+            path: path.into(),
+            node,
+        }
+    }
+}
+
+impl<T> TracedNode<T> {
+    fn trace<R, F>(
+        &self,
+        res: BehaviorResult<R, F>,
+    ) -> BehaviorResult<Vec<Traced<R>>, Vec<Traced<F>>> {
+        match res {
+            BehaviorResult::Idle => BehaviorResult::Idle,
+            BehaviorResult::Running => BehaviorResult::Running,
+            BehaviorResult::Success(r) => {
+                BehaviorResult::Success(vec![Traced::new(self.path.clone(), r)])
+            }
+            BehaviorResult::Failure(f) => {
+                BehaviorResult::Failure(vec![Traced::new(self.path.clone(), f)])
+            }
+        }
+    }
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, Vec<Traced<R>>, Vec<Traced<F>>> for TracedNode<T>
+where
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<Vec<Traced<R>>, Vec<Traced<F>>> {
+        let res = self.node.tick(payload);
+        self.trace(res)
+    }
+
+    fn try_tick(
+        &mut self,
+        payload: Payload,
+    ) -> Result<BehaviorResult<Vec<Traced<R>>, Vec<Traced<F>>>, BtError> {
+        let res = self.node.try_tick(payload)?;
+        Ok(self.trace(res))
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
+
+/// A result merger function that collects traced results in the order they were produced.
+pub fn append_traced<T>(result: &mut Vec<Traced<T>>, mut merge: Vec<Traced<T>>) {
+    result.append(&mut merge);
+}
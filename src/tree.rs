@@ -0,0 +1,316 @@
+use crate::configure::parse_path;
+use crate::report::{self, TickReport};
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, ChildMeta, HaltReport, NodeConfig, Port};
+use crate::{Livelock, LivelockDetector, TreeOutline};
+use std::any::Any;
+use std::fmt;
+
+/// The error type returned by [Tree::from_flat].
+#[derive(PartialEq, Debug)]
+pub enum TreeBuildError {
+    /// The path could not be parsed as slash-separated child indices.
+    InvalidPath(String),
+    /// No node was given for the empty path.
+    MissingRoot,
+    /// More than one node was given for the same path.
+    DuplicatePath(String),
+    /// The parent of the node at the path was not given.
+    MissingParent(String),
+    /// A node with a smaller index than the node at the path was not given.
+    MissingSibling(String),
+    /// The parent of the node at the path cannot have children.
+    NotComposite(String),
+}
+
+impl fmt::Display for TreeBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidPath(path) => write!(f, "Invalid node path \"{}\"", path),
+            Self::MissingRoot => write!(f, "Root node is missing"),
+            Self::DuplicatePath(path) => write!(f, "Duplicate node path \"{}\"", path),
+            Self::MissingParent(path) => write!(f, "Parent of \"{}\" is missing", path),
+            Self::MissingSibling(path) => {
+                write!(f, "Preceding sibling of \"{}\" is missing", path)
+            }
+            Self::NotComposite(path) => {
+                write!(f, "Parent of \"{}\" cannot have children", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TreeBuildError {}
+
+type LivelockCallback = Box<dyn FnMut(&Livelock)>;
+
+/// A whole behavior tree, owning its root node.
+///
+/// It is a node itself that ticks the root node, and it is transparent to
+/// introspection, so a path given to [Configure](crate::Configure) is relative to the root node.
+pub struct Tree<Payload, R, F> {
+    root: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    livelock: Option<(LivelockDetector, LivelockCallback)>,
+    last_report: Option<TickReport>,
+}
+
+impl<Payload, R, F> Tree<Payload, R, F> {
+    pub fn new(root: Box<dyn BehaviorNodeBase<Payload, R, F>>) -> Self {
+        Self {
+            root,
+            livelock: None,
+            last_report: None,
+        }
+    }
+
+    /// Calls `callback` when the tree returns `Running` for more than
+    /// `threshold` ticks without any change, see [LivelockDetector].
+    ///
+    /// The detection runs only in debug builds, where every tick collects a
+    /// [TickReport] for it.
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::*;
+    /// # use tiny_behavior_tree::testing::AlwaysRunning;
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// let stuck = Rc::new(RefCell::new(vec![]));
+    /// let stuck2 = stuck.clone();
+    /// let mut tree: Tree<(), (), ()> = Tree::new(Box::new(AlwaysRunning))
+    ///     .detect_livelock(10, move |livelock| stuck2.borrow_mut().push(livelock.to_string()));
+    /// for _ in 0..20 {
+    ///     tree.tick(());
+    /// }
+    /// # #[cfg(debug_assertions)]
+    /// assert_eq!(*stuck.borrow(), ["No progress in 11 ticks, running nodes: \"\""]);
+    /// ```
+    pub fn detect_livelock(
+        mut self,
+        threshold: usize,
+        callback: impl FnMut(&Livelock) + 'static,
+    ) -> Self {
+        self.livelock = Some((LivelockDetector::new(threshold), Box::new(callback)));
+        self
+    }
+
+    pub fn root(&self) -> &dyn BehaviorNodeBase<Payload, R, F> {
+        self.root.as_ref()
+    }
+
+    pub fn root_mut(&mut self) -> &mut dyn BehaviorNodeBase<Payload, R, F> {
+        self.root.as_mut()
+    }
+
+    pub fn into_root(self) -> Box<dyn BehaviorNodeBase<Payload, R, F>> {
+        self.root
+    }
+
+    /// Ticks the root node, collecting the results and durations of the nodes
+    /// and where control nodes short-circuited, see [TickReport].
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::*;
+    /// # use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+    /// type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+    ///
+    /// let mut tree = Tree::new(Box::new(FallbackNode::new([
+    ///     Box::new(AlwaysFailure(())) as Node,
+    ///     Box::new(AlwaysSuccess(())),
+    ///     Box::new(AlwaysSuccess(())),
+    /// ])));
+    /// let (res, report) = tree.tick_with_report(());
+    /// assert_eq!(res, BehaviorResult::Success(()));
+    /// assert_eq!(report.status("0"), Some(ResultKind::Failure));
+    /// assert_eq!(report.status("2"), None);
+    /// assert_eq!(report.short_circuits(), ["1"]);
+    /// ```
+    pub fn tick_with_report(&mut self, payload: Payload) -> (BehaviorResult<R, F>, TickReport) {
+        let (res, report) = report::record(|| self.root.tick(payload));
+        self.observe(&report);
+        self.last_report = Some(report.clone());
+        (res, report)
+    }
+
+    /// Returns the report of the last [Tree::tick_with_report].
+    pub fn last_report(&self) -> Option<&TickReport> {
+        self.last_report.as_ref()
+    }
+
+    fn observe(&mut self, report: &TickReport) {
+        if let Some((detector, callback)) = &mut self.livelock {
+            if let Some(livelock) = detector.observe(report) {
+                callback(&livelock);
+            }
+        }
+    }
+
+    /// Returns true if ticks should be recorded for the livelock detection.
+    fn observing(&self) -> bool {
+        cfg!(debug_assertions) && self.livelock.is_some()
+    }
+}
+
+impl<Payload: 'static, R: 'static, F: 'static> Tree<Payload, R, F> {
+    /// Returns the hierarchy of the nodes with the statuses in the last
+    /// [Tree::tick_with_report], see [TreeOutline].
+    pub fn outline(&mut self) -> TreeOutline {
+        TreeOutline::new(self.root.as_mut(), self.last_report.as_ref())
+    }
+
+    /// Prints the [Tree::outline] to stderr.
+    pub fn print_tree(&mut self) {
+        eprint!("{}", self.outline());
+    }
+
+    /// Assembles a tree from a flat list of nodes addressed by paths.
+    ///
+    /// A path is slash-separated child indices, like the ones accepted by
+    /// [Configure](crate::Configure), and the empty path is the root.
+    /// The nodes can be given in any order, but every path needs its parent
+    /// and preceding siblings, and the parents need to be control nodes that
+    /// accept [BehaviorNodeBase::push_child].
+    ///
+    /// ```
+    /// # use tiny_behavior_tree::*;
+    /// struct Succeed;
+    ///
+    /// impl BehaviorNodeBase<(), (), ()> for Succeed {
+    ///     fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+    ///         BehaviorResult::Success(())
+    ///     }
+    /// }
+    ///
+    /// type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+    ///
+    /// let mut tree = Tree::from_flat(vec![
+    ///     ("0", Box::new(Succeed) as Node),
+    ///     ("", Box::new(SequenceNode::new(vec![]))),
+    ///     ("1", Box::new(Succeed)),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    /// ```
+    pub fn from_flat<P, I>(nodes: I) -> Result<Self, TreeBuildError>
+    where
+        P: AsRef<str>,
+        I: IntoIterator<Item = (P, Box<dyn BehaviorNodeBase<Payload, R, F>>)>,
+    {
+        let mut nodes = nodes
+            .into_iter()
+            .map(|(path, node)| {
+                let path = path.as_ref();
+                parse_path(path)
+                    .map(|indices| (indices, path.to_owned(), node))
+                    .ok_or_else(|| TreeBuildError::InvalidPath(path.to_owned()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Sorting by indices puts parents before children and siblings in order.
+        nodes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut nodes = nodes.into_iter();
+        let mut root = match nodes.next() {
+            Some((indices, _, node)) if indices.is_empty() => node,
+            _ => return Err(TreeBuildError::MissingRoot),
+        };
+
+        for (indices, path, node) in nodes {
+            let (&index, parent_indices) = indices.split_last().unwrap();
+            let mut parent: &mut dyn BehaviorNodeBase<Payload, R, F> = root.as_mut();
+            for &i in parent_indices {
+                parent = match parent.children_mut().into_iter().nth(i) {
+                    Some(child) => child,
+                    None => return Err(TreeBuildError::MissingParent(path)),
+                };
+            }
+            let num_children = parent.children_mut().len();
+            if index < num_children {
+                return Err(TreeBuildError::DuplicatePath(path));
+            } else if num_children < index {
+                return Err(TreeBuildError::MissingSibling(path));
+            }
+            if parent.push_child(node).is_err() {
+                return Err(TreeBuildError::NotComposite(path));
+            }
+        }
+
+        Ok(Self::new(root))
+    }
+}
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for Tree<Payload, R, F> {
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        if !self.observing() {
+            return self.root.tick(payload);
+        }
+        self.tick_with_report(payload).0
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        if !self.observing() {
+            return self.root.try_tick(payload);
+        }
+        let (res, report) = report::record_try(|| self.root.try_tick(payload));
+        if let Some(report) = report {
+            self.observe(&report);
+        }
+        res
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        if let Some((detector, _)) = &mut self.livelock {
+            detector.reset();
+        }
+        self.root.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        if let Some((detector, _)) = &mut self.livelock {
+            detector.reset();
+        }
+        self.last_report = None;
+        self.root.reset()
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        self.root.as_any_mut()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.root.children_mut()
+    }
+
+    fn push_child(
+        &mut self,
+        child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+    where
+        Self: 'static,
+    {
+        self.root.push_child(child)
+    }
+
+    fn child_meta(&self, index: usize) -> Option<&ChildMeta> {
+        self.root.child_meta(index)
+    }
+
+    fn set_child_meta(&mut self, index: usize, meta: ChildMeta) -> Result<(), ChildMeta> {
+        self.root.set_child_meta(index, meta)
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        self.root.describe()
+    }
+
+    fn ports(&self) -> Vec<Port> {
+        self.root.ports()
+    }
+
+    fn definition(&self) -> Option<(&str, &NodeConfig)> {
+        self.root.definition()
+    }
+}
@@ -0,0 +1,189 @@
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::any::Any;
+use std::convert::Infallible;
+
+type Child<Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F>>;
+type Score<Payload> = Box<dyn Fn(&Payload) -> f64>;
+
+/// A control node that ticks the child with the highest score, which is
+/// computed from the payload for every child in every tick.
+///
+/// This brings utility AI into a tree: the scores pick an option, and the
+/// subtree of the option carries it out.
+/// The first child wins a tie, and a child with a NaN score is never selected.
+/// If the selected child changes while another child is running, the
+/// running child is halted.
+/// Without children, it fails with the default value.
+///
+/// Scores that are close to each other can make the selection switch back
+/// and forth every tick.
+/// [UtilitySelectorNode::with_hysteresis] keeps the last selected child
+/// until another child scores higher by a margin.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysSuccess;
+/// struct Npc {
+///     hunger: f64,
+///     fatigue: f64,
+/// }
+///
+/// type Node = Box<dyn BehaviorNodeBase<&'static Npc, &'static str, ()>>;
+///
+/// let mut tree = UtilitySelectorNode::new()
+///     .child(|npc: &&Npc| npc.hunger, Box::new(AlwaysSuccess("eat")) as Node)
+///     .child(|npc: &&Npc| npc.fatigue, Box::new(AlwaysSuccess("sleep")));
+/// let npc = &Npc { hunger: 0.3, fatigue: 0.8 };
+/// assert_eq!(tree.tick(npc), BehaviorResult::Success("sleep"));
+/// ```
+pub struct UtilitySelectorNode<Payload, R, F> {
+    children: Vec<(Score<Payload>, Child<Payload, R, F>)>,
+    hysteresis: f64,
+    /// The index of the child selected in the last tick.
+    selected: Option<usize>,
+    /// The index of the child that returned `Running` in the last tick.
+    running: Option<usize>,
+}
+
+impl<Payload, R, F> Default for UtilitySelectorNode<Payload, R, F> {
+    fn default() -> Self {
+        Self {
+            children: vec![],
+            hysteresis: 0.,
+            selected: None,
+            running: None,
+        }
+    }
+}
+
+impl<Payload, R, F> UtilitySelectorNode<Payload, R, F> {
+    /// Constructs a [UtilitySelectorNode] without children.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a child with its scoring function.
+    pub fn child(
+        mut self,
+        score: impl Fn(&Payload) -> f64 + 'static,
+        child: Child<Payload, R, F>,
+    ) -> Self {
+        self.children.push((Box::new(score), child));
+        self
+    }
+
+    /// Keeps the last selected child unless another child scores higher than it by more than `margin`.
+    pub fn with_hysteresis(mut self, margin: f64) -> Self {
+        self.hysteresis = margin;
+        self
+    }
+
+    /// Returns the index of the child selected in the last tick.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    fn select(&self, payload: &Payload) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (i, (score, _)) in self.children.iter().enumerate() {
+            let mut score = score(payload);
+            if Some(i) == self.selected {
+                score += self.hysteresis;
+            }
+            if score.is_nan() {
+                continue;
+            }
+            if best.is_none_or(|(_, best)| best < score) {
+                best = Some((i, score));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut Child<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        F: Default,
+    {
+        let selected = self.select(&payload);
+        if let Some(running) = self.running.take() {
+            if Some(running) != selected {
+                self.children[running].1.halt();
+            }
+        }
+        self.selected = selected;
+        let Some(index) = selected else {
+            return Ok(BehaviorResult::Failure(F::default()));
+        };
+        let child = &mut self.children[index].1;
+        let res = report::tick_child(index, || tick_child(index, child, payload))?;
+        if let BehaviorResult::Running = res {
+            self.running = Some(index);
+        }
+        Ok(res)
+    }
+}
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for UtilitySelectorNode<Payload, R, F>
+where
+    F: Default,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.selected = None;
+        self.running = None;
+        HaltReport::from_children(self.children.iter_mut().map(|(_, child)| child.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.selected = None;
+        self.running = None;
+        HaltReport::from_children(self.children.iter_mut().map(|(_, child)| child.reset()))
+    }
+
+    fn describe(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("hysteresis", self.hysteresis.to_string()),
+            ("selected", format!("{:?}", self.selected)),
+        ]
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children
+            .iter_mut()
+            .map(|(_, child)| child.as_mut() as _)
+            .collect()
+    }
+}
@@ -0,0 +1,157 @@
+//! Detection of stuck nodes, enabled by the `watchdog` feature.
+//!
+//! A node that blocks in its tick, e.g. a leaf waiting for IO, freezes the
+//! whole tree without any notice.
+//! [Watchdog] runs a thread that watches nodes wrapped by [Watchdog::watch]
+//! and reports those whose tick takes longer than a limit.
+//!
+//! ```
+//! use tiny_behavior_tree::watchdog::Watchdog;
+//! # use tiny_behavior_tree::*;
+//! # use std::sync::mpsc::channel;
+//! # use std::time::Duration;
+//! struct ReadSensor;
+//!
+//! impl BehaviorNodeBase<(), (), ()> for ReadSensor {
+//!     fn tick(&mut self, _payload: ()) -> BehaviorResult<(), ()> {
+//!         std::thread::sleep(Duration::from_millis(100));
+//!         BehaviorResult::Success(())
+//!     }
+//! }
+//!
+//! let (sender, receiver) = channel();
+//! let watchdog = Watchdog::new(Duration::from_millis(20), move |path: &str, _elapsed| {
+//!     sender.send(path.to_owned()).unwrap();
+//! });
+//! let mut node = watchdog.watch("0/1", ReadSensor);
+//! node.tick(());
+//! assert_eq!(receiver.recv().unwrap(), "0/1");
+//! ```
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+struct Slot {
+    path: String,
+    /// The time the current tick started and whether it has been reported.
+    ticking: Mutex<Option<(Instant, bool)>>,
+}
+
+impl Slot {
+    fn set(&self, ticking: Option<(Instant, bool)>) {
+        *self.ticking.lock().unwrap_or_else(PoisonError::into_inner) = ticking;
+    }
+}
+
+/// A thread that reports nodes whose tick exceeds a time limit.
+///
+/// The callback is called from the watchdog thread with the path given to
+/// [Watchdog::watch] and the time elapsed so far, once per stuck tick.
+/// The thread stops when the watchdog is dropped.
+pub struct Watchdog {
+    slots: Arc<Mutex<Vec<Weak<Slot>>>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    pub fn new(limit: Duration, callback: impl Fn(&str, Duration) + Send + 'static) -> Self {
+        let slots: Arc<Mutex<Vec<Weak<Slot>>>> = Arc::default();
+        let stop = Arc::new(AtomicBool::new(false));
+        let interval = (limit / 4).max(Duration::from_millis(1));
+        let thread = {
+            let slots = slots.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(interval);
+                    let mut stuck = vec![];
+                    slots
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .retain(|slot| {
+                            let Some(slot) = slot.upgrade() else {
+                                return false;
+                            };
+                            let mut ticking =
+                                slot.ticking.lock().unwrap_or_else(PoisonError::into_inner);
+                            if let Some((started, reported)) = ticking.as_mut() {
+                                let elapsed = started.elapsed();
+                                if !*reported && limit <= elapsed {
+                                    *reported = true;
+                                    stuck.push((slot.clone(), elapsed));
+                                }
+                            }
+                            true
+                        });
+                    // Call back without holding the locks
+                    for (slot, elapsed) in stuck {
+                        callback(&slot.path, elapsed);
+                    }
+                }
+            })
+        };
+        Self {
+            slots,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Wraps a node so that this watchdog reports it as `path` when its tick gets stuck.
+    pub fn watch<T>(&self, path: impl Into<String>, node: T) -> Watched<T> {
+        let slot = Arc::new(Slot {
+            path: path.into(),
+            ticking: Mutex::new(None),
+        });
+        self.slots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Arc::downgrade(&slot));
+        Watched { slot, node }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// A decorator created by [Watchdog::watch].
+pub struct Watched<T> {
+    slot: Arc<Slot>,
+    node: T,
+}
+
+impl<Payload, R, F, T> BehaviorNodeBase<Payload, R, F> for Watched<T>
+where
+    T: BehaviorNodeBase<Payload, R, F>,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        self.slot.set(Some((Instant::now(), false)));
+        let res = self.node.tick(payload);
+        self.slot.set(None);
+        res
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.slot.set(Some((Instant::now(), false)));
+        let res = self.node.try_tick(payload);
+        self.slot.set(None);
+        res
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.node.halt()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.node.reset()
+    }
+}
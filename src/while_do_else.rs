@@ -0,0 +1,183 @@
+use crate::report;
+use crate::{BehaviorNodeBase, BehaviorResult, BtError, HaltReport};
+use std::any::Any;
+use std::convert::Infallible;
+use std::iter::once;
+
+type Child<Payload, R, F> = Box<dyn BehaviorNodeBase<Payload, R, F>>;
+
+/// A reactive version of [IfThenElseNode](crate::IfThenElseNode), which
+/// ticks the condition child in every tick.
+///
+/// It ticks the "do" child while the condition succeeds, and the "else"
+/// child while it fails.
+/// When the condition changes, the branch that was running is halted before
+/// the other one is ticked, e.g. "while an enemy is visible, chase it,
+/// otherwise patrol".
+/// It returns the result of the ticked branch, or the failure of the
+/// condition if there is no "else" child.
+/// If the condition returns `Running`, so does this node, without ticking
+/// either branch.
+///
+/// The children are the condition, the "do" child and the "else" child in
+/// this order.
+///
+/// ```
+/// # use tiny_behavior_tree::*;
+/// # use tiny_behavior_tree::testing::AlwaysRunning;
+/// struct SeesEnemy;
+///
+/// impl BehaviorNodeBase<bool, (), ()> for SeesEnemy {
+///     fn tick(&mut self, visible: bool) -> BehaviorResult<(), ()> {
+///         if visible {
+///             BehaviorResult::Success(())
+///         } else {
+///             BehaviorResult::Failure(())
+///         }
+///     }
+/// }
+///
+/// let mut tree = WhileDoElseNode::new(Box::new(SeesEnemy), Box::new(AlwaysRunning))
+///     .with_else(Box::new(AlwaysRunning));
+/// assert_eq!(tree.tick(true), BehaviorResult::Running);
+/// // The chase is halted and the patrol starts.
+/// assert_eq!(tree.tick(false), BehaviorResult::Running);
+/// ```
+pub struct WhileDoElseNode<Payload, R, F> {
+    condition: Child<Payload, R, F>,
+    body: Child<Payload, R, F>,
+    otherwise: Option<Child<Payload, R, F>>,
+    /// The index of the branch that returned `Running` in the last tick.
+    running: Option<usize>,
+}
+
+impl<Payload, R, F> WhileDoElseNode<Payload, R, F> {
+    /// Constructs a [WhileDoElseNode] without the "else" child.
+    pub fn new(condition: Child<Payload, R, F>, body: Child<Payload, R, F>) -> Self {
+        Self {
+            condition,
+            body,
+            otherwise: None,
+            running: None,
+        }
+    }
+
+    /// Sets the child that is ticked while the condition fails.
+    pub fn with_else(mut self, otherwise: Child<Payload, R, F>) -> Self {
+        self.otherwise = Some(otherwise);
+        self
+    }
+
+    fn children(&mut self) -> impl Iterator<Item = &mut Child<Payload, R, F>> {
+        once(&mut self.condition)
+            .chain(once(&mut self.body))
+            .chain(self.otherwise.as_mut())
+    }
+
+    fn branch(&mut self, index: usize) -> Option<&mut Child<Payload, R, F>> {
+        match index {
+            1 => Some(&mut self.body),
+            _ => self.otherwise.as_mut(),
+        }
+    }
+
+    /// Halts the running branch unless it is the one at `index`.
+    fn halt_other(&mut self, index: usize) {
+        if let Some(running) = self.running.filter(|running| *running != index) {
+            if let Some(branch) = self.branch(running) {
+                branch.halt();
+            }
+            self.running = None;
+        }
+    }
+
+    fn run<E>(
+        &mut self,
+        payload: Payload,
+        mut tick_child: impl FnMut(
+            usize,
+            &mut Child<Payload, R, F>,
+            Payload,
+        ) -> Result<BehaviorResult<R, F>, E>,
+    ) -> Result<BehaviorResult<R, F>, E>
+    where
+        Payload: Clone,
+    {
+        let index =
+            match report::tick_child(0, || tick_child(0, &mut self.condition, payload.clone()))? {
+                BehaviorResult::Success(_) => 1,
+                BehaviorResult::Failure(_) if self.otherwise.is_some() => 2,
+                res @ BehaviorResult::Failure(_) => {
+                    self.halt_other(2);
+                    return Ok(res);
+                }
+                res => return Ok(res),
+            };
+        self.halt_other(index);
+        let branch = self.branch(index).expect("the branch should exist");
+        let res = report::tick_child(index, || tick_child(index, branch, payload))?;
+        self.running = match res {
+            BehaviorResult::Running => Some(index),
+            _ => None,
+        };
+        Ok(res)
+    }
+}
+
+impl<Payload, R, F> BehaviorNodeBase<Payload, R, F> for WhileDoElseNode<Payload, R, F>
+where
+    Payload: Clone,
+{
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<R, F> {
+        match self.run(payload, |_, node, payload| {
+            Ok::<_, Infallible>(node.tick(payload))
+        }) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<R, F>, BtError> {
+        self.run(payload, |i, node, payload| {
+            node.try_tick(payload).map_err(|e| e.in_child(i))
+        })
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.running = None;
+        HaltReport::from_children(self.children().map(|node| node.halt()))
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.running = None;
+        HaltReport::from_children(self.children().map(|node| node.reset()))
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn BehaviorNodeBase<Payload, R, F> + 'static)>
+    where
+        Self: 'static,
+    {
+        self.children().map(|node| node.as_mut() as _).collect()
+    }
+
+    fn push_child(
+        &mut self,
+        child: Box<dyn BehaviorNodeBase<Payload, R, F>>,
+    ) -> Result<(), Box<dyn BehaviorNodeBase<Payload, R, F>>>
+    where
+        Self: 'static,
+    {
+        if self.otherwise.is_some() {
+            return Err(child);
+        }
+        self.otherwise = Some(child);
+        Ok(())
+    }
+}
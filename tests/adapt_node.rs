@@ -0,0 +1,68 @@
+use tiny_behavior_tree::{
+    testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess},
+    AdaptNode, BehaviorNodeBase, BehaviorResult, FallbackNode, SequenceNode,
+};
+
+type Node = Box<dyn BehaviorNodeBase<i32, String, ()>>;
+type ChildNode = Box<dyn BehaviorNodeBase<u8, Vec<u8>, bool>>;
+
+/// Succeeds with the payload if it is even.
+struct Even;
+
+impl BehaviorNodeBase<u8, Vec<u8>, bool> for Even {
+    fn tick(&mut self, payload: u8) -> BehaviorResult<Vec<u8>, bool> {
+        if payload.is_multiple_of(2) {
+            BehaviorResult::Success(vec![payload])
+        } else {
+            BehaviorResult::Failure(true)
+        }
+    }
+}
+
+#[test]
+fn test_adapt_subtree() {
+    let subtree =
+        SequenceNode::merged([Box::new(Even) as ChildNode, Box::new(AlwaysSuccess(vec![]))]);
+    let mut tree = FallbackNode::new([
+        Box::new(AdaptNode::new(
+            |payload: i32| payload as u8,
+            |r: Vec<u8>| format!("{:?}", r),
+            |_: bool| (),
+            subtree,
+        )) as Node,
+        Box::new(AlwaysSuccess("odd".to_string())),
+    ]);
+    assert_eq!(tree.tick(4), BehaviorResult::Success("[4]".to_string()));
+    assert_eq!(tree.tick(3), BehaviorResult::Success("odd".to_string()));
+}
+
+#[test]
+fn test_adapt_passes_running() {
+    let mut tree = AdaptNode::new(
+        |payload: i32| payload as u8,
+        |_: ()| 1,
+        |_: ()| "failed",
+        SequenceNode::new([
+            Box::new(AlwaysRunning) as Box<dyn BehaviorNodeBase<u8, (), ()>>,
+            Box::new(AlwaysFailure(())),
+        ]),
+    );
+    assert_eq!(tree.try_tick(0).unwrap(), BehaviorResult::Running);
+}
+
+#[test]
+fn test_adapt_with_stateful_maps() {
+    let mut successes = 0;
+    let mut tree = AdaptNode::new(
+        |payload: i32| payload as u8,
+        move |r: Vec<u8>| {
+            successes += 1;
+            format!("{:?} #{}", r, successes)
+        },
+        |_: bool| (),
+        Even,
+    );
+    assert_eq!(tree.tick(2), BehaviorResult::Success("[2] #1".to_string()));
+    assert_eq!(tree.tick(1), BehaviorResult::Failure(()));
+    assert_eq!(tree.tick(4), BehaviorResult::Success("[4] #2".to_string()));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "alloc-counter")]
+use tiny_behavior_tree::alloc_counter::{CountAllocations, CountingAllocator};
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, FallbackNode, SequenceNode};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+type Node = Box<dyn BehaviorNodeBase<(), i32, i32>>;
+
+struct Collect;
+
+impl BehaviorNodeBase<(), i32, i32> for Collect {
+    fn tick(&mut self, _payload: ()) -> BehaviorResult<i32, i32> {
+        let v: Vec<i32> = (0..10).collect();
+        BehaviorResult::Success(v.len() as i32)
+    }
+}
+
+#[test]
+fn test_control_nodes_do_not_allocate() {
+    let node = FallbackNode::<(), i32, i32, _>::new([
+        Box::new(AlwaysFailure(1)) as Node,
+        Box::new(SequenceNode::<(), i32, i32, _>::new([
+            Box::new(AlwaysSuccess(2)) as Node,
+            Box::new(AlwaysSuccess(3)) as Node,
+        ])),
+    ]);
+    let mut node = CountAllocations::new(node);
+    let stats = node.stats();
+
+    for _ in 0..3 {
+        assert_eq!(node.tick(()), BehaviorResult::Success(3));
+    }
+    assert_eq!(stats.ticks(), 3);
+    assert_eq!(stats.total(), 0);
+}
+
+#[test]
+fn test_nested_counts() {
+    let inner = CountAllocations::new(Collect);
+    let inner_stats = inner.stats();
+    let mut outer = CountAllocations::new(SequenceNode::<(), i32, i32, _>::new([
+        Box::new(inner) as Node,
+        Box::new(Collect) as Node,
+    ]));
+    let outer_stats = outer.stats();
+
+    outer.tick(());
+    outer.tick(());
+    assert_eq!(inner_stats.last(), 1);
+    assert_eq!(inner_stats.total(), 2);
+    assert_eq!(outer_stats.peak(), 2);
+    assert_eq!(outer_stats.total(), 4);
+}
@@ -0,0 +1,55 @@
+use std::cell::Cell;
+use tiny_behavior_tree::{
+    behavior_action, BehaviorNodeBase, BehaviorResult, NodeConfig, NodeFactory, SequenceNode,
+};
+
+thread_local! {
+    static PINGS: Cell<u32> = const { Cell::new(0) };
+}
+
+#[behavior_action(name = "Ping")]
+fn send_ping() -> BehaviorResult<u32, ()> {
+    PINGS.with(|pings| {
+        pings.set(pings.get() + 1);
+        BehaviorResult::Success(pings.get())
+    })
+}
+
+#[behavior_action]
+pub(crate) fn take_coin(coins: &Cell<u32>) -> BehaviorResult<(), String> {
+    match coins.get() {
+        0 => BehaviorResult::Failure("no coins".to_string()),
+        n => {
+            coins.set(n - 1);
+            BehaviorResult::Success(())
+        }
+    }
+}
+
+#[test]
+fn test_function_nodes() {
+    assert_eq!(send_ping(), BehaviorResult::Success(1));
+    assert_eq!(SendPing.tick(()), BehaviorResult::Success(2));
+    assert_eq!(SendPing::NODE_NAME, "Ping");
+
+    let coins = Cell::new(2);
+    let mut seq = SequenceNode::new(vec![
+        Box::new(TakeCoin) as Box<dyn BehaviorNodeBase<_, _, _>>,
+        Box::new(TakeCoin),
+        Box::new(TakeCoin),
+    ]);
+    assert_eq!(
+        seq.tick(&coins),
+        BehaviorResult::Failure("no coins".to_string())
+    );
+    assert_eq!(coins.get(), 0);
+}
+
+#[test]
+fn test_register() {
+    let mut factory = NodeFactory::new();
+    SendPing::register(&mut factory);
+    let mut node = factory.create("Ping", &NodeConfig::new()).unwrap();
+    assert!(matches!(node.tick(()), BehaviorResult::Success(_)));
+    assert_eq!(node.definition().map(|(name, _)| name), Some("Ping"));
+}
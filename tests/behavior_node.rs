@@ -0,0 +1,87 @@
+use tiny_behavior_tree::{
+    BehaviorNode, BehaviorNodeBase, BehaviorResult, FactoryError, NodeConfig, NodeFactory,
+    ParamError,
+};
+
+/// Counts down and returns the number of ticks it took.
+#[derive(BehaviorNode)]
+#[behavior_node(success = "u32", failure = "String", name = "CountDown")]
+struct Counter {
+    #[port]
+    from: u32,
+    #[port(default = "\"done\".to_string()")]
+    message: String,
+    ticks: u32,
+}
+
+impl Counter {
+    fn tick(&mut self, _: ()) -> BehaviorResult<u32, String> {
+        self.ticks += 1;
+        if self.ticks < self.from {
+            BehaviorResult::Running
+        } else if self.message.is_empty() {
+            BehaviorResult::Failure("no message".to_string())
+        } else {
+            BehaviorResult::Success(self.ticks)
+        }
+    }
+}
+
+#[derive(BehaviorNode)]
+#[behavior_node(payload = "&mut Vec<T>", tick = "push")]
+struct Push<T: Clone + Default>(#[port] T);
+
+impl<T: Clone + Default> Push<T> {
+    fn push(&mut self, items: &mut Vec<T>) -> BehaviorResult<(), ()> {
+        items.push(self.0.clone());
+        BehaviorResult::Success(())
+    }
+}
+
+#[test]
+fn test_register_and_create() {
+    let mut factory = NodeFactory::new();
+    Counter::register(&mut factory);
+    assert_eq!(Counter::NODE_NAME, "CountDown");
+
+    let mut node = factory
+        .create("CountDown", &NodeConfig::new().with("from", "2"))
+        .unwrap();
+    assert_eq!(node.definition().map(|(name, _)| name), Some("CountDown"));
+    assert_eq!(
+        node.describe(),
+        [
+            ("from", "2".to_string()),
+            ("message", "\"done\"".to_string())
+        ]
+    );
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    assert_eq!(node.tick(()), BehaviorResult::Success(2));
+
+    assert_eq!(
+        factory.create("CountDown", &NodeConfig::new()).err(),
+        Some(FactoryError::Param {
+            node_type: "CountDown".to_string(),
+            error: ParamError::missing("from"),
+        })
+    );
+    let cfg = NodeConfig::new().with("from", "1").with("message", "");
+    let mut node = Counter::from_config(&cfg).unwrap();
+    assert_eq!(
+        BehaviorNodeBase::tick(&mut node, ()),
+        BehaviorResult::Failure("no message".to_string())
+    );
+}
+
+#[test]
+fn test_generic_node() {
+    let mut node = Push::<i32>::from_config(&NodeConfig::new().with("0", "7")).unwrap();
+    let mut items = vec![];
+    assert_eq!(node.push(&mut items), BehaviorResult::Success(()));
+    assert_eq!(
+        BehaviorNodeBase::tick(&mut node, &mut items),
+        BehaviorResult::Success(())
+    );
+    assert_eq!(items, [7, 7]);
+    assert_eq!(Push::<i32>::NODE_NAME, "Push");
+}
@@ -0,0 +1,118 @@
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, Blackboard, SequenceNodeRef};
+
+type Node<'a> = Box<dyn BehaviorNodeBase<&'a RefCell<Blackboard>, (), ()> + 'a>;
+
+struct SpotEnemy;
+
+impl<'a> BehaviorNodeBase<&'a RefCell<Blackboard>, (), ()> for SpotEnemy {
+    fn tick(&mut self, bb: &'a RefCell<Blackboard>) -> BehaviorResult<(), ()> {
+        bb.borrow_mut().set("enemy", (3, 4));
+        BehaviorResult::Success(())
+    }
+}
+
+struct IsEnemyVisible;
+
+impl<'a> BehaviorNodeBase<&'a RefCell<Blackboard>, (), ()> for IsEnemyVisible {
+    fn tick(&mut self, bb: &'a RefCell<Blackboard>) -> BehaviorResult<(), ()> {
+        if bb.borrow().contains("enemy") {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+fn build_tree<'a>() -> Node<'a> {
+    Box::new(SequenceNodeRef::<RefCell<Blackboard>, (), (), _>::new([
+        Box::new(SpotEnemy) as Node,
+        Box::new(IsEnemyVisible) as Node,
+    ]))
+}
+
+#[test]
+fn test_immediate() {
+    let bb = RefCell::new(Blackboard::new());
+    let mut tree = build_tree();
+    assert_eq!(tree.tick(&bb), BehaviorResult::Success(()));
+    assert_eq!(bb.borrow().get::<(i32, i32)>("enemy"), Some(&(3, 4)));
+}
+
+#[test]
+fn test_double_buffered() {
+    let bb = RefCell::new(Blackboard::double_buffered());
+    let mut tree = build_tree();
+    assert_eq!(tree.tick(&bb), BehaviorResult::Failure(()));
+    assert!(bb.borrow().has_pending());
+
+    bb.borrow_mut().commit();
+    assert_eq!(tree.tick(&bb), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_symbol_key() {
+    let mut bb = Blackboard::new();
+    let hp = bb.key("hp");
+    bb.set(hp, 10u32);
+    assert_eq!(bb.get::<u32>("hp"), Some(&10));
+    assert_eq!(bb.get::<i32>(hp), None);
+    *bb.get_mut::<u32>(hp).unwrap() -= 3;
+    assert_eq!(bb.get::<u32>(hp), Some(&7));
+    assert_eq!(bb.key_name(hp), Some("hp"));
+    bb.remove("hp");
+    assert!(bb.is_empty());
+}
+
+#[test]
+fn test_expire_generations() {
+    let mut bb = Blackboard::new();
+    bb.set("door", "open");
+    bb.set_for_generations("enemy", (3, 4), 1);
+    bb.set_for_generations("noise", 5, 3);
+    assert_eq!(bb.len(), 3);
+
+    bb.advance_generation();
+    assert!(!bb.contains("enemy"));
+    assert!(bb.contains("noise"));
+
+    bb.advance_generation();
+    bb.advance_generation();
+    assert!(!bb.contains("noise"));
+    assert!(bb.contains("door"));
+    assert_eq!(bb.generation(), 3);
+}
+
+#[test]
+fn test_expire_instant() {
+    let start = Instant::now();
+    let mut bb = Blackboard::new();
+    bb.set_until("enemy", (3, 4), start + Duration::from_secs(1));
+    bb.purge_expired(start);
+    assert!(bb.contains("enemy"));
+    bb.purge_expired(start + Duration::from_secs(1));
+    assert!(!bb.contains("enemy"));
+}
+
+#[test]
+fn test_expire_pending() {
+    let start = Instant::now();
+    let mut bb = Blackboard::double_buffered();
+    bb.set("enemy", (1, 1));
+    bb.commit();
+    bb.set_for_generations("enemy", (3, 4), 1);
+    bb.set_until("noise", 5, start + Duration::from_secs(1));
+    bb.set_for_generations("door", "open", 2);
+
+    bb.advance_generation();
+    bb.purge_expired(start + Duration::from_secs(1));
+    bb.commit();
+    assert!(!bb.contains("enemy"));
+    assert!(!bb.contains("noise"));
+    assert_eq!(bb.get::<&str>("door"), Some(&"open"));
+
+    bb.advance_generation();
+    assert!(!bb.contains("door"));
+    assert!(!bb.has_pending());
+}
@@ -0,0 +1,69 @@
+use tiny_behavior_tree::{Blackboard, BlackboardChange};
+
+#[derive(Debug)]
+struct Target {
+    id: u32,
+}
+
+#[test]
+fn test_diff() {
+    let mut bb = Blackboard::new();
+    bb.set("hp", 10);
+    bb.set("door", "open");
+    let before = bb.snapshot();
+
+    bb.set("hp", 7);
+    bb.set("door", "open");
+    bb.remove("door");
+    bb.set("ammo", 3u8);
+    let after = bb.snapshot();
+
+    assert_eq!(
+        Blackboard::diff(&before, &after),
+        vec![
+            BlackboardChange {
+                key: "ammo".to_string(),
+                before: None,
+                after: Some("3".to_string()),
+            },
+            BlackboardChange {
+                key: "door".to_string(),
+                before: Some("\"open\"".to_string()),
+                after: None,
+            },
+            BlackboardChange {
+                key: "hp".to_string(),
+                before: Some("10".to_string()),
+                after: Some("7".to_string()),
+            },
+        ]
+    );
+    assert!(Blackboard::diff(&after, &bb.snapshot()).is_empty());
+    assert_eq!(after.keys().collect::<Vec<_>>(), ["ammo", "hp"]);
+}
+
+#[test]
+fn test_diff_unregistered_types() {
+    let mut bb = Blackboard::new();
+    bb.set("target", Target { id: 1 });
+    let first = bb.snapshot();
+    assert_eq!(
+        first.describe("target").unwrap(),
+        format!("<{}>", std::any::type_name::<Target>())
+    );
+
+    // Without a Debug representation, setting the entry again is a change
+    bb.set("target", Target { id: 1 });
+    let second = bb.snapshot();
+    assert_eq!(Blackboard::diff(&first, &second).len(), 1);
+
+    bb.register_debug::<Target>();
+    let third = bb.snapshot();
+    bb.get_mut::<Target>("target").unwrap().id = 2;
+    let changes = Blackboard::diff(&third, &bb.snapshot());
+    assert_eq!(changes.len(), 1);
+    assert_eq!(
+        changes[0].to_string(),
+        "target: Target { id: 1 } -> Target { id: 2 }"
+    );
+}
@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Blackboard, BlackboardScope, ScopeNode, SequenceNode,
+    WithBlackboard,
+};
+
+/// Counts its ticks in the blackboard entry "visits" and copies it to "last".
+struct Visit;
+
+impl<'a> BehaviorNodeBase<WithBlackboard<'a, ()>, (), ()> for Visit {
+    fn tick(&mut self, ctx: WithBlackboard<'a, ()>) -> BehaviorResult<(), ()> {
+        let mut bb = ctx.blackboard.borrow_mut();
+        let visits = bb.get::<u32>("visits").copied().unwrap_or(0) + 1;
+        bb.set("visits", visits);
+        bb.set("last", visits);
+        BehaviorResult::Success(())
+    }
+}
+
+type Node<'a> = Box<dyn BehaviorNodeBase<WithBlackboard<'a, ()>, (), ()>>;
+
+#[test]
+fn test_reused_subtrees_keep_separate_entries() {
+    let bb = RefCell::new(Blackboard::new());
+    bb.borrow_mut().set("visits", 10u32);
+    let mut tree = SequenceNode::new([
+        Box::new(ScopeNode::new(Visit).share("last")) as Node,
+        Box::new(ScopeNode::new(Visit)),
+        Box::new(Visit),
+    ]);
+    let ctx = WithBlackboard::new((), &bb);
+
+    assert_eq!(tree.tick(ctx), BehaviorResult::Success(()));
+    // Both scopes read the global entry first, then shadow it.
+    assert_eq!(bb.borrow().get::<u32>("visits"), Some(&11));
+    assert_eq!(bb.borrow().get::<u32>("last"), Some(&11));
+
+    tree.tick(ctx);
+    assert_eq!(bb.borrow().get::<u32>("visits"), Some(&12));
+
+    tree.reset();
+    bb.borrow_mut().set("visits", 0u32);
+    tree.tick(ctx);
+    assert_eq!(bb.borrow().get::<u32>("last"), Some(&1));
+}
+
+#[test]
+fn test_nested_scopes() {
+    let mut bb = Blackboard::double_buffered();
+    bb.set("mode", "patrol");
+    bb.commit();
+
+    bb.push_scope(BlackboardScope::new());
+    bb.set("mode", "chase");
+    bb.push_scope(BlackboardScope::new().share("mode"));
+    assert_eq!(bb.get::<&str>("mode"), Some(&"chase"));
+    bb.set("mode", "flee");
+    bb.set("path", vec![1, 2]);
+    assert_eq!(bb.len(), 2);
+    let inner = bb.pop_scope().unwrap();
+    assert_eq!(inner.len(), 1);
+
+    assert_eq!(bb.get::<&str>("mode"), Some(&"flee"));
+    assert!(!bb.contains("path"));
+    bb.remove("mode");
+    assert_eq!(bb.get::<&str>("mode"), Some(&"patrol"));
+    bb.pop_scope();
+    assert!(!bb.has_pending());
+}
+
+#[test]
+fn test_scope_with_symbol_keys() {
+    let mut bb = Blackboard::new();
+    let done = bb.key("done");
+    bb.push_scope(BlackboardScope::new().share("done"));
+    bb.set(done, true);
+    let target = bb.key("target");
+    bb.set(target, "door");
+
+    // A symbol of another blackboard is not shared by any scope.
+    let mut other = Blackboard::new();
+    other.key("a");
+    other.key("b");
+    let unknown = other.key("c");
+    bb.set(unknown, 1);
+    let scope = bb.pop_scope().unwrap();
+
+    assert_eq!(bb.get::<bool>(done), Some(&true));
+    assert!(!bb.contains(target));
+    assert_eq!(scope.len(), 2);
+}
@@ -0,0 +1,23 @@
+use tiny_behavior_tree::bounded::{BoundedTree, Depth, Nested, Root};
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::BehaviorResult;
+
+#[test]
+fn test_depth() {
+    assert_eq!(Root::DEPTH, 1);
+    assert_eq!(<Nested<Nested<Root>>>::DEPTH, 3);
+}
+
+#[test]
+fn test_build_at_maximum_depth() {
+    let mut tree = BoundedTree::<4>::sequence(|b| {
+        b.leaf(AlwaysSuccess(1));
+        b.fallback(|b| {
+            b.leaf(AlwaysFailure(()));
+            b.sequence(|b| {
+                b.leaf(AlwaysSuccess(2)).leaf(AlwaysSuccess(3));
+            });
+        });
+    });
+    assert_eq!(tree.tick(()), BehaviorResult::Success(3));
+}
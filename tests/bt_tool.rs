@@ -0,0 +1,89 @@
+#![cfg(feature = "bt-tool")]
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const MANIFEST: &str =
+    r#"{ "nodes": [{ "type": "OpenDoor", "kind": "action", "params": ["force"] }] }"#;
+
+/// Writes the files in a directory for the test and runs the tool in it.
+fn run(test: &str, files: &[(&str, &str)], args: &[&str]) -> (Output, PathBuf) {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(test);
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, text) in files {
+        std::fs::write(dir.join(name), text).unwrap();
+    }
+    let output = Command::new(env!("CARGO_BIN_EXE_bt-tool"))
+        .current_dir(&dir)
+        .args(args)
+        .output()
+        .unwrap();
+    (output, dir)
+}
+
+#[test]
+fn test_bt_tool_renders_tree() {
+    let tree = r#"{
+        "format_version": 1,
+        "root": {
+            "type": "Sequence",
+            "children": [{ "type": "OpenDoor", "params": { "force": 10 } }]
+        }
+    }"#;
+    let (output, dir) = run(
+        "renders_tree",
+        &[("tree.json", tree), ("manifest.json", MANIFEST)],
+        &[
+            "tree.json",
+            "--manifest",
+            "manifest.json",
+            "--dot",
+            "tree.dot",
+            "--mermaid",
+            "-",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "\
+Sequence
+`-- OpenDoor force=10
+flowchart TD
+    n0[\"Sequence\"]
+    n1[\"OpenDoor<br/>force=10\"]
+    n0 --> n1
+"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("tree.dot")).unwrap(),
+        "\
+digraph tree {
+    n0 [label=\"Sequence\"];
+    n1 [label=\"OpenDoor\\nforce=10\"];
+    n0 -> n1;
+}
+"
+    );
+}
+
+#[test]
+fn test_bt_tool_rejects_invalid_tree() {
+    let tree = r#"{ "format_version": 1, "root": { "type": "OpenDoor" } }"#;
+    let (output, _) = run(
+        "rejects_invalid_tree",
+        &[("tree.json", tree), ("manifest.json", MANIFEST)],
+        &["tree.json", "--manifest", "manifest.json"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "tree.json: Node at path \"\": Parameter \"force\": missing in \"OpenDoor\"\n"
+    );
+}
+
+#[test]
+fn test_bt_tool_requires_manifest() {
+    let (output, _) = run("requires_manifest", &[], &["tree.json"]);
+    assert_eq!(output.status.code(), Some(2));
+}
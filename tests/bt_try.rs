@@ -0,0 +1,40 @@
+use tiny_behavior_tree::{
+    bt_try,
+    testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess},
+    BehaviorNodeBase, BehaviorResult,
+};
+
+/// Ticks the children in order and collects their results, like a sequence.
+struct Collect(Vec<Box<dyn BehaviorNodeBase<(), i32, &'static str>>>);
+
+impl BehaviorNodeBase<(), Vec<i32>, String> for Collect {
+    fn tick(&mut self, _: ()) -> BehaviorResult<Vec<i32>, String> {
+        let mut results = vec![];
+        for child in &mut self.0 {
+            results.push(bt_try!(child.tick(())));
+        }
+        BehaviorResult::Success(results)
+    }
+}
+
+#[test]
+fn test_bt_try_success() {
+    let mut node = Collect(vec![Box::new(AlwaysSuccess(1)), Box::new(AlwaysSuccess(2))]);
+    assert_eq!(node.tick(()), BehaviorResult::Success(vec![1, 2]));
+}
+
+#[test]
+fn test_bt_try_early_return() {
+    let mut node = Collect(vec![
+        Box::new(AlwaysSuccess(1)),
+        Box::new(AlwaysFailure("locked")),
+        Box::new(AlwaysRunning),
+    ]);
+    assert_eq!(node.tick(()), BehaviorResult::Failure("locked".to_string()));
+
+    let mut node = Collect(vec![
+        Box::new(AlwaysRunning),
+        Box::new(AlwaysFailure("locked")),
+    ]);
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+}
@@ -0,0 +1,152 @@
+#![cfg(feature = "xml")]
+use tiny_behavior_tree::loader::NodeDef;
+use tiny_behavior_tree::{btcpp, BehaviorNodeBase, BehaviorResult, NodeFactory};
+
+struct Check(bool);
+
+impl BehaviorNodeBase<(), (), ()> for Check {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        if self.0 {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+#[test]
+fn test_parse_explicit_and_compact_forms() {
+    let def = btcpp::parse(
+        r#"
+<root BTCPP_format="3">
+  <!-- The only tree is the main tree. -->
+  <BehaviorTree ID="Patrol">
+    <ReactiveFallback name="patrol">
+      <Condition ID="IsEnemyVisible"/>
+      <Action ID="MoveTo" name="go" goal="{waypoint}"/>
+      <Parallel success_threshold="2">
+        <Look/>
+        <Listen/>
+      </Parallel>
+    </ReactiveFallback>
+  </BehaviorTree>
+</root>"#,
+    )
+    .unwrap();
+    assert_eq!(
+        def.root,
+        NodeDef::new("ReactiveFallback")
+            .with_child(NodeDef::new("IsEnemyVisible"))
+            .with_child(NodeDef::new("MoveTo").with_param("goal", "{waypoint}"))
+            .with_child(
+                NodeDef::new("Parallel")
+                    .with_param("success_threshold", "2")
+                    .with_child(NodeDef::new("Look"))
+                    .with_child(NodeDef::new("Listen"))
+            )
+    );
+}
+
+#[test]
+fn test_load_subtrees() {
+    let mut factory = NodeFactory::with_builtins();
+    factory.register("Check", |cfg| Ok(Check(cfg.get("ok")?)));
+
+    let mut tree = factory
+        .load_btcpp_xml(
+            r#"
+<root main_tree_to_execute="Main">
+  <BehaviorTree ID="Fail"><Check ok="false"/></BehaviorTree>
+  <BehaviorTree ID="Main">
+    <Fallback>
+      <SubTree ID="Fail"/>
+      <SubTree ID="Fail" _autoremap="true"/>
+      <Check ok="true"/>
+    </Fallback>
+  </BehaviorTree>
+</root>"#,
+        )
+        .unwrap();
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    assert_eq!(tree.children_mut().len(), 3);
+}
+
+#[test]
+fn test_errors() {
+    let err = |xml: &str| btcpp::parse(xml).err().unwrap().to_string();
+    assert_eq!(
+        err(r#"<root><BehaviorTree ID="A"><SubTree ID="A"/></BehaviorTree></root>"#),
+        "Invalid tree file: <SubTree ID=\"A\"> includes itself"
+    );
+    assert_eq!(
+        err(r#"<root><BehaviorTree><SubTree ID="B" goal="{target}"/></BehaviorTree></root>"#),
+        "Invalid tree file: Remapping port \"goal\" of <SubTree ID=\"B\"> is not supported"
+    );
+    assert_eq!(
+        err(r#"<root><BehaviorTree><Action/></BehaviorTree></root>"#),
+        "Invalid tree file: <Action> requires the ID attribute"
+    );
+    assert_eq!(
+        err(r#"<root><BehaviorTree ID="A"/><BehaviorTree ID="B"/></root>"#),
+        "Invalid tree file: main_tree_to_execute is required with more than one <BehaviorTree>"
+    );
+}
+
+/// A payload with a blackboard for the guards.
+#[cfg(feature = "stdlib")]
+#[derive(Clone)]
+struct Bb(std::rc::Rc<std::cell::RefCell<tiny_behavior_tree::Blackboard>>);
+
+#[cfg(feature = "stdlib")]
+impl tiny_behavior_tree::stdlib::BlackboardPayload for Bb {
+    fn with_blackboard<T>(&self, f: impl FnOnce(&mut tiny_behavior_tree::Blackboard) -> T) -> T {
+        f(&mut self.0.borrow_mut())
+    }
+}
+
+#[cfg(feature = "stdlib")]
+#[test]
+fn test_load_inline_guards() {
+    use tiny_behavior_tree::loader::LoadError;
+    use tiny_behavior_tree::stdlib::AlwaysSuccess;
+
+    let xml = r#"
+<root main_tree_to_execute="Main">
+  <BehaviorTree ID="Enter"><Action ID="Succeed" guard="alert_level &lt; 3"/></BehaviorTree>
+  <BehaviorTree ID="Main">
+    <Sequence>
+      <Action ID="Succeed" guard="door.open == false"/>
+      <SubTree ID="Enter" guard="!alarm"/>
+    </Sequence>
+  </BehaviorTree>
+</root>"#;
+    let def = btcpp::parse(xml).unwrap();
+    assert_eq!(
+        def.root.children[1],
+        NodeDef::new("Succeed").with_param("guard", "!alarm && alert_level < 3")
+    );
+
+    let mut factory = NodeFactory::<Bb, (), ()>::with_builtins();
+    factory.register("Succeed", |_| Ok(AlwaysSuccess(())));
+    factory.enable_guards();
+    let mut tree = factory.load_btcpp_xml(xml).unwrap();
+    let bb = Bb(Default::default());
+    bb.0.borrow_mut().set("door.open", false);
+    bb.0.borrow_mut().set("alarm", false);
+    bb.0.borrow_mut().set("alert_level", 1);
+    assert_eq!(tree.tick(bb.clone()), BehaviorResult::Success(()));
+    bb.0.borrow_mut().set("alarm", true);
+    assert_eq!(tree.tick(bb.clone()), BehaviorResult::Failure(()));
+
+    let err = factory
+        .load_btcpp_xml(
+            r#"<root><BehaviorTree><Sequence><Succeed guard="door.open ="/></Sequence></BehaviorTree></root>"#,
+        )
+        .err()
+        .unwrap();
+    assert!(matches!(err, LoadError::Guard { ref path, .. } if path == "0"));
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"0\": Invalid guard term \"door.open =\""
+    );
+}
@@ -0,0 +1,45 @@
+use std::cell::Cell;
+use tiny_behavior_tree::{
+    btree,
+    testing::{AlwaysFailure, AlwaysSuccess},
+    BehaviorNodeBase, BehaviorResult, InverterNode, RepeatNode,
+};
+
+struct Count<'a>(&'a Cell<usize>);
+
+impl<'a, 'b> BehaviorNodeBase<&'b (), (), ()> for Count<'a> {
+    fn tick(&mut self, _: &'b ()) -> BehaviorResult<(), ()> {
+        self.0.set(self.0.get() + 1);
+        BehaviorResult::Success(())
+    }
+}
+
+#[test]
+fn test_btree_nested() {
+    let mut tree = btree!(Sequence [
+        Fallback [
+            AlwaysFailure(()),
+            InverterNode::new(AlwaysFailure(())),
+        ],
+        ReactiveSequence [AlwaysSuccess(())],
+        Parallel(2) [AlwaysSuccess(()), AlwaysFailure(()), AlwaysSuccess(())],
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    assert_eq!(tree.children_mut().len(), 3);
+}
+
+#[test]
+fn test_btree_leaf() {
+    let count = Cell::new(0);
+    let mut tree = btree!(RepeatNode::new(3, Count(&count)));
+    assert_eq!(tree.tick(&()), BehaviorResult::Success(()));
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn test_btree_borrowed_children() {
+    let count = Cell::new(0);
+    let mut tree = btree!(SequenceRef [Count(&count), Count(&count)]);
+    assert_eq!(tree.tick(&()), BehaviorResult::Success(()));
+    assert_eq!(count.get(), 2);
+}
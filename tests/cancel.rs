@@ -0,0 +1,49 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, CancellationToken, Coroutine, CoroutineActionNode,
+};
+
+#[test]
+fn test_halt_cancels_coroutine() {
+    let tokens = Rc::new(RefCell::new(vec![]));
+    let tokens2 = tokens.clone();
+    let mut node = CoroutineActionNode::new(move |steps: u32, co: Coroutine<u32>| {
+        tokens2.borrow_mut().push(co.cancellation_token().clone());
+        async move {
+            for _ in 0..steps {
+                co.next_tick().await;
+            }
+            Ok::<_, ()>(())
+        }
+    });
+
+    assert_eq!(node.tick(1), BehaviorResult::Running);
+    assert_eq!(node.tick(1), BehaviorResult::Success(()));
+    assert!(!tokens.borrow()[0].is_cancelled());
+
+    assert_eq!(node.tick(5), BehaviorResult::Running);
+    assert!(node.halt().is_clean());
+    assert!(tokens.borrow()[1].is_cancelled());
+
+    // A new coroutine gets a new token
+    assert_eq!(node.tick(5), BehaviorResult::Running);
+    assert!(!tokens.borrow()[2].is_cancelled());
+}
+
+#[tokio::test]
+async fn test_cancelled_wakes_task() {
+    let token = CancellationToken::new();
+    let task = tokio::spawn({
+        let token = token.clone();
+        async move {
+            token.cancelled().await;
+            "stopped"
+        }
+    });
+    tokio::task::yield_now().await;
+    token.cancel();
+    assert_eq!(task.await.unwrap(), "stopped");
+    // Already cancelled tokens resolve immediately
+    token.cancelled().await;
+}
@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, BtErrorKind, CatchBorrowConflict, SequenceNodeRef,
+};
+
+type Node<'a> = Box<dyn BehaviorNodeBase<&'a RefCell<Vec<i32>>, (), String> + 'a>;
+
+/// Holds a shared borrow of the payload while ticking the child.
+struct Inspect<'a>(Node<'a>);
+
+impl<'a> BehaviorNodeBase<&'a RefCell<Vec<i32>>, (), String> for Inspect<'a> {
+    fn tick(&mut self, payload: &'a RefCell<Vec<i32>>) -> BehaviorResult<(), String> {
+        let _items = payload.borrow();
+        self.0.tick(payload)
+    }
+}
+
+struct Push(i32);
+
+impl<'a> BehaviorNodeBase<&'a RefCell<Vec<i32>>, (), String> for Push {
+    fn tick(&mut self, payload: &'a RefCell<Vec<i32>>) -> BehaviorResult<(), String> {
+        if self.0 < 0 {
+            panic!("negative item");
+        }
+        payload.borrow_mut().push(self.0);
+        BehaviorResult::Success(())
+    }
+}
+
+#[test]
+fn test_conflict_becomes_failure() {
+    let payload = RefCell::new(vec![]);
+    let mut tree = SequenceNodeRef::<_, (), String, _>::new([
+        Box::new(CatchBorrowConflict::new("0", Push(1))) as Node,
+        Box::new(Inspect(Box::new(CatchBorrowConflict::new("1/0", Push(2))))),
+    ]);
+
+    let BehaviorResult::Failure(message) = tree.tick(&payload) else {
+        panic!("expected failure");
+    };
+    assert!(message.starts_with("Borrow conflict in \"1/0\": "));
+    assert!(message.contains("already borrowed"));
+    assert_eq!(*payload.borrow(), [1]);
+
+    let mut node = CatchBorrowConflict::new("", Push(3));
+    let _items = payload.borrow();
+    let err = node.try_tick(&payload).unwrap_err();
+    assert!(matches!(err.kind(), BtErrorKind::Borrow));
+}
+
+#[test]
+#[should_panic(expected = "negative item")]
+fn test_other_panics_propagate() {
+    let payload = RefCell::new(vec![]);
+    let mut node = CatchBorrowConflict::new("", Push(-1));
+    node.tick(&payload);
+}
@@ -0,0 +1,72 @@
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, ChildMeta, DelegateNode, FallbackNode, ParallelNode,
+    SequenceNode, Tree,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+#[test]
+fn test_child_meta() {
+    let mut tree = FallbackNode::<(), (), (), _>::new([
+        Box::new(AlwaysFailure(())) as Node,
+        Box::new(AlwaysSuccess(())),
+    ])
+    .with_child_meta(
+        1,
+        ChildMeta {
+            weight: Some(0.5),
+            comment: Some("idle animation".to_string()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(tree.child_meta(0), None);
+    assert_eq!(tree.child_meta(1).unwrap().weight, Some(0.5));
+
+    // Annotations do not affect the behavior
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+
+    tree.set_child_meta(
+        1,
+        ChildMeta {
+            priority: Some(3),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        tree.child_meta(1),
+        Some(&ChildMeta {
+            priority: Some(3),
+            ..Default::default()
+        })
+    );
+}
+
+#[derive(DelegateNode)]
+struct Wrapper<T>(T);
+
+#[test]
+fn test_child_meta_through_wrappers() {
+    let mut parallel = ParallelNode::new([Box::new(AlwaysSuccess(())) as Node], 1);
+    parallel.set_child_meta(
+        0,
+        ChildMeta {
+            comment: Some("look around".to_string()),
+            ..Default::default()
+        },
+    );
+    let tree = Tree::new(Box::new(Wrapper(parallel)) as Node);
+    assert_eq!(
+        tree.child_meta(0).and_then(|meta| meta.comment.as_deref()),
+        Some("look around")
+    );
+    let leaf: Node = Box::new(AlwaysSuccess(()));
+    assert_eq!(leaf.child_meta(0), None);
+}
+
+#[test]
+#[should_panic(expected = "no child at index 1")]
+fn test_child_meta_out_of_range() {
+    SequenceNode::<(), (), (), _>::new([Box::new(AlwaysSuccess(())) as Node])
+        .set_child_meta(1, ChildMeta::default());
+}
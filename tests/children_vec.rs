@@ -0,0 +1,39 @@
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, FallbackNode, ParallelNode, SequenceNodeRef,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), i32, i32>>;
+
+#[test]
+fn test_extend_after_construction() {
+    let waypoints = [1, 2, 3];
+    let mut tree = FallbackNode::<(), i32, i32, _>::with_capacity(waypoints.len());
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(0));
+
+    tree.extend(
+        waypoints
+            .iter()
+            .map(|&waypoint| Box::new(AlwaysFailure(waypoint)) as Node),
+    );
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(3));
+
+    tree.reserve(1);
+    tree.extend([Box::new(AlwaysSuccess(4)) as Node]);
+    tree.shrink_to_fit();
+    assert_eq!(tree.tick(()), BehaviorResult::Success(4));
+}
+
+#[test]
+fn test_extend_ref_and_parallel() {
+    let mut tree = SequenceNodeRef::<i32, i32, (), _>::with_capacity(2);
+    tree.extend(
+        (0..2).map(|i| Box::new(AlwaysSuccess(i)) as Box<dyn BehaviorNodeBase<&i32, i32, ()>>),
+    );
+    assert_eq!(tree.tick(&0), BehaviorResult::Success(1));
+
+    let mut tree = ParallelNode::new(Vec::<Node>::new(), 2);
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(0));
+    tree.extend((1..=2).map(|i| Box::new(AlwaysSuccess(i)) as Node));
+    assert_eq!(tree.tick(()), BehaviorResult::Success(2));
+}
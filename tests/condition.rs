@@ -0,0 +1,62 @@
+use std::cell::RefCell;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, Condition, SequenceNodeRef};
+
+struct Agent {
+    health: u32,
+    name: &'static str,
+}
+
+impl Agent {
+    fn ammo(&self) -> i32 {
+        self.name.len() as i32
+    }
+}
+
+#[derive(Condition)]
+#[condition(payload = "&Agent", get = "payload.health", op = ">=")]
+struct MinHealth(u32);
+
+#[derive(Condition)]
+#[condition(payload = "&RefCell<Agent>", get = "payload.borrow().ammo()", op = "<")]
+struct LowAmmo {
+    #[allow(dead_code)]
+    label: &'static str,
+    #[condition]
+    limit: i32,
+}
+
+#[derive(Condition)]
+#[condition(payload = "&Agent", get = "payload.name")]
+struct IsNamed(&'static str);
+
+#[test]
+fn test_condition() {
+    let agent = Agent {
+        health: 30,
+        name: "bob",
+    };
+    assert_eq!(MinHealth(20).tick(&agent), BehaviorResult::Success(()));
+    assert_eq!(MinHealth(40).tick(&agent), BehaviorResult::Failure(()));
+    assert_eq!(IsNamed("bob").tick(&agent), BehaviorResult::Success(()));
+    assert_eq!(IsNamed("alice").tick(&agent), BehaviorResult::Failure(()));
+
+    let agent = RefCell::new(agent);
+    let mut low_ammo = LowAmmo {
+        label: "low",
+        limit: 5,
+    };
+    assert_eq!(low_ammo.tick(&agent), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_condition_in_tree() {
+    let agent = Agent {
+        health: 30,
+        name: "bob",
+    };
+    let mut tree = SequenceNodeRef::<Agent, (), (), _>::new([
+        Box::new(MinHealth(10)) as Box<dyn BehaviorNodeBase<&Agent, (), ()>>,
+        Box::new(IsNamed("bob")),
+    ]);
+    assert_eq!(tree.tick(&agent), BehaviorResult::Success(()));
+}
@@ -0,0 +1,39 @@
+use std::cell::Cell;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, ConditionNode, FallbackNodeRef};
+
+struct Agent {
+    health: u32,
+    enemy_visible: bool,
+}
+
+#[test]
+fn test_conditions_in_tree() {
+    let wounded = Agent {
+        health: 20,
+        enemy_visible: false,
+    };
+    let alerted = Agent {
+        health: 20,
+        enemy_visible: true,
+    };
+    let mut tree = FallbackNodeRef::<Agent, (), (), _>::new([
+        Box::new(ConditionNode::new(|agent: &Agent| agent.enemy_visible))
+            as Box<dyn BehaviorNodeBase<&Agent, (), ()>>,
+        Box::new(ConditionNode::new(|agent: &Agent| 50 <= agent.health)),
+    ]);
+    assert_eq!(tree.tick(&wounded), BehaviorResult::Failure(()));
+    assert_eq!(tree.tick(&alerted), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_stateful_predicate() {
+    let checks = Cell::new(0);
+    let mut node = ConditionNode::new(|limit: u32| {
+        checks.set(checks.get() + 1);
+        checks.get() <= limit
+    });
+    assert_eq!(node.tick(2), BehaviorResult::Success(()));
+    assert_eq!(node.tick(2), BehaviorResult::Success(()));
+    assert_eq!(node.tick(2), BehaviorResult::Failure(()));
+    assert_eq!(checks.get(), 3);
+}
@@ -0,0 +1,58 @@
+use tiny_behavior_tree::{conditions_for, BehaviorNodeBase, BehaviorResult, SequenceNodeRef};
+
+struct Agent {
+    has_key: bool,
+    door_open: bool,
+    room: u32,
+}
+
+impl Agent {
+    fn in_room(&self) -> bool {
+        self.room != 0
+    }
+}
+
+conditions_for!(Agent { has_key, door_open, in_room() });
+
+mod nodes {
+    use tiny_behavior_tree::conditions_for;
+
+    pub struct Door {
+        pub is_locked: bool,
+    }
+
+    conditions_for!(pub Door { is_locked });
+}
+
+#[test]
+fn test_conditions_for() {
+    let mut agent = Agent {
+        has_key: true,
+        door_open: false,
+        room: 1,
+    };
+    assert_eq!(HasKey.tick(&agent), BehaviorResult::Success(()));
+    assert_eq!(DoorOpen.tick(&agent), BehaviorResult::Failure(()));
+    assert_eq!(InRoom.tick(&agent), BehaviorResult::Success(()));
+    agent.room = 0;
+    assert_eq!(InRoom.tick(&agent), BehaviorResult::Failure(()));
+
+    let door = nodes::Door { is_locked: true };
+    assert_eq!(nodes::IsLocked.tick(&door), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_conditions_in_tree() {
+    type Node<'a> = Box<dyn BehaviorNodeBase<&'a Agent, (), ()> + 'a>;
+    let agent = Agent {
+        has_key: true,
+        door_open: true,
+        room: 2,
+    };
+    let mut tree = SequenceNodeRef::<Agent, (), (), _>::new([
+        Box::new(HasKey) as Node,
+        Box::new(DoorOpen),
+        Box::new(InRoom),
+    ]);
+    assert_eq!(tree.tick(&agent), BehaviorResult::Success(()));
+}
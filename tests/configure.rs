@@ -0,0 +1,70 @@
+use std::any::Any;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Configure, ConfigureError, FallbackNode, SequenceNode,
+};
+
+type Node = Box<dyn BehaviorNodeBase<i32, (), ()>>;
+
+struct AtLeast(i32);
+
+impl BehaviorNodeBase<i32, (), ()> for AtLeast {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<(), ()> {
+        if self.0 <= payload {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+}
+
+struct Opaque;
+
+impl BehaviorNodeBase<i32, (), ()> for Opaque {
+    fn tick(&mut self, _payload: i32) -> BehaviorResult<(), ()> {
+        BehaviorResult::Success(())
+    }
+}
+
+fn build_tree() -> Node {
+    Box::new(SequenceNode::<i32, (), (), _>::new([
+        Box::new(Opaque) as Node,
+        Box::new(FallbackNode::<i32, (), (), _>::new([
+            Box::new(AtLeast(10)) as Node,
+            Box::new(AtLeast(20)) as Node,
+        ])),
+    ]))
+}
+
+#[test]
+fn test_configure() {
+    let mut tree = build_tree();
+    assert_eq!(tree.tick(5), BehaviorResult::Failure(()));
+
+    tree.configure::<AtLeast>("1/1", |node| node.0 = 5).unwrap();
+    assert_eq!(tree.tick(5), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_configure_errors() {
+    let mut tree = build_tree();
+    assert_eq!(
+        tree.configure::<AtLeast>("1/2", |_| ()),
+        Err(ConfigureError::NotFound("1/2".to_string()))
+    );
+    assert_eq!(
+        tree.configure::<AtLeast>("1/x", |_| ()),
+        Err(ConfigureError::InvalidPath("1/x".to_string()))
+    );
+    assert_eq!(
+        tree.configure::<AtLeast>("0", |_| ()),
+        Err(ConfigureError::NotConfigurable("0".to_string()))
+    );
+    assert!(matches!(
+        tree.configure::<AtLeast>("1", |_| ()),
+        Err(ConfigureError::TypeMismatch { .. })
+    ));
+}
@@ -0,0 +1,64 @@
+use tiny_behavior_tree::{
+    testing::{AlwaysFailure, AlwaysRunning},
+    BehaviorNode, BehaviorNodeBase, BehaviorResult, ContextFallbackNode, ContextSequenceNode,
+    HaltReport, IgnoreContext, WithContext,
+};
+
+#[derive(Default)]
+struct Services {
+    time: u32,
+    stopped: Vec<&'static str>,
+}
+
+type Node = Box<dyn BehaviorNode<Services, u32, (), ()>>;
+
+/// Succeeds once the time has passed the deadline given as the payload.
+struct WaitUntil;
+
+impl BehaviorNode<Services, u32, (), ()> for WaitUntil {
+    fn tick(&mut self, ctx: &mut Services, deadline: u32) -> BehaviorResult<(), ()> {
+        ctx.time += 1;
+        if deadline <= ctx.time {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Running
+        }
+    }
+
+    fn halt(&mut self, ctx: &mut Services) -> HaltReport {
+        ctx.stopped.push("wait");
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_context_tree() {
+    let mut tree = WithContext::new(
+        Services::default(),
+        ContextFallbackNode::new([
+            Box::new(IgnoreContext(AlwaysFailure(()))) as Node,
+            Box::new(ContextSequenceNode::new([Box::new(WaitUntil) as Node])),
+        ]),
+    );
+    assert_eq!(tree.tick(2), BehaviorResult::Running);
+    assert_eq!(tree.tick(2), BehaviorResult::Success(()));
+    assert_eq!(tree.ctx().time, 2);
+
+    tree.ctx_mut().time = 0;
+    assert_eq!(tree.tick(1), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_context_halt() {
+    let mut tree = WithContext::new(
+        Services::default(),
+        ContextSequenceNode::new([
+            Box::new(WaitUntil) as Node,
+            Box::new(IgnoreContext(AlwaysRunning)),
+        ]),
+    );
+    assert_eq!(tree.tick(10), BehaviorResult::Running);
+    assert!(tree.halt().is_clean());
+    let (services, _) = tree.into_inner();
+    assert_eq!(services.stopped, ["wait"]);
+}
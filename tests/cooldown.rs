@@ -0,0 +1,62 @@
+use std::thread;
+use std::time::{Duration, Instant};
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, Cooldown, CooldownRegistry};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+#[test]
+fn test_local_cooldown() {
+    let mut node: Node = Box::new(Cooldown::new(Duration::from_millis(50), AlwaysSuccess(())));
+    assert_eq!(node.tick(()), BehaviorResult::Success(()));
+    assert_eq!(node.tick(()), BehaviorResult::Failure(()));
+    thread::sleep(Duration::from_millis(60));
+    assert_eq!(node.tick(()), BehaviorResult::Success(()));
+
+    // Failure of the child does not start the cooldown
+    let mut node: Node = Box::new(Cooldown::new(Duration::from_secs(10), AlwaysFailure(())));
+    assert_eq!(node.tick(()), BehaviorResult::Failure(()));
+    assert_eq!(node.tick(()), BehaviorResult::Failure(()));
+}
+
+#[test]
+fn test_shared_cooldown_across_threads() {
+    let registry = CooldownRegistry::new();
+    let barks: Vec<_> = (0..4)
+        .map(|_| {
+            let registry = registry.clone();
+            thread::spawn(move || {
+                let mut node: Node = Box::new(Cooldown::shared(
+                    &registry,
+                    "bark",
+                    Duration::from_secs(10),
+                    AlwaysSuccess(()),
+                ));
+                node.tick(())
+            })
+        })
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    assert_eq!(barks[0], BehaviorResult::Success(()));
+    assert!(barks[1..]
+        .iter()
+        .all(|res| *res == BehaviorResult::Failure(())));
+
+    assert!(!registry.is_ready("bark", Instant::now()));
+    assert!(registry.is_ready("other", Instant::now()));
+    registry.reset("bark");
+    assert!(registry.is_ready("bark", Instant::now()));
+}
+
+#[test]
+fn test_global_registry() {
+    CooldownRegistry::global().trigger("test_global", Instant::now() + Duration::from_secs(10));
+    let mut node = Cooldown::shared(
+        CooldownRegistry::global(),
+        "test_global",
+        Duration::from_secs(1),
+        AlwaysSuccess(()),
+    );
+    let res: BehaviorResult<(), ()> = node.tick(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+}
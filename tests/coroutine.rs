@@ -0,0 +1,50 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, Coroutine, CoroutineActionNode};
+
+#[test]
+fn test_coroutine_steps() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let log2 = log.clone();
+    let mut node = CoroutineActionNode::new(move |door: &'static str, co: Coroutine<&str>| {
+        let log = log2.clone();
+        async move {
+            log.borrow_mut().push(format!("walk to {}", door));
+            let door = co.next_tick().await;
+            log.borrow_mut().push(format!("open {}", door));
+            co.next_tick().await;
+            if door == "locked" {
+                Err(())
+            } else {
+                Ok(door)
+            }
+        }
+    });
+
+    assert_eq!(node.tick("front"), BehaviorResult::Running);
+    assert_eq!(node.tick("back"), BehaviorResult::Running);
+    assert!(node.is_running());
+    assert_eq!(node.tick("ignored"), BehaviorResult::Success("back"));
+    assert!(!node.is_running());
+    assert_eq!(*log.borrow(), ["walk to front", "open back"]);
+
+    // The next tick starts a new coroutine
+    assert_eq!(node.tick("front"), BehaviorResult::Running);
+    assert_eq!(node.tick("locked"), BehaviorResult::Running);
+    assert_eq!(node.tick("front"), BehaviorResult::Failure(()));
+}
+
+#[test]
+fn test_coroutine_halt() {
+    let mut node = CoroutineActionNode::new(|(), co: Coroutine<()>| async move {
+        co.next_tick().await;
+        Ok::<_, ()>(1)
+    });
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    assert!(BehaviorNodeBase::<(), i32, ()>::halt(&mut node).is_clean());
+    assert!(!node.is_running());
+
+    // Halting dropped the coroutine, so it starts over
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    assert_eq!(node.tick(()), BehaviorResult::Success(1));
+}
@@ -0,0 +1,49 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+use tiny_behavior_tree::testing::AlwaysRunning;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, DelayNode, ManualClock};
+
+/// Returns `Success`, counting ticks.
+struct Count(Rc<Cell<u32>>);
+
+impl BehaviorNodeBase<(), (), ()> for Count {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        self.0.set(self.0.get() + 1);
+        BehaviorResult::Success(())
+    }
+}
+
+#[test]
+fn test_delay() {
+    let clock = ManualClock::new();
+    let ticks = Rc::new(Cell::new(0));
+    let mut node =
+        DelayNode::with_clock(Duration::from_secs(3), clock.clone(), Count(ticks.clone()));
+    for _ in 0..3 {
+        assert_eq!(node.tick(()), BehaviorResult::Running);
+        clock.advance(Duration::from_secs(1));
+    }
+    assert_eq!(ticks.get(), 0);
+    assert_eq!(node.tick(()), BehaviorResult::Success(()));
+    assert_eq!(ticks.get(), 1);
+
+    // The delay starts over after the child completed
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    assert_eq!(ticks.get(), 1);
+}
+
+#[test]
+fn test_delay_halt() {
+    let clock = ManualClock::new();
+    let mut node = DelayNode::with_clock(Duration::from_secs(2), clock.clone(), AlwaysRunning);
+    let res: BehaviorResult<(), ()> = node.tick(());
+    assert_eq!(res, BehaviorResult::Running);
+    clock.advance(Duration::from_secs(2));
+    assert!(BehaviorNodeBase::<(), (), ()>::halt(&mut node).is_clean());
+
+    // Halting starts the delay over
+    clock.advance(Duration::from_secs(1));
+    let res: BehaviorResult<(), ()> = node.tick(());
+    assert_eq!(res, BehaviorResult::Running);
+}
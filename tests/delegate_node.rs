@@ -0,0 +1,61 @@
+use std::cell::RefCell;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, DelegateNode};
+
+struct IsPositive;
+
+impl BehaviorNodeBase<i32, i32, i32> for IsPositive {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<i32, i32> {
+        if 0 < payload {
+            BehaviorResult::Success(payload)
+        } else {
+            BehaviorResult::Failure(payload)
+        }
+    }
+}
+
+impl<'a> BehaviorNodeBase<&'a RefCell<i32>, (), ()> for IsPositive {
+    fn tick(&mut self, payload: &'a RefCell<i32>) -> BehaviorResult<(), ()> {
+        if 0 < *payload.borrow() {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+#[derive(DelegateNode)]
+struct Wrapper<T>(T);
+
+#[derive(DelegateNode)]
+struct Labeled<T> {
+    #[allow(dead_code)]
+    label: &'static str,
+    #[delegate]
+    node: T,
+}
+
+#[test]
+fn test_delegate_newtype() {
+    let mut node = Wrapper(IsPositive);
+    assert_eq!(node.tick(3), BehaviorResult::Success(3));
+    assert_eq!(node.tick(-1), BehaviorResult::Failure(-1));
+    assert_eq!(node.tick(&RefCell::new(1)), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_delegate_marked_field() {
+    let mut node = Labeled {
+        label: "positive",
+        node: Wrapper(IsPositive),
+    };
+    assert_eq!(node.tick(5), BehaviorResult::Success(5));
+}
+
+#[derive(DelegateNode)]
+struct Concrete(IsPositive);
+
+#[test]
+fn test_delegate_concrete() {
+    let mut node = Concrete(IsPositive);
+    assert_eq!(node.tick(2), BehaviorResult::Success(2));
+}
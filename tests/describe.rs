@@ -0,0 +1,77 @@
+use std::time::Duration;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, DelegateNode, Describe, RepeatNode, RetryNode, TimeoutNode,
+    Tree,
+};
+
+/// Fails twice, then keeps running.
+struct Flaky(u32);
+
+impl BehaviorNodeBase<(), (), ()> for Flaky {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        if 0 < self.0 {
+            self.0 -= 1;
+            BehaviorResult::Failure(())
+        } else {
+            BehaviorResult::Running
+        }
+    }
+}
+
+fn show(entries: Vec<(&'static str, String)>) -> String {
+    entries
+        .into_iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[test]
+fn test_describe_builtin_nodes() {
+    let mut retry = RetryNode::new(3, Flaky(1));
+    assert_eq!(show(retry.describe()), "attempts=3, used=0");
+    assert_eq!(retry.tick(()), BehaviorResult::Running);
+    assert_eq!(show(retry.describe()), "attempts=3, used=1");
+
+    let tree: Tree<(), (), ()> = Tree::new(Box::new(TimeoutNode::new(
+        Duration::from_millis(1500),
+        AlwaysRunning,
+    )));
+    assert_eq!(show(tree.describe()), "duration=1.5s");
+
+    let repeat = RepeatNode::forever(AlwaysFailure(()));
+    assert_eq!(
+        show(BehaviorNodeBase::<(), (), ()>::describe(&repeat)),
+        "times=forever, done=0"
+    );
+}
+
+#[derive(Describe, DelegateNode)]
+struct Labeled<T> {
+    label: &'static str,
+    #[delegate]
+    #[describe(skip)]
+    node: T,
+}
+
+#[derive(Describe)]
+struct Pair(i32, #[describe(skip)] Vec<u8>, bool);
+
+#[test]
+fn test_derive_describe() {
+    let labeled = Labeled {
+        label: "retry door",
+        node: RetryNode::new(2, Flaky(0)),
+    };
+    assert_eq!(show(labeled.describe_fields()), "label=\"retry door\"");
+    // DelegateNode forwards describe to the inner node.
+    assert_eq!(
+        show(BehaviorNodeBase::<(), (), ()>::describe(&labeled)),
+        "attempts=2, used=0"
+    );
+
+    let pair = Pair(1, vec![], true);
+    assert_eq!(show(pair.describe_fields()), "0=1, 2=true");
+    assert!(pair.1.is_empty());
+}
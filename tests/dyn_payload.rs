@@ -0,0 +1,50 @@
+use std::cell::RefCell;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Comparison, DynCondition, DynPayload, DynSet, DynValue,
+    FallbackNodeRef,
+};
+
+type Node<'a> = Box<dyn BehaviorNodeBase<&'a RefCell<DynPayload>, (), ()> + 'a>;
+
+#[test]
+fn test_comparison() {
+    use Comparison::*;
+    let int = DynValue::from(2);
+    let float = DynValue::from(2.5);
+    let text = DynValue::from("2");
+    assert!(Lt.test(&int, &float));
+    assert!(Ge.test(&float, &int));
+    assert!(Eq.test(&int, &DynValue::Float(2.)));
+    assert!(!Eq.test(&int, &text));
+    assert!(Ne.test(&int, &text));
+    assert!(!Lt.test(&int, &text));
+
+    assert_eq!("<=".parse(), Ok(Le));
+    assert_eq!(
+        "=>".parse::<Comparison>().unwrap_err().to_string(),
+        "Unknown comparison operator \"=>\""
+    );
+}
+
+#[test]
+fn test_data_driven_tree() {
+    let payload = RefCell::new(DynPayload::new());
+    let mut tree = FallbackNodeRef::new([
+        Box::new(DynCondition::new("alerted", Comparison::Eq, true)) as Node,
+        Box::new(DynSet::new("alerted", true)),
+    ]);
+
+    // The condition fails on a missing entry
+    assert_eq!(tree.tick(&payload), BehaviorResult::Success(()));
+    assert_eq!(payload.borrow().get("alerted"), Some(&DynValue::Bool(true)));
+
+    payload.borrow_mut().set("alerted", "yes");
+    assert_eq!(tree.tick(&payload), BehaviorResult::Success(()));
+    assert_eq!(
+        payload
+            .borrow_mut()
+            .remove("alerted")
+            .and_then(|v| v.as_bool()),
+        Some(true)
+    );
+}
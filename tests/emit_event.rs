@@ -0,0 +1,55 @@
+use std::cell::RefCell;
+use std::sync::mpsc::channel;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, EmitEvent, SequenceNodeRef};
+
+struct Agent {
+    name: &'static str,
+    in_room: bool,
+}
+
+#[derive(PartialEq, Debug)]
+enum Command {
+    PlayAnimation(&'static str),
+    Bark(String),
+}
+
+type Node<'a> = Box<dyn BehaviorNodeBase<&'a RefCell<Agent>, (), ()> + 'a>;
+
+#[test]
+fn test_emit_event() {
+    let agent = RefCell::new(Agent {
+        name: "guard",
+        in_room: true,
+    });
+    let (sender, receiver) = channel();
+    let mut tree = SequenceNodeRef::<RefCell<Agent>, (), (), _>::new([
+        Box::new(EmitEvent::new(sender.clone(), |agent: &RefCell<Agent>| {
+            Command::PlayAnimation(if agent.borrow().in_room {
+                "idle"
+            } else {
+                "walk"
+            })
+        })) as Node,
+        Box::new(EmitEvent::new(sender, |agent: &RefCell<Agent>| {
+            Command::Bark(format!("{} says hi", agent.borrow().name))
+        })),
+    ]);
+
+    assert_eq!(tree.tick(&agent), BehaviorResult::Success(()));
+    assert_eq!(receiver.try_recv(), Ok(Command::PlayAnimation("idle")));
+    assert_eq!(
+        receiver.try_recv(),
+        Ok(Command::Bark("guard says hi".to_string()))
+    );
+}
+
+#[test]
+fn test_emit_event_disconnected() {
+    let (sender, receiver) = channel();
+    drop(receiver);
+    let mut node = EmitEvent::new(sender, |x: i32| x);
+    assert_eq!(
+        BehaviorNodeBase::<_, (), ()>::tick(&mut node, 1),
+        BehaviorResult::Failure(())
+    );
+}
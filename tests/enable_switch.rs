@@ -0,0 +1,65 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Configure, ConfigureError, EnableSwitch, HaltReport,
+    SequenceNode,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+struct CountHalts(Rc<Cell<u32>>);
+
+impl BehaviorNodeBase<(), (), ()> for CountHalts {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.0.set(self.0.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_disable_by_path() {
+    let halts = Rc::new(Cell::new(0));
+    let mut tree = SequenceNode::<(), (), (), _>::new([
+        Box::new(AlwaysSuccess(())) as Node,
+        Box::new(
+            EnableSwitch::new(Box::new(CountHalts(halts.clone())) as Node)
+                .with_disabled_result(BehaviorResult::Success(())),
+        ),
+    ]);
+    tree.tick(());
+
+    tree.set_enabled("1", false).unwrap();
+    assert_eq!(halts.get(), 1);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    assert_eq!(halts.get(), 1);
+
+    tree.set_enabled("1", true).unwrap();
+    tree.configure::<EnableSwitch<(), (), ()>>("1", |switch| assert!(switch.is_enabled()))
+        .unwrap();
+}
+
+#[test]
+fn test_disabled_result_and_errors() {
+    let mut switch = EnableSwitch::new(Box::new(AlwaysRunning) as Node);
+    assert_eq!(switch.tick(()), BehaviorResult::Running);
+    switch.set_enabled(false);
+    assert_eq!(switch.tick(()), BehaviorResult::Failure(()));
+
+    let mut tree = SequenceNode::<(), (), (), _>::new([Box::new(EnableSwitch::new(Box::new(
+        AlwaysFailure(()),
+    ) as Node)) as Node]);
+    // The wrapped node is the only child of the switch
+    assert!(matches!(
+        tree.set_enabled("0/0", false),
+        Err(ConfigureError::NotConfigurable(_))
+    ));
+    assert!(matches!(
+        tree.set_enabled("1", false),
+        Err(ConfigureError::NotFound(_))
+    ));
+}
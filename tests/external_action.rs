@@ -0,0 +1,40 @@
+use std::thread;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, ExternalActionNode, SequenceNodeWithMemory,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), String>>;
+
+#[test]
+fn test_completed_from_another_thread() {
+    let action = ExternalActionNode::new();
+    let handle = action.handle();
+    let mut tree =
+        SequenceNodeWithMemory::new([Box::new(AlwaysSuccess(())) as Node, Box::new(action)]);
+
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    thread::spawn(move || handle.complete(Err("blocked".to_string())))
+        .join()
+        .unwrap();
+    assert_eq!(
+        tree.tick(()),
+        BehaviorResult::Failure("blocked".to_string())
+    );
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+}
+
+#[test]
+fn test_halt_cancels_request() {
+    let mut action = ExternalActionNode::<(), ()>::new();
+    let handle = action.handle();
+    assert!(!handle.is_requested());
+    assert_eq!(
+        BehaviorNodeBase::<(), _, _>::tick(&mut action, ()),
+        BehaviorResult::Running
+    );
+    assert!(handle.is_requested());
+    assert!(BehaviorNodeBase::<(), _, _>::halt(&mut action).is_clean());
+    assert!(!handle.is_requested());
+}
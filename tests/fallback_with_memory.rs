@@ -0,0 +1,60 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, FallbackNodeWithMemory};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), Vec<&'static str>>>;
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+/// Returns the scripted results in order, logging ticks.
+struct Script {
+    name: &'static str,
+    results: Vec<BehaviorResult<(), Vec<&'static str>>>,
+    log: Log,
+}
+
+impl Script {
+    fn node(name: &'static str, results: &[BehaviorResult<(), ()>], log: &Log) -> Node {
+        Box::new(Self {
+            name,
+            results: results
+                .iter()
+                .rev()
+                .map(|res| match res {
+                    Failure(()) => Failure(vec![name]),
+                    Success(()) => Success(()),
+                    Running => Running,
+                    BehaviorResult::Idle => BehaviorResult::Idle,
+                })
+                .collect(),
+            log: log.clone(),
+        })
+    }
+}
+
+impl BehaviorNodeBase<(), (), Vec<&'static str>> for Script {
+    fn tick(&mut self, _payload: ()) -> BehaviorResult<(), Vec<&'static str>> {
+        self.log.borrow_mut().push(self.name);
+        self.results.pop().unwrap()
+    }
+}
+
+use BehaviorResult::{Failure, Running, Success};
+
+#[test]
+fn test_resumes_at_running_child() {
+    let log = Log::default();
+    let mut tree = FallbackNodeWithMemory::new_with_merger(
+        [
+            Script::node("a", &[Failure(()), Success(())], &log),
+            Script::node("b", &[Running, Failure(())], &log),
+            Script::node("c", &[Failure(())], &log),
+        ],
+        |acc: &mut Vec<_>, mut f: Vec<_>| acc.append(&mut f),
+    );
+
+    assert_eq!(tree.tick(()), Running);
+    assert_eq!(tree.tick(()), Failure(vec!["a", "b", "c"]));
+    // Starts over after completion
+    assert_eq!(tree.tick(()), Success(()));
+    assert_eq!(*log.borrow(), ["a", "b", "b", "c", "a"]);
+}
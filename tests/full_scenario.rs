@@ -1,6 +1,6 @@
 use std::cell::RefCell;
 use tiny_behavior_tree::{
-    peel_node_def, BehaviorNodeBase, BehaviorResult, FallbackNodeRef, SequenceNodeRef,
+    btree, peel_node_def, BehaviorNodeBase, BehaviorResult, FallbackNodeRef, SequenceNodeRef,
 };
 
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -261,3 +261,47 @@ fn test_unlock_door_fail() {
         }
     );
 }
+
+fn build_tree_with_macro<'a, 'b>() -> Box<dyn BehaviorNodeBase<&'a State, (), ()> + 'b>
+where
+    'a: 'b,
+{
+    btree!(SequenceRef [
+        FallbackRef [
+            PeelDoorNode(IsDoorOpen),
+            PeelDoorNode(OpenDoor),
+            SequenceRef [
+                PeelAgentNode(HaveKey),
+                PeelDoorNode(UnlockDoor),
+                PeelDoorNode(OpenDoor),
+            ],
+            PeelDoorNode(SmashDoor),
+        ],
+        EnterRoom,
+    ])
+}
+
+#[test]
+fn test_btree_macro_builds_same_tree() {
+    for (open, locked, has_key) in [
+        (true, false, false),
+        (false, false, false),
+        (false, true, true),
+        (false, true, false),
+    ] {
+        let new_state = || State {
+            door: RefCell::new(Door { open, locked }),
+            agent: RefCell::new(Agent {
+                has_key,
+                in_room: false,
+            }),
+        };
+        let (state, macro_state) = (new_state(), new_state());
+        let mut tree = build_tree();
+        let mut macro_tree = build_tree_with_macro();
+
+        assert_eq!(macro_tree.tick(&macro_state), tree.tick(&state));
+        assert_eq!(*macro_state.door.borrow(), *state.door.borrow());
+        assert_eq!(*macro_state.agent.borrow(), *state.agent.borrow());
+    }
+}
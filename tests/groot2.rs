@@ -0,0 +1,67 @@
+#![cfg(feature = "groot2")]
+use tiny_behavior_tree::groot2::Groot2Publisher;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{NodeConfig, NodeFactory, Tree};
+
+fn tree() -> Tree<(), (), ()> {
+    let mut factory = NodeFactory::with_builtins();
+    factory.register("Succeed", |_| Ok(AlwaysSuccess(())));
+    factory.register("Fail", |_| Ok(AlwaysFailure(())));
+    let mut root = factory.create("Fallback", &NodeConfig::new()).unwrap();
+    for (name, cfg) in [
+        ("Fail", NodeConfig::new().with("reason", "a < b")),
+        ("Succeed", NodeConfig::new()),
+        ("Succeed", NodeConfig::new()),
+    ] {
+        assert!(root.push_child(factory.create(name, &cfg).unwrap()).is_ok());
+    }
+    Tree::new(root)
+}
+
+/// Sends a request with the given type to the publisher and returns the reply.
+fn request(groot: &mut Groot2Publisher, client: &zmq::Socket, kind: u8) -> Vec<Vec<u8>> {
+    client.send(&[2, kind, 42, 0, 0, 0][..], 0).unwrap();
+    for _ in 0..500 {
+        groot.poll().unwrap();
+        if client.poll(zmq::POLLIN, 10).unwrap() != 0 {
+            return client.recv_multipart(0).unwrap();
+        }
+    }
+    panic!("no reply");
+}
+
+#[test]
+fn test_full_tree_and_status() {
+    let mut tree = tree();
+    let mut groot = Groot2Publisher::new(&mut tree, 16671).unwrap();
+    assert_eq!(
+        groot.xml(),
+        "<root BTCPP_format=\"4\"><BehaviorTree ID=\"MainTree\">\
+         <Fallback _uid=\"1\">\
+         <Fail _uid=\"2\" reason=\"a &lt; b\"></Fail>\
+         <Succeed _uid=\"3\"></Succeed>\
+         <Succeed _uid=\"4\"></Succeed>\
+         </Fallback></BehaviorTree>\
+         <TreeNodesModel><Action ID=\"Fail\"/><Action ID=\"Succeed\"/></TreeNodesModel></root>"
+    );
+
+    let client = zmq::Context::new().socket(zmq::REQ).unwrap();
+    client.connect("tcp://127.0.0.1:16671").unwrap();
+    let reply = request(&mut groot, &client, b'T');
+    assert_eq!(reply[0][..6], [2, b'T', 42, 0, 0, 0]);
+    assert_eq!(reply[0].len(), 22);
+    assert_eq!(reply[1], groot.xml().as_bytes());
+
+    let (_, report) = tree.tick_with_report(());
+    groot.update(&report);
+    let reply = request(&mut groot, &client, b'S');
+    assert_eq!(reply[1], [1, 0, 2, 2, 0, 3, 3, 0, 2, 4, 0, 10]);
+
+    // The nodes that were not ticked fade out.
+    groot.update(&Default::default());
+    let reply = request(&mut groot, &client, b'S');
+    assert_eq!(reply[1], [1, 0, 12, 2, 0, 13, 3, 0, 12, 4, 0, 10]);
+
+    let reply = request(&mut groot, &client, b'B');
+    assert_eq!(reply, [&b"error"[..], &b"unsupported request"[..]]);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "stdlib")]
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use tiny_behavior_tree::stdlib::{Guard, GuardExpr};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, Blackboard, HaltReport};
+
+#[test]
+fn test_guard_expr() {
+    let mut bb = Blackboard::new();
+    bb.set("alarm", true);
+    bb.set("hp", 30u32);
+    bb.set("speed", 1.5);
+    bb.set("state", "patrol".to_string());
+
+    let holds = |expr: &str| expr.parse::<GuardExpr>().unwrap().test(&bb);
+    assert!(holds("alarm"));
+    assert!(!holds("!alarm"));
+    assert!(holds("hp >= 30 && hp < 31 && speed > 1"));
+    assert!(holds("state == \"patrol\" && state != \"flee\""));
+    // Missing entries only satisfy "!="
+    assert!(!holds("missing == 1"));
+    assert!(!holds("!missing"));
+    assert!(holds("missing != 1"));
+
+    for invalid in ["", "hp >", "== 1", "hp == patrol", "a b"] {
+        assert!(invalid.parse::<GuardExpr>().is_err(), "{}", invalid);
+    }
+}
+
+struct Walk(Rc<Cell<u32>>);
+
+impl<'a> BehaviorNodeBase<&'a RefCell<Blackboard>, (), ()> for Walk {
+    fn tick(&mut self, _: &'a RefCell<Blackboard>) -> BehaviorResult<(), ()> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.0.set(self.0.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_guard_halts_running_child() {
+    let halts = Rc::new(Cell::new(0));
+    let mut node = Guard::new("!door.open".parse().unwrap(), Walk(halts.clone()));
+    let bb = RefCell::new(Blackboard::new());
+    bb.borrow_mut().set("door.open", false);
+    assert_eq!(node.tick(&bb), BehaviorResult::Running);
+
+    bb.borrow_mut().set("door.open", true);
+    assert_eq!(node.tick(&bb), BehaviorResult::Failure(()));
+    assert_eq!(node.tick(&bb), BehaviorResult::Failure(()));
+    assert_eq!(halts.get(), 1);
+}
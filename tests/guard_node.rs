@@ -0,0 +1,69 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, GuardNode, HaltReport, NodeCondition};
+
+/// Always returns `Running`, counting ticks and halts.
+#[derive(Default)]
+struct Track {
+    ticks: Rc<Cell<u32>>,
+    halts: Rc<Cell<u32>>,
+}
+
+impl BehaviorNodeBase<i32, (), ()> for Track {
+    fn tick(&mut self, _: i32) -> BehaviorResult<(), ()> {
+        self.ticks.set(self.ticks.get() + 1);
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.halts.set(self.halts.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+/// Succeeds if the payload is positive.
+struct IsPositive;
+
+impl BehaviorNodeBase<i32, (), ()> for IsPositive {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<(), ()> {
+        if 0 < payload {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+#[test]
+fn test_guard_node_closure_aborts() {
+    let node = Track::default();
+    let (ticks, halts) = (node.ticks.clone(), node.halts.clone());
+    let mut guard = GuardNode::new(|payload: &i32| *payload < 10, node);
+    assert_eq!(guard.tick(1), BehaviorResult::Running);
+    assert_eq!(guard.tick(2), BehaviorResult::Running);
+    assert_eq!(guard.tick(10), BehaviorResult::Failure(()));
+    assert_eq!((ticks.get(), halts.get()), (2, 1));
+
+    // The child is not halted again while the guard keeps failing.
+    assert_eq!(guard.tick(11), BehaviorResult::Failure(()));
+    assert_eq!(halts.get(), 1);
+    assert_eq!(guard.tick(3), BehaviorResult::Running);
+    assert_eq!(ticks.get(), 3);
+}
+
+#[test]
+fn test_guard_node_condition_child() {
+    let mut guard = GuardNode::new(NodeCondition::new(IsPositive), AlwaysSuccess(()));
+    let res: BehaviorResult<(), ()> = guard.tick(1);
+    assert_eq!(res, BehaviorResult::Success(()));
+    let res: BehaviorResult<(), ()> = guard.tick(-1);
+    assert_eq!(res, BehaviorResult::Failure(()));
+
+    let node = Track::default();
+    let halts = node.halts.clone();
+    let mut guard = GuardNode::new(NodeCondition::new(IsPositive), node);
+    assert_eq!(guard.tick(1), BehaviorResult::Running);
+    assert_eq!(guard.tick(0), BehaviorResult::Failure(()));
+    assert_eq!(halts.get(), 1);
+}
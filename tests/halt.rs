@@ -0,0 +1,152 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, DelegateNode, FallbackNode, FallbackNodeRef, HaltIssue,
+    HaltReport, SequenceNode, SequenceNodeRef, Tree,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+struct Idle;
+
+impl BehaviorNodeBase<(), (), ()> for Idle {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        BehaviorResult::Success(())
+    }
+}
+
+struct MoveArm {
+    moving: bool,
+}
+
+impl BehaviorNodeBase<(), (), ()> for MoveArm {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        self.moving = true;
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        if self.moving {
+            HaltReport::incomplete("irreversible motion in progress")
+        } else {
+            HaltReport::clean()
+        }
+    }
+}
+
+#[derive(DelegateNode)]
+struct Wrapper<T>(T);
+
+#[test]
+fn test_halt_clean() {
+    let mut tree = Tree::new(Box::new(SequenceNode::<(), (), (), _>::new([
+        Box::new(Idle) as Node,
+        Box::new(MoveArm { moving: false }),
+    ])));
+    let report = tree.halt();
+    assert!(report.is_clean());
+    assert_eq!(report.to_string(), "halted cleanly");
+}
+
+#[test]
+fn test_halt_incomplete() {
+    let mut tree = Tree::new(Box::new(SequenceNode::<(), (), (), _>::new([
+        Box::new(Idle) as Node,
+        Box::new(FallbackNode::<(), (), (), _>::new([
+            Box::new(Wrapper(MoveArm { moving: false })) as Node,
+            Box::new(Idle),
+        ])),
+    ])));
+    assert!(tree.halt().is_clean());
+
+    tree.tick(());
+    let report = tree.halt();
+    assert!(!report.is_clean());
+    assert_eq!(
+        report.issues(),
+        [HaltIssue {
+            path: "1/0".to_string(),
+            reason: "irreversible motion in progress".to_string()
+        }]
+    );
+    assert_eq!(
+        report.to_string(),
+        "\"1/0\": irreversible motion in progress"
+    );
+}
+
+/// Returns the result set from outside.
+struct Scripted(Rc<Cell<BehaviorResult<(), ()>>>);
+
+impl<P> BehaviorNodeBase<P, (), ()> for Scripted {
+    fn tick(&mut self, _: P) -> BehaviorResult<(), ()> {
+        self.0.get()
+    }
+}
+
+/// Keeps running, counting the halts.
+struct Motor(Rc<Cell<u32>>);
+
+impl<P> BehaviorNodeBase<P, (), ()> for Motor {
+    fn tick(&mut self, _: P) -> BehaviorResult<(), ()> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.0.set(self.0.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_sequence_halts_abandoned_child() {
+    let res = Rc::new(Cell::new(BehaviorResult::Success(())));
+    let halts = Rc::new(Cell::new(0));
+    let mut tree = SequenceNode::<(), (), (), _>::new([
+        Box::new(Scripted(res.clone())) as Node,
+        Box::new(Motor(halts.clone())),
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!(halts.get(), 0);
+
+    res.set(BehaviorResult::Failure(()));
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(()));
+    assert_eq!(halts.get(), 1);
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(()));
+    assert_eq!(halts.get(), 1);
+
+    res.set(BehaviorResult::Success(()));
+    let mut tree = SequenceNodeRef::<(), (), (), _>::new(vec![
+        Box::new(Scripted(res.clone())) as Box<dyn BehaviorNodeBase<&(), (), ()>>,
+        Box::new(Motor(halts.clone())),
+    ]);
+    assert_eq!(tree.tick(&()), BehaviorResult::Running);
+    res.set(BehaviorResult::Failure(()));
+    assert_eq!(tree.tick(&()), BehaviorResult::Failure(()));
+    assert_eq!(halts.get(), 2);
+}
+
+#[test]
+fn test_fallback_halts_abandoned_child() {
+    let res = Rc::new(Cell::new(BehaviorResult::Failure(())));
+    let halts = Rc::new(Cell::new(0));
+    let mut tree = FallbackNode::<(), (), (), _>::new([
+        Box::new(Scripted(res.clone())) as Node,
+        Box::new(Motor(halts.clone())),
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    res.set(BehaviorResult::Success(()));
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    assert_eq!(halts.get(), 1);
+
+    res.set(BehaviorResult::Failure(()));
+    let mut tree = FallbackNodeRef::<(), (), (), _>::new(vec![
+        Box::new(Scripted(res.clone())) as Box<dyn BehaviorNodeBase<&(), (), ()>>,
+        Box::new(Motor(halts.clone())),
+    ]);
+    assert_eq!(tree.tick(&()), BehaviorResult::Running);
+    res.set(BehaviorResult::Success(()));
+    assert_eq!(tree.tick(&()), BehaviorResult::Success(()));
+    assert_eq!(halts.get(), 2);
+}
@@ -0,0 +1,61 @@
+use std::time::Instant;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, ResultHistory, ResultKind, SequenceNode,
+};
+
+type Node = Box<dyn BehaviorNodeBase<i32, (), ()>>;
+
+struct Positive;
+
+impl BehaviorNodeBase<i32, (), ()> for Positive {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<(), ()> {
+        if 0 < payload {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+#[test]
+fn test_ring_buffer_keeps_last_results() {
+    let start = Instant::now();
+    let history = ResultHistory::new(3);
+    let mut tree = history.record(
+        "",
+        SequenceNode::new([
+            Box::new(history.record("0", Positive)) as Node,
+            Box::new(history.record("1", AlwaysRunning)),
+        ]),
+    );
+
+    for payload in [1, -1, 2, 3, -4] {
+        tree.tick(payload);
+    }
+
+    let kinds = |path| -> Vec<_> {
+        history
+            .entries(path)
+            .iter()
+            .map(|entry| entry.kind)
+            .collect()
+    };
+    use ResultKind::*;
+    assert_eq!(kinds("0"), [Success, Success, Failure]);
+    assert_eq!(kinds("1"), [Running, Running, Running]);
+    assert_eq!(kinds(""), [Running, Running, Failure]);
+
+    let entries = history.entries("0");
+    assert!(start <= entries[0].timestamp);
+    assert!(entries.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+}
+
+#[test]
+fn test_unknown_path() {
+    let history = ResultHistory::new(3);
+    let mut node = history.record("a", AlwaysFailure(()));
+    let _: BehaviorResult<(), ()> = node.tick(());
+    assert!(history.entries("b").is_empty());
+    assert_eq!(history.paths(), ["a"]);
+}
@@ -0,0 +1,65 @@
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, IfThenElseNode};
+
+type Node = Box<dyn BehaviorNodeBase<i32, i32, i32>>;
+
+/// Succeeds with the payload if it is positive, otherwise fails with it.
+struct IsPositive;
+
+impl BehaviorNodeBase<i32, i32, i32> for IsPositive {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<i32, i32> {
+        if 0 < payload {
+            BehaviorResult::Success(payload)
+        } else {
+            BehaviorResult::Failure(payload)
+        }
+    }
+}
+
+/// Returns `Running` for the given number of ticks, then succeeds with the payload doubled.
+struct Double(u32);
+
+impl BehaviorNodeBase<i32, i32, i32> for Double {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<i32, i32> {
+        if 0 < self.0 {
+            self.0 -= 1;
+            BehaviorResult::Running
+        } else {
+            BehaviorResult::Success(payload * 2)
+        }
+    }
+}
+
+#[test]
+fn test_if_then_else() {
+    let mut tree = IfThenElseNode::new(Box::new(IsPositive) as Node, Box::new(Double(0)))
+        .with_else(Box::new(AlwaysFailure(-1)));
+    assert_eq!(tree.tick(3), BehaviorResult::Success(6));
+    assert_eq!(tree.tick(-3), BehaviorResult::Failure(-1));
+
+    let mut tree = IfThenElseNode::new(Box::new(IsPositive) as Node, Box::new(AlwaysSuccess(1)));
+    assert_eq!(tree.tick(-3), BehaviorResult::Failure(-3));
+    assert!(tree.push_child(Box::new(AlwaysSuccess(2))).is_ok());
+    assert_eq!(tree.tick(-3), BehaviorResult::Success(2));
+    assert!(tree.push_child(Box::new(AlwaysSuccess(3))).is_err());
+    assert_eq!(tree.children_mut().len(), 3);
+}
+
+#[test]
+fn test_if_then_else_resumes_running_branch() {
+    let mut tree = IfThenElseNode::new(Box::new(IsPositive) as Node, Box::new(Double(1)))
+        .with_else(Box::new(AlwaysFailure(-1)));
+    assert_eq!(tree.tick(3), BehaviorResult::Running);
+    // The condition is not checked again while the branch is running
+    assert_eq!(tree.tick(-3), BehaviorResult::Success(-6));
+
+    // Halting discards the running branch
+    let mut tree = IfThenElseNode::new(Box::new(IsPositive) as Node, Box::new(Double(1)))
+        .with_else(Box::new(AlwaysFailure(-1)));
+    assert_eq!(tree.tick(3), BehaviorResult::Running);
+    tree.halt();
+    assert_eq!(tree.tick(-3), BehaviorResult::Failure(-1));
+
+    let mut tree = IfThenElseNode::new(Box::new(AlwaysRunning) as Node, Box::new(Double(0)));
+    assert_eq!(tree.tick(3), BehaviorResult::Running);
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "tracing")]
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tiny_behavior_tree::instrument::InstrumentedNode;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, FallbackNode, SequenceNode};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Formats the fields of spans and events, omitting the values of durations.
+#[derive(Default)]
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push(match field.name() {
+            "message" => format!("{:?}", value),
+            "duration" => "duration".to_owned(),
+            name => format!("{}={:?}", name, value),
+        });
+    }
+}
+
+/// A subscriber that collects the events with the spans they are in, like
+/// `child{index=0}:node{name=Open} ticked status=Failure duration`.
+#[derive(Default)]
+struct Collector {
+    next_id: AtomicU64,
+    spans: Mutex<HashMap<u64, String>>,
+    stack: Mutex<Vec<u64>>,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut fields = Fields::default();
+        span.record(&mut fields);
+        let name = format!("{}{{{}}}", span.metadata().name(), fields.0.join(","));
+        self.spans.lock().unwrap().insert(id, name);
+        Id::from_u64(id)
+    }
+
+    fn record(&self, _: &Id, _: &Record) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event) {
+        let spans = self.spans.lock().unwrap();
+        let path: Vec<_> = self
+            .stack
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|id| spans[id].clone())
+            .collect();
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", path.join(":"), fields.0.join(" ")));
+    }
+
+    fn enter(&self, span: &Id) {
+        self.stack.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.stack.lock().unwrap().pop();
+    }
+}
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+#[test]
+fn test_control_node_spans() {
+    let collector = Collector::default();
+    let events = collector.events.clone();
+    let mut tree = SequenceNode::new([
+        Box::new(FallbackNode::new([
+            Box::new(InstrumentedNode::new("Open", AlwaysFailure(()))) as Node,
+            Box::new(AlwaysSuccess(())),
+        ])) as Node,
+        Box::new(AlwaysRunning),
+    ]);
+    let res = tracing::subscriber::with_default(collector, || tree.tick(()));
+    assert_eq!(res, BehaviorResult::Running);
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "child{index=0}:child{index=0}:node{name=Open} ticked status=Failure duration",
+            "child{index=0}:child{index=0} ticked status=Failure duration",
+            "child{index=0}:child{index=1} ticked status=Success duration",
+            "child{index=0} ticked status=Success duration",
+            "child{index=1} ticked status=Running duration",
+        ]
+    );
+}
+
+#[test]
+fn test_instrumented_halt() {
+    let collector = Collector::default();
+    let events = collector.events.clone();
+    let mut node = InstrumentedNode::new("Walk", AlwaysRunning);
+    tracing::subscriber::with_default(collector, || {
+        let res: BehaviorResult<(), ()> = node.tick(());
+        assert_eq!(res, BehaviorResult::Running);
+        assert!(BehaviorNodeBase::<(), (), ()>::halt(&mut node).is_clean());
+    });
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "node{name=Walk} ticked status=Running duration",
+            "node{name=Walk} halted clean=true",
+        ]
+    );
+}
@@ -0,0 +1,80 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, InterlockNode};
+
+/// Returns `Running`, trips the interlock while ticked if asked to, and counts halts.
+struct Motor {
+    trip: Option<Rc<Cell<bool>>>,
+    halts: Rc<Cell<u32>>,
+}
+
+impl BehaviorNodeBase<(), (), ()> for Motor {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        if let Some(trip) = &self.trip {
+            trip.set(false);
+        }
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.halts.set(self.halts.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_interlock_before_tick() {
+    let safe = Rc::new(Cell::new(true));
+    let halts = Rc::new(Cell::new(0));
+    let safe2 = safe.clone();
+    let mut node = InterlockNode::new(
+        move || safe2.get(),
+        Motor {
+            trip: None,
+            halts: halts.clone(),
+        },
+    );
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    safe.set(false);
+    assert_eq!(node.tick(()), BehaviorResult::Failure(()));
+    assert!(node.is_tripped());
+    assert_eq!(halts.get(), 1);
+
+    // A non-latching interlock recovers by itself.
+    safe.set(true);
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    assert!(!node.is_tripped());
+}
+
+#[test]
+fn test_interlock_after_tick() {
+    // The child trips the interlock during its tick.
+    let safe = Rc::new(Cell::new(true));
+    let halts = Rc::new(Cell::new(0));
+    let safe2 = safe.clone();
+    let mut node = InterlockNode::new(
+        move || safe2.get(),
+        Motor {
+            trip: Some(safe.clone()),
+            halts: halts.clone(),
+        },
+    );
+    assert_eq!(node.tick(()), BehaviorResult::Failure(()));
+    assert_eq!(halts.get(), 1);
+
+    // A success is not reported once the interlock trips.
+    let safe = Rc::new(Cell::new(true));
+    let safe2 = safe.clone();
+    let mut node = InterlockNode::new(
+        move || {
+            let ok = safe2.get();
+            safe2.set(false);
+            ok
+        },
+        AlwaysSuccess(()),
+    );
+    let res: BehaviorResult<(), ()> = node.tick(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+    assert!(!safe.get());
+}
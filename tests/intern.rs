@@ -0,0 +1,25 @@
+use tiny_behavior_tree::Interner;
+
+#[test]
+fn test_intern() {
+    let mut interner = Interner::new();
+    let door = interner.intern("door");
+    let agent = interner.intern("agent");
+    assert_ne!(door, agent);
+    assert_eq!(interner.intern("door"), door);
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.resolve(door), "door");
+    assert_eq!(interner.resolve(agent), "agent");
+    assert_eq!(interner.get("agent"), Some(agent));
+    assert_eq!(interner.get("key"), None);
+    assert_eq!(format!("{}/open", interner.display(door)), "door/open");
+}
+
+#[test]
+fn test_intern_foreign_symbol() {
+    let mut a = Interner::new();
+    a.intern("door");
+    let sym = a.intern("agent");
+    let b = Interner::new();
+    assert_eq!(b.try_resolve(sym), None);
+}
@@ -0,0 +1,44 @@
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, FallbackNode, InverterNode};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+#[test]
+fn test_inverter() {
+    let mut node: Node = Box::new(InverterNode::new(AlwaysSuccess(())));
+    assert_eq!(node.tick(()), BehaviorResult::Failure(()));
+    let mut node: Node = Box::new(InverterNode::new(AlwaysFailure(())));
+    assert_eq!(node.tick(()), BehaviorResult::Success(()));
+    let mut node: Node = Box::new(InverterNode::new(AlwaysRunning));
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+}
+
+#[test]
+fn test_inverter_in_fallback() {
+    // "Attack unless an ally is in the line of fire"
+    let mut tree = FallbackNode::<(), &str, (), _>::new([
+        Box::new(InverterNode::new(AlwaysFailure("ally clear")))
+            as Box<dyn BehaviorNodeBase<(), &str, ()>>,
+        Box::new(AlwaysSuccess("hold fire")),
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Success("ally clear"));
+}
+
+#[test]
+fn test_inverter_with_map() {
+    let mut node = InverterNode::with_map(
+        AlwaysFailure(404),
+        |_: ()| "found",
+        |code: i32| code.to_string(),
+    );
+    let res: BehaviorResult<String, &str> = node.tick(());
+    assert_eq!(res, BehaviorResult::Success("404".to_string()));
+
+    let mut node = InverterNode::with_map(
+        AlwaysSuccess(()),
+        |_: ()| "found",
+        |code: i32| code.to_string(),
+    );
+    let res: BehaviorResult<String, &str> = node.tick(());
+    assert_eq!(res, BehaviorResult::Failure("found"));
+}
@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::{AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Livelock, LivelockDetector, SequenceNodeWithMemory, Tree,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+/// Returns `Running` for the given number of ticks, then `Success`.
+struct Countdown(u32);
+
+impl BehaviorNodeBase<(), (), ()> for Countdown {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        if 0 < self.0 {
+            self.0 -= 1;
+            BehaviorResult::Running
+        } else {
+            BehaviorResult::Success(())
+        }
+    }
+}
+
+fn stuck_tree() -> Tree<(), (), ()> {
+    Tree::new(Box::new(SequenceNodeWithMemory::new([
+        Box::new(Countdown(1)) as Node,
+        Box::new(AlwaysRunning),
+    ])))
+}
+
+#[test]
+fn test_livelock_detector() {
+    let mut tree = stuck_tree();
+    let mut detector = LivelockDetector::new(3);
+    let mut found = vec![];
+    for _ in 0..10 {
+        let (_, report) = tree.tick_with_report(());
+        found.extend(detector.observe(&report));
+    }
+    // The first two ticks differ, then the sequence keeps resuming the second child.
+    assert_eq!(
+        found,
+        [Livelock {
+            ticks: 4,
+            paths: vec!["".to_string(), "1".to_string()],
+        }]
+    );
+
+    // A node that completes within the threshold is not reported.
+    let mut tree = Tree::new(Box::new(Countdown(2)) as Node);
+    let mut detector = LivelockDetector::new(2);
+    for _ in 0..5 {
+        let (_, report) = tree.tick_with_report(());
+        assert_eq!(detector.observe(&report), None);
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_tree_detect_livelock() {
+    let found = Rc::new(RefCell::new(vec![]));
+    let found2 = found.clone();
+    let mut tree = stuck_tree().detect_livelock(2, move |livelock| {
+        found2.borrow_mut().push(livelock.clone());
+    });
+    for _ in 0..5 {
+        assert_eq!(tree.tick(()), BehaviorResult::Running);
+    }
+    assert_eq!(found.borrow().len(), 1);
+
+    // Halting starts over.
+    tree.halt();
+    for _ in 0..3 {
+        tree.tick(());
+    }
+    assert_eq!(found.borrow().len(), 1);
+    tree.tick(());
+    assert_eq!(found.borrow().len(), 2);
+
+    let mut tree = Tree::new(Box::new(AlwaysSuccess(())) as Node).detect_livelock(0, |_| panic!());
+    for _ in 0..3 {
+        assert_eq!(tree.try_tick(()).unwrap(), BehaviorResult::Success(()));
+    }
+}
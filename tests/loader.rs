@@ -0,0 +1,441 @@
+#![cfg(feature = "serde")]
+use tiny_behavior_tree::loader::{LoadError, NodeDef, TreeDef, UndefinedNodeError};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, ChildMeta, FactoryError, NodeConfig, NodeFactory, ParamError,
+    SequenceNode,
+};
+
+/// Returns `Success` if the parameter "ok" is true.
+struct Check(bool);
+
+impl BehaviorNodeBase<(), (), ()> for Check {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        if self.0 {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+fn factory() -> NodeFactory<(), (), ()> {
+    let mut factory = NodeFactory::with_builtins();
+    factory.register("Check", |cfg| Ok(Check(cfg.get("ok")?)));
+    factory.register("Broken", |_| -> Result<Check, ParamError> {
+        panic!("not implemented")
+    });
+    factory
+}
+
+#[test]
+fn test_load_nested() {
+    let mut tree = factory()
+        .load_json(
+            r#"{
+                "format_version": 1,
+                "root": {
+                    "type": "Fallback",
+                    "children": [
+                        {
+                            "type": "Parallel",
+                            "params": { "success_threshold": 2 },
+                            "children": [
+                                { "type": "Check", "params": { "ok": true } },
+                                { "type": "Check", "params": { "ok": "false" } }
+                            ]
+                        },
+                        { "type": "Check", "params": { "ok": true } }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    assert_eq!(tree.children_mut().len(), 2);
+}
+
+#[test]
+fn test_definition_round_trip() {
+    let def = TreeDef::new(
+        NodeDef::new("Sequence")
+            .with_child(NodeDef::new("Check").with_param("ok", true))
+            .with_child(NodeDef::new("Check").with_param("ok", false)),
+    );
+    let json = serde_json::to_string(&def).unwrap();
+    assert_eq!(
+        json,
+        r#"{"format_version":1,"root":{"type":"Sequence","children":[{"type":"Check","params":{"ok":true}},{"type":"Check","params":{"ok":false}}]}}"#
+    );
+    let mut tree = factory().load_json(&json).unwrap();
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(()));
+}
+
+#[test]
+fn test_save_loaded_tree() {
+    let factory = factory();
+    let json = r#"{"format_version":1,"root":{"type":"Sequence","children":[{"type":"Check","params":{"ok":"true"}},{"type":"Parallel","params":{"success_threshold":"1"},"children":[{"type":"Check","params":{"ok":"false"}}]}]}}"#;
+    let mut tree = factory.load_json(json).unwrap();
+    let def = TreeDef::from_tree(tree.as_mut()).unwrap();
+    assert_eq!(serde_json::to_string(&def).unwrap(), json);
+    assert_eq!(
+        serde_json::from_str::<TreeDef>(&def.to_json()).unwrap(),
+        def
+    );
+
+    // Nodes created without the factory cannot be saved.
+    let mut tree = SequenceNode::new(vec![
+        factory
+            .create("Check", &NodeConfig::new().with("ok", "true"))
+            .unwrap(),
+        Box::new(Check(true)),
+    ]);
+    assert_eq!(
+        TreeDef::from_tree(&mut tree),
+        Err(UndefinedNodeError {
+            path: "".to_string()
+        })
+    );
+    let mut root = factory.create("Fallback", &NodeConfig::new()).unwrap();
+    assert!(root.push_child(Box::new(tree)).is_ok());
+    assert_eq!(
+        TreeDef::from_tree(root.as_mut()).unwrap_err().to_string(),
+        "Node at path \"0\" was not created by a NodeFactory"
+    );
+}
+
+#[test]
+fn test_errors_have_paths() {
+    let factory = factory();
+    let load = |root: NodeDef| factory.build(&TreeDef::new(root)).err().unwrap();
+
+    let err = load(
+        NodeDef::new("Sequence")
+            .with_child(NodeDef::new("Sequence").with_child(NodeDef::new("Broken"))),
+    );
+    assert!(matches!(
+        err,
+        LoadError::Node {
+            ref path,
+            error: FactoryError::Panicked { .. },
+        } if path == "0/0"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"0/0\": Constructor of \"Broken\" panicked: not implemented"
+    );
+
+    let err = load(
+        NodeDef::new("Sequence").with_child(
+            NodeDef::new("Check")
+                .with_param("ok", true)
+                .with_child(NodeDef::new("Sequence")),
+        ),
+    );
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"0\" has children, but \"Check\" does not accept them"
+    );
+
+    let err = load(NodeDef::new("Check").with_param("ok", 1));
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"\": Parameter \"ok\": \"1\" is not a valid bool in \"Check\""
+    );
+
+    let err = factory
+        .load_json(r#"{"format_version":2,"root":{"type":"Sequence"}}"#)
+        .err()
+        .unwrap();
+    assert!(matches!(err, LoadError::UnsupportedVersion(2)));
+
+    // A guard is never given to the constructor as an ordinary parameter.
+    let err = load(
+        NodeDef::new("Sequence").with_child(
+            NodeDef::new("Check")
+                .with_param("ok", true)
+                .with_param("guard", "door.open == false"),
+        ),
+    );
+    assert!(matches!(err, LoadError::GuardsDisabled { ref path } if path == "0"));
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"0\" has a guard, but the guards are not enabled"
+    );
+}
+
+#[test]
+fn test_child_meta_round_trip() {
+    let factory = factory();
+    let json = r#"{"format_version":1,"root":{"type":"Sequence","children":[{"type":"Check","params":{"ok":"true"}},{"type":"Parallel","params":{"success_threshold":"1"},"children":[{"type":"Check","params":{"ok":"false"},"meta":{"weight":0.5,"comment":"rarely"}}],"meta":{"priority":2}}]}}"#;
+    let mut tree = factory.load_json(json).unwrap();
+    assert_eq!(tree.child_meta(0), None);
+    assert_eq!(
+        tree.child_meta(1),
+        Some(&ChildMeta {
+            priority: Some(2),
+            ..ChildMeta::default()
+        })
+    );
+    assert_eq!(
+        tree.children_mut()[1].child_meta(0),
+        Some(&ChildMeta {
+            weight: Some(0.5),
+            comment: Some("rarely".to_owned()),
+            ..ChildMeta::default()
+        })
+    );
+
+    let def = TreeDef::from_tree(tree.as_mut()).unwrap();
+    assert_eq!(serde_json::to_string(&def).unwrap(), json);
+
+    // Only control nodes that store annotations accept them.
+    let err = factory
+        .build(&TreeDef::new(
+            NodeDef::new("RoundRobin").with_child(
+                NodeDef::new("Check")
+                    .with_param("ok", true)
+                    .with_meta(ChildMeta::default()),
+            ),
+        ))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"0\" has meta, but \"RoundRobin\" does not store it"
+    );
+}
+
+#[test]
+fn test_migrate_old_definitions() {
+    let mut factory = factory();
+    assert_eq!(factory.supported_versions(), 1..=1);
+    let old = r#"{"format_version":0,"root":{"type":"Selector","children":[{"type":"Check","params":{"pass":false}},{"type":"Check","params":{"pass":true}}]}}"#;
+    let err = factory.load_json(old).err().unwrap();
+    assert!(matches!(err, LoadError::UnsupportedVersion(0)));
+    assert_eq!(
+        err.to_string(),
+        "Unsupported format version 0 (no migrations to 1)"
+    );
+
+    // Version 0 called fallbacks "Selector" and the parameter of Check "pass".
+    factory.register_migration(0, |def: &mut TreeDef| {
+        def.root.visit_mut(&mut |node| {
+            if node.node_type == "Selector" {
+                node.node_type = "Fallback".to_owned();
+            }
+            if let Some(pass) = node.params.remove("pass") {
+                node.params.insert("ok".to_owned(), pass);
+            }
+        })
+    });
+    // A hook that is not chained to the current version does not count.
+    factory.register_migration(5, |_| panic!("not chained"));
+    assert_eq!(factory.supported_versions(), 0..=1);
+
+    let mut tree = factory.load_json(old).unwrap();
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+
+    // The tree is saved back in the current version.
+    let def = TreeDef::from_tree(tree.as_mut()).unwrap();
+    assert_eq!(
+        serde_json::to_string(&def).unwrap(),
+        r#"{"format_version":1,"root":{"type":"Fallback","children":[{"type":"Check","params":{"ok":"false"}},{"type":"Check","params":{"ok":"true"}}]}}"#
+    );
+    let mut tree = factory.build(&def).unwrap();
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_load_yaml() {
+    let factory = factory();
+    let mut tree = factory
+        .load_yaml(
+            r#"
+format_version: 1
+root:
+  type: ReactiveSequence
+  children:
+    - type: Check
+      params: { ok: true }
+    - type: Fallback
+      children:
+        - type: Check
+          params: { ok: false }
+        - type: Check
+          params: { ok: "true" }
+"#,
+        )
+        .unwrap();
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+
+    let err = factory
+        .load_yaml("format_version: 1\nroot:\n  type: Sequence\n  children:\n    - type: Jump\n")
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"0\": Unknown node type \"Jump\""
+    );
+}
+
+/// A payload with a blackboard for the guards.
+#[cfg(feature = "stdlib")]
+#[derive(Clone)]
+struct Bb(std::rc::Rc<std::cell::RefCell<tiny_behavior_tree::Blackboard>>);
+
+#[cfg(feature = "stdlib")]
+impl tiny_behavior_tree::stdlib::BlackboardPayload for Bb {
+    fn with_blackboard<T>(&self, f: impl FnOnce(&mut tiny_behavior_tree::Blackboard) -> T) -> T {
+        f(&mut self.0.borrow_mut())
+    }
+}
+
+#[cfg(feature = "stdlib")]
+#[test]
+fn test_load_inline_guards() {
+    let mut factory = NodeFactory::<Bb, (), ()>::with_builtins();
+    factory.register("Succeed", |_| {
+        Ok(tiny_behavior_tree::stdlib::AlwaysSuccess(()))
+    });
+    let json = r#"{"format_version":1,"root":{"type":"Fallback","children":[{"type":"Sequence","params":{"guard":"!door.open"},"children":[{"type":"Succeed","params":{"guard":"alert_level < 3"}}]}]}}"#;
+    assert!(matches!(
+        factory.load_json(json).err().unwrap(),
+        LoadError::GuardsDisabled { ref path } if path == "0"
+    ));
+
+    factory.enable_guards();
+    let mut tree = factory.load_json(json).unwrap();
+    let bb = Bb(Default::default());
+    bb.0.borrow_mut().set("door.open", false);
+    bb.0.borrow_mut().set("alert_level", 1);
+    assert_eq!(tree.tick(bb.clone()), BehaviorResult::Success(()));
+    bb.0.borrow_mut().set("alert_level", 5);
+    assert_eq!(tree.tick(bb.clone()), BehaviorResult::Failure(()));
+
+    // The guards are saved back with the nodes and their children.
+    let def = TreeDef::from_tree(tree.as_mut()).unwrap();
+    assert_eq!(serde_json::to_string(&def).unwrap(), json);
+
+    let err = factory
+        .build(&TreeDef::new(NodeDef::new("Sequence").with_child(
+            NodeDef::new("Succeed").with_param("guard", "door.open = true"),
+        )))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"0\": Invalid guard term \"door.open = true\""
+    );
+}
+
+#[cfg(all(feature = "stdlib", feature = "yaml"))]
+#[test]
+fn test_load_yaml_inline_guards() {
+    let mut factory = NodeFactory::<Bb, (), ()>::with_builtins();
+    factory.register("Succeed", |_| {
+        Ok(tiny_behavior_tree::stdlib::AlwaysSuccess(()))
+    });
+    factory.enable_guards();
+    let mut tree = factory
+        .load_yaml(
+            r#"
+format_version: 1
+root:
+  type: Succeed
+  params: { guard: 'state == "patrol"' }
+"#,
+        )
+        .unwrap();
+    let bb = Bb(Default::default());
+    assert_eq!(tree.tick(bb.clone()), BehaviorResult::Failure(()));
+    bb.0.borrow_mut().set("state", "patrol");
+    assert_eq!(tree.tick(bb.clone()), BehaviorResult::Success(()));
+
+    let err = factory
+        .load_yaml("format_version: 1\nroot:\n  type: Succeed\n  params: { guard: 'hp >' }\n")
+        .err()
+        .unwrap();
+    assert!(matches!(err, LoadError::Guard { ref path, .. } if path.is_empty()));
+}
+
+#[cfg(feature = "stdlib")]
+#[test]
+fn test_run_stdlib_leaves() {
+    use tiny_behavior_tree::stdlib::Stdlib;
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut factory = NodeFactory::<Bb, (), ()>::with_builtins();
+    factory.install(&Stdlib::with_events(sender));
+    let mut tree = factory
+        .load_json(
+            r#"{
+                "format_version": 1,
+                "root": {
+                    "type": "Sequence",
+                    "children": [
+                        { "type": "Log", "params": { "message": "patrolling" } },
+                        { "type": "SetBlackboard", "params": { "key": "door", "value": "open" } },
+                        { "type": "SetBlackboard", "params": { "key": "hp", "value": "3" } },
+                        { "type": "CompareBlackboard", "params": { "key": "door", "value": "open" } },
+                        { "type": "CompareBlackboard", "params": { "key": "hp", "value": "3" } },
+                        { "type": "Wait", "params": { "duration": "0.01" } },
+                        {
+                            "type": "Fallback",
+                            "children": [
+                                { "type": "AlwaysFailure" },
+                                { "type": "AlwaysSuccess" },
+                                { "type": "AlwaysRunning" }
+                            ]
+                        },
+                        { "type": "EmitEvent", "params": { "event": "arrived" } }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+    let bb = Bb(Default::default());
+    let res = loop {
+        match tree.tick(bb.clone()) {
+            BehaviorResult::Running => std::thread::sleep(std::time::Duration::from_millis(5)),
+            res => break res,
+        }
+    };
+    assert_eq!(res, BehaviorResult::Success(()));
+    assert_eq!(bb.0.borrow().get::<i64>("hp"), Some(&3));
+    assert_eq!(receiver.try_recv().as_deref(), Ok("arrived"));
+
+    let factory = NodeFactory::<Bb, (), ()>::with_stdlib();
+    assert!(!factory.contains("EmitEvent"));
+    let err = factory
+        .load_json(r#"{"format_version":1,"root":{"type":"Wait","params":{"duration":"-1"}}}"#)
+        .err()
+        .unwrap();
+    assert!(
+        err.to_string().contains("Parameter \"duration\""),
+        "{}",
+        err
+    );
+}
+
+#[cfg(all(feature = "stdlib", feature = "yaml"))]
+#[test]
+fn test_run_yaml_stdlib_leaves() {
+    let mut tree = NodeFactory::<Bb, (), ()>::with_stdlib()
+        .load_yaml(
+            r#"
+format_version: 1
+root:
+  type: Fallback
+  children:
+    - type: CompareBlackboard
+      params: { key: alert, value: "true" }
+    - type: SetBlackboard
+      params: { key: alert, value: "true" }
+"#,
+        )
+        .unwrap();
+    let bb = Bb(Default::default());
+    assert_eq!(tree.tick(bb.clone()), BehaviorResult::Success(()));
+    assert_eq!(bb.0.borrow().get::<bool>("alert"), Some(&true));
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "serde")]
+use tiny_behavior_tree::loader::{LoadError, NodeDef, TreeDef};
+use tiny_behavior_tree::manifest::{Manifest, NodeKind, NodeSpec};
+use tiny_behavior_tree::{BehaviorResult, FactoryError, NodeFactory};
+
+fn factory() -> NodeFactory<(), (), ()> {
+    let manifest = Manifest::from_json(
+        r#"{
+            "nodes": [
+                { "type": "OpenDoor", "kind": "action", "params": ["force"] },
+                { "type": "IsLocked", "kind": "condition" },
+                { "type": "Retry", "kind": "decorator", "params": ["times"] },
+                { "type": "Race", "kind": "control" }
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        manifest.nodes[2],
+        NodeSpec {
+            node_type: "Retry".to_owned(),
+            kind: NodeKind::Decorator,
+            params: vec!["times".to_owned()],
+        }
+    );
+    let mut factory = NodeFactory::with_builtins();
+    manifest.register(&mut factory);
+    factory
+}
+
+#[test]
+fn test_manifest_builds_valid_tree() {
+    let def = TreeDef::new(
+        NodeDef::new("Race")
+            .with_child(NodeDef::new("IsLocked"))
+            .with_child(
+                NodeDef::new("Retry")
+                    .with_param("times", 3)
+                    .with_child(NodeDef::new("OpenDoor").with_param("force", 10)),
+            ),
+    );
+    let mut tree = factory().build(&def).unwrap();
+    assert_eq!(tree.children_mut().len(), 2);
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(()));
+}
+
+#[test]
+fn test_manifest_rejects_missing_param() {
+    let def = TreeDef::new(NodeDef::new("Sequence").with_child(NodeDef::new("OpenDoor")));
+    match factory().build(&def) {
+        Err(LoadError::Node {
+            path,
+            error: FactoryError::Param { node_type, error },
+        }) => {
+            assert_eq!(path, "0");
+            assert_eq!(node_type, "OpenDoor");
+            assert_eq!(error.param, "force");
+        }
+        res => panic!("unexpected result: {:?}", res.err()),
+    }
+}
+
+#[test]
+fn test_manifest_rejects_extra_children() {
+    let leaf = NodeDef::new("IsLocked").with_child(NodeDef::new("IsLocked"));
+    assert!(matches!(
+        factory().build(&TreeDef::new(leaf)),
+        Err(LoadError::NotComposite { .. })
+    ));
+
+    let decorator = NodeDef::new("Retry")
+        .with_param("times", 1)
+        .with_child(NodeDef::new("IsLocked"))
+        .with_child(NodeDef::new("IsLocked"));
+    assert!(matches!(
+        factory().build(&TreeDef::new(decorator)),
+        Err(LoadError::NotComposite { .. })
+    ));
+}
@@ -0,0 +1,42 @@
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, FallbackNode, MapPayloadNode};
+
+type Node = Box<dyn BehaviorNodeBase<(i32, i32), (), ()>>;
+
+struct World {
+    agent: (i32, i32),
+    target: (i32, i32),
+}
+
+/// Succeeds if the offset to the target is within the range along either axis.
+struct Within(i32);
+
+impl BehaviorNodeBase<(i32, i32), (), ()> for Within {
+    fn tick(&mut self, offset: (i32, i32)) -> BehaviorResult<(), ()> {
+        if offset.0.abs() <= self.0 && offset.1.abs() <= self.0 {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+#[test]
+fn test_owned_payload() {
+    let mut tree = MapPayloadNode::new(
+        |world: &World| {
+            (
+                world.target.0 - world.agent.0,
+                world.target.1 - world.agent.1,
+            )
+        },
+        FallbackNode::new([Box::new(Within(1)) as Node, Box::new(Within(3))]),
+    );
+
+    let mut world = World {
+        agent: (0, 0),
+        target: (2, -3),
+    };
+    assert_eq!(tree.tick(&world), BehaviorResult::Success(()));
+    world.agent = (-2, 0);
+    assert_eq!(tree.tick(&world), BehaviorResult::Failure(()));
+}
@@ -0,0 +1,94 @@
+use std::cell::RefCell;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, FallbackNode, FallbackNodeRef, SequenceNode,
+};
+
+type Node = Box<dyn BehaviorNodeBase<i32, Vec<i32>, Vec<i32>>>;
+
+/// Succeeds with its id if the payload is at least its id, fails otherwise.
+struct AtLeast(i32);
+
+impl BehaviorNodeBase<i32, Vec<i32>, Vec<i32>> for AtLeast {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<Vec<i32>, Vec<i32>> {
+        if self.0 <= payload {
+            BehaviorResult::Success(vec![self.0])
+        } else {
+            BehaviorResult::Failure(vec![self.0])
+        }
+    }
+}
+
+fn append(acc: &mut Vec<i32>, mut v: Vec<i32>) {
+    acc.append(&mut v);
+}
+
+#[test]
+fn test_sequence_spreads_children_over_ticks() {
+    let mut tree = SequenceNode::new_with_merger(
+        [
+            Box::new(AtLeast(1)) as Node,
+            Box::new(AtLeast(2)) as Node,
+            Box::new(AtLeast(3)) as Node,
+            Box::new(AtLeast(4)) as Node,
+            Box::new(AtLeast(5)) as Node,
+        ],
+        append,
+    )
+    .max_children_per_tick(2);
+
+    assert_eq!(tree.tick(10), BehaviorResult::Running);
+    assert_eq!(tree.tick(10), BehaviorResult::Running);
+    assert_eq!(tree.tick(10), BehaviorResult::Success(vec![1, 2, 3, 4, 5]));
+
+    // Starts over after completion
+    assert_eq!(tree.tick(10), BehaviorResult::Running);
+    assert_eq!(tree.tick(2), BehaviorResult::Failure(vec![3]));
+    assert_eq!(tree.tick(10), BehaviorResult::Running);
+}
+
+#[test]
+fn test_fallback_halt_discards_progress() {
+    let mut tree = FallbackNode::new_with_merger(
+        [
+            Box::new(AtLeast(3)) as Node,
+            Box::new(AtLeast(2)) as Node,
+            Box::new(AtLeast(1)) as Node,
+        ],
+        append,
+    )
+    .max_children_per_tick(1);
+
+    assert_eq!(tree.tick(0), BehaviorResult::Running);
+    assert_eq!(tree.tick(0), BehaviorResult::Running);
+    assert_eq!(tree.tick(0), BehaviorResult::Failure(vec![3, 2, 1]));
+
+    assert_eq!(tree.tick(0), BehaviorResult::Running);
+    assert!(tree.halt().is_clean());
+    assert_eq!(tree.tick(2), BehaviorResult::Running);
+    assert_eq!(tree.tick(2), BehaviorResult::Success(vec![2]));
+}
+
+#[test]
+fn test_fallback_ref_spreads_children_over_ticks() {
+    struct Counter<'a>(&'a RefCell<Vec<usize>>, usize);
+
+    impl<'a> BehaviorNodeBase<&'a i32, (), ()> for Counter<'a> {
+        fn tick(&mut self, _payload: &'a i32) -> BehaviorResult<(), ()> {
+            self.0.borrow_mut().push(self.1);
+            BehaviorResult::Failure(())
+        }
+    }
+
+    let log = RefCell::new(vec![]);
+    let mut tree = FallbackNodeRef::<i32, (), (), _>::new(
+        (0..5)
+            .map(|i| Box::new(Counter(&log, i)) as Box<dyn BehaviorNodeBase<&i32, (), ()>>)
+            .collect::<Vec<_>>(),
+    )
+    .max_children_per_tick(3);
+
+    assert_eq!(tree.tick(&0), BehaviorResult::Running);
+    assert_eq!(*log.borrow(), [0, 1, 2]);
+    assert_eq!(tree.tick(&0), BehaviorResult::Failure(()));
+    assert_eq!(*log.borrow(), [0, 1, 2, 3, 4]);
+}
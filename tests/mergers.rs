@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use tiny_behavior_tree::mergers;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, FallbackNode, MergeResult, ParallelNode, SequenceNode,
+};
+
+#[test]
+fn test_numeric_mergers() {
+    type Node = Box<dyn BehaviorNodeBase<(), i32, ()>>;
+    let children = || {
+        vec![
+            Box::new(AlwaysSuccess(3)) as Node,
+            Box::new(AlwaysSuccess(-4)),
+            Box::new(AlwaysSuccess(5)),
+        ]
+    };
+    let mut tree = SequenceNode::new_with_merger(children(), mergers::sum);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(4));
+
+    type OptNode = Box<dyn BehaviorNodeBase<(), Option<i32>, ()>>;
+    let children = || {
+        vec![
+            Box::new(AlwaysSuccess(Some(-3))) as OptNode,
+            Box::new(AlwaysSuccess(None)),
+            Box::new(AlwaysSuccess(Some(2))),
+        ]
+    };
+    let mut tree = SequenceNode::new_with_merger(children(), mergers::max);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(Some(2)));
+    let mut tree = SequenceNode::new_with_merger(children(), mergers::min);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(Some(-3)));
+}
+
+#[test]
+fn test_collection_mergers() {
+    type Node = Box<dyn BehaviorNodeBase<(), BTreeMap<&'static str, i32>, ()>>;
+    let mut tree = SequenceNode::new_with_merger(
+        [
+            Box::new(AlwaysSuccess(BTreeMap::from([("hp", 10), ("mp", 3)]))) as Node,
+            Box::new(AlwaysSuccess(BTreeMap::from([("mp", 5)]))),
+        ],
+        mergers::extend,
+    );
+    assert_eq!(
+        tree.tick(()),
+        BehaviorResult::Success(BTreeMap::from([("hp", 10), ("mp", 5)]))
+    );
+
+    // Count the failed attempts of a fallback.
+    type CountNode = Box<dyn BehaviorNodeBase<(), (), usize>>;
+    let mut tree = FallbackNode::new_with_merger(
+        [
+            Box::new(AlwaysFailure(0usize)) as CountNode,
+            Box::new(AlwaysFailure(0)),
+            Box::new(AlwaysFailure(0)),
+        ],
+        mergers::count,
+    );
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(3));
+}
+
+#[test]
+fn test_merged() {
+    type Node = Box<dyn BehaviorNodeBase<(), Vec<i32>, Option<&'static str>>>;
+    let children = || {
+        vec![
+            Box::new(AlwaysSuccess(vec![1])) as Node,
+            Box::new(AlwaysFailure(Some("locked"))),
+            Box::new(AlwaysSuccess(vec![2, 3])),
+            Box::new(AlwaysFailure(None)),
+        ]
+    };
+    let mut tree = ParallelNode::merged(children(), 2);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(vec![1, 2, 3]));
+    let mut tree = FallbackNode::merged(children().split_off(1));
+    assert_eq!(tree.tick(()), BehaviorResult::Success(vec![2, 3]));
+    let mut tree = FallbackNode::merged([children().remove(1), children().remove(3)]);
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(Some("locked")));
+}
+
+#[test]
+fn test_merge_result_impl() {
+    struct Concat(&'static str);
+
+    impl MergeResult<String> for Concat {
+        fn merge(&self, acc: &mut String, v: String) {
+            if !acc.is_empty() {
+                acc.push_str(self.0);
+            }
+            acc.push_str(&v);
+        }
+    }
+
+    type Node = Box<dyn BehaviorNodeBase<(), String, ()>>;
+    let mut tree = SequenceNode::new_with_merger(
+        [
+            Box::new(AlwaysSuccess("open".to_string())) as Node,
+            Box::new(AlwaysSuccess("enter".to_string())),
+        ],
+        Concat(", "),
+    );
+    assert_eq!(
+        tree.tick(()),
+        BehaviorResult::Success("open, enter".to_string())
+    );
+}
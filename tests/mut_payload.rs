@@ -0,0 +1,85 @@
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, ChildMut, FallbackNodeMut, SequenceNodeMut,
+};
+
+#[derive(Default)]
+struct Door {
+    open: bool,
+    locked: bool,
+    hits: u32,
+}
+
+type Node = ChildMut<Door, (), ()>;
+
+struct IsDoorOpen;
+
+impl<'p> BehaviorNodeBase<&'p mut Door, (), ()> for IsDoorOpen {
+    fn tick(&mut self, door: &'p mut Door) -> BehaviorResult<(), ()> {
+        if door.open {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+struct OpenDoor;
+
+impl<'p> BehaviorNodeBase<&'p mut Door, (), ()> for OpenDoor {
+    fn tick(&mut self, door: &'p mut Door) -> BehaviorResult<(), ()> {
+        if door.locked {
+            BehaviorResult::Failure(())
+        } else {
+            door.open = true;
+            BehaviorResult::Success(())
+        }
+    }
+}
+
+struct SmashDoor;
+
+impl<'p> BehaviorNodeBase<&'p mut Door, (), ()> for SmashDoor {
+    fn tick(&mut self, door: &'p mut Door) -> BehaviorResult<(), ()> {
+        door.hits += 1;
+        if 3 <= door.hits {
+            door.locked = false;
+        }
+        BehaviorResult::Running
+    }
+}
+
+fn build_tree() -> Node {
+    Box::new(SequenceNodeMut::new([
+        Box::new(FallbackNodeMut::new([
+            Box::new(OpenDoor) as Node,
+            Box::new(SmashDoor),
+        ])) as Node,
+        Box::new(IsDoorOpen),
+    ]))
+}
+
+#[test]
+fn test_mut_payload() {
+    let mut tree = build_tree();
+    let mut door = Door::default();
+    assert_eq!(tree.tick(&mut door), BehaviorResult::Success(()));
+    assert!(door.open);
+}
+
+#[test]
+fn test_mut_payload_running() {
+    let mut tree = build_tree();
+    let mut door = Door {
+        locked: true,
+        ..Door::default()
+    };
+    for _ in 0..3 {
+        assert_eq!(tree.tick(&mut door), BehaviorResult::Running);
+    }
+    assert!(!door.open);
+    assert_eq!(
+        tree.try_tick(&mut door).unwrap(),
+        BehaviorResult::Success(())
+    );
+    assert_eq!(door.hits, 3);
+}
@@ -0,0 +1,80 @@
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, FactoryError, NodeConfig, NodeFactory, NodePlugin, ParamError,
+};
+
+struct Succeed;
+
+impl BehaviorNodeBase<(), (), ()> for Succeed {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        BehaviorResult::Success(())
+    }
+}
+
+struct Fail;
+
+impl BehaviorNodeBase<(), (), ()> for Fail {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        BehaviorResult::Failure(())
+    }
+}
+
+struct Leaves;
+
+impl NodePlugin<(), (), ()> for Leaves {
+    fn register(&self, factory: &mut NodeFactory<(), (), ()>) {
+        factory.register("Succeed", |_| Ok(Succeed));
+        factory.register("Fail", |_| Ok(Fail));
+    }
+}
+
+#[test]
+fn test_builtins_with_plugin() {
+    let mut factory = NodeFactory::with_builtins();
+    factory.install(&Leaves);
+    assert!(factory.contains("ReactiveFallback"));
+    assert!(factory.names().any(|name| name == "Succeed"));
+
+    let mut root = factory.create("Fallback", &NodeConfig::new()).unwrap();
+    assert!(root
+        .push_child(factory.create("Fail", &NodeConfig::new()).unwrap())
+        .is_ok());
+    assert!(root
+        .push_child(factory.create("Succeed", &NodeConfig::new()).unwrap())
+        .is_ok());
+    assert_eq!(root.tick(()), BehaviorResult::Success(()));
+
+    let cfg = NodeConfig::new().with("success_threshold", "2");
+    let mut parallel = factory.create("Parallel", &cfg).unwrap();
+    assert!(parallel
+        .push_child(factory.create("Succeed", &NodeConfig::new()).unwrap())
+        .is_ok());
+    assert!(parallel
+        .push_child(factory.create("Fail", &NodeConfig::new()).unwrap())
+        .is_ok());
+    assert_eq!(parallel.tick(()), BehaviorResult::Failure(()));
+}
+
+#[test]
+fn test_errors() {
+    let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+    factory.register("Explode", |cfg| -> Result<Succeed, ParamError> {
+        panic!("cannot explode with {:?}", cfg.get_str("power"))
+    });
+
+    assert_eq!(
+        factory.create("Jump", &NodeConfig::new()).err(),
+        Some(FactoryError::UnknownType("Jump".to_string()))
+    );
+    assert_eq!(
+        factory.create("Parallel", &NodeConfig::new()).err(),
+        Some(FactoryError::Param {
+            node_type: "Parallel".to_string(),
+            error: ParamError::missing("success_threshold"),
+        })
+    );
+    let cfg = NodeConfig::new().with("power", "9000");
+    assert_eq!(
+        factory.create("Explode", &cfg).err().unwrap().to_string(),
+        "Constructor of \"Explode\" panicked: cannot explode with Some(\"9000\")"
+    );
+}
@@ -0,0 +1,113 @@
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, NodeConfig, NodeFactory, ResultKind, SequenceNode, Tree, TreeOutline,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+#[test]
+fn test_nested_outline() {
+    let mut factory = NodeFactory::with_builtins();
+    factory.register("Walk", |_| Ok(AlwaysRunning));
+    factory.register("Look", |_| Ok(AlwaysSuccess(())));
+    let node = |name: &str| factory.create(name, &NodeConfig::new()).unwrap();
+
+    let mut inner = node("ReactiveSequence");
+    assert!(inner.push_child(node("Look")).is_ok());
+    assert!(inner.push_child(node("Walk")).is_ok());
+    let mut root = node("Sequence");
+    assert!(root.push_child(inner).is_ok());
+    assert!(root.push_child(Box::new(AlwaysFailure(()))).is_ok());
+    let mut tree = Tree::new(root);
+
+    assert_eq!(
+        tree.outline().to_string(),
+        "\
+Sequence
+|-- ReactiveSequence
+|   |-- Look
+|   `-- Walk
+`-- Node
+"
+    );
+
+    tree.tick_with_report(());
+    let outline = tree.outline();
+    assert_eq!(outline.nodes[3].path, "0/1");
+    assert_eq!(outline.nodes[3].status, Some(ResultKind::Running));
+    assert_eq!(
+        outline.to_string(),
+        "\
+Sequence [Running]
+|-- ReactiveSequence [Running]
+|   |-- Look [Success]
+|   `-- Walk [Running]
+`-- Node
+"
+    );
+
+    tree.reset();
+    assert!(tree.last_report().is_none());
+    assert_eq!(tree.outline().nodes[0].status, None);
+}
+
+#[test]
+fn test_outline_of_node() {
+    let mut seq = SequenceNode::new([
+        Box::new(SequenceNode::new([Box::new(AlwaysSuccess(())) as Node])) as Node,
+        Box::new(AlwaysSuccess(())),
+    ]);
+    assert_eq!(
+        TreeOutline::new(&mut seq, None).to_string(),
+        "Node\n|-- Node\n|   `-- Node\n`-- Node\n"
+    );
+}
+
+#[test]
+fn test_nested_dot_and_mermaid() {
+    let mut factory = NodeFactory::<(), (), ()>::with_builtins();
+    factory.register("Walk", |_| Ok(AlwaysRunning));
+    factory.register("Say", |_| Ok(AlwaysSuccess(())));
+    let node = |name: &str, cfg: &NodeConfig| factory.create(name, cfg).unwrap();
+
+    let mut inner = node("Sequence", &NodeConfig::new());
+    let say = NodeConfig::new().with("text", "\"hi\"");
+    assert!(inner.push_child(node("Say", &say)).is_ok());
+    assert!(inner.push_child(node("Walk", &NodeConfig::new())).is_ok());
+    let mut root = node("Fallback", &NodeConfig::new());
+    assert!(root.push_child(inner).is_ok());
+    assert!(root.push_child(node("Walk", &NodeConfig::new())).is_ok());
+    let mut tree = Tree::new(root);
+    tree.tick_with_report(());
+    let outline = tree.outline();
+
+    assert_eq!(
+        outline.to_dot(),
+        r#"digraph tree {
+    n0 [label="Fallback\n[Running]"];
+    n1 [label="Sequence\n[Running]"];
+    n2 [label="Say\ntext=\"hi\"\n[Success]"];
+    n3 [label="Walk\n[Running]"];
+    n4 [label="Walk"];
+    n0 -> n1;
+    n1 -> n2;
+    n1 -> n3;
+    n0 -> n4;
+}
+"#
+    );
+    assert_eq!(
+        outline.to_mermaid(),
+        r#"flowchart TD
+    n0["Fallback<br/>[Running]"]
+    n1["Sequence<br/>[Running]"]
+    n2["Say<br/>text=#quot;hi#quot;<br/>[Success]"]
+    n3["Walk<br/>[Running]"]
+    n4["Walk"]
+    n0 --> n1
+    n1 --> n2
+    n1 --> n3
+    n0 --> n4
+"#
+    );
+}
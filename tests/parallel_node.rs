@@ -0,0 +1,102 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, ParallelNode};
+
+type Node = Box<dyn BehaviorNodeBase<(), Vec<i32>, i32>>;
+
+fn append(acc: &mut Vec<i32>, mut v: Vec<i32>) {
+    acc.append(&mut v);
+}
+
+/// Runs until halted, recording the halts.
+struct Motion(Rc<RefCell<usize>>);
+
+impl BehaviorNodeBase<(), Vec<i32>, i32> for Motion {
+    fn tick(&mut self, _payload: ()) -> BehaviorResult<Vec<i32>, i32> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        *self.0.borrow_mut() += 1;
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_success_threshold() {
+    let halts = Rc::new(RefCell::new(0));
+    let mut tree = ParallelNode::new_with_merger(
+        [
+            Box::new(AlwaysSuccess(vec![1])) as Node,
+            Box::new(Motion(halts.clone())),
+            Box::new(AlwaysFailure(2)),
+            Box::new(AlwaysSuccess(vec![3])),
+        ],
+        2,
+        append,
+    );
+    assert_eq!(tree.tick(()), BehaviorResult::Success(vec![1, 3]));
+    assert_eq!(*halts.borrow(), 1);
+}
+
+#[test]
+fn test_running_until_decided() {
+    let halts = Rc::new(RefCell::new(0));
+    let mut tree = ParallelNode::new(
+        [
+            Box::new(AlwaysSuccess(vec![1])) as Node,
+            Box::new(Motion(halts.clone())),
+            Box::new(AlwaysFailure(2)),
+        ],
+        2,
+    );
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!(*halts.borrow(), 0);
+
+    let mut tree = ParallelNode::new(
+        [
+            Box::new(AlwaysFailure(1)) as Node,
+            Box::new(AlwaysRunning),
+            Box::new(AlwaysFailure(2)),
+        ],
+        2,
+    );
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(2));
+}
+
+/// Succeeds once in the given tick, and runs otherwise, counting the ticks.
+struct SucceedAt(usize, Rc<RefCell<usize>>);
+
+impl BehaviorNodeBase<(), Vec<i32>, i32> for SucceedAt {
+    fn tick(&mut self, _payload: ()) -> BehaviorResult<Vec<i32>, i32> {
+        let mut ticks = self.1.borrow_mut();
+        *ticks += 1;
+        if *ticks == self.0 {
+            BehaviorResult::Success(vec![self.0 as i32])
+        } else {
+            BehaviorResult::Running
+        }
+    }
+}
+
+#[test]
+fn test_completed_children_are_ticked_again() {
+    let first = Rc::new(RefCell::new(0));
+    let second = Rc::new(RefCell::new(0));
+    let mut tree = ParallelNode::new_with_merger(
+        [
+            Box::new(SucceedAt(1, first.clone())) as Node,
+            Box::new(SucceedAt(2, second.clone())),
+        ],
+        2,
+        append,
+    );
+    // The first child succeeds in the first tick, but it is ticked again in
+    // the second one and runs, so its success does not count anymore.
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!((*first.borrow(), *second.borrow()), (2, 2));
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!((*first.borrow(), *second.borrow()), (3, 3));
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "rayon")]
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread::{self, ThreadId};
+use tiny_behavior_tree::parallel_rayon::ParallelRayonNode;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, ResultKind, Tree};
+
+type Node = Box<dyn BehaviorNodeBase<u32, Vec<u32>, ()> + Send>;
+
+/// Succeeds with its number times the payload, remembering the thread.
+struct Scale(u32, Arc<std::sync::Mutex<Vec<ThreadId>>>);
+
+impl BehaviorNodeBase<u32, Vec<u32>, ()> for Scale {
+    fn tick(&mut self, payload: u32) -> BehaviorResult<Vec<u32>, ()> {
+        self.1.lock().unwrap().push(thread::current().id());
+        BehaviorResult::Success(vec![self.0 * payload])
+    }
+}
+
+#[test]
+fn test_parallel_rayon_merged_in_order() {
+    let pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap(),
+    );
+    let threads = Arc::default();
+    let mut tree = ParallelRayonNode::merged(
+        (1..=6)
+            .map(|i| Box::new(Scale(i, Arc::clone(&threads))) as Node)
+            .collect::<Vec<_>>(),
+        6,
+    )
+    .in_pool(pool.clone());
+    assert_eq!(
+        tree.tick(10),
+        BehaviorResult::Success(vec![10, 20, 30, 40, 50, 60])
+    );
+
+    let threads = threads.lock().unwrap();
+    assert_eq!(threads.len(), 6);
+    assert!(!threads.contains(&thread::current().id()));
+}
+
+/// Runs forever, counting halts.
+struct Halted(Arc<AtomicU32>);
+
+impl BehaviorNodeBase<(), (), ()> for Halted {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_parallel_rayon_threshold() {
+    type Node = Box<dyn BehaviorNodeBase<(), (), ()> + Send>;
+    let halted = Arc::new(AtomicU32::new(0));
+    let mut tree = ParallelRayonNode::new(
+        [
+            Box::new(AlwaysSuccess(())) as Node,
+            Box::new(AlwaysRunning),
+            Box::new(Halted(halted.clone())),
+        ],
+        2,
+    );
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!(halted.load(Ordering::Relaxed), 0);
+
+    let mut tree = Tree::new(Box::new(ParallelRayonNode::new(
+        [
+            Box::new(AlwaysFailure(())) as Node,
+            Box::new(AlwaysFailure(())),
+            Box::new(Halted(halted.clone())),
+        ],
+        2,
+    )));
+    let (res, report) = tree.tick_with_report(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+    assert_eq!(halted.load(Ordering::Relaxed), 1);
+    assert_eq!(report.status("0"), Some(ResultKind::Failure));
+    assert_eq!(report.status("2"), Some(ResultKind::Running));
+}
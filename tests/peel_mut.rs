@@ -0,0 +1,130 @@
+use tiny_behavior_tree::{
+    peel_node_def_mut, BehaviorNodeBase, BehaviorResult, ChildMut, FallbackNodeMut, SequenceNodeMut,
+};
+
+struct Door {
+    open: bool,
+    locked: bool,
+}
+
+struct Agent {
+    has_key: bool,
+    in_room: bool,
+}
+
+struct State {
+    door: Door,
+    agent: Agent,
+}
+
+peel_node_def_mut!(
+    PeelDoorNode,
+    State,
+    Door,
+    (),
+    (),
+    |payload: &'a mut State| &mut payload.door
+);
+
+peel_node_def_mut!(
+    PeelAgentNode,
+    State,
+    Agent,
+    (),
+    (),
+    |payload: &'a mut State| &mut payload.agent
+);
+
+struct OpenDoor;
+
+impl<'a> BehaviorNodeBase<&'a mut Door, (), ()> for OpenDoor {
+    fn tick(&mut self, door: &'a mut Door) -> BehaviorResult<(), ()> {
+        if door.locked {
+            BehaviorResult::Failure(())
+        } else {
+            door.open = true;
+            BehaviorResult::Success(())
+        }
+    }
+}
+
+struct UnlockDoor;
+
+impl<'a> BehaviorNodeBase<&'a mut Door, (), ()> for UnlockDoor {
+    fn tick(&mut self, door: &'a mut Door) -> BehaviorResult<(), ()> {
+        door.locked = false;
+        BehaviorResult::Success(())
+    }
+}
+
+struct HaveKey;
+
+impl<'a> BehaviorNodeBase<&'a mut Agent, (), ()> for HaveKey {
+    fn tick(&mut self, agent: &'a mut Agent) -> BehaviorResult<(), ()> {
+        if agent.has_key {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+struct EnterRoom;
+
+impl<'a> BehaviorNodeBase<&'a mut Agent, (), ()> for EnterRoom {
+    fn tick(&mut self, agent: &'a mut Agent) -> BehaviorResult<(), ()> {
+        agent.in_room = true;
+        BehaviorResult::Success(())
+    }
+}
+
+type Node = ChildMut<State, (), ()>;
+
+fn build_tree() -> Node {
+    Box::new(SequenceNodeMut::new([
+        Box::new(FallbackNodeMut::new([
+            Box::new(PeelDoorNode(OpenDoor)) as Node,
+            Box::new(SequenceNodeMut::new([
+                Box::new(PeelAgentNode(HaveKey)) as Node,
+                Box::new(PeelDoorNode(UnlockDoor)),
+                Box::new(PeelDoorNode(OpenDoor)),
+            ])),
+        ])) as Node,
+        Box::new(PeelAgentNode(EnterRoom)),
+    ]))
+}
+
+#[test]
+fn test_unlock_door() {
+    let mut state = State {
+        door: Door {
+            open: false,
+            locked: true,
+        },
+        agent: Agent {
+            has_key: true,
+            in_room: false,
+        },
+    };
+    assert_eq!(build_tree().tick(&mut state), BehaviorResult::Success(()));
+    assert!(state.door.open);
+    assert!(!state.door.locked);
+    assert!(state.agent.in_room);
+}
+
+#[test]
+fn test_locked_door() {
+    let mut state = State {
+        door: Door {
+            open: false,
+            locked: true,
+        },
+        agent: Agent {
+            has_key: false,
+            in_room: false,
+        },
+    };
+    assert_eq!(build_tree().tick(&mut state), BehaviorResult::Failure(()));
+    assert!(!state.door.open);
+    assert!(!state.agent.in_room);
+}
@@ -0,0 +1,168 @@
+mod nodes {
+    use tiny_behavior_tree::{peel_node_def, peel_node_def_mut, peel_node_def_opt};
+
+    pub struct Slot<T> {
+        pub item: Option<T>,
+    }
+
+    pub struct Inventory<T> {
+        pub left: Slot<T>,
+        pub right: Slot<T>,
+    }
+
+    peel_node_def!(pub PeelLeftNode<T>, Inventory<T>, Slot<T>, (), (), |payload: &'a Inventory<T>| &payload.left);
+
+    peel_node_def_mut!(pub(crate) PeelRightNode<T: Clone>, Inventory<T>, Slot<T>, (), (), |payload: &'a mut Inventory<T>| &mut payload.right);
+
+    peel_node_def_opt!(pub PeelLeftItemNode<T>, Inventory<T>, T, (), Option<&'static str>, |payload: &'a Inventory<T>| payload.left.item.as_ref());
+}
+
+use nodes::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, SequenceNodeRef};
+
+struct IsHolding;
+
+impl<'a, T> BehaviorNodeBase<&'a Slot<T>, (), ()> for IsHolding {
+    fn tick(&mut self, slot: &'a Slot<T>) -> BehaviorResult<(), ()> {
+        if slot.item.is_some() {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+struct Drop;
+
+impl<'a, T> BehaviorNodeBase<&'a mut Slot<T>, (), ()> for Drop {
+    fn tick(&mut self, slot: &'a mut Slot<T>) -> BehaviorResult<(), ()> {
+        slot.item = None;
+        BehaviorResult::Success(())
+    }
+}
+
+#[test]
+fn test_generic_peel_node() {
+    let inventory = Inventory {
+        left: Slot {
+            item: Some("sword"),
+        },
+        right: Slot { item: None },
+    };
+    assert_eq!(
+        PeelLeftNode(IsHolding).tick(&inventory),
+        BehaviorResult::Success(())
+    );
+
+    let inventory = Inventory {
+        left: Slot::<u32> { item: None },
+        right: Slot { item: Some(3) },
+    };
+    assert_eq!(
+        PeelLeftNode(IsHolding).tick(&inventory),
+        BehaviorResult::Failure(())
+    );
+}
+
+#[test]
+fn test_generic_peel_node_mut() {
+    let mut inventory = Inventory {
+        left: Slot { item: Some(1) },
+        right: Slot { item: Some(2) },
+    };
+    assert_eq!(
+        PeelRightNode(Drop).tick(&mut inventory),
+        BehaviorResult::Success(())
+    );
+    assert_eq!(inventory.left.item, Some(1));
+    assert_eq!(inventory.right.item, None);
+}
+
+struct IsSword;
+
+impl<'a> BehaviorNodeBase<&'a &'static str, (), Option<&'static str>> for IsSword {
+    fn tick(&mut self, item: &'a &'static str) -> BehaviorResult<(), Option<&'static str>> {
+        if *item == "sword" {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(Some(item))
+        }
+    }
+}
+
+#[test]
+fn test_optional_peel_node() {
+    let mut tree = PeelLeftItemNode(IsSword);
+    let mut inventory = Inventory {
+        left: Slot {
+            item: Some("shield"),
+        },
+        right: Slot { item: None },
+    };
+    assert_eq!(
+        tree.tick(&inventory),
+        BehaviorResult::Failure(Some("shield"))
+    );
+
+    inventory.left.item = None;
+    assert_eq!(tree.tick(&inventory), BehaviorResult::Failure(None));
+    assert_eq!(
+        tree.try_tick(&inventory).unwrap(),
+        BehaviorResult::Failure(None)
+    );
+}
+
+/// Keeps running, counting halts and resets.
+struct Waiting {
+    halted: Rc<Cell<u32>>,
+    reset: Rc<Cell<u32>>,
+}
+
+impl<P, R, F> BehaviorNodeBase<P, R, F> for Waiting {
+    fn tick(&mut self, _: P) -> BehaviorResult<R, F> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.halted.set(self.halted.get() + 1);
+        HaltReport::clean()
+    }
+
+    fn reset(&mut self) -> HaltReport {
+        self.reset.set(self.reset.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_peel_node_forwards_halt() {
+    let halted = Rc::new(Cell::new(0));
+    let reset = Rc::new(Cell::new(0));
+    let waiting = || Waiting {
+        halted: halted.clone(),
+        reset: reset.clone(),
+    };
+    let inventory = Inventory {
+        left: Slot { item: Some(1) },
+        right: Slot { item: None },
+    };
+
+    let mut tree = SequenceNodeRef::new([
+        Box::new(PeelLeftNode(waiting())) as Box<dyn BehaviorNodeBase<&Inventory<i32>, (), ()>>
+    ]);
+    assert_eq!(tree.tick(&inventory), BehaviorResult::Running);
+    assert!(tree.halt().is_clean());
+    assert_eq!(halted.get(), 1);
+    assert!(tree.reset().is_clean());
+    assert_eq!(reset.get(), 1);
+
+    type Item<'a> = dyn BehaviorNodeBase<&'a Inventory<i32>, (), Option<&'static str>>;
+    let node: &mut Item = &mut PeelLeftItemNode(waiting());
+    assert_eq!(node.tick(&inventory), BehaviorResult::Running);
+    assert!(node.halt().is_clean());
+    assert_eq!(halted.get(), 2);
+    assert!(node.reset().is_clean());
+    assert_eq!(reset.get(), 2);
+}
@@ -0,0 +1,68 @@
+use std::cell::Cell;
+use tiny_behavior_tree::testing::AlwaysRunning;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, PeelOnce, SequenceNode};
+
+type Node = Box<dyn BehaviorNodeBase<i32, (), ()>>;
+
+struct AtLeast(i32);
+
+impl BehaviorNodeBase<i32, (), ()> for AtLeast {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<(), ()> {
+        if self.0 <= payload {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+struct World {
+    positions: Vec<i32>,
+}
+
+#[test]
+fn test_projection_runs_once_per_tick() {
+    let queries = Cell::new(0);
+    let mut tree = PeelOnce::new(
+        |world: &World| {
+            queries.set(queries.get() + 1);
+            world.positions.iter().copied().max().unwrap_or(0)
+        },
+        SequenceNode::<i32, (), (), _>::new([
+            Box::new(AtLeast(1)) as Node,
+            Box::new(AtLeast(2)) as Node,
+            Box::new(AtLeast(3)) as Node,
+        ]),
+    );
+
+    let world = World {
+        positions: vec![1, 5, 2],
+    };
+    assert_eq!(tree.tick(&world), BehaviorResult::Success(()));
+    assert_eq!(queries.get(), 1);
+
+    let world = World { positions: vec![2] };
+    assert_eq!(tree.tick(&world), BehaviorResult::Failure(()));
+    assert_eq!(queries.get(), 2);
+}
+
+#[test]
+fn test_halt_is_forwarded() {
+    struct Stubborn;
+
+    impl BehaviorNodeBase<i32, (), ()> for Stubborn {
+        fn tick(&mut self, _payload: i32) -> BehaviorResult<(), ()> {
+            BehaviorResult::Running
+        }
+
+        fn halt(&mut self) -> HaltReport {
+            HaltReport::incomplete("stubborn")
+        }
+    }
+
+    let mut tree = PeelOnce::new(
+        |world: &World| world.positions.len() as i32,
+        SequenceNode::<i32, (), (), _>::new([Box::new(AlwaysRunning) as Node, Box::new(Stubborn)]),
+    );
+    assert_eq!(tree.halt().issues()[0].path, "1");
+}
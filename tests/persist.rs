@@ -0,0 +1,135 @@
+#![cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use tiny_behavior_tree::persist::{RestoreError, SavedBlackboard, SerdeRegistry};
+use tiny_behavior_tree::Blackboard;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Memory {
+    last_seen: (i32, i32),
+    alerted: bool,
+}
+
+struct Opaque;
+
+fn registry() -> SerdeRegistry {
+    let mut types = SerdeRegistry::new();
+    types.register::<Memory>("memory");
+    types.register::<u32>("u32");
+    types
+}
+
+#[test]
+fn test_round_trip() {
+    let types = registry();
+    let mut bb = Blackboard::new();
+    bb.set(
+        "enemy",
+        Memory {
+            last_seen: (3, 4),
+            alerted: true,
+        },
+    );
+    bb.set_for_generations("alert", 2u32, 3);
+    bb.advance_generation();
+    bb.set("handle", Opaque);
+
+    let saved = types.save(&bb).unwrap();
+    assert_eq!(saved.skipped, ["handle"]);
+    let json = serde_json::to_string(&saved).unwrap();
+    assert_eq!(
+        json,
+        r#"{"entries":[{"key":"alert","type":"u32","value":2,"expires_in":2},{"key":"enemy","type":"memory","value":{"alerted":true,"last_seen":[3,4]}}],"skipped":["handle"]}"#
+    );
+
+    let mut restored = Blackboard::new();
+    types
+        .restore(&mut restored, &serde_json::from_str(&json).unwrap())
+        .unwrap();
+    assert_eq!(
+        restored.get::<Memory>("enemy"),
+        Some(&Memory {
+            last_seen: (3, 4),
+            alerted: true
+        })
+    );
+    restored.advance_generation();
+    assert!(restored.contains("alert"));
+    restored.advance_generation();
+    assert!(!restored.contains("alert"));
+}
+
+#[test]
+fn test_restore_is_all_or_nothing() {
+    let types = registry();
+    let saved: SavedBlackboard = serde_json::from_str(
+        r#"{"entries":[{"key":"alert","type":"u32","value":1},{"key":"enemy","type":"memory","value":5}]}"#,
+    )
+    .unwrap();
+    let mut bb = Blackboard::new();
+    let err = types.restore(&mut bb, &saved).unwrap_err();
+    assert!(matches!(err, RestoreError::Invalid { ref key, .. } if key == "enemy"));
+    assert!(bb.is_empty());
+
+    let saved: SavedBlackboard =
+        serde_json::from_str(r#"{"entries":[{"key":"alert","type":"u16","value":1}]}"#).unwrap();
+    assert_eq!(
+        types.restore(&mut bb, &saved).unwrap_err().to_string(),
+        "Unknown type \"u16\" of entry \"alert\""
+    );
+}
+
+#[test]
+fn test_register_tag_again() {
+    let mut types = registry();
+    // Another type takes over the tag, so the former one is not saved with it.
+    types.register::<i64>("u32");
+    let mut bb = Blackboard::new();
+    bb.set("alert", 2u32);
+    bb.set("hp", 30i64);
+    let saved = types.save(&bb).unwrap();
+    assert_eq!(saved.skipped, ["alert"]);
+    assert_eq!(
+        serde_json::to_string(&saved.entries).unwrap(),
+        r#"[{"key":"hp","type":"u32","value":30}]"#
+    );
+
+    let mut restored = Blackboard::new();
+    types.restore(&mut restored, &saved).unwrap();
+    assert_eq!(restored.get::<i64>("hp"), Some(&30));
+    assert_eq!(restored.get::<u32>("hp"), None);
+}
+
+#[test]
+fn test_register_type_again() {
+    let mut types = registry();
+    let mut bb = Blackboard::new();
+    bb.set("alert", 2u32);
+    let old = types.save(&bb).unwrap();
+
+    // The type moves to the new tag, and the former tag is unknown.
+    types.register::<u32>("alert_level");
+    let saved = types.save(&bb).unwrap();
+    assert_eq!(
+        serde_json::to_string(&saved.entries).unwrap(),
+        r#"[{"key":"alert","type":"alert_level","value":2}]"#
+    );
+    let mut restored = Blackboard::new();
+    types.restore(&mut restored, &saved).unwrap();
+    assert_eq!(restored.get::<u32>("alert"), Some(&2));
+    assert!(matches!(
+        types.restore(&mut restored, &old),
+        Err(RestoreError::UnknownTag { ref tag, .. }) if tag == "u32"
+    ));
+}
+
+#[test]
+fn test_serialize_snapshot_diff() {
+    let mut bb = Blackboard::new();
+    let before = bb.snapshot();
+    bb.set("alert", 1u32);
+    let changes = Blackboard::diff(&before, &bb.snapshot());
+    assert_eq!(
+        serde_json::to_string(&changes).unwrap(),
+        r#"[{"key":"alert","before":null,"after":"1"}]"#
+    );
+}
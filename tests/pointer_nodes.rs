@@ -0,0 +1,48 @@
+use std::any::Any;
+use tiny_behavior_tree::{
+    testing::{AlwaysFailure, AlwaysSuccess},
+    BehaviorNodeBase, BehaviorResult, Configure, InverterNode, RepeatNode, SequenceNode,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+struct Counter(usize);
+
+impl BehaviorNodeBase<(), (), ()> for Counter {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        self.0 += 1;
+        BehaviorResult::Success(())
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+}
+
+#[test]
+fn test_boxed_node() {
+    let child: Node = Box::new(SequenceNode::new([
+        Box::new(AlwaysSuccess(())) as Node,
+        Box::new(AlwaysFailure(())),
+    ]));
+    let mut tree = InverterNode::new(child);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+
+    // The box of a box is downcast to the node inside.
+    let mut tree: Node = Box::new(SequenceNode::new([
+        Box::new(Box::new(Counter(0)) as Node) as Node
+    ]));
+    assert!(tree
+        .configure("0", |counter: &mut Counter| counter.0 = 5)
+        .is_ok());
+}
+
+#[test]
+fn test_borrowed_node() {
+    let mut counter = Counter(0);
+    {
+        let mut repeat = RepeatNode::new(3, &mut counter);
+        assert_eq!(repeat.tick(()), BehaviorResult::Success(()));
+    }
+    assert_eq!(counter.0, 3);
+}
@@ -0,0 +1,73 @@
+use std::cell::RefCell;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Blackboard, Port, PortError, SequenceNode, Tree, TypedKey,
+    ValidatePorts,
+};
+
+type Bb = &'static RefCell<Blackboard>;
+type Node = Box<dyn BehaviorNodeBase<Bb, (), ()>>;
+
+const TARGET: TypedKey<(i32, i32)> = TypedKey::new("target");
+const TARGET_NAME: TypedKey<String> = TypedKey::new("target");
+const SPEED: TypedKey<f64> = TypedKey::new("speed");
+
+/// A leaf that only declares its ports.
+struct Ports(Vec<Port>);
+
+impl BehaviorNodeBase<Bb, (), ()> for Ports {
+    fn tick(&mut self, _: Bb) -> BehaviorResult<(), ()> {
+        BehaviorResult::Success(())
+    }
+
+    fn ports(&self) -> Vec<Port> {
+        self.0.clone()
+    }
+}
+
+fn node(ports: impl Into<Vec<Port>>) -> Node {
+    Box::new(Ports(ports.into()))
+}
+
+#[test]
+fn test_outputs_provide_inputs() {
+    let mut tree = Tree::new(Box::new(SequenceNode::new([
+        node([Port::input(TARGET)]),
+        node([Port::output(TARGET), Port::in_out(SPEED)]),
+    ])));
+    let mut bb = Blackboard::new();
+    assert_eq!(
+        tree.validate_ports(&bb),
+        Err(vec![PortError::Unprovided {
+            path: "1".to_string(),
+            key: "speed",
+        }])
+    );
+
+    bb.set_typed(SPEED, 1.5);
+    assert_eq!(tree.validate_ports(&bb), Ok(()));
+}
+
+#[test]
+fn test_type_mismatch() {
+    let mut tree = SequenceNode::new([
+        node([Port::output(TARGET)]),
+        Box::new(SequenceNode::new([node([Port::input(TARGET_NAME)])])) as Node,
+    ]);
+    let errors = tree.validate_ports(&Blackboard::new()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Node at path \"1/0\" uses \"target\" as alloc::string::String, but it is (i32, i32)"
+    );
+
+    let mut bb = Blackboard::new();
+    bb.set("speed", 3);
+    let mut tree = node([Port::input(SPEED)]);
+    assert!(matches!(
+        tree.validate_ports(&bb).unwrap_err()[..],
+        [PortError::TypeMismatch {
+            expected: "i32",
+            ..
+        }]
+    ));
+}
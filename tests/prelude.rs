@@ -0,0 +1,35 @@
+use tiny_behavior_tree::prelude::*;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+
+struct Body {
+    arm: u32,
+}
+
+peel_node_def!(PeelArm, Body, u32, (), (), |payload: &'a Body| &payload.arm);
+
+struct IsLong;
+
+impl BehaviorNodeBase<&u32, (), ()> for IsLong {
+    fn tick(&mut self, arm: &u32) -> BehaviorResult<(), ()> {
+        if 10 < *arm {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+#[test]
+fn test_prelude() {
+    type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+    let mut tree = FallbackNode::<(), (), (), _>::new([
+        Box::new(InverterNode::new(AlwaysSuccess(()))) as Node,
+        Box::new(RetryNode::new(2, AlwaysFailure(()))),
+    ]);
+    assert_eq!(tree.tick(()).kind(), ResultKind::Failure);
+
+    let mut tree = SequenceNodeRef::<Body, (), (), _>::new([
+        Box::new(PeelArm(IsLong)) as Box<dyn BehaviorNodeBase<&Body, (), ()>>
+    ]);
+    assert_eq!(tree.tick(&Body { arm: 12 }), BehaviorResult::Success(()));
+}
@@ -0,0 +1,72 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::AlwaysFailure;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, PrioritySelectorNode};
+
+type Node = Box<dyn BehaviorNodeBase<[i32; 3], usize, ()>>;
+
+/// Returns `Running` forever, logging its index when halted.
+struct Action {
+    index: usize,
+    halted: Rc<RefCell<Vec<usize>>>,
+}
+
+impl BehaviorNodeBase<[i32; 3], usize, ()> for Action {
+    fn tick(&mut self, _: [i32; 3]) -> BehaviorResult<usize, ()> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.halted.borrow_mut().push(self.index);
+        HaltReport::clean()
+    }
+}
+
+/// Orders children by descending priority given in the payload.
+fn by_priority(priorities: &[i32; 3], a: usize, b: usize) -> Ordering {
+    priorities[b].cmp(&priorities[a])
+}
+
+#[test]
+fn test_priority_selector_aborts_lower_priority() {
+    let halted = Rc::new(RefCell::new(vec![]));
+    let children: Vec<Node> = (0..3)
+        .map(|index| {
+            Box::new(Action {
+                index,
+                halted: halted.clone(),
+            }) as Node
+        })
+        .collect();
+    let mut tree = PrioritySelectorNode::new(children, by_priority);
+    assert_eq!(tree.tick([1, 3, 2]), BehaviorResult::Running);
+    assert_eq!(tree.order(), Some(&[1, 2, 0][..]));
+    assert!(halted.borrow().is_empty());
+
+    // The same child keeps running without being halted.
+    assert_eq!(tree.tick([1, 3, 0]), BehaviorResult::Running);
+    assert!(halted.borrow().is_empty());
+
+    // A higher priority child takes over.
+    assert_eq!(tree.tick([5, 3, 0]), BehaviorResult::Running);
+    assert_eq!(*halted.borrow(), [1]);
+    tree.halt();
+    assert_eq!(*halted.borrow(), [1, 0, 1, 2]);
+}
+
+#[test]
+fn test_priority_selector_fallback() {
+    let mut tree = PrioritySelectorNode::new_with_merger(
+        [
+            Box::new(AlwaysFailure(vec![0])) as Box<dyn BehaviorNodeBase<[i32; 3], (), Vec<i32>>>,
+            Box::new(AlwaysFailure(vec![1])),
+            Box::new(AlwaysFailure(vec![2])),
+        ],
+        by_priority,
+        |acc: &mut Vec<i32>, mut v: Vec<i32>| acc.append(&mut v),
+    );
+    // Children with equal priorities keep their order.
+    assert_eq!(tree.tick([0, 1, 0]), BehaviorResult::Failure(vec![1, 0, 2]));
+    assert_eq!(tree.order(), Some(&[1, 0, 2][..]));
+}
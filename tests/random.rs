@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, RandomContext, RandomFallbackNode, RandomSequenceNode,
+    RandomSource, SplitMix64, Tree,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+/// Records its index when ticked, returning `Running` on the first tick if `wait` is set.
+struct Visit {
+    index: usize,
+    wait: bool,
+    log: Rc<RefCell<Vec<usize>>>,
+}
+
+impl BehaviorNodeBase<(), (), ()> for Visit {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        self.log.borrow_mut().push(self.index);
+        if std::mem::take(&mut self.wait) {
+            BehaviorResult::Running
+        } else {
+            BehaviorResult::Success(())
+        }
+    }
+}
+
+fn visits(log: &Rc<RefCell<Vec<usize>>>, wait: Option<usize>) -> Vec<Node> {
+    (0..5)
+        .map(|index| {
+            Box::new(Visit {
+                index,
+                wait: wait == Some(index),
+                log: log.clone(),
+            }) as Node
+        })
+        .collect()
+}
+
+/// Always returns the same number, which makes `below` return 0.
+struct Zero;
+
+impl RandomSource for Zero {
+    fn next_u64(&mut self) -> u64 {
+        0
+    }
+}
+
+#[test]
+fn test_random_sequence_is_deterministic_with_seed() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut tree =
+        RandomSequenceNode::new(visits(&log, None)).with_rng(SplitMix64::seed_from_u64(1));
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    let order = tree.order().unwrap().to_vec();
+    assert_eq!(*log.borrow(), order);
+    let mut sorted = order.clone();
+    sorted.sort();
+    assert_eq!(sorted, [0, 1, 2, 3, 4]);
+
+    let log2 = Rc::new(RefCell::new(vec![]));
+    let mut tree =
+        RandomSequenceNode::new(visits(&log2, None)).with_rng(SplitMix64::seed_from_u64(1));
+    tree.tick(());
+    assert_eq!(*log2.borrow(), order);
+
+    // Fisher-Yates with always the first choice rotates the children.
+    let log3 = Rc::new(RefCell::new(vec![]));
+    let mut tree = RandomSequenceNode::new(visits(&log3, None)).with_rng(Zero);
+    tree.tick(());
+    assert_eq!(*log3.borrow(), [1, 2, 3, 4, 0]);
+}
+
+#[test]
+fn test_random_order_is_fixed_per_activation() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut tree = Tree::new(Box::new(
+        RandomSequenceNode::new(visits(&log, Some(2))).with_rng(SplitMix64::seed_from_u64(3)),
+    ));
+    let (res, report) = tree.tick_with_report(());
+    assert_eq!(res, BehaviorResult::Running);
+    let order = report.order("").unwrap().to_vec();
+    let (res, report) = tree.tick_with_report(());
+    assert_eq!(res, BehaviorResult::Success(()));
+    // The running child is resumed without choosing a new order.
+    assert_eq!(report.order(""), None);
+
+    let expected: Vec<_> = order
+        .iter()
+        .copied()
+        .take_while(|i| *i != 2)
+        .chain(order.iter().copied().skip_while(|i| *i != 2))
+        .collect();
+    let mut ticked = log.borrow().clone();
+    ticked.dedup();
+    assert_eq!(ticked, expected);
+}
+
+#[test]
+fn test_random_fallback() {
+    let children = || {
+        vec![
+            Box::new(AlwaysFailure(())) as Node,
+            Box::new(AlwaysFailure(())),
+            Box::new(AlwaysSuccess(())),
+        ]
+    };
+    let mut tree = RandomFallbackNode::new(children()).with_rng(Zero);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    assert_eq!(tree.order(), Some(&[1, 2, 0][..]));
+
+    let mut picked = [0; 3];
+    let mut tree = RandomFallbackNode::new(vec![
+        Box::new(AlwaysSuccess(0usize)) as Box<dyn BehaviorNodeBase<(), usize, ()>>,
+        Box::new(AlwaysSuccess(1)),
+        Box::new(AlwaysSuccess(2)),
+    ])
+    .with_rng(SplitMix64::seed_from_u64(5));
+    for _ in 0..300 {
+        let BehaviorResult::Success(i) = tree.tick(()) else {
+            panic!()
+        };
+        picked[i] += 1;
+    }
+    assert!(picked.iter().all(|count| 50 < *count), "{:?}", picked);
+}
+
+#[test]
+fn test_random_context_streams() {
+    let draw = |seed: u64, path: &str| {
+        let mut rng = RandomContext::new(seed).stream(path);
+        (0..4).map(|_| rng.below(1000)).collect::<Vec<_>>()
+    };
+    assert_eq!(draw(42, "0/1"), draw(42, "0/1"));
+    assert_ne!(draw(42, "0/1"), draw(42, "0/2"));
+    assert_ne!(draw(42, "0/1"), draw(43, "0/1"));
+    // The streams must not change between versions to keep old seeds reproducible.
+    assert_eq!(draw(42, "0/1"), [283, 640, 366, 918]);
+    assert_eq!(draw(42, ""), [484, 959, 163, 584]);
+}
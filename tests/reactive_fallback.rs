@@ -0,0 +1,80 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, ReactiveFallbackNode};
+
+type Node = Box<dyn BehaviorNodeBase<&'static Cell<i32>, (), ()>>;
+
+/// Succeeds if the payload equals the given value.
+struct Equals(i32);
+
+impl BehaviorNodeBase<&Cell<i32>, (), ()> for Equals {
+    fn tick(&mut self, payload: &Cell<i32>) -> BehaviorResult<(), ()> {
+        if self.0 == payload.get() {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+/// Runs while the payload equals the given value, logging ticks and halts.
+struct RunWhile {
+    name: &'static str,
+    value: i32,
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl BehaviorNodeBase<&Cell<i32>, (), ()> for RunWhile {
+    fn tick(&mut self, payload: &Cell<i32>) -> BehaviorResult<(), ()> {
+        self.log.borrow_mut().push(format!("tick {}", self.name));
+        if self.value == payload.get() {
+            BehaviorResult::Running
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.log.borrow_mut().push(format!("halt {}", self.name));
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_higher_priority_child_interrupts() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let state: &'static Cell<i32> = Box::leak(Box::new(Cell::new(0)));
+    let mut tree = ReactiveFallbackNode::new([
+        Box::new(Equals(1)) as Node,
+        Box::new(RunWhile {
+            name: "chase",
+            value: 2,
+            log: log.clone(),
+        }),
+        Box::new(RunWhile {
+            name: "patrol",
+            value: 0,
+            log: log.clone(),
+        }),
+    ]);
+
+    assert_eq!(tree.tick(state), BehaviorResult::Running);
+    state.set(2);
+    assert_eq!(tree.tick(state), BehaviorResult::Running);
+    state.set(1);
+    assert_eq!(tree.tick(state), BehaviorResult::Success(()));
+    state.set(3);
+    assert_eq!(tree.tick(state), BehaviorResult::Failure(()));
+    assert_eq!(
+        *log.borrow(),
+        [
+            "tick chase",
+            "tick patrol",
+            "tick chase",
+            "halt patrol",
+            "halt chase",
+            "tick chase",
+            "tick patrol"
+        ]
+    );
+}
@@ -0,0 +1,108 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, ReactiveSequenceNode};
+
+type Node = Box<dyn BehaviorNodeBase<&'static Cell<i32>, (), ()>>;
+
+/// Succeeds if the payload is at least the given value.
+struct AtLeast(i32);
+
+impl BehaviorNodeBase<&Cell<i32>, (), ()> for AtLeast {
+    fn tick(&mut self, payload: &Cell<i32>) -> BehaviorResult<(), ()> {
+        if self.0 <= payload.get() {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+/// Runs for the given number of ticks, logging ticks and halts.
+struct Action {
+    name: &'static str,
+    ticks: usize,
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl BehaviorNodeBase<&Cell<i32>, (), ()> for Action {
+    fn tick(&mut self, _payload: &Cell<i32>) -> BehaviorResult<(), ()> {
+        self.log.borrow_mut().push(format!("tick {}", self.name));
+        if self.ticks == 0 {
+            BehaviorResult::Success(())
+        } else {
+            self.ticks -= 1;
+            BehaviorResult::Running
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.log.borrow_mut().push(format!("halt {}", self.name));
+        HaltReport::clean()
+    }
+}
+
+fn payload(value: i32) -> &'static Cell<i32> {
+    Box::leak(Box::new(Cell::new(value)))
+}
+
+#[test]
+fn test_conditions_are_rechecked() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let checks = payload(10);
+    let mut tree = ReactiveSequenceNode::new([
+        Box::new(AtLeast(5)) as Node,
+        Box::new(Action {
+            name: "a",
+            ticks: 2,
+            log: log.clone(),
+        }),
+        Box::new(Action {
+            name: "b",
+            ticks: 1,
+            log: log.clone(),
+        }),
+    ]);
+
+    assert_eq!(tree.tick(checks), BehaviorResult::Running);
+    assert_eq!(tree.tick(checks), BehaviorResult::Running);
+    assert_eq!(tree.tick(checks), BehaviorResult::Running);
+    checks.set(0);
+    assert_eq!(tree.tick(checks), BehaviorResult::Failure(()));
+    assert_eq!(
+        *log.borrow(),
+        ["tick a", "tick a", "tick a", "tick b", "halt b"]
+    );
+}
+
+/// Keeps running while the payload is below the given value.
+struct RunWhileBelow(i32);
+
+impl BehaviorNodeBase<&Cell<i32>, (), ()> for RunWhileBelow {
+    fn tick(&mut self, payload: &Cell<i32>) -> BehaviorResult<(), ()> {
+        if payload.get() < self.0 {
+            BehaviorResult::Running
+        } else {
+            BehaviorResult::Success(())
+        }
+    }
+}
+
+#[test]
+fn test_earlier_running_child_halts_later_one() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let distance = payload(10);
+    let mut tree = ReactiveSequenceNode::new([
+        Box::new(RunWhileBelow(5)) as Node,
+        Box::new(Action {
+            name: "a",
+            ticks: 5,
+            log: log.clone(),
+        }),
+    ]);
+
+    assert_eq!(tree.tick(distance), BehaviorResult::Running);
+    distance.set(0);
+    assert_eq!(tree.tick(distance), BehaviorResult::Running);
+    assert_eq!(tree.tick(distance), BehaviorResult::Running);
+    assert_eq!(*log.borrow(), ["tick a", "halt a"]);
+}
@@ -0,0 +1,83 @@
+use std::cell::Cell;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, RepeatNode};
+
+/// Succeeds with the number of ticks so far, except that it fails at `fail_at`
+/// and returns `Running` once at `run_at`.
+struct Counter {
+    fail_at: Option<i32>,
+    run_at: Option<i32>,
+}
+
+impl<'a> BehaviorNodeBase<&'a Cell<i32>, i32, i32> for Counter {
+    fn tick(&mut self, ticks: &'a Cell<i32>) -> BehaviorResult<i32, i32> {
+        ticks.set(ticks.get() + 1);
+        if Some(ticks.get()) == self.fail_at {
+            BehaviorResult::Failure(ticks.get())
+        } else if self.run_at == Some(ticks.get()) {
+            self.run_at = None;
+            ticks.set(ticks.get() - 1);
+            BehaviorResult::Running
+        } else {
+            BehaviorResult::Success(ticks.get())
+        }
+    }
+}
+
+#[test]
+fn test_repeat() {
+    let ticks = Cell::new(0);
+    let mut node = RepeatNode::new(
+        3,
+        Counter {
+            fail_at: None,
+            run_at: None,
+        },
+    );
+    assert_eq!(node.tick(&ticks), BehaviorResult::Success(3));
+    assert_eq!(ticks.get(), 3);
+
+    let mut node = RepeatNode::new(0, AlwaysSuccess(1));
+    assert_eq!(
+        BehaviorNodeBase::<(), i32, ()>::tick(&mut node, ()),
+        BehaviorResult::Success(0)
+    );
+}
+
+#[test]
+fn test_repeat_stops_on_failure() {
+    let ticks = Cell::new(0);
+    let mut node = RepeatNode::forever(Counter {
+        fail_at: Some(4),
+        run_at: None,
+    });
+    assert_eq!(node.tick(&ticks), BehaviorResult::Failure(4));
+
+    ticks.set(0);
+    let mut node = RepeatNode::new_with_merger(
+        5,
+        Counter {
+            fail_at: Some(2),
+            run_at: None,
+        },
+        |sum: &mut i32, r| *sum += r,
+    );
+    assert_eq!(node.tick(&ticks), BehaviorResult::Failure(2));
+}
+
+#[test]
+fn test_repeat_resumes_after_running() {
+    let ticks = Cell::new(0);
+    let mut node = RepeatNode::new_with_merger(
+        3,
+        Counter {
+            fail_at: None,
+            run_at: Some(2),
+        },
+        |sum: &mut i32, r| *sum += r,
+    );
+    assert_eq!(node.tick(&ticks), BehaviorResult::Running);
+    // The first success is kept, and the child is ticked until it succeeds two more times
+    assert_eq!(node.tick(&ticks), BehaviorResult::Success(1 + 2 + 3));
+    assert_eq!(ticks.get(), 3);
+}
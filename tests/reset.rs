@@ -0,0 +1,68 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, DelegateNode, RoundRobinNode, RunOnceNode, SequenceNode, Tree,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), u32, ()>>;
+
+/// Succeeds with its id, counting ticks.
+struct Count {
+    id: u32,
+    ticks: Rc<Cell<u32>>,
+}
+
+impl BehaviorNodeBase<(), u32, ()> for Count {
+    fn tick(&mut self, _: ()) -> BehaviorResult<u32, ()> {
+        self.ticks.set(self.ticks.get() + 1);
+        BehaviorResult::Success(self.id)
+    }
+}
+
+fn count(id: u32, ticks: &Rc<Cell<u32>>) -> Node {
+    Box::new(Count {
+        id,
+        ticks: ticks.clone(),
+    })
+}
+
+#[derive(DelegateNode)]
+struct Wrapper<T>(T);
+
+#[test]
+fn test_reset_rewinds_state_kept_by_halt() {
+    let ticks = Rc::new(Cell::new(0));
+    let mut tree = Tree::new(Box::new(Wrapper(RoundRobinNode::new([
+        count(1, &ticks),
+        count(2, &ticks),
+        count(3, &ticks),
+    ]))));
+    assert_eq!(tree.tick(()), BehaviorResult::Success(1));
+    assert!(tree.halt().is_clean());
+    assert_eq!(tree.tick(()), BehaviorResult::Success(2));
+    assert!(tree.reset().is_clean());
+    assert_eq!(tree.tick(()), BehaviorResult::Success(1));
+}
+
+#[test]
+fn test_reset_reaches_nested_nodes() {
+    let ticks = Rc::new(Cell::new(0));
+    let mut tree = SequenceNode::new([
+        count(1, &ticks),
+        Box::new(RunOnceNode::new(Count {
+            id: 2,
+            ticks: ticks.clone(),
+        })) as Node,
+    ]);
+    tree.tick(());
+    tree.tick(());
+    assert_eq!(ticks.get(), 3);
+
+    tree.halt();
+    tree.tick(());
+    assert_eq!(ticks.get(), 4);
+
+    tree.reset();
+    tree.tick(());
+    assert_eq!(ticks.get(), 6);
+}
@@ -0,0 +1,39 @@
+use std::collections::{HashMap, HashSet};
+use tiny_behavior_tree::{BehaviorResult, ResultKind};
+
+#[test]
+fn test_kind() {
+    assert_eq!(BehaviorResult::<i32, i32>::Idle.kind(), ResultKind::Idle);
+    assert_eq!(
+        BehaviorResult::<i32, i32>::Running.kind(),
+        ResultKind::Running
+    );
+    assert_eq!(
+        BehaviorResult::<i32, i32>::Success(1).kind(),
+        ResultKind::Success
+    );
+    assert_eq!(
+        BehaviorResult::<i32, i32>::Failure(1).kind(),
+        ResultKind::Failure
+    );
+}
+
+#[test]
+fn test_results_in_collections() {
+    let results = [
+        BehaviorResult::Success(1),
+        BehaviorResult::Failure("far"),
+        BehaviorResult::Success(1),
+        BehaviorResult::Running,
+    ];
+
+    let unique: HashSet<_> = results.iter().copied().collect();
+    assert_eq!(unique.len(), 3);
+
+    let mut counts = HashMap::new();
+    for res in &results {
+        *counts.entry(res.kind()).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&ResultKind::Success], 2);
+    assert_eq!(counts.get(&ResultKind::Idle), None);
+}
@@ -0,0 +1,69 @@
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, RetryNode};
+
+/// Returns the given results in order, and `Success` with the number of ticks afterwards.
+struct Script {
+    results: Vec<BehaviorResult<u32, u32>>,
+    ticks: u32,
+}
+
+impl Script {
+    fn new(results: impl Into<Vec<BehaviorResult<u32, u32>>>) -> Self {
+        Self {
+            results: results.into(),
+            ticks: 0,
+        }
+    }
+}
+
+impl BehaviorNodeBase<(), u32, u32> for Script {
+    fn tick(&mut self, _: ()) -> BehaviorResult<u32, u32> {
+        self.ticks += 1;
+        if self.results.is_empty() {
+            BehaviorResult::Success(self.ticks)
+        } else {
+            self.results.remove(0)
+        }
+    }
+}
+
+#[test]
+fn test_retry() {
+    use BehaviorResult::*;
+    let mut node = RetryNode::new(3, Script::new([Failure(1), Failure(2)]));
+    assert_eq!(node.tick(()), Success(3));
+
+    let mut node = RetryNode::new(
+        3,
+        Script::new([Failure(1), Failure(2), Failure(3), Failure(4)]),
+    );
+    assert_eq!(node.tick(()), Failure(3));
+    // The attempts are counted from scratch in the next tick
+    assert_eq!(node.tick(()), Success(5));
+
+    let mut node = RetryNode::new(1, AlwaysSuccess(1));
+    assert_eq!(
+        BehaviorNodeBase::<(), i32, ()>::tick(&mut node, ()),
+        Success(1)
+    );
+}
+
+#[test]
+fn test_retry_counts_attempts_across_running() {
+    use BehaviorResult::*;
+    let mut node = RetryNode::new(2, Script::new([Failure(1), Running, Failure(3)]));
+    assert_eq!(node.tick(()), Running);
+    assert_eq!(node.tick(()), Failure(3));
+
+    let mut node = RetryNode::new(2, Script::new([Failure(1), Running, Failure(3)]));
+    assert_eq!(node.tick(()), Running);
+    // Halting discards the failed attempts
+    node.halt();
+    assert_eq!(node.tick(()), Success(4));
+}
+
+#[test]
+#[should_panic(expected = "max_attempts must be positive")]
+fn test_retry_zero_attempts() {
+    RetryNode::new(0, AlwaysSuccess(()));
+}
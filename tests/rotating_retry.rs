@@ -0,0 +1,78 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::AlwaysFailure;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, RotatingRetryNode};
+
+type Node = Box<dyn BehaviorNodeBase<(), &'static str, &'static str>>;
+
+/// Records its name on every tick and returns the given results in order, then fails.
+struct TryPath {
+    name: &'static str,
+    results: Vec<BehaviorResult<&'static str, &'static str>>,
+    log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl BehaviorNodeBase<(), &'static str, &'static str> for TryPath {
+    fn tick(&mut self, _: ()) -> BehaviorResult<&'static str, &'static str> {
+        self.log.borrow_mut().push(self.name);
+        if self.results.is_empty() {
+            BehaviorResult::Failure(self.name)
+        } else {
+            self.results.remove(0)
+        }
+    }
+}
+
+fn path(
+    name: &'static str,
+    results: impl Into<Vec<BehaviorResult<&'static str, &'static str>>>,
+    log: &Rc<RefCell<Vec<&'static str>>>,
+) -> Node {
+    Box::new(TryPath {
+        name,
+        results: results.into(),
+        log: log.clone(),
+    })
+}
+
+#[test]
+fn test_rotating_retry() {
+    use BehaviorResult::*;
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut tree = RotatingRetryNode::new(
+        [
+            path("A", [Failure("A")], &log),
+            path("B", [Failure("B")], &log),
+            path("C", [Failure("C"), Success("C")], &log),
+        ],
+        5,
+    );
+    assert_eq!(tree.tick(()), Failure("B"));
+    assert_eq!(*log.borrow(), ["A", "B", "C", "A", "B"]);
+
+    // The next activation starts over from A, which has run out of results
+    log.borrow_mut().clear();
+    assert_eq!(tree.tick(()), Success("C"));
+    assert_eq!(*log.borrow(), ["A", "B", "C"]);
+}
+
+#[test]
+fn test_rotating_retry_running() {
+    use BehaviorResult::*;
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut tree = RotatingRetryNode::new(
+        [
+            path("A", [Failure("A")], &log),
+            path("B", [Running, Success("B")], &log),
+        ],
+        2,
+    );
+    assert_eq!(tree.tick(()), Running);
+    assert_eq!(tree.tick(()), Success("B"));
+    assert_eq!(*log.borrow(), ["A", "B", "B"]);
+
+    let mut empty = RotatingRetryNode::new(Vec::<Node>::new(), 1);
+    assert_eq!(empty.tick(()), Failure(""));
+    assert!(empty.push_child(Box::new(AlwaysFailure("D"))).is_ok());
+    assert_eq!(empty.tick(()), Failure("D"));
+}
@@ -0,0 +1,81 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, RoundRobinNode};
+
+type Node = Box<dyn BehaviorNodeBase<(), i32, i32>>;
+
+/// Returns `Running` once, then succeeds with the given value, counting halts.
+struct Walk {
+    to: i32,
+    arrived: bool,
+    halts: Rc<Cell<u32>>,
+}
+
+impl BehaviorNodeBase<(), i32, i32> for Walk {
+    fn tick(&mut self, _: ()) -> BehaviorResult<i32, i32> {
+        self.arrived = !self.arrived;
+        if !self.arrived {
+            BehaviorResult::Success(self.to)
+        } else {
+            BehaviorResult::Running
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.arrived = false;
+        self.halts.set(self.halts.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_round_robin() {
+    let mut tree = RoundRobinNode::new([
+        Box::new(AlwaysSuccess(1)) as Node,
+        Box::new(AlwaysFailure(2)),
+        Box::new(AlwaysSuccess(3)),
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(1));
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(2));
+    assert_eq!(tree.tick(()), BehaviorResult::Success(3));
+    assert_eq!(tree.position(), 0);
+
+    tree.extend([Box::new(AlwaysSuccess(4)) as Node]);
+    let results: Vec<_> = (0..4).map(|_| tree.tick(())).collect();
+    assert_eq!(
+        results,
+        [
+            BehaviorResult::Success(1),
+            BehaviorResult::Failure(2),
+            BehaviorResult::Success(3),
+            BehaviorResult::Success(4),
+        ]
+    );
+
+    let mut empty = RoundRobinNode::new(Vec::<Node>::new());
+    assert_eq!(empty.tick(()), BehaviorResult::Failure(0));
+}
+
+#[test]
+fn test_round_robin_running_child() {
+    let halts = Rc::new(Cell::new(0));
+    let walk = |to| {
+        Box::new(Walk {
+            to,
+            arrived: false,
+            halts: halts.clone(),
+        }) as Node
+    };
+    let mut patrol = RoundRobinNode::new([walk(1), walk(2)]);
+    assert_eq!(patrol.tick(()), BehaviorResult::Running);
+    assert_eq!(patrol.tick(()), BehaviorResult::Success(1));
+    assert_eq!(patrol.tick(()), BehaviorResult::Running);
+
+    // An interrupted patrol resumes at the same point.
+    patrol.halt();
+    assert_eq!(halts.get(), 2);
+    assert_eq!(patrol.position(), 1);
+    assert_eq!(patrol.tick(()), BehaviorResult::Running);
+    assert_eq!(patrol.tick(()), BehaviorResult::Success(2));
+}
@@ -0,0 +1,45 @@
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, RunOnceNode};
+
+/// Returns `Running` for the given number of ticks, then fails with the number of ticks.
+struct Load {
+    running: u32,
+    ticks: u32,
+}
+
+impl BehaviorNodeBase<(), (), u32> for Load {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), u32> {
+        self.ticks += 1;
+        if self.ticks <= self.running {
+            BehaviorResult::Running
+        } else {
+            BehaviorResult::Failure(self.ticks)
+        }
+    }
+}
+
+#[test]
+fn test_run_once_waits_for_completion() {
+    let mut node = RunOnceNode::new(Load {
+        running: 2,
+        ticks: 0,
+    });
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    assert_eq!(node.result(), None);
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    assert_eq!(node.tick(()), BehaviorResult::Failure(3));
+    assert_eq!(node.tick(()), BehaviorResult::Failure(3));
+    assert_eq!(node.result(), Some(&BehaviorResult::Failure(3)));
+}
+
+#[test]
+fn test_run_once_reset() {
+    let mut node = RunOnceNode::new(Load {
+        running: 0,
+        ticks: 0,
+    });
+    assert_eq!(node.tick(()), BehaviorResult::Failure(1));
+    node.halt();
+    assert_eq!(node.tick(()), BehaviorResult::Failure(1));
+    node.reset();
+    assert_eq!(node.tick(()), BehaviorResult::Failure(2));
+}
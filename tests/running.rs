@@ -0,0 +1,119 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, FallbackNode, HaltReport, SequenceNode,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+/// Logs its name and returns a fixed result.
+struct Logged {
+    name: &'static str,
+    res: BehaviorResult<(), ()>,
+    log: Log,
+}
+
+fn logged(name: &'static str, res: BehaviorResult<(), ()>, log: &Log) -> Node {
+    Box::new(Logged {
+        name,
+        res,
+        log: log.clone(),
+    })
+}
+
+impl BehaviorNodeBase<(), (), ()> for Logged {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        self.log.borrow_mut().push(self.name);
+        self.res
+    }
+}
+
+#[test]
+fn test_sequence_returns_running() {
+    let log = Log::default();
+    let mut tree = SequenceNode::new([
+        logged("a", BehaviorResult::Success(()), &log),
+        Box::new(AlwaysRunning) as Node,
+        logged("c", BehaviorResult::Success(()), &log),
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert_eq!(*log.borrow(), ["a", "a"]);
+}
+
+#[test]
+fn test_fallback_returns_running() {
+    let log = Log::default();
+    let mut tree = FallbackNode::new([
+        Box::new(AlwaysFailure(())) as Node,
+        Box::new(AlwaysRunning),
+        logged("c", BehaviorResult::Success(()), &log),
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Running);
+    assert!(log.borrow().is_empty());
+}
+
+#[test]
+fn test_idle_child_is_skipped() {
+    let log = Log::default();
+    let mut tree = SequenceNode::new([
+        logged("a", BehaviorResult::Idle, &log),
+        Box::new(AlwaysSuccess(())) as Node,
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Success(()));
+    assert_eq!(*log.borrow(), ["a"]);
+}
+
+/// Returns the result set from outside.
+struct Scripted(Rc<Cell<BehaviorResult<(), ()>>>);
+
+impl BehaviorNodeBase<(), (), ()> for Scripted {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        self.0.get()
+    }
+}
+
+/// Keeps running, counting the ticks since it was started or halted.
+struct Progress(Rc<Cell<u32>>);
+
+impl BehaviorNodeBase<(), (), ()> for Progress {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        self.0.set(self.0.get() + 1);
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.0.set(0);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_interrupted_child_is_halted_before_restart() {
+    for first in [BehaviorResult::Success(()), BehaviorResult::Failure(())] {
+        let res = Rc::new(Cell::new(first));
+        let progress = Rc::new(Cell::new(0));
+        let children = [
+            Box::new(Scripted(res.clone())) as Node,
+            Box::new(Progress(progress.clone())),
+        ];
+        let mut tree: Node = if first == BehaviorResult::Success(()) {
+            Box::new(SequenceNode::new(children))
+        } else {
+            Box::new(FallbackNode::new(children))
+        };
+        assert_eq!(tree.tick(()), BehaviorResult::Running);
+        assert_eq!(tree.tick(()), BehaviorResult::Running);
+        assert_eq!(progress.get(), 2);
+
+        res.set(BehaviorResult::Running);
+        assert_eq!(tree.tick(()), BehaviorResult::Running);
+        assert_eq!(progress.get(), 0);
+
+        res.set(first);
+        assert_eq!(tree.tick(()), BehaviorResult::Running);
+        assert_eq!(progress.get(), 1);
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, ChildSend, FallbackNodeSend, SequenceNodeSend,
+};
+
+/// Appends its name to the shared log and succeeds.
+struct Log(&'static str, Arc<Mutex<Vec<&'static str>>>);
+
+impl BehaviorNodeBase<(), (), ()> for Log {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        self.1.lock().unwrap().push(self.0);
+        BehaviorResult::Success(())
+    }
+}
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+#[test]
+fn test_tree_in_thread() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let mut tree = assert_send(SequenceNodeSend::new([
+        Box::new(FallbackNodeSend::new([
+            Box::new(AlwaysFailure(())) as ChildSend<(), (), ()>,
+            Box::new(Log("fallback", log.clone())),
+        ])) as ChildSend<(), (), ()>,
+        Box::new(Log("sequence", log.clone())),
+        Box::new(AlwaysRunning),
+        Box::new(Log("unreachable", log.clone())),
+    ]));
+
+    let worker = thread::spawn(move || {
+        let res = tree.tick(());
+        (res, tree)
+    });
+    let (res, mut tree) = worker.join().unwrap();
+    assert_eq!(res, BehaviorResult::Running);
+    assert_eq!(*log.lock().unwrap(), ["fallback", "sequence"]);
+    assert!(tree.halt().is_clean());
+}
+
+#[test]
+fn test_send_merger() {
+    type Node = ChildSend<(), Vec<i32>, ()>;
+    let mut tree = SequenceNodeSend::new_with_merger(
+        [
+            Box::new(AlwaysSuccess(vec![1])) as Node,
+            Box::new(AlwaysSuccess(vec![2])),
+        ],
+        |acc: &mut Vec<i32>, mut v: Vec<i32>| acc.append(&mut v),
+    );
+    assert_eq!(tree.tick(()), BehaviorResult::Success(vec![1, 2]));
+    assert_eq!(tree.children_mut().len(), 2);
+}
@@ -0,0 +1,63 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, SequenceNodeWithMemory};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+/// Returns the scripted results in order, logging ticks.
+struct Script {
+    name: &'static str,
+    results: Vec<BehaviorResult<(), ()>>,
+    log: Log,
+}
+
+impl Script {
+    fn node(name: &'static str, results: &[BehaviorResult<(), ()>], log: &Log) -> Node {
+        Box::new(Self {
+            name,
+            results: results.iter().rev().copied().collect(),
+            log: log.clone(),
+        })
+    }
+}
+
+impl BehaviorNodeBase<(), (), ()> for Script {
+    fn tick(&mut self, _payload: ()) -> BehaviorResult<(), ()> {
+        self.log.borrow_mut().push(self.name);
+        self.results.pop().unwrap()
+    }
+}
+
+use BehaviorResult::{Failure, Running, Success};
+
+#[test]
+fn test_resumes_at_running_child() {
+    let log = Log::default();
+    let mut tree = SequenceNodeWithMemory::new([
+        Script::node("a", &[Success(()), Success(())], &log),
+        Script::node("b", &[Running, Running, Success(()), Failure(())], &log),
+        Script::node("c", &[Success(())], &log),
+    ]);
+
+    assert_eq!(tree.tick(()), Running);
+    assert_eq!(tree.tick(()), Running);
+    assert_eq!(tree.tick(()), Success(()));
+    // Starts over after completion
+    assert_eq!(tree.tick(()), Failure(()));
+    assert_eq!(*log.borrow(), ["a", "b", "b", "b", "c", "a", "b"]);
+}
+
+#[test]
+fn test_halt_resets() {
+    let log = Log::default();
+    let mut tree = SequenceNodeWithMemory::new([
+        Script::node("a", &[Success(()), Success(())], &log),
+        Script::node("b", &[Running, Success(())], &log),
+    ]);
+
+    assert_eq!(tree.tick(()), Running);
+    assert!(tree.halt().is_clean());
+    assert_eq!(tree.tick(()), Success(()));
+    assert_eq!(*log.borrow(), ["a", "b", "a", "b"]);
+}
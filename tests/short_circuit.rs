@@ -0,0 +1,81 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, FallbackNode, SequenceNode};
+
+type Node = Box<dyn BehaviorNodeBase<i32, i32, i32>>;
+
+struct AtLeast(i32);
+
+impl BehaviorNodeBase<i32, i32, i32> for AtLeast {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<i32, i32> {
+        if self.0 <= payload {
+            BehaviorResult::Success(self.0)
+        } else {
+            BehaviorResult::Failure(self.0)
+        }
+    }
+}
+
+#[test]
+fn test_fallback_short_circuit() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let log2 = log.clone();
+    let mut tree = FallbackNode::<i32, i32, i32, _>::new([
+        Box::new(AtLeast(10)) as Node,
+        Box::new(AtLeast(5)) as Node,
+        Box::new(AtLeast(0)) as Node,
+    ])
+    .on_short_circuit(move |i, res| log2.borrow_mut().push((i, format!("{:?}", res))));
+
+    assert_eq!(tree.tick(20), BehaviorResult::Success(10));
+    assert_eq!(tree.tick(7), BehaviorResult::Success(5));
+    assert_eq!(tree.tick(-1), BehaviorResult::Failure(0));
+    assert_eq!(
+        *log.borrow(),
+        [
+            (0, "Success(10)".to_string()),
+            (1, "Success(5)".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_sequence_short_circuit() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let log2 = log.clone();
+    let mut tree = SequenceNode::<i32, i32, i32, _>::new([
+        Box::new(AtLeast(0)) as Node,
+        Box::new(AtLeast(5)) as Node,
+    ])
+    .on_short_circuit(move |i, res| log2.borrow_mut().push((i, format!("{:?}", res))));
+
+    assert_eq!(tree.tick(7), BehaviorResult::Success(5));
+    assert_eq!(tree.tick(3), BehaviorResult::Failure(5));
+    assert_eq!(*log.borrow(), [(1, "Failure(5)".to_string())]);
+}
+
+#[test]
+fn test_short_circuit_at_last_child() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let log2 = log.clone();
+    let mut tree = FallbackNode::<i32, i32, i32, _>::new([
+        Box::new(AtLeast(10)) as Node,
+        Box::new(AtLeast(5)) as Node,
+    ])
+    .on_short_circuit(move |i, res| log2.borrow_mut().push((i, format!("{:?}", res))));
+
+    // The last child ends the node with its success.
+    assert_eq!(tree.tick(7), BehaviorResult::Success(5));
+    assert_eq!(*log.borrow(), [(1, "Success(5)".to_string())]);
+
+    // Running children do not short-circuit.
+    let log = Rc::new(RefCell::new(vec![]));
+    let log2 = log.clone();
+    let mut tree = SequenceNode::<i32, i32, i32, _>::new([
+        Box::new(AtLeast(0)) as Node,
+        Box::new(tiny_behavior_tree::testing::AlwaysRunning),
+    ])
+    .on_short_circuit(move |i, res| log2.borrow_mut().push((i, format!("{:?}", res))));
+    assert_eq!(tree.tick(7), BehaviorResult::Running);
+    assert!(log.borrow().is_empty());
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "stdlib")]
+use std::cell::RefCell;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+use tiny_behavior_tree::stdlib::{
+    AlwaysSuccess, CompareBlackboard, EmitEvent, Log, LogBlackboardChanges, SetBlackboard, Wait,
+};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Blackboard, FallbackNode, ManualClock, SequenceNode,
+    SequenceNodeRef, WithBlackboard,
+};
+
+type Node<'a> = Box<dyn BehaviorNodeBase<&'a RefCell<Blackboard>, (), ()> + 'a>;
+
+#[test]
+fn test_wait() {
+    let mut wait = Wait::new(Duration::from_millis(20));
+    assert_eq!(
+        BehaviorNodeBase::<(), (), ()>::tick(&mut wait, ()),
+        BehaviorResult::Running
+    );
+    std::thread::sleep(Duration::from_millis(30));
+    assert_eq!(
+        BehaviorNodeBase::<(), (), ()>::tick(&mut wait, ()),
+        BehaviorResult::Success(())
+    );
+    assert_eq!(
+        BehaviorNodeBase::<(), (), ()>::tick(&mut wait, ()),
+        BehaviorResult::Running
+    );
+}
+
+#[test]
+fn test_halted_wait_starts_over() {
+    let clock = ManualClock::new();
+    let mut wait = Wait::with_clock(Duration::from_secs(2), clock.clone());
+    let wait: &mut dyn BehaviorNodeBase<(), (), ()> = &mut wait;
+    assert_eq!(wait.tick(()), BehaviorResult::Running);
+    clock.advance(Duration::from_secs(1));
+    wait.halt();
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(wait.tick(()), BehaviorResult::Running);
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(wait.tick(()), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_blackboard_leaves() {
+    let bb = RefCell::new(Blackboard::new());
+    let (sender, receiver) = channel();
+    let mut tree = SequenceNodeRef::<RefCell<Blackboard>, (), (), _>::new([
+        Box::new(Log("setting door".to_string())) as Node,
+        Box::new(SetBlackboard::new("door", "open")),
+        Box::new(CompareBlackboard::new("door", "open")),
+        Box::new(EmitEvent::new(sender, |_| "door opened")),
+    ]);
+    assert_eq!(tree.tick(&bb), BehaviorResult::Success(()));
+    assert_eq!(bb.borrow().get::<&str>("door"), Some(&"open"));
+    assert_eq!(receiver.try_recv(), Ok("door opened"));
+
+    let mut compare = CompareBlackboard::new("door", "closed");
+    assert_eq!(
+        BehaviorNodeBase::<_, (), ()>::tick(&mut compare, &bb),
+        BehaviorResult::Failure(())
+    );
+}
+
+#[test]
+fn test_blackboard_leaves_with_state() {
+    type Ctx<'a> = WithBlackboard<'a, &'a str>;
+    type Node<'a> = Box<dyn BehaviorNodeBase<Ctx<'a>, (), ()>>;
+
+    let bb = RefCell::new(Blackboard::new());
+    let mut open = SequenceNode::new([
+        Box::new(SetBlackboard::new("door", "open")) as Node,
+        Box::new(AlwaysSuccess(())),
+    ]);
+    let mut is_open = FallbackNode::new([
+        Box::new(CompareBlackboard::new("door", "open")) as Node,
+        Box::new(CompareBlackboard::new("door", "ajar")),
+    ]);
+    let ctx = WithBlackboard::new("agent", &bb);
+    assert_eq!(is_open.tick(ctx), BehaviorResult::Failure(()));
+    assert_eq!(open.tick(ctx), BehaviorResult::Success(()));
+    assert_eq!(is_open.tick(ctx), BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_log_blackboard_changes() {
+    let bb = RefCell::new(Blackboard::new());
+    let mut tree = LogBlackboardChanges(SequenceNodeRef::<RefCell<Blackboard>, (), (), _>::new([
+        Box::new(SetBlackboard::new("state", "patrol".to_string())) as Node,
+    ]));
+    assert_eq!(tree.tick(&bb), BehaviorResult::Success(()));
+    assert_eq!(bb.borrow().get::<String>("state").unwrap(), "patrol");
+}
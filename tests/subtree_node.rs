@@ -0,0 +1,93 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, HaltReport, ResultKind, SequenceNode, SubtreeNode, Tree,
+};
+
+/// Returns `Running` until the distance reaches zero, counting halts.
+struct Approach {
+    halts: Rc<Cell<u32>>,
+}
+
+impl BehaviorNodeBase<&Cell<u32>, u32, String> for Approach {
+    fn tick(&mut self, distance: &Cell<u32>) -> BehaviorResult<u32, String> {
+        match distance.get() {
+            0 => BehaviorResult::Success(0),
+            d if 10 < d => BehaviorResult::Failure(format!("too far: {d}")),
+            d => {
+                distance.set(d - 1);
+                BehaviorResult::Running
+            }
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.halts.set(self.halts.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+struct Agent {
+    distance: Cell<u32>,
+}
+
+fn approach(halts: &Rc<Cell<u32>>) -> Tree<&'static Cell<u32>, u32, String> {
+    Tree::new(Box::new(Approach {
+        halts: halts.clone(),
+    }))
+}
+
+#[test]
+fn test_maps_payload_and_summarizes_result() {
+    let halts = Rc::new(Cell::new(0));
+    let reasons = Rc::new(Cell::new(0));
+    let counted = reasons.clone();
+    let mut node = SubtreeNode::new(approach(&halts), |agent: &'static Agent| &agent.distance)
+        .summarize(move |res| match res {
+            BehaviorResult::Success(_) => BehaviorResult::Success(()),
+            BehaviorResult::Failure(_) => {
+                counted.set(counted.get() + 1);
+                BehaviorResult::Failure(())
+            }
+            BehaviorResult::Running => BehaviorResult::Running,
+            BehaviorResult::Idle => BehaviorResult::Idle,
+        });
+
+    let agent: &'static Agent = Box::leak(Box::new(Agent {
+        distance: Cell::new(1),
+    }));
+    assert_eq!(node.tick(agent), BehaviorResult::Running);
+    assert_eq!(node.tick(agent), BehaviorResult::Success(()));
+
+    agent.distance.set(20);
+    assert_eq!(node.tick(agent), BehaviorResult::Failure(()));
+    assert_eq!(reasons.get(), 1);
+}
+
+#[test]
+fn test_halt_reaches_embedded_tree() {
+    let halts = Rc::new(Cell::new(0));
+    let mut node = SubtreeNode::new(approach(&halts), |distance| distance);
+    let distance: &'static Cell<u32> = Box::leak(Box::new(Cell::new(3)));
+    assert_eq!(node.tick(distance), BehaviorResult::Running);
+    assert!(node.halt().is_clean());
+    assert_eq!(halts.get(), 1);
+    assert_eq!(node.tree_mut().tick(distance), BehaviorResult::Running);
+}
+
+#[test]
+fn test_report_includes_embedded_nodes() {
+    let inner: Tree<(), (), ()> = Tree::new(Box::new(SequenceNode::new([
+        Box::new(AlwaysSuccess(())) as Box<dyn BehaviorNodeBase<(), (), ()>>,
+        Box::new(AlwaysSuccess(())),
+    ])));
+    let mut tree = Tree::new(Box::new(SequenceNode::new([
+        Box::new(AlwaysSuccess(())) as Box<dyn BehaviorNodeBase<(), (), ()>>,
+        Box::new(SubtreeNode::new(inner, |payload| payload)),
+    ])));
+    let (res, report) = tree.tick_with_report(());
+    assert_eq!(res, BehaviorResult::Success(()));
+    assert_eq!(report.status("1"), Some(ResultKind::Success));
+    assert_eq!(report.status("1/1"), Some(ResultKind::Success));
+}
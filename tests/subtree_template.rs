@@ -0,0 +1,61 @@
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, InstantiateError, SubtreeLibrary, SubtreeTemplate,
+};
+
+type Node = Box<dyn BehaviorNodeBase<i32, i32, ()>>;
+
+/// Succeeds with the payload multiplied by a factor.
+struct Scale(i32);
+
+impl BehaviorNodeBase<i32, i32, ()> for Scale {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<i32, ()> {
+        BehaviorResult::Success(payload * self.0)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Params {
+    factor: i32,
+}
+
+#[test]
+fn test_subtree_template_instances() {
+    let template = SubtreeTemplate::new("Scale", |params: Params| {
+        Box::new(Scale(params.factor)) as Node
+    });
+    let mut double = template.instantiate(Params { factor: 2 });
+    let mut triple = template.clone().instantiate(Params { factor: 3 });
+    assert_eq!(double.tick(5), BehaviorResult::Success(10));
+    assert_eq!(triple.tick(5), BehaviorResult::Success(15));
+    assert_eq!(template.name(), "Scale");
+}
+
+#[test]
+fn test_subtree_library() {
+    let mut library = SubtreeLibrary::<i32, i32, ()>::new();
+    library.register(SubtreeTemplate::new("Scale", |params: Params| {
+        Box::new(Scale(params.factor)) as Node
+    }));
+    library.register(SubtreeTemplate::new("Constant", |value: i32| {
+        Box::new(AlwaysSuccess(value)) as Node
+    }));
+    let mut names: Vec<_> = library.names().collect();
+    names.sort();
+    assert_eq!(names, ["Constant", "Scale"]);
+
+    let mut node = library.instantiate("Scale", Params { factor: 4 }).unwrap();
+    assert_eq!(node.tick(2), BehaviorResult::Success(8));
+    let mut node = library.instantiate("Constant", 7).unwrap();
+    assert_eq!(node.tick(2), BehaviorResult::Success(7));
+
+    assert_eq!(
+        library.instantiate("Missing", 7).err(),
+        Some(InstantiateError::UnknownTemplate("Missing".to_string()))
+    );
+    let err = library.instantiate("Constant", "7").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Subtree template \"Constant\" takes parameters of type i32"
+    );
+}
@@ -0,0 +1,53 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::{AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, SwitchNode};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Mood {
+    Calm,
+    Angry,
+    Scared,
+}
+
+type Node = Box<dyn BehaviorNodeBase<Mood, &'static str, ()>>;
+
+struct Shout(Rc<Cell<u32>>);
+
+impl BehaviorNodeBase<Mood, &'static str, ()> for Shout {
+    fn tick(&mut self, _: Mood) -> BehaviorResult<&'static str, ()> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.0.set(self.0.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_switch() {
+    let mut tree = SwitchNode::new(|mood: &Mood| *mood)
+        .case(Mood::Calm, Box::new(AlwaysSuccess("idle")) as Node)
+        .case(Mood::Scared, Box::new(AlwaysSuccess("hide")));
+    assert_eq!(tree.tick(Mood::Calm), BehaviorResult::Success("idle"));
+    assert_eq!(tree.tick(Mood::Scared), BehaviorResult::Success("hide"));
+    assert_eq!(tree.tick(Mood::Angry), BehaviorResult::Failure(()));
+
+    let mut tree = tree.default(Box::new(AlwaysRunning));
+    assert_eq!(tree.tick(Mood::Angry), BehaviorResult::Running);
+    assert_eq!(tree.children_mut().len(), 3);
+}
+
+#[test]
+fn test_switch_halts_previous_child() {
+    let halts = Rc::new(Cell::new(0));
+    let mut tree = SwitchNode::new(|mood: &Mood| *mood == Mood::Angry)
+        .case(true, Box::new(Shout(halts.clone())) as Node)
+        .default(Box::new(AlwaysSuccess("idle")));
+    assert_eq!(tree.tick(Mood::Angry), BehaviorResult::Running);
+    assert_eq!(tree.tick(Mood::Angry), BehaviorResult::Running);
+    assert_eq!(halts.get(), 0);
+    assert_eq!(tree.tick(Mood::Calm), BehaviorResult::Success("idle"));
+    assert_eq!(halts.get(), 1);
+}
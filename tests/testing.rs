@@ -0,0 +1,33 @@
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, FallbackNode, SequenceNode};
+
+type Node = Box<dyn BehaviorNodeBase<(), i32, i32>>;
+
+#[test]
+fn test_constant_leaves() {
+    assert_eq!(
+        BehaviorNodeBase::<(), i32, ()>::tick(&mut AlwaysSuccess(3), ()),
+        BehaviorResult::Success(3)
+    );
+    assert_eq!(
+        BehaviorNodeBase::<(), (), &str>::tick(&mut AlwaysFailure("no"), ()),
+        BehaviorResult::Failure("no")
+    );
+    assert_eq!(
+        BehaviorNodeBase::<(), (), ()>::tick(&mut AlwaysRunning, ()),
+        BehaviorResult::Running
+    );
+}
+
+#[test]
+fn test_constant_leaves_in_tree() {
+    let mut tree = SequenceNode::<(), i32, i32, _>::new([
+        Box::new(AlwaysSuccess(1)) as Node,
+        Box::new(FallbackNode::<(), i32, i32, _>::new([
+            Box::new(AlwaysFailure(2)) as Node,
+            Box::new(AlwaysFailure(3)),
+        ])),
+        Box::new(AlwaysRunning),
+    ]);
+    assert_eq!(tree.tick(()), BehaviorResult::Failure(3));
+}
@@ -0,0 +1,69 @@
+use std::thread::sleep;
+use std::time::Duration;
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, FallbackNode, ParallelNode, ResultKind, SequenceNode, Tree,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), (), ()>>;
+
+struct Slow;
+
+impl BehaviorNodeBase<(), (), ()> for Slow {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        sleep(Duration::from_millis(10));
+        BehaviorResult::Success(())
+    }
+}
+
+#[test]
+fn test_tick_with_report() {
+    let mut tree = Tree::new(Box::new(SequenceNode::new([
+        Box::new(Slow) as Node,
+        Box::new(FallbackNode::new([
+            Box::new(AlwaysFailure(())) as Node,
+            Box::new(AlwaysSuccess(())),
+        ])),
+        Box::new(AlwaysFailure(())),
+        Box::new(AlwaysSuccess(())),
+    ])));
+    let (res, report) = tree.tick_with_report(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+
+    let statuses: Vec<_> = report
+        .nodes()
+        .iter()
+        .map(|node| (node.path.as_str(), node.kind))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("0", ResultKind::Success),
+            ("1/0", ResultKind::Failure),
+            ("1/1", ResultKind::Success),
+            ("1", ResultKind::Success),
+            ("2", ResultKind::Failure),
+            ("", ResultKind::Failure),
+        ]
+    );
+    assert_eq!(report.short_circuits(), ["1/1", "2"]);
+    assert!(Duration::from_millis(10) <= report.duration("0").unwrap());
+    assert!(report.duration("0").unwrap() <= report.duration("").unwrap());
+    assert_eq!(report.duration("3"), None);
+}
+
+#[test]
+fn test_report_is_per_tick() {
+    let mut tree = Tree::new(Box::new(ParallelNode::new(
+        [Box::new(AlwaysRunning) as Node, Box::new(AlwaysSuccess(()))],
+        2,
+    )));
+    let (_, report) = tree.tick_with_report(());
+    assert_eq!(report.status("0"), Some(ResultKind::Running));
+    assert_eq!(report.status(""), Some(ResultKind::Running));
+
+    // Ticking without a report records nothing
+    tree.tick(());
+    let (_, report) = tree.tick_with_report(());
+    assert_eq!(report.nodes().len(), 3);
+}
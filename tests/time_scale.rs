@@ -0,0 +1,65 @@
+use std::time::Duration;
+use tiny_behavior_tree::testing::{AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Clock, Cooldown, ManualClock, ScaledClock, TimeoutNode,
+};
+
+#[test]
+fn test_scaled_clock() {
+    let real = ManualClock::new();
+    let clock = ScaledClock::new(real.clone());
+    let start = clock.now();
+
+    clock.set_scale(2.);
+    real.advance(Duration::from_secs(1));
+    assert_eq!(clock.now() - start, Duration::from_secs(2));
+
+    // Pausing keeps the time that has passed.
+    clock.set_scale(0.);
+    real.advance(Duration::from_secs(10));
+    assert_eq!(clock.now() - start, Duration::from_secs(2));
+    assert_eq!(clock.scale(), 0.);
+}
+
+#[test]
+fn test_slow_motion() {
+    let real = ManualClock::new();
+    let clock = ScaledClock::new(real.clone());
+    clock.set_scale(0.5);
+    let mut timeout = TimeoutNode::with_clock(Duration::from_secs(2), clock.clone(), AlwaysRunning);
+    let mut cooldown =
+        Cooldown::new(Duration::from_secs(2), AlwaysSuccess(())).with_clock(clock.clone());
+
+    let res: BehaviorResult<(), ()> = timeout.tick(());
+    assert_eq!(res, BehaviorResult::Running);
+    let res: BehaviorResult<(), ()> = cooldown.tick(());
+    assert_eq!(res, BehaviorResult::Success(()));
+
+    // Only 1.5 seconds have passed in the game.
+    real.advance(Duration::from_secs(3));
+    let res: BehaviorResult<(), ()> = timeout.tick(());
+    assert_eq!(res, BehaviorResult::Running);
+    let res: BehaviorResult<(), ()> = cooldown.tick(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+
+    real.advance(Duration::from_secs(1));
+    let res: BehaviorResult<(), ()> = timeout.tick(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+    let res: BehaviorResult<(), ()> = cooldown.tick(());
+    assert_eq!(res, BehaviorResult::Success(()));
+}
+
+#[test]
+fn test_fast_forward() {
+    let real = ManualClock::new();
+    let clock = ScaledClock::new(real.clone());
+    let mut timeout =
+        TimeoutNode::with_clock(Duration::from_secs(10), clock.clone(), AlwaysRunning);
+    let res: BehaviorResult<(), ()> = timeout.tick(());
+    assert_eq!(res, BehaviorResult::Running);
+
+    clock.set_scale(100.);
+    real.advance(Duration::from_millis(100));
+    let res: BehaviorResult<(), ()> = timeout.tick(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+}
@@ -0,0 +1,82 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, ManualClock, TimeoutNode};
+
+/// Returns `Running` for the given number of ticks, then `Success`, counting halts.
+struct Walk {
+    steps: u32,
+    halted: Rc<Cell<u32>>,
+}
+
+impl BehaviorNodeBase<(), (), ()> for Walk {
+    fn tick(&mut self, _: ()) -> BehaviorResult<(), ()> {
+        if 0 < self.steps {
+            self.steps -= 1;
+            BehaviorResult::Running
+        } else {
+            BehaviorResult::Success(())
+        }
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.halted.set(self.halted.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_timeout() {
+    let clock = ManualClock::new();
+    let mut node = TimeoutNode::with_clock(
+        Duration::from_secs(3),
+        clock.clone(),
+        Walk {
+            steps: 10,
+            halted: Rc::default(),
+        },
+    );
+    for _ in 0..3 {
+        assert_eq!(node.tick(()), BehaviorResult::Running);
+        clock.advance(Duration::from_secs(1));
+    }
+    assert_eq!(node.tick(()), BehaviorResult::Failure(()));
+
+    // The measurement starts over in the next activation
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+}
+
+#[test]
+fn test_timeout_halts_child() {
+    let clock = ManualClock::new();
+    let halted = Rc::new(Cell::new(0));
+    let mut node = TimeoutNode::with_clock(
+        Duration::from_secs(1),
+        clock.clone(),
+        Walk {
+            steps: 1,
+            halted: halted.clone(),
+        },
+    );
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(node.tick(()), BehaviorResult::Failure(()));
+    assert_eq!(halted.get(), 1);
+
+    let mut node =
+        TimeoutNode::with_clock(Duration::from_secs(1), clock.clone(), AlwaysSuccess(()));
+    clock.advance(Duration::from_secs(5));
+    // A child that completes in time resets the measurement
+    assert_eq!(
+        BehaviorNodeBase::<(), (), ()>::tick(&mut node, ()),
+        BehaviorResult::Success(())
+    );
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(
+        BehaviorNodeBase::<(), (), ()>::tick(&mut node, ()),
+        BehaviorResult::Success(())
+    );
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "tokio")]
+use std::time::Duration;
+use tiny_behavior_tree::testing::{AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::tokio_time::{self, CooldownNode, TimeoutNode, TokioClock};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult};
+
+#[tokio::test(start_paused = true)]
+async fn test_tokio_timeout() {
+    let mut node = tokio_time::timeout(Duration::from_secs(3), AlwaysRunning);
+    let res: BehaviorResult<(), ()> = node.tick(());
+    assert_eq!(res, BehaviorResult::Running);
+    tokio::time::advance(Duration::from_secs(2)).await;
+    let res: BehaviorResult<(), ()> = node.tick(());
+    assert_eq!(res, BehaviorResult::Running);
+    tokio::time::advance(Duration::from_secs(1)).await;
+    let res: BehaviorResult<(), ()> = node.tick(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+
+    // The aliases still take other constructors.
+    let mut node: TimeoutNode<_> =
+        TimeoutNode::with_clock(Duration::from_secs(3), TokioClock, AlwaysSuccess(()));
+    let res: BehaviorResult<(), ()> = node.tick(());
+    assert_eq!(res, BehaviorResult::Success(()));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_tokio_delay_and_cooldown() {
+    let mut delay = tokio_time::delay(Duration::from_secs(1), AlwaysSuccess(()));
+    let mut cooldown: CooldownNode<_> =
+        tokio_time::cooldown(Duration::from_secs(5), AlwaysSuccess(()));
+
+    let res: BehaviorResult<(), ()> = delay.tick(());
+    assert_eq!(res, BehaviorResult::Running);
+    let res: BehaviorResult<(), ()> = cooldown.tick(());
+    assert_eq!(res, BehaviorResult::Success(()));
+
+    // Sleeping in paused time advances the clock without waiting
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let res: BehaviorResult<(), ()> = delay.tick(());
+    assert_eq!(res, BehaviorResult::Success(()));
+    let res: BehaviorResult<(), ()> = cooldown.tick(());
+    assert_eq!(res, BehaviorResult::Failure(()));
+
+    tokio::time::sleep(Duration::from_secs(4)).await;
+    let res: BehaviorResult<(), ()> = cooldown.tick(());
+    assert_eq!(res, BehaviorResult::Success(()));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_tokio_sleep() {
+    let mut node = tokio_time::sleep::<(), (), ()>(Duration::from_secs(2));
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    tokio::time::advance(Duration::from_secs(1)).await;
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    tokio::time::advance(Duration::from_secs(1)).await;
+    assert_eq!(node.tick(()), BehaviorResult::Success(()));
+
+    // It starts over, and halting it drops the timer.
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    tokio::time::advance(Duration::from_secs(1)).await;
+    node.halt();
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    tokio::time::advance(Duration::from_secs(1)).await;
+    assert_eq!(node.tick(()), BehaviorResult::Running);
+    tokio::time::advance(Duration::from_secs(1)).await;
+    assert_eq!(node.tick(()), BehaviorResult::Success(()));
+}
@@ -0,0 +1,43 @@
+use tiny_behavior_tree::testing::{AlwaysFailure, AlwaysSuccess};
+use tiny_behavior_tree::{
+    append_traced, BehaviorNodeBase, BehaviorResult, FallbackNode, Traced, TracedNode,
+};
+
+type Node = Box<dyn BehaviorNodeBase<(), Vec<Traced<i32>>, Vec<Traced<&'static str>>>>;
+
+#[test]
+fn test_traced_failures() {
+    let mut tree = FallbackNode::new_with_merger(
+        [
+            Box::new(TracedNode::new("path_a", AlwaysFailure("blocked"))) as Node,
+            Box::new(TracedNode::new("path_b", AlwaysFailure("too far"))),
+        ],
+        append_traced,
+    );
+    let BehaviorResult::Failure(failures) = tree.tick(()) else {
+        panic!("should fail")
+    };
+    let failures: Vec<_> = failures
+        .iter()
+        .map(|f| (f.path.as_str(), f.value))
+        .collect();
+    assert_eq!(failures, [("path_a", "blocked"), ("path_b", "too far")]);
+}
+
+#[test]
+fn test_traced_timestamps() {
+    let mut tree = FallbackNode::new_with_merger(
+        [
+            Box::new(TracedNode::new("a", AlwaysFailure("no"))) as Node,
+            Box::new(TracedNode::new("b", AlwaysSuccess(1))),
+        ],
+        append_traced,
+    );
+    let BehaviorResult::Success(results) = tree.tick(()) else {
+        panic!("should succeed")
+    };
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].path, "b");
+    let later = Traced::new("later", ());
+    assert!(results[0].timestamp <= later.timestamp);
+}
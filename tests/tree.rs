@@ -0,0 +1,74 @@
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, FallbackNode, SequenceNode, Tree, TreeBuildError,
+};
+
+type Node = Box<dyn BehaviorNodeBase<i32, (), ()>>;
+
+struct AtLeast(i32);
+
+impl BehaviorNodeBase<i32, (), ()> for AtLeast {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<(), ()> {
+        if self.0 <= payload {
+            BehaviorResult::Success(())
+        } else {
+            BehaviorResult::Failure(())
+        }
+    }
+}
+
+fn sequence() -> Node {
+    Box::new(SequenceNode::<i32, (), (), _>::new(vec![]))
+}
+
+fn fallback() -> Node {
+    Box::new(FallbackNode::<i32, (), (), _>::new(vec![]))
+}
+
+#[test]
+fn test_from_flat() {
+    let mut tree = Tree::from_flat(vec![
+        ("1/1", Box::new(AtLeast(5)) as Node),
+        ("", sequence()),
+        ("1", fallback()),
+        ("0", Box::new(AtLeast(0))),
+        ("1/0", Box::new(AtLeast(10))),
+    ])
+    .unwrap();
+
+    assert_eq!(tree.tick(7), BehaviorResult::Success(()));
+    assert_eq!(tree.tick(3), BehaviorResult::Failure(()));
+    assert_eq!(tree.tick(-1), BehaviorResult::Failure(()));
+}
+
+#[test]
+fn test_from_flat_errors() {
+    assert_eq!(
+        Tree::from_flat(vec![("0", sequence())]).err(),
+        Some(TreeBuildError::MissingRoot)
+    );
+    assert_eq!(
+        Tree::from_flat(vec![("", sequence()), ("0/0", sequence())]).err(),
+        Some(TreeBuildError::MissingParent("0/0".to_string()))
+    );
+    assert_eq!(
+        Tree::from_flat(vec![("", sequence()), ("1", sequence())]).err(),
+        Some(TreeBuildError::MissingSibling("1".to_string()))
+    );
+    assert_eq!(
+        Tree::from_flat(vec![("", sequence()), ("0", sequence()), ("0", sequence())]).err(),
+        Some(TreeBuildError::DuplicatePath("0".to_string()))
+    );
+    assert_eq!(
+        Tree::from_flat(vec![
+            ("", sequence()),
+            ("0", Box::new(AtLeast(0)) as Node),
+            ("0/0", sequence())
+        ])
+        .err(),
+        Some(TreeBuildError::NotComposite("0/0".to_string()))
+    );
+    assert_eq!(
+        Tree::from_flat(vec![("", sequence()), ("a", sequence())]).err(),
+        Some(TreeBuildError::InvalidPath("a".to_string()))
+    );
+}
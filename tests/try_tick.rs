@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+use std::sync::mpsc::channel;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, BtError, BtErrorKind, EmitEvent, FallbackNode, SequenceNode,
+    Tree,
+};
+
+type Payload = &'static RefCell<i32>;
+type Node = Box<dyn BehaviorNodeBase<Payload, (), ()>>;
+
+struct Increment;
+
+impl BehaviorNodeBase<Payload, (), ()> for Increment {
+    fn tick(&mut self, payload: Payload) -> BehaviorResult<(), ()> {
+        self.try_tick(payload)
+            .unwrap_or(BehaviorResult::Failure(()))
+    }
+
+    fn try_tick(&mut self, payload: Payload) -> Result<BehaviorResult<(), ()>, BtError> {
+        *payload.try_borrow_mut()? += 1;
+        Ok(BehaviorResult::Success(()))
+    }
+}
+
+fn tree() -> Tree<Payload, (), ()> {
+    Tree::new(Box::new(SequenceNode::new([
+        Box::new(AlwaysSuccess(())) as Node,
+        Box::new(FallbackNode::new([Box::new(Increment) as Node])),
+    ])))
+}
+
+#[test]
+fn test_error_is_separate_from_failure() {
+    let payload: Payload = Box::leak(Box::new(RefCell::new(0)));
+    let mut tree = tree();
+    assert_eq!(tree.try_tick(payload).unwrap(), BehaviorResult::Success(()));
+    assert_eq!(*payload.borrow(), 1);
+
+    let _guard = payload.borrow();
+    assert_eq!(tree.tick(payload), BehaviorResult::Failure(()));
+    let err = tree.try_tick(payload).unwrap_err();
+    assert!(matches!(err.kind(), BtErrorKind::Borrow));
+    assert_eq!(err.path(), "1/0");
+    assert_eq!(
+        err.to_string(),
+        "Node at path \"1/0\": Payload was already borrowed"
+    );
+}
+
+#[test]
+fn test_emit_event_disconnected() {
+    let (sender, receiver) = channel();
+    let mut node = EmitEvent::new(sender, |i: i32| i);
+    assert_eq!(
+        BehaviorNodeBase::<_, (), ()>::try_tick(&mut node, 1).unwrap(),
+        BehaviorResult::Success(())
+    );
+    drop(receiver);
+    let res = BehaviorNodeBase::<_, (), ()>::try_tick(&mut node, 2);
+    assert!(matches!(res.unwrap_err().kind(), BtErrorKind::Disconnected));
+    assert_eq!(
+        BehaviorNodeBase::<_, (), ()>::tick(&mut node, 3),
+        BehaviorResult::Failure(())
+    );
+}
@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+use tiny_behavior_tree::{
+    BehaviorNodeBase, BehaviorResult, Blackboard, SequenceNode, TypedKey, WithBlackboard,
+};
+
+const TARGET: TypedKey<(i32, i32)> = TypedKey::new("target");
+const ALERT: TypedKey<u32> = TypedKey::new("alert");
+
+struct World {
+    enemy: Option<(i32, i32)>,
+}
+
+type Ctx<'a> = WithBlackboard<'a, &'a World>;
+type Node<'a> = Box<dyn BehaviorNodeBase<Ctx<'a>, (), ()>>;
+
+struct PickTarget;
+
+impl<'a> BehaviorNodeBase<Ctx<'a>, (), ()> for PickTarget {
+    fn tick(&mut self, ctx: Ctx<'a>) -> BehaviorResult<(), ()> {
+        match ctx.state.enemy {
+            Some(enemy) => {
+                ctx.blackboard.borrow_mut().set_typed(TARGET, enemy);
+                BehaviorResult::Success(())
+            }
+            None => BehaviorResult::Failure(()),
+        }
+    }
+}
+
+struct RaiseAlert;
+
+impl<'a> BehaviorNodeBase<Ctx<'a>, (), ()> for RaiseAlert {
+    fn tick(&mut self, ctx: Ctx<'a>) -> BehaviorResult<(), ()> {
+        let mut bb = ctx.blackboard.borrow_mut();
+        if bb.get_typed(TARGET).is_none() {
+            return BehaviorResult::Failure(());
+        }
+        match bb.get_typed_mut(ALERT) {
+            Some(alert) => *alert += 1,
+            None => bb.set_typed(ALERT, 1),
+        }
+        BehaviorResult::Success(())
+    }
+}
+
+#[test]
+fn test_nodes_share_blackboard_alongside_state() {
+    let calm = World { enemy: None };
+    let spotted = World {
+        enemy: Some((3, 4)),
+    };
+    let bb = RefCell::new(Blackboard::new());
+    let mut tree = SequenceNode::new([Box::new(PickTarget) as Node, Box::new(RaiseAlert)]);
+
+    assert_eq!(
+        tree.tick(WithBlackboard::new(&calm, &bb)),
+        BehaviorResult::Failure(())
+    );
+    assert!(!bb.borrow().contains(ALERT));
+
+    for _ in 0..2 {
+        assert_eq!(
+            tree.tick(WithBlackboard::new(&spotted, &bb)),
+            BehaviorResult::Success(())
+        );
+    }
+    assert_eq!(bb.borrow().get_typed(TARGET), Some(&(3, 4)));
+    assert_eq!(bb.borrow().get_typed(ALERT), Some(&2));
+}
+
+#[test]
+fn test_typed_key_shares_entry_with_name() {
+    let mut bb = Blackboard::new();
+    bb.set("alert", 5u32);
+    assert_eq!(bb.get_typed(ALERT), Some(&5));
+
+    bb.set("alert", "high");
+    assert_eq!(bb.get_typed(ALERT), None);
+    assert_eq!(ALERT.name(), "alert");
+}
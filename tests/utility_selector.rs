@@ -0,0 +1,59 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, UtilitySelectorNode};
+
+type Node = Box<dyn BehaviorNodeBase<(f64, f64), i32, ()>>;
+
+/// Always returns `Running`, counting halts.
+struct Busy(Rc<Cell<u32>>);
+
+impl BehaviorNodeBase<(f64, f64), i32, ()> for Busy {
+    fn tick(&mut self, _: (f64, f64)) -> BehaviorResult<i32, ()> {
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.0.set(self.0.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_utility_selector() {
+    let mut tree = UtilitySelectorNode::new()
+        .child(|p: &(f64, f64)| p.0, Box::new(AlwaysSuccess(0)) as Node)
+        .child(|p: &(f64, f64)| p.1, Box::new(AlwaysSuccess(1)));
+    assert_eq!(tree.tick((0.5, 0.2)), BehaviorResult::Success(0));
+    assert_eq!(tree.tick((0.5, 0.7)), BehaviorResult::Success(1));
+    // The first child wins a tie, and NaN is never selected.
+    assert_eq!(tree.tick((0.5, 0.5)), BehaviorResult::Success(0));
+    assert_eq!(tree.tick((f64::NAN, 0.1)), BehaviorResult::Success(1));
+    assert_eq!(tree.selected(), Some(1));
+
+    let mut tree = UtilitySelectorNode::new().child(
+        |_: &(f64, f64)| f64::NAN,
+        Box::new(AlwaysSuccess(0)) as Node,
+    );
+    assert_eq!(tree.tick((0., 0.)), BehaviorResult::Failure(()));
+    assert_eq!(tree.selected(), None);
+}
+
+#[test]
+fn test_utility_selector_hysteresis() {
+    let halts = Rc::new(Cell::new(0));
+    let mut tree = UtilitySelectorNode::new()
+        .child(|p: &(f64, f64)| p.0, Box::new(Busy(halts.clone())) as Node)
+        .child(|p: &(f64, f64)| p.1, Box::new(AlwaysSuccess(1)))
+        .with_hysteresis(0.2);
+    assert_eq!(tree.tick((0.5, 0.4)), BehaviorResult::Running);
+    // Within the margin, the running child keeps going.
+    assert_eq!(tree.tick((0.5, 0.65)), BehaviorResult::Running);
+    assert_eq!(halts.get(), 0);
+    // Beyond the margin, it is halted.
+    assert_eq!(tree.tick((0.5, 0.8)), BehaviorResult::Success(1));
+    assert_eq!(halts.get(), 1);
+    // Now the other child has the advantage.
+    assert_eq!(tree.tick((0.85, 0.8)), BehaviorResult::Success(1));
+    assert_eq!(tree.tick((1.1, 0.8)), BehaviorResult::Running);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "watchdog")]
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tiny_behavior_tree::testing::AlwaysSuccess;
+use tiny_behavior_tree::watchdog::Watchdog;
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, SequenceNode};
+
+type Node = Box<dyn BehaviorNodeBase<u64, (), ()>>;
+
+/// Blocks for the given number of milliseconds.
+struct Block;
+
+impl BehaviorNodeBase<u64, (), ()> for Block {
+    fn tick(&mut self, millis: u64) -> BehaviorResult<(), ()> {
+        thread::sleep(Duration::from_millis(millis));
+        BehaviorResult::Success(())
+    }
+}
+
+#[test]
+fn test_reports_stuck_node_once_per_tick() {
+    let reports = Arc::new(Mutex::new(vec![]));
+    let watchdog = {
+        let reports = reports.clone();
+        Watchdog::new(Duration::from_millis(30), move |path, elapsed| {
+            reports.lock().unwrap().push((path.to_owned(), elapsed));
+        })
+    };
+    let mut tree = SequenceNode::new([
+        Box::new(watchdog.watch("0", AlwaysSuccess(()))) as Node,
+        Box::new(watchdog.watch("1", Block)),
+    ]);
+
+    assert_eq!(tree.tick(0), BehaviorResult::Success(()));
+    assert_eq!(tree.tick(150), BehaviorResult::Success(()));
+    assert_eq!(tree.tick(150), BehaviorResult::Success(()));
+    drop(watchdog);
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 2);
+    assert!(reports
+        .iter()
+        .all(|(path, elapsed)| path == "1" && Duration::from_millis(30) <= *elapsed));
+}
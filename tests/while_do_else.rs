@@ -0,0 +1,81 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use tiny_behavior_tree::testing::{AlwaysRunning, AlwaysSuccess};
+use tiny_behavior_tree::{BehaviorNodeBase, BehaviorResult, HaltReport, WhileDoElseNode};
+
+type Node = Box<dyn BehaviorNodeBase<i32, i32, i32>>;
+
+/// Succeeds with the payload if it is positive, otherwise fails with it.
+struct IsPositive;
+
+impl BehaviorNodeBase<i32, i32, i32> for IsPositive {
+    fn tick(&mut self, payload: i32) -> BehaviorResult<i32, i32> {
+        if 0 < payload {
+            BehaviorResult::Success(payload)
+        } else {
+            BehaviorResult::Failure(payload)
+        }
+    }
+}
+
+/// Always returns `Running`, counting ticks and halts.
+#[derive(Default)]
+struct Track {
+    ticks: Rc<Cell<u32>>,
+    halts: Rc<Cell<u32>>,
+}
+
+impl BehaviorNodeBase<i32, i32, i32> for Track {
+    fn tick(&mut self, _: i32) -> BehaviorResult<i32, i32> {
+        self.ticks.set(self.ticks.get() + 1);
+        BehaviorResult::Running
+    }
+
+    fn halt(&mut self) -> HaltReport {
+        self.halts.set(self.halts.get() + 1);
+        HaltReport::clean()
+    }
+}
+
+#[test]
+fn test_while_do_else() {
+    let body = Track::default();
+    let (body_ticks, body_halts) = (body.ticks.clone(), body.halts.clone());
+    let otherwise = Track::default();
+    let (else_ticks, else_halts) = (otherwise.ticks.clone(), otherwise.halts.clone());
+    let mut tree = WhileDoElseNode::new(Box::new(IsPositive) as Node, Box::new(body))
+        .with_else(Box::new(otherwise));
+
+    assert_eq!(tree.tick(1), BehaviorResult::Running);
+    assert_eq!(tree.tick(1), BehaviorResult::Running);
+    assert_eq!((body_ticks.get(), body_halts.get()), (2, 0));
+
+    // The condition flips, so the "do" child is halted.
+    assert_eq!(tree.tick(-1), BehaviorResult::Running);
+    assert_eq!((body_ticks.get(), body_halts.get()), (2, 1));
+    assert_eq!((else_ticks.get(), else_halts.get()), (1, 0));
+
+    assert_eq!(tree.tick(1), BehaviorResult::Running);
+    assert_eq!((else_ticks.get(), else_halts.get()), (1, 1));
+    assert_eq!(body_ticks.get(), 3);
+}
+
+#[test]
+fn test_while_do_without_else() {
+    let body = Track::default();
+    let body_halts = body.halts.clone();
+    let mut tree = WhileDoElseNode::new(Box::new(IsPositive) as Node, Box::new(body));
+    assert_eq!(tree.tick(1), BehaviorResult::Running);
+    assert_eq!(tree.tick(-3), BehaviorResult::Failure(-3));
+    assert_eq!(body_halts.get(), 1);
+
+    assert!(tree.push_child(Box::new(AlwaysSuccess(2))).is_ok());
+    assert_eq!(tree.tick(-3), BehaviorResult::Success(2));
+    assert!(tree.push_child(Box::new(AlwaysSuccess(3))).is_err());
+    assert_eq!(tree.children_mut().len(), 3);
+
+    // A running condition keeps the branches as they are.
+    let mut tree =
+        WhileDoElseNode::new(Box::new(AlwaysRunning) as Node, Box::new(AlwaysSuccess(1)));
+    assert_eq!(tree.tick(1), BehaviorResult::Running);
+}
@@ -0,0 +1,709 @@
+//! Derive macros for [tiny_behavior_tree](https://github.com/msakuta/rusty_tiny_behavior_tree).
+//!
+//! You usually don't depend on this crate directly, but use the re-exports
+//! from `tiny_behavior_tree` with the `derive` feature (enabled by default).
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    braced, parenthesized, parse_macro_input, parse_quote, BinOp, Data, DeriveInput, Expr, FnArg,
+    GenericArgument, Ident, Index, ItemFn, LitStr, Member, PathArguments, ReturnType, Token, Type,
+    Visibility,
+};
+
+/// Implements `BehaviorNodeBase` for a wrapper type by forwarding every
+/// method, including `halt` and `reset`, to the inner node.
+///
+/// The wrapper is transparent to introspection, i.e. `as_any_mut`,
+/// `children_mut`, `push_child`, `child_meta`, `describe` and `ports` behave as
+/// the inner node does.
+///
+/// The inner node is the only field of the struct, or the field marked with
+/// `#[delegate]` if the struct has more than one field.
+/// The implementation is generic over the payload and result types, so it
+/// applies whenever the inner node implements `BehaviorNodeBase`.
+#[proc_macro_derive(DelegateNode, attributes(delegate))]
+pub fn derive_delegate_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match delegate_node(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn delegate_node(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let (member, inner_ty) = find_field(&input, "DelegateNode", "delegate")?;
+    let name = &input.ident;
+
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!(__Payload));
+    generics.params.push(parse_quote!(__R));
+    generics.params.push(parse_quote!(__F));
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#inner_ty: ::tiny_behavior_tree::BehaviorNodeBase<__Payload, __R, __F>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let inner = quote!(<#inner_ty as ::tiny_behavior_tree::BehaviorNodeBase<__Payload, __R, __F>>);
+
+    Ok(quote! {
+        impl #impl_generics ::tiny_behavior_tree::BehaviorNodeBase<__Payload, __R, __F>
+            for #name #ty_generics #where_clause
+        {
+            fn tick(
+                &mut self,
+                payload: __Payload,
+            ) -> ::tiny_behavior_tree::BehaviorResult<__R, __F> {
+                #inner::tick(&mut self.#member, payload)
+            }
+
+            fn try_tick(
+                &mut self,
+                payload: __Payload,
+            ) -> ::std::result::Result<
+                ::tiny_behavior_tree::BehaviorResult<__R, __F>,
+                ::tiny_behavior_tree::BtError,
+            > {
+                #inner::try_tick(&mut self.#member, payload)
+            }
+
+            fn halt(&mut self) -> ::tiny_behavior_tree::HaltReport {
+                #inner::halt(&mut self.#member)
+            }
+
+            fn reset(&mut self) -> ::tiny_behavior_tree::HaltReport {
+                #inner::reset(&mut self.#member)
+            }
+
+            fn as_any_mut(&mut self) -> ::std::option::Option<&mut dyn ::std::any::Any>
+            where
+                Self: 'static,
+            {
+                #inner::as_any_mut(&mut self.#member)
+            }
+
+            fn children_mut(
+                &mut self,
+            ) -> ::std::vec::Vec<
+                &mut (dyn ::tiny_behavior_tree::BehaviorNodeBase<__Payload, __R, __F> + 'static),
+            >
+            where
+                Self: 'static,
+            {
+                #inner::children_mut(&mut self.#member)
+            }
+
+            fn push_child(
+                &mut self,
+                child: ::std::boxed::Box<
+                    dyn ::tiny_behavior_tree::BehaviorNodeBase<__Payload, __R, __F>,
+                >,
+            ) -> ::std::result::Result<
+                (),
+                ::std::boxed::Box<dyn ::tiny_behavior_tree::BehaviorNodeBase<__Payload, __R, __F>>,
+            >
+            where
+                Self: 'static,
+            {
+                #inner::push_child(&mut self.#member, child)
+            }
+
+            fn child_meta(
+                &self,
+                index: usize,
+            ) -> ::std::option::Option<&::tiny_behavior_tree::ChildMeta> {
+                #inner::child_meta(&self.#member, index)
+            }
+
+            fn describe(&self) -> ::std::vec::Vec<(&'static str, ::std::string::String)> {
+                #inner::describe(&self.#member)
+            }
+
+            fn ports(&self) -> ::std::vec::Vec<::tiny_behavior_tree::Port> {
+                #inner::ports(&self.#member)
+            }
+
+            fn definition(
+                &self,
+            ) -> ::std::option::Option<(&str, &::tiny_behavior_tree::NodeConfig)> {
+                #inner::definition(&self.#member)
+            }
+        }
+    })
+}
+
+/// Implements `BehaviorNodeBase` for a condition node that compares a value
+/// obtained from the payload with a field of the struct.
+///
+/// The struct needs a `#[condition(...)]` attribute with these arguments:
+///
+/// * `payload`: The payload type, e.g. `"&Agent"`.
+/// * `get`: An expression to obtain the value to compare from the payload,
+///   which is available as `payload`, e.g. `"payload.health"`.
+/// * `op`: The comparison operator, one of `==`, `!=`, `<`, `<=`, `>` and `>=`.
+///   It defaults to `==`.
+///
+/// The value is compared with the only field of the struct, or the field
+/// marked with `#[condition]`, with the value from the payload on the left hand side.
+/// The node returns `Success(())` if the comparison holds and `Failure(())` otherwise.
+#[proc_macro_derive(Condition, attributes(condition))]
+pub fn derive_condition(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match condition(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn condition(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let (member, _) = find_field(&input, "Condition", "condition")?;
+    let name = &input.ident;
+
+    let mut payload: Option<Type> = None;
+    let mut get: Option<Expr> = None;
+    let mut op: BinOp = parse_quote!(==);
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("condition"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let value: LitStr = meta.value()?.parse()?;
+            if meta.path.is_ident("payload") {
+                payload = Some(value.parse()?);
+            } else if meta.path.is_ident("get") {
+                get = Some(value.parse()?);
+            } else if meta.path.is_ident("op") {
+                op = value.parse()?;
+                if !matches!(
+                    op,
+                    BinOp::Eq(_)
+                        | BinOp::Ne(_)
+                        | BinOp::Lt(_)
+                        | BinOp::Le(_)
+                        | BinOp::Gt(_)
+                        | BinOp::Ge(_)
+                ) {
+                    return Err(meta.error("op must be a comparison operator"));
+                }
+            } else {
+                return Err(meta.error("unknown condition argument"));
+            }
+            Ok(())
+        })?;
+    }
+    let missing = |arg: &str| {
+        syn::Error::new_spanned(
+            name,
+            format!("Condition requires #[condition({} = \"...\")]", arg),
+        )
+    };
+    let payload = payload.ok_or_else(|| missing("payload"))?;
+    let get = get.ok_or_else(|| missing("get"))?;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::tiny_behavior_tree::BehaviorNodeBase<#payload, (), ()>
+            for #name #ty_generics #where_clause
+        {
+            fn tick(&mut self, payload: #payload) -> ::tiny_behavior_tree::BehaviorResult<(), ()> {
+                if (#get) #op self.#member {
+                    ::tiny_behavior_tree::BehaviorResult::Success(())
+                } else {
+                    ::tiny_behavior_tree::BehaviorResult::Failure(())
+                }
+            }
+        }
+    })
+}
+
+/// Implements `BehaviorNodeBase` for a leaf node that calls a method of the
+/// struct, with a name and a constructor for `NodeFactory`.
+///
+/// The struct can have a `#[behavior_node(...)]` attribute with these arguments:
+///
+/// * `payload`: The payload type, e.g. `"&Agent"`. It defaults to `()`.
+/// * `success`, `failure`: The result types of `Success` and `Failure`.
+///   They default to `()`.
+/// * `tick`: The name of the method to call, which takes `&mut self` and the
+///   payload and returns a `BehaviorResult`. It defaults to `tick`.
+/// * `name`: The name of the node type. It defaults to the name of the struct.
+///
+/// Fields marked with `#[port]` are parameters of the node, which are parsed
+/// from the `NodeConfig` by `from_config`, and listed by `describe`.
+/// `#[port(default = "expr")]` gives a default for a missing parameter.
+/// The other fields are initialized with `Default::default()`.
+#[proc_macro_derive(BehaviorNode, attributes(behavior_node, port))]
+pub fn derive_behavior_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match behavior_node(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn behavior_node(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "BehaviorNode can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut payload: Type = parse_quote!(());
+    let mut success: Type = parse_quote!(());
+    let mut failure: Type = parse_quote!(());
+    let mut tick: Ident = parse_quote!(tick);
+    let mut node_name = LitStr::new(&name.to_string(), name.span());
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("behavior_node"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let value: LitStr = meta.value()?.parse()?;
+            if meta.path.is_ident("payload") {
+                payload = value.parse()?;
+            } else if meta.path.is_ident("success") {
+                success = value.parse()?;
+            } else if meta.path.is_ident("failure") {
+                failure = value.parse()?;
+            } else if meta.path.is_ident("tick") {
+                tick = value.parse()?;
+            } else if meta.path.is_ident("name") {
+                node_name = value;
+            } else {
+                return Err(meta.error("unknown behavior_node argument"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let mut generics = input.generics.clone();
+    let mut inits = vec![];
+    let mut entries = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let (member, key) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+            None => (Member::Unnamed(Index::from(i)), i.to_string()),
+        };
+        let ty = &field.ty;
+        let mut port = false;
+        let mut default: Option<Expr> = None;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("port")) {
+            port = true;
+            if matches!(attr.meta, syn::Meta::Path(_)) {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    default = Some(value.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown port argument"))
+                }
+            })?;
+        }
+        if !port {
+            inits.push(quote!(#member: ::std::default::Default::default()));
+            continue;
+        }
+        inits.push(match default {
+            Some(default) => quote!(#member: cfg.get_or(#key, #default)?),
+            None => quote!(#member: cfg.get(#key)?),
+        });
+        generics.make_where_clause().predicates.push(parse_quote!(
+            #ty: ::std::str::FromStr + ::std::fmt::Debug
+        ));
+        entries.push(quote!((#key, ::std::format!("{:?}", self.#member))));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut static_generics = generics.clone();
+    static_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: 'static));
+    let static_where_clause = &static_generics.where_clause;
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The name of the node type in definition files.
+            pub const NODE_NAME: &'static str = #node_name;
+
+            /// Creates the node with the parameters in `cfg`.
+            pub fn from_config(
+                cfg: &::tiny_behavior_tree::NodeConfig,
+            ) -> ::std::result::Result<Self, ::tiny_behavior_tree::ParamError> {
+                ::std::result::Result::Ok(Self { #(#inits),* })
+            }
+
+            /// Registers `from_config` in `factory` with `NODE_NAME`.
+            pub fn register(
+                factory: &mut ::tiny_behavior_tree::NodeFactory<#payload, #success, #failure>,
+            ) #static_where_clause {
+                factory.register(Self::NODE_NAME, Self::from_config);
+            }
+        }
+
+        impl #impl_generics ::tiny_behavior_tree::BehaviorNodeBase<#payload, #success, #failure>
+            for #name #ty_generics #where_clause
+        {
+            fn tick(
+                &mut self,
+                payload: #payload,
+            ) -> ::tiny_behavior_tree::BehaviorResult<#success, #failure> {
+                #name::#tick(self, payload)
+            }
+
+            fn describe(&self) -> ::std::vec::Vec<(&'static str, ::std::string::String)> {
+                ::std::vec![#(#entries),*]
+            }
+        }
+    })
+}
+
+/// Generates a `describe_fields` method that returns the names of the fields
+/// of a struct with their `Debug` representations, for implementing
+/// `BehaviorNodeBase::describe`.
+///
+/// Fields marked with `#[describe(skip)]` are left out, e.g. child nodes or
+/// values without a `Debug` implementation.
+/// Fields of a tuple struct are named by their indices.
+#[proc_macro_derive(Describe, attributes(describe))]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match describe(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn describe(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Describe can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    let mut entries = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let mut skip = false;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("describe")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown describe argument"))
+                }
+            })?;
+        }
+        if skip {
+            continue;
+        }
+        let (member, key) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+            None => (Member::Unnamed(Index::from(i)), i.to_string()),
+        };
+        let ty = &field.ty;
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: ::std::fmt::Debug));
+        entries.push(quote!((#key, ::std::format!("{:?}", self.#member))));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the names of the fields with their `Debug` representations.
+            pub fn describe_fields(
+                &self,
+            ) -> ::std::vec::Vec<(&'static str, ::std::string::String)> {
+                ::std::vec![#(#entries),*]
+            }
+        }
+    })
+}
+
+/// Defines a condition node for each boolean field or getter of a payload type.
+///
+/// The input is an optional visibility, the payload type and a braced list of
+/// field names or getter names followed by `()`, e.g. `pub Agent { has_key, in_room() }`.
+/// Each name is converted to CamelCase for the node type, e.g. `HasKey`,
+/// which implements `BehaviorNodeBase<&Agent, (), ()>`.
+/// The node returns `Success(())` if the value is true and `Failure(())` otherwise.
+#[proc_macro]
+pub fn conditions_for(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ConditionsFor);
+    conditions_for_impl(input).into()
+}
+
+struct ConditionsFor {
+    vis: Visibility,
+    payload: Type,
+    accessors: Punctuated<Accessor, Token![,]>,
+}
+
+struct Accessor {
+    name: Ident,
+    getter: bool,
+}
+
+impl Parse for ConditionsFor {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let payload = Type::Path(input.parse()?);
+        let content;
+        braced!(content in input);
+        let accessors = content.parse_terminated(Accessor::parse, Token![,])?;
+        Ok(Self {
+            vis,
+            payload,
+            accessors,
+        })
+    }
+}
+
+impl Parse for Accessor {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let getter = input.peek(syn::token::Paren);
+        if getter {
+            let args;
+            parenthesized!(args in input);
+            if !args.is_empty() {
+                return Err(args.error("getters cannot take arguments"));
+            }
+        }
+        Ok(Self { name, getter })
+    }
+}
+
+fn conditions_for_impl(input: ConditionsFor) -> TokenStream2 {
+    let ConditionsFor {
+        vis,
+        payload,
+        accessors,
+    } = input;
+    let nodes = accessors.iter().map(|Accessor { name, getter }| {
+        let node = Ident::new(&camel_case(&name.to_string()), name.span());
+        let (get, doc) = if *getter {
+            (
+                quote!(payload.#name()),
+                format!(
+                    "Succeeds if `{}::{}()` returns true.",
+                    quote!(#payload),
+                    name
+                ),
+            )
+        } else {
+            (
+                quote!(payload.#name),
+                format!("Succeeds if `{}::{}` is true.", quote!(#payload), name),
+            )
+        };
+        quote! {
+            #[doc = #doc]
+            #[derive(Clone, Copy, Default, Debug)]
+            #vis struct #node;
+
+            impl<'a> ::tiny_behavior_tree::BehaviorNodeBase<&'a #payload, (), ()> for #node {
+                fn tick(
+                    &mut self,
+                    payload: &'a #payload,
+                ) -> ::tiny_behavior_tree::BehaviorResult<(), ()> {
+                    if #get {
+                        ::tiny_behavior_tree::BehaviorResult::Success(())
+                    } else {
+                        ::tiny_behavior_tree::BehaviorResult::Failure(())
+                    }
+                }
+            }
+        }
+    });
+    quote!(#(#nodes)*)
+}
+
+/// Turns a function into an action node, keeping the function as it is.
+///
+/// The function takes the payload, or nothing, and returns a
+/// `BehaviorResult`. A unit struct named in CamelCase, e.g. `OpenDoor` for
+/// `open_door`, implements `BehaviorNodeBase` by calling the function, with
+/// the visibility of the function.
+/// Like `#[derive(BehaviorNode)]`, it has `NODE_NAME`, which can be set with
+/// `#[behavior_action(name = "...")]`, and `register` to register it in a `NodeFactory`.
+#[proc_macro_attribute]
+pub fn behavior_action(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as ItemFn);
+    let mut node_name = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            node_name = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        } else {
+            Err(meta.error("unknown behavior_action argument"))
+        }
+    });
+    parse_macro_input!(args with parser);
+    match behavior_action_impl(item, node_name) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn behavior_action_impl(item: ItemFn, node_name: Option<LitStr>) -> syn::Result<TokenStream2> {
+    let sig = &item.sig;
+    let func = &sig.ident;
+    let vis = &item.vis;
+    let node = Ident::new(&camel_case(&func.to_string()), func.span());
+    let node_name = node_name.unwrap_or_else(|| LitStr::new(&node.to_string(), func.span()));
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "behavior_action functions cannot be generic",
+        ));
+    }
+
+    let (payload, call): (Type, _) = match sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+        [] => (parse_quote!(()), quote!(#func())),
+        [FnArg::Typed(arg)] => (arg.ty.as_ref().clone(), quote!(#func(payload))),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "behavior_action functions take only the payload",
+            ))
+        }
+    };
+
+    let results = match &sig.output {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .and_then(|seg| match &seg.arguments {
+                    PathArguments::AngleBracketed(args) if seg.ident == "BehaviorResult" => {
+                        match args.args.iter().collect::<Vec<_>>().as_slice() {
+                            [GenericArgument::Type(r), GenericArgument::Type(f)] => {
+                                Some((r.clone(), f.clone()))
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }),
+            _ => None,
+        },
+        ReturnType::Default => None,
+    };
+    let (success, failure) = results.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &sig.output,
+            "behavior_action functions must return BehaviorResult<R, F>",
+        )
+    })?;
+    let doc = format!("An action node that calls [`{}`].", func);
+
+    Ok(quote! {
+        #item
+
+        #[doc = #doc]
+        #[derive(Clone, Copy, Default, Debug)]
+        #vis struct #node;
+
+        impl #node {
+            /// The name of the node type in definition files.
+            pub const NODE_NAME: &'static str = #node_name;
+
+            /// Registers this node in `factory` with `NODE_NAME`.
+            pub fn register(
+                factory: &mut ::tiny_behavior_tree::NodeFactory<#payload, #success, #failure>,
+            ) {
+                factory.register(Self::NODE_NAME, |_| ::std::result::Result::Ok(Self));
+            }
+        }
+
+        impl ::tiny_behavior_tree::BehaviorNodeBase<#payload, #success, #failure> for #node {
+            #[allow(unused_variables)]
+            fn tick(
+                &mut self,
+                payload: #payload,
+            ) -> ::tiny_behavior_tree::BehaviorResult<#success, #failure> {
+                #call
+            }
+        }
+    })
+}
+
+/// Converts a snake_case name to CamelCase, e.g. `has_key` to `HasKey`.
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Finds the field that the derived impl operates on, which is either
+/// the only field of the struct or the field marked with `#[attr]`.
+fn find_field(input: &DeriveInput, derive: &str, attr: &str) -> syn::Result<(Member, syn::Type)> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("{} can only be derived for structs", derive),
+            ))
+        }
+    };
+
+    let member = |i: usize, field: &syn::Field| match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(i)),
+    };
+
+    let marked: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.attrs.iter().any(|a| a.path().is_ident(attr)))
+        .collect();
+
+    match marked.as_slice() {
+        [(i, field)] => Ok((member(*i, field), field.ty.clone())),
+        [] if fields.len() == 1 => {
+            let field = fields.iter().next().unwrap();
+            Ok((member(0, field), field.ty.clone()))
+        }
+        [] => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!(
+                "{} requires a single field or a field marked with #[{}]",
+                derive, attr
+            ),
+        )),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("only one field can be marked with #[{}]", attr),
+        )),
+    }
+}